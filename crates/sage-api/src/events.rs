@@ -2,15 +2,38 @@ use serde::{Deserialize, Serialize};
 use specta::Type;
 use tauri_specta::Event;
 
+use crate::{InvoiceRecordStatus, OfferRecordStatus};
+
 #[derive(Debug, Clone, Serialize, Deserialize, Type, Event)]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum SyncEvent {
-    Start { ip: String },
+    Start {
+        ip: String,
+    },
     Stop,
     Subscribed,
     Derivation,
-    CoinState,
+    CoinState {
+        coin_ids: Vec<String>,
+    },
+    TransactionEnded {
+        transaction_id: String,
+        success: bool,
+    },
+    TransactionReplaced {
+        transaction_id: String,
+        new_transaction_id: String,
+    },
+    OfferUpdated {
+        offer_id: String,
+        status: OfferRecordStatus,
+    },
+    InvoiceUpdated {
+        invoice_id: u32,
+        status: InvoiceRecordStatus,
+    },
     PuzzleBatchSynced,
+    TransactionHistory,
     CatInfo,
     DidInfo,
     NftData,
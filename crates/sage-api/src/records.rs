@@ -1,7 +1,10 @@
 mod cat;
+mod clawback;
 mod coin;
+mod contact;
 mod derivation;
 mod did;
+mod invoice;
 mod nft;
 mod nft_collection;
 mod offer;
@@ -12,9 +15,12 @@ mod transaction;
 mod transaction_summary;
 
 pub use cat::*;
+pub use clawback::*;
 pub use coin::*;
+pub use contact::*;
 pub use derivation::*;
 pub use did::*;
+pub use invoice::*;
 pub use nft::*;
 pub use nft_collection::*;
 pub use offer::*;
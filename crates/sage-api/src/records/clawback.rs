@@ -0,0 +1,16 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+use crate::Amount;
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct ClawbackRecord {
+    pub coin_id: String,
+    pub asset_id: Option<String>,
+    pub amount: Amount,
+    pub sender_address: String,
+    pub recipient_address: String,
+    pub seconds: u64,
+    pub created_height: Option<u32>,
+    pub incoming: bool,
+}
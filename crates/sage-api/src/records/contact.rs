@@ -0,0 +1,11 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct ContactRecord {
+    pub id: u32,
+    pub name: String,
+    pub address: String,
+    pub did_id: Option<String>,
+    pub notes: Option<String>,
+}
@@ -4,6 +4,8 @@ use specta::Type;
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
 pub struct DerivationRecord {
     pub index: u32,
+    pub hardened: bool,
     pub public_key: String,
     pub address: String,
+    pub label: Option<String>,
 }
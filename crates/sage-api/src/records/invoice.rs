@@ -0,0 +1,35 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+use crate::Amount;
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct InvoiceRecord {
+    pub id: u32,
+    pub address: String,
+    pub asset_id: Option<String>,
+    pub amount: Amount,
+    pub paid_amount: Amount,
+    pub memo: Option<String>,
+    pub expires_at: Option<u64>,
+    pub created_at: u64,
+    pub status: InvoiceRecordStatus,
+    pub uri: String,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum InvoiceRecordStatus {
+    Unpaid,
+    PartiallyPaid,
+    Paid,
+    Overpaid,
+    Expired,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct InvoicePaymentRecord {
+    pub coin_id: String,
+    pub amount: Amount,
+    pub height: u32,
+}
@@ -24,6 +24,43 @@ pub struct NftRecord {
     pub license_hash: Option<String>,
     pub edition_number: Option<u32>,
     pub edition_total: Option<u32>,
+    pub uri_health: Vec<NftUriRecord>,
+    pub attributes: Vec<NftAttributeRecord>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct NftUriRecord {
+    pub uri: String,
+    pub hash: String,
+    pub status: NftUriStatus,
+    pub attempts: u32,
+    pub last_error: Option<String>,
+    pub last_attempt: Option<u64>,
+    pub next_retry: Option<u64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum NftUriStatus {
+    Pending,
+    Fetched,
+    Retrying,
+    Failed,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct NftAttributeRecord {
+    pub trait_type: String,
+    pub value: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct NftTraitRecord {
+    pub trait_type: String,
+    pub value: String,
+    pub count: u32,
+    /// The fraction of NFTs that have this trait, between 0 and 1.
+    pub rarity: f64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
@@ -5,16 +5,32 @@ use crate::{Amount, AssetKind};
 
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
 pub struct TransactionRecord {
-    pub height: u32,
+    pub id: String,
+    pub height: Option<u32>,
+    pub direction: TransactionDirection,
+    pub fee: Amount,
+    pub counterparty_address: Option<String>,
+    pub memos: Vec<String>,
+    pub label: Option<String>,
     pub spent: Vec<TransactionCoin>,
     pub created: Vec<TransactionCoin>,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum TransactionDirection {
+    Incoming,
+    Outgoing,
+    Internal,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
 pub struct TransactionCoin {
     pub coin_id: String,
     pub amount: Amount,
     pub address: Option<String>,
+    /// The name of the contact with this address, if there is one.
+    pub contact: Option<String>,
     #[serde(flatten)]
     pub kind: AssetKind,
 }
@@ -44,6 +44,8 @@ pub struct TransactionOutput {
     pub coin_id: String,
     pub amount: Amount,
     pub address: String,
+    /// The name of the contact with this address, if there is one.
+    pub contact: Option<String>,
     pub receiving: bool,
     pub burning: bool,
 }
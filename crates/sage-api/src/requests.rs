@@ -1,12 +1,16 @@
 mod actions;
+mod contacts;
 mod data;
+mod invoices;
 mod keys;
 mod offers;
 mod settings;
 mod transactions;
 
 pub use actions::*;
+pub use contacts::*;
 pub use data::*;
+pub use invoices::*;
 pub use keys::*;
 pub use offers::*;
 pub use settings::*;
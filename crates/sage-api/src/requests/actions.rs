@@ -37,3 +37,29 @@ pub struct UpdateNft {
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, Type)]
 pub struct UpdateNftResponse {}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct RetryNftData {
+    pub nft_id: String,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Type)]
+pub struct RetryNftDataResponse {}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct UpdateTransaction {
+    pub id: String,
+    pub label: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Type)]
+pub struct UpdateTransactionResponse {}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct UpdateDerivation {
+    pub address: String,
+    pub label: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Type)]
+pub struct UpdateDerivationResponse {}
@@ -0,0 +1,51 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+use crate::ContactRecord;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Type)]
+pub struct GetContacts {}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct GetContactsResponse {
+    pub contacts: Vec<ContactRecord>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct AddContact {
+    pub name: String,
+    pub address: String,
+    #[serde(default)]
+    pub did_id: Option<String>,
+    #[serde(default)]
+    pub notes: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct AddContactResponse {
+    pub contact: ContactRecord,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct UpdateContact {
+    pub id: u32,
+    pub name: String,
+    pub address: String,
+    #[serde(default)]
+    pub did_id: Option<String>,
+    #[serde(default)]
+    pub notes: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct UpdateContactResponse {
+    pub contact: ContactRecord,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Type)]
+pub struct DeleteContact {
+    pub id: u32,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Type)]
+pub struct DeleteContactResponse {}
@@ -2,12 +2,15 @@ use serde::{Deserialize, Serialize};
 use specta::Type;
 
 use crate::{
-    Amount, CatRecord, CoinRecord, DerivationRecord, DidRecord, NftCollectionRecord, NftData,
-    NftRecord, PendingTransactionRecord, TransactionRecord, Unit,
+    Amount, CatRecord, ClawbackRecord, CoinRecord, DerivationRecord, DidRecord,
+    NftCollectionRecord, NftData, NftRecord, NftTraitRecord, PendingTransactionRecord,
+    TransactionDirection, TransactionRecord, Unit,
 };
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, Type)]
 pub struct GetDerivations {
+    #[serde(default)]
+    pub hardened: bool,
     pub offset: u32,
     pub limit: u32,
 }
@@ -17,6 +20,19 @@ pub struct GetDerivationsResponse {
     pub derivations: Vec<DerivationRecord>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct GetNextAddress {
+    #[serde(default)]
+    pub mark_used: bool,
+    #[serde(default)]
+    pub label: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct GetNextAddressResponse {
+    pub derivation: DerivationRecord,
+}
+
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, Type)]
 pub struct GetSyncStatus {}
 
@@ -48,6 +64,17 @@ pub struct GetCatCoinsResponse {
     pub coins: Vec<CoinRecord>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct GetCoinsByIds {
+    pub coin_ids: Vec<String>,
+}
+
+/// Only includes the coins that the wallet has synced.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct GetCoinsByIdsResponse {
+    pub coins: Vec<CoinRecord>,
+}
+
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, Type)]
 pub struct GetCats {}
 
@@ -83,9 +110,24 @@ pub struct GetPendingTransactionsResponse {
 }
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, Type)]
+pub struct GetClawbacks {}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct GetClawbacksResponse {
+    pub clawbacks: Vec<ClawbackRecord>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
 pub struct GetTransactions {
     pub offset: u32,
     pub limit: u32,
+    #[serde(default)]
+    pub direction: Option<TransactionDirection>,
+    /// Either "xch" or the asset id of a CAT.
+    #[serde(default)]
+    pub asset_id: Option<String>,
+    #[serde(default)]
+    pub label: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
@@ -94,6 +136,40 @@ pub struct GetTransactionsResponse {
     pub total: u32,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct GetTransaction {
+    pub id: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct GetTransactionResponse {
+    pub transaction: Option<TransactionRecord>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct ExportTransactions {
+    pub format: ExportFormat,
+    #[serde(default)]
+    pub from_height: Option<u32>,
+    #[serde(default)]
+    pub to_height: Option<u32>,
+    /// Either "xch" or the asset id of a CAT.
+    #[serde(default)]
+    pub asset_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum ExportFormat {
+    Csv,
+    Json,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct ExportTransactionsResponse {
+    pub content: String,
+}
+
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, Type)]
 pub struct GetNftStatus {}
 
@@ -134,6 +210,14 @@ pub struct GetNfts {
     pub limit: u32,
     pub sort_mode: NftSortMode,
     pub include_hidden: bool,
+    /// Only includes NFTs whose name contains words starting with each of the words given.
+    #[serde(default)]
+    pub search: Option<String>,
+    #[serde(default)]
+    pub trait_type: Option<String>,
+    /// Only used along with the trait type, to match a specific value of it.
+    #[serde(default)]
+    pub trait_value: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
@@ -141,6 +225,18 @@ pub struct GetNftsResponse {
     pub nfts: Vec<NftRecord>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct GetNftTraits {
+    /// Either "all", a collection id, or none for NFTs without a collection.
+    pub collection_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct GetNftTraitsResponse {
+    pub total: u32,
+    pub traits: Vec<NftTraitRecord>,
+}
+
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, Type)]
 #[serde(rename_all = "snake_case")]
 pub enum NftSortMode {
@@ -161,6 +257,17 @@ pub struct GetNftResponse {
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
 pub struct GetNftData {
     pub nft_id: String,
+    #[serde(default)]
+    pub size: NftDataSize,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum NftDataSize {
+    /// A PNG thumbnail, if one could be generated for the media.
+    Thumbnail,
+    #[default]
+    Full,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
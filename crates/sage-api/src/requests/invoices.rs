@@ -0,0 +1,56 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+use crate::{Amount, InvoicePaymentRecord, InvoiceRecord};
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct CreateInvoice {
+    /// Either "xch" or the asset id of a CAT.
+    pub asset: String,
+    pub amount: Amount,
+    #[serde(default)]
+    pub memo: Option<String>,
+    #[serde(default)]
+    pub expires_at: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct CreateInvoiceResponse {
+    pub invoice: InvoiceRecord,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Type)]
+pub struct GetInvoices {
+    pub offset: u32,
+    pub limit: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct GetInvoicesResponse {
+    pub invoices: Vec<InvoiceRecord>,
+    pub total: u32,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Type)]
+pub struct GetInvoice {
+    pub id: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct GetInvoiceResponse {
+    pub invoice: Option<InvoiceRecord>,
+    pub payments: Vec<InvoicePaymentRecord>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct ParsePaymentUri {
+    pub uri: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct ParsePaymentUriResponse {
+    pub address: String,
+    pub asset_id: Option<String>,
+    pub amount: Option<Amount>,
+    pub memo: Option<String>,
+}
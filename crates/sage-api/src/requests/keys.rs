@@ -45,6 +45,8 @@ pub struct ImportKey {
     pub save_secrets: bool,
     #[serde(default = "yes")]
     pub login: bool,
+    #[serde(default)]
+    pub password: String,
 }
 
 fn yes() -> bool {
@@ -56,6 +58,29 @@ pub struct ImportKeyResponse {
     pub fingerprint: u32,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct UnlockKeychain {
+    pub password: String,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Type)]
+pub struct UnlockKeychainResponse {}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Type)]
+pub struct LockKeychain {}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Type)]
+pub struct LockKeychainResponse {}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct ChangePassword {
+    pub old_password: String,
+    pub new_password: String,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Type)]
+pub struct ChangePasswordResponse {}
+
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, Type)]
 pub struct DeleteKey {
     pub fingerprint: u32,
@@ -101,3 +126,38 @@ pub struct GetSecretKey {
 pub struct GetSecretKeyResponse {
     pub secrets: Option<SecretKeyInfo>,
 }
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct SignMessageByAddress {
+    pub address: String,
+    pub message: String,
+    /// Whether the message is hex encoded, rather than UTF-8 text.
+    #[serde(default)]
+    pub is_hex: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct SignMessageByAddressResponse {
+    pub public_key: String,
+    pub signature: String,
+}
+
+/// Verifies a signed message against either a public key, an address owned by
+/// this wallet, or both.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct VerifySignature {
+    #[serde(default)]
+    pub public_key: Option<String>,
+    #[serde(default)]
+    pub address: Option<String>,
+    pub message: String,
+    pub signature: String,
+    /// Whether the message is hex encoded, rather than UTF-8 text.
+    #[serde(default)]
+    pub is_hex: bool,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Type)]
+pub struct VerifySignatureResponse {
+    pub is_valid: bool,
+}
@@ -1,14 +1,23 @@
+use sage_config::CoinSelectionStrategy;
 use serde::{Deserialize, Serialize};
 use specta::Type;
 
-use crate::{Amount, OfferRecord, OfferSummary, SpendBundleJson, TransactionSummary};
+use crate::{
+    Amount, OfferRecord, OfferSummary, SpendBundleJson, TransactionResponse, TransactionSummary,
+};
 
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
 pub struct MakeOffer {
     pub requested_assets: Assets,
     pub offered_assets: Assets,
     pub fee: Amount,
+    #[serde(default)]
+    pub fee_target_seconds: Option<u64>,
     pub expires_at_second: Option<u64>,
+    #[serde(default)]
+    pub coin_ids: Vec<String>,
+    #[serde(default)]
+    pub coin_selection_strategy: Option<CoinSelectionStrategy>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
@@ -35,6 +44,8 @@ pub struct TakeOffer {
     pub offer: String,
     pub fee: Amount,
     #[serde(default)]
+    pub fee_target_seconds: Option<u64>,
+    #[serde(default)]
     pub auto_submit: bool,
 }
 
@@ -88,3 +99,26 @@ pub struct DeleteOffer {
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, Type)]
 pub struct DeleteOfferResponse {}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct CancelOffer {
+    pub offer_id: String,
+    pub fee: Amount,
+    #[serde(default)]
+    pub fee_target_seconds: Option<u64>,
+    #[serde(default)]
+    pub auto_submit: bool,
+}
+
+pub type CancelOfferResponse = TransactionResponse;
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct CancelOffers {
+    pub fee: Amount,
+    #[serde(default)]
+    pub fee_target_seconds: Option<u64>,
+    #[serde(default)]
+    pub auto_submit: bool,
+}
+
+pub type CancelOffersResponse = TransactionResponse;
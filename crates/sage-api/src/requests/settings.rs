@@ -1,5 +1,5 @@
 use indexmap::IndexMap;
-use sage_config::Network;
+use sage_config::{AutoConsolidateConfig, CoinSelectionStrategy, Network};
 use serde::{Deserialize, Serialize};
 use specta::Type;
 
@@ -73,6 +73,33 @@ pub struct SetDerivationBatchSize {
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, Type)]
 pub struct SetDerivationBatchSizeResponse {}
 
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Type)]
+pub struct SetHardenedAddresses {
+    pub fingerprint: u32,
+    pub hardened_addresses: bool,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Type)]
+pub struct SetHardenedAddressesResponse {}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Type)]
+pub struct SetCoinSelectionStrategy {
+    pub fingerprint: u32,
+    pub coin_selection_strategy: CoinSelectionStrategy,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Type)]
+pub struct SetCoinSelectionStrategyResponse {}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Type)]
+pub struct SetAutoConsolidate {
+    pub fingerprint: u32,
+    pub auto_consolidate: AutoConsolidateConfig,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Type)]
+pub struct SetAutoConsolidateResponse {}
+
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, Type)]
 pub struct GetNetworks {}
 
@@ -1,3 +1,4 @@
+use sage_config::CoinSelectionStrategy;
 use serde::{Deserialize, Serialize};
 use specta::Type;
 
@@ -9,16 +10,59 @@ pub struct SendXch {
     pub amount: Amount,
     pub fee: Amount,
     #[serde(default)]
+    pub fee_target_seconds: Option<u64>,
+    #[serde(default)]
     pub memos: Vec<String>,
     #[serde(default)]
+    pub clawback_seconds: Option<u64>,
+    #[serde(default)]
+    pub coin_ids: Vec<String>,
+    #[serde(default)]
+    pub coin_selection_strategy: Option<CoinSelectionStrategy>,
+    #[serde(default)]
+    pub auto_submit: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct SendXchMulti {
+    pub payments: Vec<Payment>,
+    pub fee: Amount,
+    #[serde(default)]
+    pub fee_target_seconds: Option<u64>,
+    #[serde(default)]
+    pub coin_ids: Vec<String>,
+    #[serde(default)]
+    pub coin_selection_strategy: Option<CoinSelectionStrategy>,
+    #[serde(default)]
     pub auto_submit: bool,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct Payment {
+    pub address: String,
+    pub amount: Amount,
+    #[serde(default)]
+    pub memos: Vec<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
 pub struct CombineXch {
     pub coin_ids: Vec<String>,
     pub fee: Amount,
     #[serde(default)]
+    pub fee_target_seconds: Option<u64>,
+    #[serde(default)]
+    pub auto_submit: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct AutoConsolidate {
+    pub max_coins_per_asset: u32,
+    pub dust_threshold: Amount,
+    pub fee: Amount,
+    #[serde(default)]
+    pub fee_target_seconds: Option<u64>,
+    #[serde(default)]
     pub auto_submit: bool,
 }
 
@@ -28,6 +72,8 @@ pub struct SplitXch {
     pub output_count: u32,
     pub fee: Amount,
     #[serde(default)]
+    pub fee_target_seconds: Option<u64>,
+    #[serde(default)]
     pub auto_submit: bool,
 }
 
@@ -36,6 +82,8 @@ pub struct CombineCat {
     pub coin_ids: Vec<String>,
     pub fee: Amount,
     #[serde(default)]
+    pub fee_target_seconds: Option<u64>,
+    #[serde(default)]
     pub auto_submit: bool,
 }
 
@@ -45,6 +93,30 @@ pub struct SplitCat {
     pub output_count: u32,
     pub fee: Amount,
     #[serde(default)]
+    pub fee_target_seconds: Option<u64>,
+    #[serde(default)]
+    pub auto_submit: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct DenominationOutput {
+    pub amount: Amount,
+    pub count: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct SplitIntoDenominations {
+    #[serde(default)]
+    pub asset_id: Option<String>,
+    pub outputs: Vec<DenominationOutput>,
+    pub fee: Amount,
+    #[serde(default)]
+    pub fee_target_seconds: Option<u64>,
+    #[serde(default)]
+    pub coin_ids: Vec<String>,
+    #[serde(default)]
+    pub coin_selection_strategy: Option<CoinSelectionStrategy>,
+    #[serde(default)]
     pub auto_submit: bool,
 }
 
@@ -55,6 +127,44 @@ pub struct IssueCat {
     pub amount: Amount,
     pub fee: Amount,
     #[serde(default)]
+    pub fee_target_seconds: Option<u64>,
+    #[serde(default)]
+    pub multi_issuance: bool,
+    #[serde(default)]
+    pub coin_ids: Vec<String>,
+    #[serde(default)]
+    pub coin_selection_strategy: Option<CoinSelectionStrategy>,
+    #[serde(default)]
+    pub auto_submit: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct MintMoreCat {
+    pub asset_id: String,
+    pub amount: Amount,
+    pub fee: Amount,
+    #[serde(default)]
+    pub fee_target_seconds: Option<u64>,
+    #[serde(default)]
+    pub coin_ids: Vec<String>,
+    #[serde(default)]
+    pub coin_selection_strategy: Option<CoinSelectionStrategy>,
+    #[serde(default)]
+    pub auto_submit: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct MeltCat {
+    pub asset_id: String,
+    pub amount: Amount,
+    pub fee: Amount,
+    #[serde(default)]
+    pub fee_target_seconds: Option<u64>,
+    #[serde(default)]
+    pub coin_ids: Vec<String>,
+    #[serde(default)]
+    pub coin_selection_strategy: Option<CoinSelectionStrategy>,
+    #[serde(default)]
     pub auto_submit: bool,
 }
 
@@ -65,8 +175,31 @@ pub struct SendCat {
     pub amount: Amount,
     pub fee: Amount,
     #[serde(default)]
+    pub fee_target_seconds: Option<u64>,
+    #[serde(default)]
     pub memos: Vec<String>,
     #[serde(default)]
+    pub clawback_seconds: Option<u64>,
+    #[serde(default)]
+    pub coin_ids: Vec<String>,
+    #[serde(default)]
+    pub coin_selection_strategy: Option<CoinSelectionStrategy>,
+    #[serde(default)]
+    pub auto_submit: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct SendCatMulti {
+    pub asset_id: String,
+    pub payments: Vec<Payment>,
+    pub fee: Amount,
+    #[serde(default)]
+    pub fee_target_seconds: Option<u64>,
+    #[serde(default)]
+    pub coin_ids: Vec<String>,
+    #[serde(default)]
+    pub coin_selection_strategy: Option<CoinSelectionStrategy>,
+    #[serde(default)]
     pub auto_submit: bool,
 }
 
@@ -75,6 +208,12 @@ pub struct CreateDid {
     pub name: String,
     pub fee: Amount,
     #[serde(default)]
+    pub fee_target_seconds: Option<u64>,
+    #[serde(default)]
+    pub coin_ids: Vec<String>,
+    #[serde(default)]
+    pub coin_selection_strategy: Option<CoinSelectionStrategy>,
+    #[serde(default)]
     pub auto_submit: bool,
 }
 
@@ -84,6 +223,12 @@ pub struct BulkMintNfts {
     pub did_id: String,
     pub fee: Amount,
     #[serde(default)]
+    pub fee_target_seconds: Option<u64>,
+    #[serde(default)]
+    pub coin_ids: Vec<String>,
+    #[serde(default)]
+    pub coin_selection_strategy: Option<CoinSelectionStrategy>,
+    #[serde(default)]
     pub auto_submit: bool,
 }
 
@@ -92,18 +237,41 @@ pub struct NftMint {
     pub edition_number: Option<u32>,
     pub edition_total: Option<u32>,
     pub data_uris: Vec<String>,
+    /// The hash of the data, which is computed by fetching the URIs if it's omitted.
+    #[serde(default)]
+    pub data_hash: Option<String>,
     pub metadata_uris: Vec<String>,
+    /// The hash of the metadata, which is computed by fetching the URIs if it's omitted.
+    #[serde(default)]
+    pub metadata_hash: Option<String>,
     pub license_uris: Vec<String>,
+    /// The hash of the license, which is computed by fetching the URIs if it's omitted.
+    #[serde(default)]
+    pub license_hash: Option<String>,
     pub royalty_address: Option<String>,
     pub royalty_ten_thousandths: u16,
 }
 
+/// Fetches the URIs of each mint from every mirror and fills in their hashes,
+/// checking that the metadata follows CHIP-0007.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct PrepareNftMints {
+    pub mints: Vec<NftMint>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct PrepareNftMintsResponse {
+    pub mints: Vec<NftMint>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
 pub struct TransferNfts {
     pub nft_ids: Vec<String>,
     pub address: String,
     pub fee: Amount,
     #[serde(default)]
+    pub fee_target_seconds: Option<u64>,
+    #[serde(default)]
     pub auto_submit: bool,
 }
 
@@ -112,6 +280,8 @@ pub struct AddNftUri {
     pub nft_id: String,
     pub uri: String,
     pub fee: Amount,
+    #[serde(default)]
+    pub fee_target_seconds: Option<u64>,
     pub kind: NftUriKind,
     #[serde(default)]
     pub auto_submit: bool,
@@ -131,6 +301,8 @@ pub struct AssignNftsToDid {
     pub did_id: Option<String>,
     pub fee: Amount,
     #[serde(default)]
+    pub fee_target_seconds: Option<u64>,
+    #[serde(default)]
     pub auto_submit: bool,
 }
 
@@ -140,6 +312,8 @@ pub struct TransferDids {
     pub address: String,
     pub fee: Amount,
     #[serde(default)]
+    pub fee_target_seconds: Option<u64>,
+    #[serde(default)]
     pub auto_submit: bool,
 }
 
@@ -167,6 +341,17 @@ pub struct ViewCoinSpendsResponse {
     pub summary: TransactionSummary,
 }
 
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Type)]
+pub struct EstimateFee {
+    pub target_seconds: u64,
+    pub cost: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct EstimateFeeResponse {
+    pub fee: Amount,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
 pub struct SubmitTransaction {
     pub spend_bundle: SpendBundleJson,
@@ -175,22 +360,79 @@ pub struct SubmitTransaction {
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, Type)]
 pub struct SubmitTransactionResponse {}
 
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct IncreaseTransactionFee {
+    pub transaction_id: String,
+    pub new_fee: Amount,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct IncreaseTransactionFeeResponse {
+    pub transaction_id: String,
+    pub summary: TransactionSummary,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct CancelTransaction {
+    pub transaction_id: String,
+    pub fee: Amount,
+    #[serde(default)]
+    pub fee_target_seconds: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct CancelTransactionResponse {
+    pub transaction_id: String,
+    pub summary: TransactionSummary,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct ClaimClawback {
+    pub coin_id: String,
+    pub fee: Amount,
+    #[serde(default)]
+    pub fee_target_seconds: Option<u64>,
+    #[serde(default)]
+    pub auto_submit: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct RevokeClawback {
+    pub coin_id: String,
+    pub fee: Amount,
+    #[serde(default)]
+    pub fee_target_seconds: Option<u64>,
+    #[serde(default)]
+    pub auto_submit: bool,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
 pub struct TransactionResponse {
     pub summary: TransactionSummary,
     pub coin_spends: Vec<CoinSpendJson>,
+    /// The id of the transaction, if it was submitted.
+    #[serde(default)]
+    pub transaction_id: Option<String>,
 }
 
 pub type SendXchResponse = TransactionResponse;
+pub type SendXchMultiResponse = TransactionResponse;
 pub type CombineXchResponse = TransactionResponse;
 pub type SplitXchResponse = TransactionResponse;
 pub type CombineCatResponse = TransactionResponse;
 pub type SplitCatResponse = TransactionResponse;
+pub type AutoConsolidateResponse = TransactionResponse;
+pub type SplitIntoDenominationsResponse = TransactionResponse;
 pub type IssueCatResponse = TransactionResponse;
+pub type MintMoreCatResponse = TransactionResponse;
+pub type MeltCatResponse = TransactionResponse;
 pub type SendCatResponse = TransactionResponse;
+pub type SendCatMultiResponse = TransactionResponse;
 pub type CreateDidResponse = TransactionResponse;
 pub type BulkMintNftsResponse = TransactionResponse;
 pub type TransferNftsResponse = TransactionResponse;
 pub type AddNftUriResponse = TransactionResponse;
 pub type AssignNftsToDidResponse = TransactionResponse;
 pub type TransferDidsResponse = TransactionResponse;
+pub type ClaimClawbackResponse = TransactionResponse;
+pub type RevokeClawbackResponse = TransactionResponse;
@@ -8,5 +8,6 @@ pub enum ErrorKind {
     Api,
     NotFound,
     Unauthorized,
+    Locked,
     Internal,
 }
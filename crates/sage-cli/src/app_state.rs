@@ -1,9 +1,11 @@
 use std::sync::Arc;
 
 use sage::Sage;
-use tokio::sync::Mutex;
+use sage_api::SyncEvent;
+use tokio::sync::{broadcast, Mutex};
 
 #[derive(Debug, Clone)]
 pub struct AppState {
     pub sage: Arc<Mutex<Sage>>,
+    pub events: broadcast::Sender<SyncEvent>,
 }
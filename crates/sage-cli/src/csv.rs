@@ -0,0 +1,168 @@
+use std::{iter::Peekable, str::Chars};
+
+use anyhow::{bail, Result};
+
+/// A row of a CSV file, along with the line it starts on for error messages.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CsvRecord {
+    pub line: usize,
+    pub fields: Vec<String>,
+}
+
+/// Parses CSV text into records. Fields can be quoted to include commas, quotes (escaped by
+/// doubling them) or line breaks, and whitespace around unquoted fields is trimmed.
+/// Blank lines are skipped, as is a leading header row whose first field is `header`.
+pub fn parse_csv(text: &str, header: &str) -> Result<Vec<CsvRecord>> {
+    let mut records = Vec::new();
+    let mut chars = text.chars().peekable();
+    let mut line = 1;
+
+    while chars.peek().is_some() {
+        let start = line;
+        let mut fields = Vec::new();
+        let mut field = String::new();
+        let mut quoted = false;
+
+        loop {
+            match chars.next() {
+                Some('"') if !quoted && field.trim().is_empty() => {
+                    field = parse_quoted(&mut chars, &mut line, start)?;
+                    quoted = true;
+                }
+                Some(',') => {
+                    fields.push(finish_field(field, quoted));
+                    field = String::new();
+                    quoted = false;
+                }
+                Some('\n') => {
+                    line += 1;
+                    break;
+                }
+                Some('\r') if chars.peek() == Some(&'\n') => {}
+                Some(_) if quoted => {
+                    bail!("Unexpected text after a quoted field on line {line}");
+                }
+                Some(c) => field.push(c),
+                None => break,
+            }
+        }
+
+        if fields.is_empty() && !quoted && field.trim().is_empty() {
+            continue;
+        }
+
+        fields.push(finish_field(field, quoted));
+
+        if records.is_empty() && fields[0].eq_ignore_ascii_case(header) {
+            continue;
+        }
+
+        records.push(CsvRecord {
+            line: start,
+            fields,
+        });
+    }
+
+    Ok(records)
+}
+
+/// Reads the rest of a quoted field, after the opening quote.
+fn parse_quoted(chars: &mut Peekable<Chars<'_>>, line: &mut usize, start: usize) -> Result<String> {
+    let mut field = String::new();
+
+    loop {
+        match chars.next() {
+            Some('"') if chars.peek() == Some(&'"') => {
+                chars.next();
+                field.push('"');
+            }
+            Some('"') => break,
+            Some(c) => {
+                if c == '\n' {
+                    *line += 1;
+                }
+                field.push(c);
+            }
+            None => bail!("Unterminated quoted field on line {start}"),
+        }
+    }
+
+    // Whitespace between the closing quote and the next separator is allowed.
+    while chars.next_if(|c| *c == ' ' || *c == '\t').is_some() {}
+
+    Ok(field)
+}
+
+fn finish_field(field: String, quoted: bool) -> String {
+    if quoted {
+        field
+    } else {
+        field.trim().to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fields(text: &str) -> Vec<Vec<String>> {
+        parse_csv(text, "name")
+            .expect("invalid csv")
+            .into_iter()
+            .map(|record| record.fields)
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_csv() {
+        assert_eq!(fields("a,b,c\n1,2,3"), [["a", "b", "c"], ["1", "2", "3"]]);
+        assert_eq!(fields(" a , b \r\n"), [["a", "b"]]);
+        assert_eq!(fields("a,,\n"), [["a", "", ""]]);
+        assert!(fields("").is_empty());
+    }
+
+    #[test]
+    fn test_parse_quoted_fields() {
+        assert_eq!(
+            fields("\"https://example.com/a,b.png\",2"),
+            [["https://example.com/a,b.png", "2"]]
+        );
+        assert_eq!(fields("\"say \"\"hi\"\"\""), [["say \"hi\""]]);
+        assert_eq!(fields("\" padded \" ,x"), [[" padded ", "x"]]);
+        assert_eq!(fields("\"\""), [[""]]);
+        assert_eq!(fields("\"line\nbreak\",x"), [["line\nbreak", "x"]]);
+    }
+
+    #[test]
+    fn test_skip_header_and_blank_lines() {
+        let records =
+            parse_csv("\nName,value\n\n a,1\n\"b\n\",2\n  \nc,3\n", "name").expect("invalid csv");
+
+        assert_eq!(
+            records,
+            [
+                CsvRecord {
+                    line: 4,
+                    fields: vec!["a".to_string(), "1".to_string()],
+                },
+                CsvRecord {
+                    line: 5,
+                    fields: vec!["b\n".to_string(), "2".to_string()],
+                },
+                CsvRecord {
+                    line: 8,
+                    fields: vec!["c".to_string(), "3".to_string()],
+                },
+            ]
+        );
+
+        // Only a leading header row is skipped.
+        assert_eq!(fields("a,1\nname,2"), [["a", "1"], ["name", "2"]]);
+    }
+
+    #[test]
+    fn test_invalid_csv() {
+        assert!(parse_csv("a,\"b", "name").is_err());
+        assert!(parse_csv("a,\"b\"c", "name").is_err());
+    }
+}
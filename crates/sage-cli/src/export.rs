@@ -0,0 +1,61 @@
+use std::{fs, path::PathBuf};
+
+use anyhow::Result;
+use clap::{Parser, ValueEnum};
+use sage_api::{ExportFormat, ExportTransactions, ExportTransactionsResponse};
+
+use crate::router::request_rpc;
+
+/// Writes every coin received and sent by the wallet to a file, for accounting.
+///
+/// Each row lists the block height and timestamp, the asset, the amount in display
+/// units, the fee of the transaction and the transaction id.
+#[derive(Debug, Parser)]
+pub struct ExportArgs {
+    /// The file to write the export to.
+    file: PathBuf,
+
+    /// The format of the export.
+    #[clap(long, value_enum, default_value_t = Format::Csv)]
+    format: Format,
+
+    /// Only exports transactions confirmed at or after this height.
+    #[clap(long)]
+    from_height: Option<u32>,
+
+    /// Only exports transactions confirmed at or before this height.
+    #[clap(long)]
+    to_height: Option<u32>,
+
+    /// Only exports coins of the given asset, either `xch` or a CAT asset id.
+    #[clap(long)]
+    asset_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum Format {
+    Csv,
+    Json,
+}
+
+impl ExportArgs {
+    pub async fn handle(self, path: PathBuf) -> Result<()> {
+        let body = ExportTransactions {
+            format: match self.format {
+                Format::Csv => ExportFormat::Csv,
+                Format::Json => ExportFormat::Json,
+            },
+            from_height: self.from_height,
+            to_height: self.to_height,
+            asset_id: self.asset_id,
+        };
+
+        let response =
+            request_rpc::<_, ExportTransactionsResponse>(path, "/export_transactions", body)
+                .await?;
+
+        fs::write(&self.file, response.content)?;
+
+        Ok(())
+    }
+}
@@ -1,9 +1,16 @@
 mod app_state;
+mod csv;
+mod export;
+mod mint;
+mod payout;
 mod router;
 mod tls;
 
 use anyhow::Result;
 use clap::Parser;
+use export::ExportArgs;
+use mint::MintArgs;
+use payout::PayoutArgs;
 use router::RpcCommand;
 use rustls::crypto::aws_lc_rs::default_provider;
 
@@ -19,6 +26,9 @@ enum Command {
         #[clap(subcommand)]
         command: RpcCommand,
     },
+    Payout(PayoutArgs),
+    Export(ExportArgs),
+    Mint(MintArgs),
 }
 
 #[tokio::main]
@@ -35,6 +45,9 @@ async fn main() -> Result<()> {
 
     match args.command {
         Command::Rpc { command } => command.handle(path).await?,
+        Command::Payout(args) => args.handle(path).await?,
+        Command::Export(args) => args.handle(path).await?,
+        Command::Mint(args) => args.handle(path).await?,
     }
 
     Ok(())
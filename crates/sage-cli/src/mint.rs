@@ -0,0 +1,477 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use anyhow::{bail, Result};
+use clap::Parser;
+use sage_api::{
+    Amount, AssetKind, BulkMintNfts, BulkMintNftsResponse, CoinRecord, GetCoinsByIds,
+    GetCoinsByIdsResponse, GetPendingTransactions, GetPendingTransactionsResponse, NftMint,
+    PrepareNftMints, PrepareNftMintsResponse,
+};
+use serde::{Deserialize, Serialize};
+use tokio::time::sleep;
+
+use crate::{csv::parse_csv, router::request_rpc};
+
+/// Mints every NFT in a manifest file, in chunks of one transaction each.
+///
+/// The manifest is either a JSON array of mints, or a CSV file with the columns
+/// `data_uris,metadata_uris,license_uris,edition_number,edition_total,royalty_address,royalty_ten_thousandths`,
+/// where multiple URIs are separated by spaces, and fields containing commas can be quoted.
+/// Blank lines, and a leading header row starting with `data_uris`, are skipped.
+/// Hashes are computed by fetching the URIs.
+///
+/// The transaction of each chunk is written to a progress file when it's submitted, and the
+/// chunk is only counted once the coins it spends are spent on chain. Running the command again
+/// resumes where it left off. The last chunk is only minted again if its transaction failed,
+/// meaning that none of its coins were spent and nothing is pending anymore.
+#[derive(Debug, Parser)]
+pub struct MintArgs {
+    /// The JSON or CSV manifest containing the NFTs to mint.
+    manifest: PathBuf,
+
+    /// The DID to mint the NFTs with.
+    #[clap(long)]
+    did_id: String,
+
+    /// The number of NFTs to mint in each transaction.
+    #[clap(long, default_value_t = 25)]
+    chunk_size: usize,
+
+    /// The transaction fee of each chunk, in mojos.
+    #[clap(long, default_value_t = 0)]
+    fee: u64,
+
+    /// Estimates the fee needed to confirm within this many seconds instead.
+    #[clap(long)]
+    fee_target_seconds: Option<u64>,
+
+    /// The file to track progress in, which defaults to the manifest path with `.progress` appended.
+    #[clap(long)]
+    progress: Option<PathBuf>,
+}
+
+impl MintArgs {
+    pub async fn handle(self, path: PathBuf) -> Result<()> {
+        if self.chunk_size == 0 {
+            bail!("The chunk size must be at least 1");
+        }
+
+        let text = fs::read_to_string(&self.manifest)?;
+
+        let mints = if self
+            .manifest
+            .extension()
+            .is_some_and(|extension| extension.eq_ignore_ascii_case("json"))
+        {
+            serde_json::from_str::<Vec<NftMint>>(&text)?
+        } else {
+            parse_mints(&text)?
+        };
+
+        let progress_path = self.progress.clone().unwrap_or_else(|| {
+            let mut progress = self.manifest.clone().into_os_string();
+            progress.push(".progress");
+            progress.into()
+        });
+
+        let mut progress = Progress::load(&progress_path)?;
+
+        // A chunk submitted by a previous run is only minted again if its transaction failed.
+        if let Some(pending) = progress.pending.clone() {
+            println!(
+                "Checking transaction {} from the previous run",
+                pending.transaction_id
+            );
+
+            match chunk_status(path.clone(), &pending).await? {
+                ChunkStatus::Confirmed => progress.minted += pending.count,
+                ChunkStatus::Failed => {
+                    println!("The transaction failed, so it will be minted again");
+                }
+            }
+
+            progress.pending = None;
+            progress.save(&progress_path)?;
+        }
+
+        if progress.minted > 0 {
+            println!("Resuming after {} of {} NFTs", progress.minted, mints.len());
+        }
+
+        // The chunks spend the change of each other, so only one can be pending at a time.
+        wait_for_pending(path.clone()).await?;
+
+        while progress.minted < mints.len() {
+            let end = mints.len().min(progress.minted + self.chunk_size);
+            let chunk = &mints[progress.minted..end];
+
+            let prepared = request_rpc::<_, PrepareNftMintsResponse>(
+                path.clone(),
+                "/prepare_nft_mints",
+                PrepareNftMints {
+                    mints: chunk.to_vec(),
+                },
+            )
+            .await?;
+
+            let body = BulkMintNfts {
+                mints: prepared.mints,
+                did_id: self.did_id.clone(),
+                fee: Amount::u64(self.fee),
+                fee_target_seconds: self.fee_target_seconds,
+                coin_ids: Vec::new(),
+                coin_selection_strategy: None,
+                auto_submit: true,
+            };
+
+            let response =
+                request_rpc::<_, BulkMintNftsResponse>(path.clone(), "/bulk_mint_nfts", body)
+                    .await?;
+
+            let Some(transaction_id) = response.transaction_id else {
+                bail!("The transaction wasn't submitted");
+            };
+
+            // The launchers are created and spent in the same transaction, so the wallet
+            // never syncs them.
+            let coin_ids = response
+                .summary
+                .inputs
+                .into_iter()
+                .filter(|input| !matches!(input.kind, AssetKind::Launcher))
+                .map(|input| input.coin_id)
+                .collect();
+
+            let pending = PendingChunk {
+                transaction_id: transaction_id.clone(),
+                count: chunk.len(),
+                coin_ids,
+            };
+
+            progress.pending = Some(pending.clone());
+            progress.save(&progress_path)?;
+
+            println!(
+                "Submitted transaction {transaction_id} for {} NFTs",
+                chunk.len()
+            );
+
+            if chunk_status(path.clone(), &pending).await? == ChunkStatus::Failed {
+                progress.pending = None;
+                progress.save(&progress_path)?;
+
+                bail!(
+                    "Transaction {transaction_id} failed, run the command again to mint the chunk again"
+                );
+            }
+
+            progress.minted += chunk.len();
+            progress.pending = None;
+            progress.save(&progress_path)?;
+
+            println!("Minted {} of {} NFTs", progress.minted, mints.len());
+        }
+
+        println!("Minted all {} NFTs", mints.len());
+
+        Ok(())
+    }
+}
+
+/// How far the mint has gotten, which is saved after every step so that it can be resumed.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Progress {
+    /// The number of NFTs at the start of the manifest whose transactions have been confirmed.
+    minted: usize,
+
+    /// The transaction of the next chunk, if it has been submitted but not confirmed yet.
+    pending: Option<PendingChunk>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct PendingChunk {
+    transaction_id: String,
+    count: usize,
+
+    /// The wallet's coins spent by the transaction, which are checked to tell whether it was
+    /// confirmed.
+    coin_ids: Vec<String>,
+}
+
+impl Progress {
+    fn load(path: &Path) -> Result<Self> {
+        if !path.try_exists()? {
+            return Ok(Self::default());
+        }
+
+        Ok(serde_json::from_str(&fs::read_to_string(path)?)?)
+    }
+
+    fn save(&self, path: &Path) -> Result<()> {
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+}
+
+/// The number of times the coins are checked, 5 seconds apart, before giving up.
+const STATUS_CHECKS: usize = 120;
+
+/// The number of checks in a row that the coins need to be unspent before the transaction
+/// is considered to have failed, which covers a few blocks.
+const FAILED_CHECKS: usize = 36;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ChunkStatus {
+    Confirmed,
+    Failed,
+}
+
+/// Waits until the transaction of the chunk is resolved, based on the coins it spends.
+/// It's confirmed once all of them have been spent on chain. It only failed if none of them
+/// are spent or reserved by a pending transaction, and this stays the same for long enough
+/// for the wallet to have synced a block that confirmed it. Otherwise, the command exits
+/// without minting the chunk again, so that it can be checked again later.
+async fn chunk_status(path: PathBuf, pending: &PendingChunk) -> Result<ChunkStatus> {
+    if pending.coin_ids.is_empty() {
+        bail!(
+            "Transaction {} didn't spend any of the wallet's coins, so it can't be checked",
+            pending.transaction_id
+        );
+    }
+
+    let mut unspent_checks = 0;
+
+    for _ in 0..STATUS_CHECKS {
+        wait_for_pending(path.clone()).await?;
+
+        let response = request_rpc::<_, GetCoinsByIdsResponse>(
+            path.clone(),
+            "/get_coins_by_ids",
+            GetCoinsByIds {
+                coin_ids: pending.coin_ids.clone(),
+            },
+        )
+        .await?;
+
+        // Every coin should be synced already, since the wallet spent them.
+        let status = if response.coins.len() == pending.coin_ids.len() {
+            coins_status(&response.coins)
+        } else {
+            None
+        };
+
+        match status {
+            Some(ChunkStatus::Confirmed) => return Ok(ChunkStatus::Confirmed),
+            Some(ChunkStatus::Failed) => {
+                unspent_checks += 1;
+
+                if unspent_checks >= FAILED_CHECKS {
+                    return Ok(ChunkStatus::Failed);
+                }
+            }
+            None => unspent_checks = 0,
+        }
+
+        sleep(Duration::from_secs(5)).await;
+    }
+
+    bail!(
+        "Couldn't tell whether transaction {} was confirmed, run the command again to check it and continue",
+        pending.transaction_id
+    );
+}
+
+/// Returns the status of a chunk from the state of the coins it spends, or `None` if only
+/// some of them are spent, or they are still reserved by a transaction.
+fn coins_status(coins: &[CoinRecord]) -> Option<ChunkStatus> {
+    if coins.iter().all(|coin| coin.spent_height.is_some()) {
+        Some(ChunkStatus::Confirmed)
+    } else if coins
+        .iter()
+        .all(|coin| coin.spent_height.is_none() && coin.spend_transaction_id.is_none())
+    {
+        Some(ChunkStatus::Failed)
+    } else {
+        None
+    }
+}
+
+async fn wait_for_pending(path: PathBuf) -> Result<()> {
+    loop {
+        let response = request_rpc::<_, GetPendingTransactionsResponse>(
+            path.clone(),
+            "/get_pending_transactions",
+            GetPendingTransactions {},
+        )
+        .await?;
+
+        if response.transactions.is_empty() {
+            return Ok(());
+        }
+
+        sleep(Duration::from_secs(5)).await;
+    }
+}
+
+fn parse_mints(text: &str) -> Result<Vec<NftMint>> {
+    let mut mints = Vec::new();
+
+    for record in parse_csv(text, "data_uris")? {
+        let (line, fields) = (record.line, record.fields);
+
+        if fields.len() != 7 {
+            bail!("Expected 7 columns on line {line}");
+        }
+
+        let uris = |field: &str| {
+            field
+                .split_whitespace()
+                .map(str::to_string)
+                .collect::<Vec<_>>()
+        };
+
+        let Ok(edition_number) = parse_optional(&fields[3]) else {
+            bail!("Invalid edition number on line {line}");
+        };
+
+        let Ok(edition_total) = parse_optional(&fields[4]) else {
+            bail!("Invalid edition total on line {line}");
+        };
+
+        let Ok(royalty_ten_thousandths) = parse_optional(&fields[6]) else {
+            bail!("Invalid royalty on line {line}");
+        };
+
+        mints.push(NftMint {
+            edition_number,
+            edition_total,
+            data_uris: uris(&fields[0]),
+            data_hash: None,
+            metadata_uris: uris(&fields[1]),
+            metadata_hash: None,
+            license_uris: uris(&fields[2]),
+            license_hash: None,
+            royalty_address: Some(fields[5].clone()).filter(|address| !address.is_empty()),
+            royalty_ten_thousandths: royalty_ten_thousandths.unwrap_or(0),
+        });
+    }
+
+    if mints.is_empty() {
+        bail!("No mints found");
+    }
+
+    Ok(mints)
+}
+
+fn parse_optional<T: std::str::FromStr>(field: &str) -> Result<Option<T>, T::Err> {
+    if field.is_empty() {
+        Ok(None)
+    } else {
+        field.parse().map(Some)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+
+    use super::*;
+
+    #[test]
+    fn test_parse_mints() -> Result<()> {
+        let mints = parse_mints(
+            "data_uris,metadata_uris,license_uris,edition_number,edition_total,royalty_address,royalty_ten_thousandths\n\
+            \"https://a.com/1.png?size=1,2 https://b.com/1.png\",https://a.com/1.json,,1,2,xch1a,300\n\
+            \n\
+            https://a.com/2.png,,,,,,\n",
+        )?;
+
+        assert_eq!(mints.len(), 2);
+        assert_eq!(
+            mints[0].data_uris,
+            ["https://a.com/1.png?size=1,2", "https://b.com/1.png"]
+        );
+        assert_eq!(mints[0].metadata_uris, ["https://a.com/1.json"]);
+        assert!(mints[0].license_uris.is_empty());
+        assert_eq!(mints[0].edition_number, Some(1));
+        assert_eq!(mints[0].edition_total, Some(2));
+        assert_eq!(mints[0].royalty_address.as_deref(), Some("xch1a"));
+        assert_eq!(mints[0].royalty_ten_thousandths, 300);
+
+        assert_eq!(mints[1].data_uris, ["https://a.com/2.png"]);
+        assert!(mints[1].metadata_uris.is_empty());
+        assert_eq!(mints[1].edition_number, None);
+        assert_eq!(mints[1].royalty_address, None);
+        assert_eq!(mints[1].royalty_ten_thousandths, 0);
+
+        assert!(parse_mints("").is_err());
+        assert!(parse_mints("https://a.com/1.png,,,,,").is_err());
+        assert!(parse_mints("https://a.com/1.png,,,one,,,").is_err());
+        assert!(parse_mints("https://a.com/1.png,,,,,,-1").is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_progress() -> Result<()> {
+        let path = env::temp_dir().join(format!("sage-mint-{}.progress", std::process::id()));
+
+        let progress = Progress::load(&path)?;
+        assert_eq!(progress.minted, 0);
+        assert!(progress.pending.is_none());
+
+        Progress {
+            minted: 25,
+            pending: Some(PendingChunk {
+                transaction_id: "ab".to_string(),
+                count: 10,
+                coin_ids: vec!["cd".to_string()],
+            }),
+        }
+        .save(&path)?;
+
+        let progress = Progress::load(&path)?;
+        fs::remove_file(&path)?;
+
+        assert_eq!(progress.minted, 25);
+        let pending = progress.pending.expect("missing pending chunk");
+        assert_eq!(pending.transaction_id, "ab");
+        assert_eq!(pending.count, 10);
+        assert_eq!(pending.coin_ids, ["cd"]);
+
+        Ok(())
+    }
+
+    fn coin(spent_height: Option<u32>, spend_transaction_id: Option<&str>) -> CoinRecord {
+        CoinRecord {
+            coin_id: "ab".to_string(),
+            address: "xch1a".to_string(),
+            amount: Amount::u64(1),
+            created_height: Some(1),
+            spent_height,
+            create_transaction_id: None,
+            spend_transaction_id: spend_transaction_id.map(str::to_string),
+            offer_id: None,
+        }
+    }
+
+    #[test]
+    fn test_coins_status() {
+        assert_eq!(
+            coins_status(&[coin(Some(5), None), coin(Some(5), None)]),
+            Some(ChunkStatus::Confirmed)
+        );
+        assert_eq!(
+            coins_status(&[coin(None, None), coin(None, None)]),
+            Some(ChunkStatus::Failed)
+        );
+
+        // Partially spent or still reserved coins are unresolved.
+        assert_eq!(coins_status(&[coin(Some(5), None), coin(None, None)]), None);
+        assert_eq!(coins_status(&[coin(None, Some("ef"))]), None);
+    }
+}
@@ -0,0 +1,119 @@
+use std::{fs, path::PathBuf};
+
+use anyhow::{bail, Result};
+use clap::Parser;
+use sage_api::{
+    Amount, Payment, SendCatMulti, SendCatMultiResponse, SendXchMulti, SendXchMultiResponse,
+};
+
+use crate::{csv::parse_csv, router::call_rpc};
+
+/// Sends a single transaction paying out to every row of a CSV file.
+///
+/// Each row has the form `address,amount[,memo...]`, where the amount is in mojos and
+/// memos are hex encoded. Fields containing commas can be quoted. Blank lines, and a leading
+/// header row starting with `address`, are skipped.
+#[derive(Debug, Parser)]
+pub struct PayoutArgs {
+    /// The CSV file containing the payments.
+    file: PathBuf,
+
+    /// Pays out the given CAT instead of XCH.
+    #[clap(long)]
+    asset_id: Option<String>,
+
+    /// The transaction fee, in mojos.
+    #[clap(long, default_value_t = 0)]
+    fee: u64,
+
+    /// Estimates the fee needed to confirm within this many seconds instead.
+    #[clap(long)]
+    fee_target_seconds: Option<u64>,
+
+    /// Submits the transaction rather than only returning its summary.
+    #[clap(long)]
+    auto_submit: bool,
+}
+
+impl PayoutArgs {
+    pub async fn handle(self, path: PathBuf) -> Result<()> {
+        let payments = parse_payments(&fs::read_to_string(&self.file)?)?;
+        let fee = Amount::u64(self.fee);
+
+        if let Some(asset_id) = self.asset_id {
+            let body = SendCatMulti {
+                asset_id,
+                payments,
+                fee,
+                fee_target_seconds: self.fee_target_seconds,
+                coin_ids: Vec::new(),
+                coin_selection_strategy: None,
+                auto_submit: self.auto_submit,
+            };
+            call_rpc::<_, SendCatMultiResponse>(path, "/send_cat_multi", body).await
+        } else {
+            let body = SendXchMulti {
+                payments,
+                fee,
+                fee_target_seconds: self.fee_target_seconds,
+                coin_ids: Vec::new(),
+                coin_selection_strategy: None,
+                auto_submit: self.auto_submit,
+            };
+            call_rpc::<_, SendXchMultiResponse>(path, "/send_xch_multi", body).await
+        }
+    }
+}
+
+fn parse_payments(text: &str) -> Result<Vec<Payment>> {
+    let mut payments = Vec::new();
+
+    for record in parse_csv(text, "address")? {
+        let mut fields = record.fields.into_iter();
+
+        let Some(address) = fields.next().filter(|address| !address.is_empty()) else {
+            bail!("Missing address on line {}", record.line);
+        };
+
+        let Some(amount) = fields.next().and_then(|amount| amount.parse().ok()) else {
+            bail!("Invalid amount on line {}", record.line);
+        };
+
+        payments.push(Payment {
+            address,
+            amount: Amount::u64(amount),
+            memos: fields.filter(|memo| !memo.is_empty()).collect(),
+        });
+    }
+
+    if payments.is_empty() {
+        bail!("No payments found");
+    }
+
+    Ok(payments)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_payments() -> Result<()> {
+        let payments =
+            parse_payments("address,amount,memo\nxch1a,100\n\n xch1b , 5 ,ab,\"\",cd\n")?;
+
+        assert_eq!(payments.len(), 2);
+        assert_eq!(payments[0].address, "xch1a");
+        assert_eq!(payments[0].amount, Amount::u64(100));
+        assert!(payments[0].memos.is_empty());
+        assert_eq!(payments[1].address, "xch1b");
+        assert_eq!(payments[1].amount, Amount::u64(5));
+        assert_eq!(payments[1].memos, ["ab", "cd"]);
+
+        assert!(parse_payments("").is_err());
+        assert!(parse_payments("xch1a,1.5").is_err());
+        assert!(parse_payments(",100").is_err());
+
+        Ok(())
+    }
+}
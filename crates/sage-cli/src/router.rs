@@ -5,26 +5,37 @@ use std::{
     net::SocketAddr,
     path::PathBuf,
     sync::Arc,
+    time::Duration,
 };
 
 use anyhow::{bail, Result};
 use axum::{
     extract::State,
     http::StatusCode,
-    response::{IntoResponse, Response},
-    routing::post,
+    response::{
+        sse::{Event, KeepAlive, Sse},
+        IntoResponse, Response,
+    },
+    routing::{get, post},
     Json, Router,
 };
 use axum_server::tls_rustls::RustlsConfig;
 use clap::Parser;
+use futures_util::{stream, Stream, StreamExt};
 use paste::paste;
 use reqwest::{Client, Identity};
-use sage::Sage;
+use sage::{api_event, needs_hardened_derivations, Sage};
 use sage_api::ErrorKind;
 use sage_config::Config;
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
-use tokio::sync::Mutex;
-use tracing::info;
+use tokio::{
+    sync::{
+        broadcast::{self, error::RecvError},
+        Mutex,
+    },
+    time::sleep,
+};
+use tracing::{error, info, warn};
 
 use crate::{app_state::AppState, tls::load_rustls_config};
 
@@ -69,40 +80,63 @@ routes!(
     rename_key: RenameKey = "/rename_key",
     get_key: GetKey = "/get_key",
     get_secret_key: GetSecretKey = "/get_secret_key",
+    sign_message_by_address await: SignMessageByAddress = "/sign_message_by_address",
+    verify_signature await: VerifySignature = "/verify_signature",
+    unlock_keychain await: UnlockKeychain = "/unlock_keychain",
+    lock_keychain: LockKeychain = "/lock_keychain",
+    change_password: ChangePassword = "/change_password",
     get_keys: GetKeys = "/get_keys",
 
     get_sync_status await: GetSyncStatus = "/get_sync_status",
     get_derivations await: GetDerivations = "/get_derivations",
+    get_next_address await: GetNextAddress = "/get_next_address",
     get_xch_coins await: GetXchCoins = "/get_xch_coins",
     get_cat_coins await: GetCatCoins = "/get_cat_coins",
+    get_coins_by_ids await: GetCoinsByIds = "/get_coins_by_ids",
     get_cats await: GetCats = "/get_cats",
     get_cat await: GetCat = "/get_cat",
     get_dids await: GetDids = "/get_dids",
     get_pending_transactions await: GetPendingTransactions = "/get_pending_transactions",
+    get_clawbacks await: GetClawbacks = "/get_clawbacks",
     get_transactions await: GetTransactions = "/get_transactions",
+    get_transaction await: GetTransaction = "/get_transaction",
+    export_transactions await: ExportTransactions = "/export_transactions",
     get_nft_status await: GetNftStatus = "/get_nft_status",
     get_nft_collections await: GetNftCollections = "/get_nft_collections",
     get_nft_collection await: GetNftCollection = "/get_nft_collection",
     get_nfts await: GetNfts = "/get_nfts",
+    get_nft_traits await: GetNftTraits = "/get_nft_traits",
     get_nft await: GetNft = "/get_nft",
     get_nft_data await: GetNftData = "/get_nft_data",
 
     send_xch await: SendXch = "/send_xch",
+    send_xch_multi await: SendXchMulti = "/send_xch_multi",
     combine_xch await: CombineXch = "/combine_xch",
+    auto_consolidate await: AutoConsolidate = "/auto_consolidate",
+    split_into_denominations await: SplitIntoDenominations = "/split_into_denominations",
     split_xch await: SplitXch = "/split_xch",
     combine_cat await: CombineCat = "/combine_cat",
     split_cat await: SplitCat = "/split_cat",
     issue_cat await: IssueCat = "/issue_cat",
+    mint_more_cat await: MintMoreCat = "/mint_more_cat",
+    melt_cat await: MeltCat = "/melt_cat",
     send_cat await: SendCat = "/send_cat",
+    send_cat_multi await: SendCatMulti = "/send_cat_multi",
     create_did await: CreateDid = "/create_did",
     bulk_mint_nfts await: BulkMintNfts = "/bulk_mint_nfts",
+    prepare_nft_mints await: PrepareNftMints = "/prepare_nft_mints",
     transfer_nfts await: TransferNfts = "/transfer_nfts",
     add_nft_uri await: AddNftUri = "/add_nft_uri",
     assign_nfts_to_did await: AssignNftsToDid = "/assign_nfts_to_did",
     transfer_dids await: TransferDids = "/transfer_dids",
+    claim_clawback await: ClaimClawback = "/claim_clawback",
+    revoke_clawback await: RevokeClawback = "/revoke_clawback",
     sign_coin_spends await: SignCoinSpends = "/sign_coin_spends",
     view_coin_spends await: ViewCoinSpends = "/view_coin_spends",
     submit_transaction await: SubmitTransaction = "/submit_transaction",
+    increase_transaction_fee await: IncreaseTransactionFee = "/increase_transaction_fee",
+    cancel_transaction await: CancelTransaction = "/cancel_transaction",
+    estimate_fee await: EstimateFee = "/estimate_fee",
 
     make_offer await: MakeOffer = "/make_offer",
     take_offer await: TakeOffer = "/take_offer",
@@ -111,6 +145,8 @@ routes!(
     get_offers await: GetOffers = "/get_offers",
     get_offer await: GetOffer = "/get_offer",
     delete_offer await: DeleteOffer = "/delete_offer",
+    cancel_offer await: CancelOffer = "/cancel_offer",
+    cancel_offers await: CancelOffers = "/cancel_offers",
 
     get_peers await: GetPeers = "/get_peers",
     remove_peer await: RemovePeer = "/remove_peer",
@@ -120,27 +156,96 @@ routes!(
     set_network_id await: SetNetworkId = "/set_network_id",
     set_derive_automatically: SetDeriveAutomatically = "/set_derive_automatically",
     set_derivation_batch_size: SetDerivationBatchSize = "/set_derivation_batch_size",
+    set_hardened_addresses await: SetHardenedAddresses = "/set_hardened_addresses",
+    set_coin_selection_strategy: SetCoinSelectionStrategy = "/set_coin_selection_strategy",
+    set_auto_consolidate: SetAutoConsolidate = "/set_auto_consolidate",
     get_networks: GetNetworks = "/get_networks",
 
     remove_cat await: RemoveCat = "/remove_cat",
     update_cat await: UpdateCat = "/update_cat",
     update_did await: UpdateDid = "/update_did",
     update_nft await: UpdateNft = "/update_nft",
+    retry_nft_data await: RetryNftData = "/retry_nft_data",
+    update_transaction await: UpdateTransaction = "/update_transaction",
+    update_derivation await: UpdateDerivation = "/update_derivation",
+
+    get_contacts await: GetContacts = "/get_contacts",
+    add_contact await: AddContact = "/add_contact",
+    update_contact await: UpdateContact = "/update_contact",
+    delete_contact await: DeleteContact = "/delete_contact",
+
+    create_invoice await: CreateInvoice = "/create_invoice",
+    get_invoices await: GetInvoices = "/get_invoices",
+    get_invoice await: GetInvoice = "/get_invoice",
+    parse_payment_uri: ParsePaymentUri = "/parse_payment_uri",
 );
 
 async fn start_rpc(path: PathBuf) -> Result<()> {
     let mut app = Sage::new(&path);
     let mut receiver = app.initialize().await?;
 
-    tokio::spawn(async move { while let Some(_message) = receiver.recv().await {} });
-
     let addr: SocketAddr = ([127, 0, 0, 1], app.config.rpc.server_port).into();
     info!("RPC server is listening at {addr}");
 
-    let app = api_router().with_state(AppState {
-        sage: Arc::new(Mutex::new(app)),
+    let sage = Arc::new(Mutex::new(app));
+
+    let (events, _) = broadcast::channel(256);
+    let sender = events.clone();
+    let event_sage = sage.clone();
+
+    tokio::spawn(async move {
+        while let Some(event) = receiver.recv().await {
+            // This runs separately so that events keep draining while a command holds the lock.
+            if needs_hardened_derivations(&event) {
+                let sage = event_sage.clone();
+
+                tokio::spawn(async move {
+                    if let Err(error) = sage.lock().await.sync_hardened_derivations().await {
+                        error!("Error while deriving hardened keys: {error:?}");
+                    }
+                });
+            }
+
+            // If nobody is subscribed to the event stream, the event is simply dropped.
+            let _ = sender.send(api_event(event));
+        }
+    });
+
+    let consolidation_sage = sage.clone();
+
+    tokio::spawn(async move {
+        loop {
+            let interval = consolidation_sage
+                .lock()
+                .await
+                .auto_consolidate_config()
+                .interval_seconds;
+
+            sleep(Duration::from_secs(interval.max(60))).await;
+
+            // The transaction is submitted after the lock is released, since that waits on a peer.
+            let result = consolidation_sage
+                .lock()
+                .await
+                .prepare_auto_consolidation()
+                .await;
+
+            let result = match result {
+                Ok(Some(submission)) => submission.submit().await,
+                Ok(None) => Ok(()),
+                Err(error) => Err(error),
+            };
+
+            if let Err(error) = result {
+                error!("Error while consolidating coins: {error:?}");
+            }
+        }
     });
 
+    let app = api_router()
+        .route("/events", get(sync_events))
+        .with_state(AppState { sage, events });
+
     let config = load_rustls_config(
         path.join("ssl")
             .join("wallet.crt")
@@ -159,11 +264,47 @@ async fn start_rpc(path: PathBuf) -> Result<()> {
     Ok(())
 }
 
+/// Streams sync events to the client as server-sent events, each encoded as JSON.
+async fn sync_events(
+    State(state): State<AppState>,
+) -> Sse<impl Stream<Item = Result<Event, axum::Error>>> {
+    let stream =
+        event_stream(state.events.subscribe()).map(|event| Event::default().json_data(event));
+    Sse::new(stream).keep_alive(KeepAlive::default())
+}
+
+/// Yields events until the sender is dropped. A client that falls behind skips the events it missed.
+fn event_stream<T: Clone>(receiver: broadcast::Receiver<T>) -> impl Stream<Item = T> {
+    stream::unfold(receiver, |mut receiver| async move {
+        loop {
+            match receiver.recv().await {
+                Ok(event) => return Some((event, receiver)),
+                Err(RecvError::Lagged(skipped)) => {
+                    warn!("Event stream fell behind, skipped {skipped} events");
+                }
+                Err(RecvError::Closed) => return None,
+            }
+        }
+    })
+}
+
 pub async fn call_rpc<T: Serialize, R: Serialize + DeserializeOwned>(
     path: PathBuf,
     url: &str,
     body: T,
 ) -> Result<()> {
+    let json = request_rpc::<T, R>(path, url, body).await?;
+
+    println!("{}", serde_json::to_string_pretty(&json)?);
+
+    Ok(())
+}
+
+pub async fn request_rpc<T: Serialize, R: DeserializeOwned>(
+    path: PathBuf,
+    url: &str,
+    body: T,
+) -> Result<R> {
     let addr = if let Ok(addr) = env::var("SAGE_RPC_HOST") {
         addr.parse::<SocketAddr>()?
     } else {
@@ -212,11 +353,7 @@ pub async fn call_rpc<T: Serialize, R: Serialize + DeserializeOwned>(
         bail!(response.text().await?);
     }
 
-    let json = response.json::<R>().await?;
-
-    println!("{}", serde_json::to_string_pretty(&json)?);
-
-    Ok(())
+    Ok(response.json::<R>().await?)
 }
 
 fn parse_with_serde<T: for<'de> Deserialize<'de>>(s: &str) -> Result<T, String> {
@@ -234,9 +371,51 @@ where
                 ErrorKind::Api => StatusCode::BAD_REQUEST,
                 ErrorKind::NotFound => StatusCode::NOT_FOUND,
                 ErrorKind::Unauthorized => StatusCode::UNAUTHORIZED,
+                ErrorKind::Locked => StatusCode::LOCKED,
                 ErrorKind::Wallet | ErrorKind::Internal => StatusCode::INTERNAL_SERVER_ERROR,
             };
             (status, error.to_string()).into_response()
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use sage_api::SyncEvent;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_event_stream() {
+        let (sender, receiver) = broadcast::channel(2);
+
+        sender.send(SyncEvent::Stop).ok();
+        sender.send(SyncEvent::Subscribed).ok();
+        drop(sender);
+
+        let events: Vec<SyncEvent> = event_stream(receiver).collect().await;
+
+        assert!(matches!(
+            events.as_slice(),
+            [SyncEvent::Stop, SyncEvent::Subscribed]
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_event_stream_lagged() {
+        let (sender, receiver) = broadcast::channel(2);
+
+        sender.send(SyncEvent::Stop).ok();
+        sender.send(SyncEvent::Subscribed).ok();
+        sender.send(SyncEvent::Derivation).ok();
+        drop(sender);
+
+        // The oldest event no longer fits in the channel, so it's skipped rather than ending the stream.
+        let events: Vec<SyncEvent> = event_stream(receiver).collect().await;
+
+        assert!(matches!(
+            events.as_slice(),
+            [SyncEvent::Subscribed, SyncEvent::Derivation]
+        ));
+    }
+}
@@ -6,6 +6,8 @@ use specta::Type;
 pub struct AppConfig {
     pub log_level: String,
     pub active_fingerprint: Option<u32>,
+    /// How long the keychain can go unused before it's locked again. Zero disables auto-lock.
+    pub auto_lock_minutes: u32,
 }
 
 impl Default for AppConfig {
@@ -13,6 +15,7 @@ impl Default for AppConfig {
         Self {
             log_level: "INFO".to_string(),
             active_fingerprint: None,
+            auto_lock_minutes: 15,
         }
     }
 }
@@ -0,0 +1,18 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Type)]
+#[serde(default)]
+pub struct CacheConfig {
+    /// The total size of NFT media cached on disk, in megabytes, before the least recently used
+    /// media is evicted.
+    pub media_size_mb: u64,
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        Self {
+            media_size_mb: 2048,
+        }
+    }
+}
@@ -2,7 +2,7 @@ use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
 use specta::Type;
 
-use crate::{AppConfig, NetworkConfig, RpcConfig, WalletConfig};
+use crate::{AppConfig, CacheConfig, GatewayConfig, NetworkConfig, RpcConfig, WalletConfig};
 
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
 #[serde(default)]
@@ -12,6 +12,8 @@ pub struct Config {
     #[serde(skip_serializing_if = "IndexMap::is_empty")]
     pub wallets: IndexMap<String, WalletConfig>,
     pub network: NetworkConfig,
+    pub gateways: GatewayConfig,
+    pub cache: CacheConfig,
 }
 
 impl Default for Config {
@@ -21,6 +23,8 @@ impl Default for Config {
             rpc: RpcConfig::default(),
             wallets: IndexMap::new(),
             network: NetworkConfig::default(),
+            gateways: GatewayConfig::default(),
+            cache: CacheConfig::default(),
         }
     }
 }
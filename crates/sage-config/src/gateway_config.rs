@@ -0,0 +1,30 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+/// HTTP gateways used to fetch NFT data stored on IPFS or Arweave.
+/// Each list is tried in order until one of the gateways returns the expected data.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(default)]
+pub struct GatewayConfig {
+    pub ipfs: Vec<String>,
+    pub arweave: Vec<String>,
+    /// How long to wait for each gateway to return the data before moving on to the next one.
+    pub timeout_seconds: u64,
+}
+
+impl Default for GatewayConfig {
+    fn default() -> Self {
+        Self {
+            ipfs: vec![
+                "https://ipfs.io/ipfs/".to_string(),
+                "https://dweb.link/ipfs/".to_string(),
+                "https://w3s.link/ipfs/".to_string(),
+            ],
+            arweave: vec![
+                "https://arweave.net/".to_string(),
+                "https://ar-io.net/".to_string(),
+            ],
+            timeout_seconds: 10,
+        }
+    }
+}
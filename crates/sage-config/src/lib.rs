@@ -1,12 +1,16 @@
 mod app_config;
+mod cache_config;
 mod config;
+mod gateway_config;
 mod network;
 mod network_config;
 mod rpc_config;
 mod wallet_config;
 
 pub use app_config::*;
+pub use cache_config::*;
 pub use config::*;
+pub use gateway_config::*;
 pub use network::*;
 pub use network_config::*;
 pub use rpc_config::*;
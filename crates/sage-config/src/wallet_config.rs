@@ -7,6 +7,9 @@ pub struct WalletConfig {
     pub name: String,
     pub derive_automatically: bool,
     pub derivation_batch_size: u32,
+    pub hardened_addresses: bool,
+    pub coin_selection_strategy: CoinSelectionStrategy,
+    pub auto_consolidate: AutoConsolidateConfig,
 }
 
 impl Default for WalletConfig {
@@ -15,6 +18,58 @@ impl Default for WalletConfig {
             name: "Unnamed Wallet".to_string(),
             derive_automatically: true,
             derivation_batch_size: 500,
+            hardened_addresses: false,
+            coin_selection_strategy: CoinSelectionStrategy::default(),
+            auto_consolidate: AutoConsolidateConfig::default(),
         }
     }
 }
+
+/// Periodically merges the dust coins of every asset in the wallet into one coin per asset.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(default)]
+pub struct AutoConsolidateConfig {
+    pub enabled: bool,
+    pub interval_seconds: u64,
+    pub max_coins_per_asset: u32,
+    pub dust_threshold: u64,
+    pub fee: u64,
+}
+
+impl Default for AutoConsolidateConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            interval_seconds: 24 * 60 * 60,
+            max_coins_per_asset: 100,
+            dust_threshold: 1_000_000,
+            fee: 0,
+        }
+    }
+}
+
+/// How the inputs of a transaction are chosen when they aren't pinned.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum CoinSelectionStrategy {
+    /// Uses the selection algorithm of the wallet SDK.
+    #[default]
+    Automatic,
+
+    /// Spends the largest coins first, which keeps the number of inputs low.
+    LargestFirst,
+
+    /// Spends the smallest coins first, which consolidates dust over time.
+    SmallestFirst,
+
+    /// Spends the coins that were created the longest time ago first.
+    OldestFirst,
+
+    /// Searches for coins that add up to exactly the amount, so that no change is created.
+    /// Falls back to automatic selection if there is no exact match.
+    ExactMatch,
+
+    /// Spends coins received by only one address, so that addresses aren't linked together.
+    /// Falls back to automatic selection if no single address has enough to cover the amount.
+    SingleAddress,
+}
@@ -0,0 +1,50 @@
+use sqlx::SqliteExecutor;
+
+use crate::{to_u64, Database, Result};
+
+impl Database {
+    pub async fn block_timestamp(&self, height: u32) -> Result<Option<u64>> {
+        block_timestamp(&self.pool, height).await
+    }
+
+    pub async fn insert_block_timestamp(&self, height: u32, timestamp: u64) -> Result<()> {
+        insert_block_timestamp(&self.pool, height, timestamp).await
+    }
+}
+
+async fn block_timestamp(conn: impl SqliteExecutor<'_>, height: u32) -> Result<Option<u64>> {
+    sqlx::query!(
+        "
+        SELECT `timestamp`
+        FROM `block_timestamps`
+        WHERE `height` = ?
+        ",
+        height
+    )
+    .fetch_optional(conn)
+    .await?
+    .map(|row| to_u64(&row.timestamp))
+    .transpose()
+}
+
+async fn insert_block_timestamp(
+    conn: impl SqliteExecutor<'_>,
+    height: u32,
+    timestamp: u64,
+) -> Result<()> {
+    let timestamp = timestamp.to_be_bytes();
+    let timestamp = timestamp.as_ref();
+
+    sqlx::query!(
+        "
+        REPLACE INTO `block_timestamps` (`height`, `timestamp`)
+        VALUES (?, ?)
+        ",
+        height,
+        timestamp
+    )
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
@@ -0,0 +1,120 @@
+use chia::protocol::Bytes32;
+use sqlx::SqliteExecutor;
+
+use crate::{
+    into_row, ClawbackCoinRow, ClawbackCoinSql, ClawbackRow, Database, DatabaseTx, Result,
+};
+
+impl Database {
+    pub async fn clawback_coin(&self, coin_id: Bytes32) -> Result<Option<ClawbackCoinRow>> {
+        clawback_coin(&self.pool, coin_id).await
+    }
+
+    /// Returns the clawback coins sent to or from the wallet that haven't been spent yet.
+    pub async fn unspent_clawback_coins(&self) -> Result<Vec<ClawbackCoinRow>> {
+        unspent_clawback_coins(&self.pool).await
+    }
+}
+
+impl DatabaseTx<'_> {
+    pub async fn insert_clawback_coin(&mut self, coin_id: Bytes32, row: ClawbackRow) -> Result<()> {
+        insert_clawback_coin(&mut *self.tx, coin_id, row).await
+    }
+}
+
+async fn insert_clawback_coin(
+    conn: impl SqliteExecutor<'_>,
+    coin_id: Bytes32,
+    row: ClawbackRow,
+) -> Result<()> {
+    let coin_id = coin_id.as_ref();
+    let sender_puzzle_hash = row.sender_puzzle_hash.as_ref();
+    let recipient_puzzle_hash = row.recipient_puzzle_hash.as_ref();
+    let seconds = row.seconds.to_be_bytes();
+    let seconds = seconds.as_ref();
+    let asset_id = row.asset_id.as_deref();
+    let parent_parent_coin_id = row
+        .lineage_proof
+        .map(|lineage_proof| lineage_proof.parent_parent_coin_info);
+    let parent_parent_coin_id = parent_parent_coin_id.as_deref();
+    let parent_inner_puzzle_hash = row
+        .lineage_proof
+        .map(|lineage_proof| lineage_proof.parent_inner_puzzle_hash);
+    let parent_inner_puzzle_hash = parent_inner_puzzle_hash.as_deref();
+    let parent_amount = row
+        .lineage_proof
+        .map(|lineage_proof| lineage_proof.parent_amount.to_be_bytes().to_vec());
+
+    sqlx::query!(
+        "
+        INSERT OR IGNORE INTO `clawback_coins` (
+            `coin_id`,
+            `sender_puzzle_hash`,
+            `recipient_puzzle_hash`,
+            `seconds`,
+            `asset_id`,
+            `parent_parent_coin_id`,
+            `parent_inner_puzzle_hash`,
+            `parent_amount`
+        )
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+        ",
+        coin_id,
+        sender_puzzle_hash,
+        recipient_puzzle_hash,
+        seconds,
+        asset_id,
+        parent_parent_coin_id,
+        parent_inner_puzzle_hash,
+        parent_amount
+    )
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+async fn clawback_coin(
+    conn: impl SqliteExecutor<'_>,
+    coin_id: Bytes32,
+) -> Result<Option<ClawbackCoinRow>> {
+    let coin_id = coin_id.as_ref();
+
+    let row = sqlx::query_as!(
+        ClawbackCoinSql,
+        "
+        SELECT
+            `parent_coin_id`, `puzzle_hash`, `amount`, `created_height`, `spent_height`,
+            `sender_puzzle_hash`, `recipient_puzzle_hash`, `seconds`, `asset_id`,
+            `parent_parent_coin_id`, `parent_inner_puzzle_hash`, `parent_amount`
+        FROM `coin_states`
+        INNER JOIN `clawback_coins` ON `coin_states`.`coin_id` = `clawback_coins`.`coin_id`
+        WHERE `coin_states`.`coin_id` = ?
+        ",
+        coin_id
+    )
+    .fetch_optional(conn)
+    .await?;
+
+    row.map(into_row).transpose()
+}
+
+async fn unspent_clawback_coins(conn: impl SqliteExecutor<'_>) -> Result<Vec<ClawbackCoinRow>> {
+    let rows = sqlx::query_as!(
+        ClawbackCoinSql,
+        "
+        SELECT
+            `parent_coin_id`, `puzzle_hash`, `amount`, `created_height`, `spent_height`,
+            `sender_puzzle_hash`, `recipient_puzzle_hash`, `seconds`, `asset_id`,
+            `parent_parent_coin_id`, `parent_inner_puzzle_hash`, `parent_amount`
+        FROM `coin_states`
+        INNER JOIN `clawback_coins` ON `coin_states`.`coin_id` = `clawback_coins`.`coin_id`
+        WHERE `coin_states`.`spent_height` IS NULL
+        ORDER BY `coin_states`.`created_height` ASC
+        "
+    )
+    .fetch_all(conn)
+    .await?;
+
+    rows.into_iter().map(into_row).collect()
+}
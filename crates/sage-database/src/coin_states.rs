@@ -14,6 +14,12 @@ impl Database {
     }
 
     pub async fn coin_state(&self, coin_id: Bytes32) -> Result<Option<CoinState>> {
+        Ok(coin_state(&self.pool, coin_id)
+            .await?
+            .map(|row| row.coin_state))
+    }
+
+    pub async fn coin_state_row(&self, coin_id: Bytes32) -> Result<Option<CoinStateRow>> {
         coin_state(&self.pool, coin_id).await
     }
 
@@ -301,7 +307,10 @@ async fn synced_coin_count(conn: impl SqliteExecutor<'_>) -> Result<u32> {
     Ok(row.synced.try_into()?)
 }
 
-async fn coin_state(conn: impl SqliteExecutor<'_>, coin_id: Bytes32) -> Result<Option<CoinState>> {
+async fn coin_state(
+    conn: impl SqliteExecutor<'_>,
+    coin_id: Bytes32,
+) -> Result<Option<CoinStateRow>> {
     let coin_id = coin_id.as_ref();
 
     let Some(sql) = sqlx::query_as!(
@@ -319,7 +328,7 @@ async fn coin_state(conn: impl SqliteExecutor<'_>, coin_id: Bytes32) -> Result<O
         return Ok(None);
     };
 
-    Ok(Some(sql.into_row()?.coin_state))
+    Ok(Some(sql.into_row()?))
 }
 
 async fn unspent_nft_coin_ids(conn: impl SqliteExecutor<'_>) -> Result<Vec<Bytes32>> {
@@ -399,10 +408,13 @@ async fn is_coin_locked(conn: impl SqliteExecutor<'_>, coin_id: Bytes32) -> Resu
         FROM `coin_states`
         LEFT JOIN `transaction_spends` ON `coin_states`.`coin_id` = `transaction_spends`.`coin_id`
         LEFT JOIN `offered_coins` ON `coin_states`.`coin_id` = `offered_coins`.`coin_id`
+        LEFT JOIN `offers` ON `offered_coins`.`offer_id` = `offers`.`offer_id`
         WHERE `coin_states`.`coin_id` = ?
-        AND `offer_id` IS NULL
-        AND `coin_states`.`transaction_id` IS NULL
-        AND `transaction_spends`.`transaction_id` IS NULL
+        AND (
+            `coin_states`.`transaction_id` IS NOT NULL
+            OR `transaction_spends`.`transaction_id` IS NOT NULL
+            OR `offers`.`status` = 0
+        )
         ",
         coin_id
     )
@@ -0,0 +1,222 @@
+use chia::protocol::Bytes32;
+use sqlx::SqliteExecutor;
+
+use crate::{into_row, ContactRow, ContactSql, Database, Result};
+
+impl Database {
+    pub async fn contacts(&self) -> Result<Vec<ContactRow>> {
+        contacts(&self.pool).await
+    }
+
+    pub async fn contact(&self, id: u32) -> Result<Option<ContactRow>> {
+        contact(&self.pool, id).await
+    }
+
+    pub async fn contact_by_puzzle_hash(&self, puzzle_hash: Bytes32) -> Result<Option<ContactRow>> {
+        contact_by_puzzle_hash(&self.pool, puzzle_hash).await
+    }
+
+    /// Inserts a new contact and returns its id.
+    pub async fn insert_contact(
+        &self,
+        name: String,
+        puzzle_hash: Bytes32,
+        did_id: Option<Bytes32>,
+        notes: Option<String>,
+    ) -> Result<u32> {
+        insert_contact(&self.pool, name, puzzle_hash, did_id, notes).await
+    }
+
+    pub async fn update_contact(&self, row: ContactRow) -> Result<()> {
+        update_contact(&self.pool, row).await
+    }
+
+    pub async fn delete_contact(&self, id: u32) -> Result<()> {
+        delete_contact(&self.pool, id).await
+    }
+}
+
+async fn contacts(conn: impl SqliteExecutor<'_>) -> Result<Vec<ContactRow>> {
+    sqlx::query_as!(
+        ContactSql,
+        "
+        SELECT `id`, `name`, `puzzle_hash`, `did_id`, `notes`
+        FROM `contacts`
+        ORDER BY `name` ASC, `id` ASC
+        "
+    )
+    .fetch_all(conn)
+    .await?
+    .into_iter()
+    .map(into_row)
+    .collect()
+}
+
+async fn contact(conn: impl SqliteExecutor<'_>, id: u32) -> Result<Option<ContactRow>> {
+    sqlx::query_as!(
+        ContactSql,
+        "
+        SELECT `id`, `name`, `puzzle_hash`, `did_id`, `notes`
+        FROM `contacts`
+        WHERE `id` = ?
+        ",
+        id
+    )
+    .fetch_optional(conn)
+    .await?
+    .map(into_row)
+    .transpose()
+}
+
+async fn contact_by_puzzle_hash(
+    conn: impl SqliteExecutor<'_>,
+    puzzle_hash: Bytes32,
+) -> Result<Option<ContactRow>> {
+    let puzzle_hash = puzzle_hash.as_ref();
+
+    sqlx::query_as!(
+        ContactSql,
+        "
+        SELECT `id`, `name`, `puzzle_hash`, `did_id`, `notes`
+        FROM `contacts`
+        WHERE `puzzle_hash` = ?
+        ",
+        puzzle_hash
+    )
+    .fetch_optional(conn)
+    .await?
+    .map(into_row)
+    .transpose()
+}
+
+async fn insert_contact(
+    conn: impl SqliteExecutor<'_>,
+    name: String,
+    puzzle_hash: Bytes32,
+    did_id: Option<Bytes32>,
+    notes: Option<String>,
+) -> Result<u32> {
+    let puzzle_hash = puzzle_hash.as_ref();
+    let did_id = did_id.as_deref();
+
+    let id = sqlx::query!(
+        "
+        INSERT INTO `contacts` (`name`, `puzzle_hash`, `did_id`, `notes`)
+        VALUES (?, ?, ?, ?)
+        ",
+        name,
+        puzzle_hash,
+        did_id,
+        notes
+    )
+    .execute(conn)
+    .await?
+    .last_insert_rowid();
+
+    Ok(id.try_into()?)
+}
+
+async fn update_contact(conn: impl SqliteExecutor<'_>, row: ContactRow) -> Result<()> {
+    let puzzle_hash = row.puzzle_hash.as_ref();
+    let did_id = row.did_id.as_deref();
+
+    sqlx::query!(
+        "
+        UPDATE `contacts`
+        SET `name` = ?, `puzzle_hash` = ?, `did_id` = ?, `notes` = ?
+        WHERE `id` = ?
+        ",
+        row.name,
+        puzzle_hash,
+        did_id,
+        row.notes,
+        row.id
+    )
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+async fn delete_contact(conn: impl SqliteExecutor<'_>, id: u32) -> Result<()> {
+    sqlx::query!(
+        "
+        DELETE FROM `contacts`
+        WHERE `id` = ?
+        ",
+        id
+    )
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use test_log::test;
+
+    use crate::test_database;
+
+    use super::*;
+
+    #[test(tokio::test)]
+    async fn test_contacts() -> anyhow::Result<()> {
+        let db = test_database().await?;
+
+        let alice = Bytes32::new([1; 32]);
+        let bob = Bytes32::new([2; 32]);
+
+        let alice_id = db
+            .insert_contact("Alice".to_string(), alice, None, None)
+            .await?;
+
+        let contact = db.contact_by_puzzle_hash(alice).await?;
+        assert_eq!(contact.map(|row| row.id), Some(alice_id));
+        assert!(db.contact_by_puzzle_hash(bob).await?.is_none());
+
+        // An address can only belong to a single contact.
+        assert!(db
+            .insert_contact("Mallory".to_string(), alice, None, None)
+            .await
+            .is_err());
+
+        let bob_id = db
+            .insert_contact("Bob".to_string(), bob, None, Some("Notes".to_string()))
+            .await?;
+
+        assert!(db
+            .update_contact(ContactRow {
+                id: bob_id,
+                name: "Bob".to_string(),
+                puzzle_hash: alice,
+                did_id: None,
+                notes: None,
+            })
+            .await
+            .is_err());
+
+        db.update_contact(ContactRow {
+            id: alice_id,
+            name: "Carol".to_string(),
+            puzzle_hash: alice,
+            did_id: Some(bob),
+            notes: None,
+        })
+        .await?;
+
+        let names: Vec<String> = db
+            .contacts()
+            .await?
+            .into_iter()
+            .map(|row| row.name)
+            .collect();
+        assert_eq!(names, ["Bob", "Carol"]);
+
+        db.delete_contact(alice_id).await?;
+        assert!(db.contact(alice_id).await?.is_none());
+        assert!(db.contact_by_puzzle_hash(alice).await?.is_none());
+
+        Ok(())
+    }
+}
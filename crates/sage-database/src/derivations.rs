@@ -6,29 +6,53 @@ use crate::{
 };
 
 impl Database {
-    pub async fn unhardened_derivations(
+    pub async fn derivations(
         &self,
+        hardened: bool,
         limit: u32,
         offset: u32,
     ) -> Result<Vec<DerivationRow>> {
-        unhardened_derivations(&self.pool, limit, offset).await
+        derivations(&self.pool, hardened, limit, offset).await
+    }
+
+    pub async fn p2_puzzle_hashes(&self, hardened: bool) -> Result<Vec<Bytes32>> {
+        p2_puzzle_hashes(&self.pool, hardened).await
     }
 
-    pub async fn p2_puzzle_hashes(&self) -> Result<Vec<Bytes32>> {
-        p2_puzzle_hashes(&self.pool).await
+    pub async fn derivation_index(&self, hardened: bool) -> Result<u32> {
+        derivation_index(&self.pool, hardened).await
     }
 
     pub async fn synthetic_key(&self, p2_puzzle_hash: Bytes32) -> Result<PublicKey> {
         synthetic_key(&self.pool, p2_puzzle_hash).await
     }
 
-    pub async fn synthetic_key_index(&self, synthetic_key: PublicKey) -> Result<Option<u32>> {
-        synthetic_key_index(&self.pool, synthetic_key).await
+    pub async fn synthetic_key_derivation(
+        &self,
+        synthetic_key: PublicKey,
+    ) -> Result<Option<DerivationRow>> {
+        synthetic_key_derivation(&self.pool, synthetic_key).await
     }
 
     pub async fn is_p2_puzzle_hash(&self, p2_puzzle_hash: Bytes32) -> Result<bool> {
         is_p2_puzzle_hash(&self.pool, p2_puzzle_hash).await
     }
+
+    pub async fn derivation(&self, p2_puzzle_hash: Bytes32) -> Result<Option<DerivationRow>> {
+        derivation(&self.pool, p2_puzzle_hash).await
+    }
+
+    pub async fn derivation_label(&self, p2_puzzle_hash: Bytes32) -> Result<Option<String>> {
+        derivation_label(&self.pool, p2_puzzle_hash).await
+    }
+
+    pub async fn set_derivation_label(
+        &self,
+        p2_puzzle_hash: Bytes32,
+        label: Option<String>,
+    ) -> Result<()> {
+        set_derivation_label(&self.pool, p2_puzzle_hash, label).await
+    }
 }
 
 impl<'a> DatabaseTx<'a> {
@@ -57,6 +81,10 @@ impl<'a> DatabaseTx<'a> {
         max_used_derivation_index(&mut *self.tx, hardened).await
     }
 
+    pub async fn max_reusable_derivation_index(&mut self, hardened: bool) -> Result<Option<u32>> {
+        max_reusable_derivation_index(&mut *self.tx, hardened).await
+    }
+
     pub async fn synthetic_key(&mut self, p2_puzzle_hash: Bytes32) -> Result<PublicKey> {
         synthetic_key(&mut *self.tx, p2_puzzle_hash).await
     }
@@ -68,6 +96,18 @@ impl<'a> DatabaseTx<'a> {
     pub async fn is_p2_puzzle_hash(&mut self, p2_puzzle_hash: Bytes32) -> Result<bool> {
         is_p2_puzzle_hash(&mut *self.tx, p2_puzzle_hash).await
     }
+
+    pub async fn reserve_derivation(&mut self, p2_puzzle_hash: Bytes32) -> Result<()> {
+        reserve_derivation(&mut *self.tx, p2_puzzle_hash).await
+    }
+
+    pub async fn set_derivation_label(
+        &mut self,
+        p2_puzzle_hash: Bytes32,
+        label: Option<String>,
+    ) -> Result<()> {
+        set_derivation_label(&mut *self.tx, p2_puzzle_hash, label).await
+    }
 }
 
 async fn insert_derivation(
@@ -111,9 +151,44 @@ async fn derivation_index(conn: impl SqliteExecutor<'_>, hardened: bool) -> Resu
     .try_into()?)
 }
 
+/// Addresses which have been reserved, either explicitly or for an invoice, count as used.
+/// This way they're never handed out again, even before anything has been received.
 async fn max_used_derivation_index(
     conn: impl SqliteExecutor<'_>,
     hardened: bool,
+) -> Result<Option<u32>> {
+    let row = sqlx::query!(
+        "
+        SELECT MAX(`index`) AS `max_index`
+        FROM `derivations`
+        WHERE (
+            EXISTS (
+                SELECT 1 FROM `coin_states`
+                WHERE `puzzle_hash` = `p2_puzzle_hash`
+                OR `hint` = `p2_puzzle_hash`
+            )
+            OR EXISTS (
+                SELECT 1 FROM `reserved_derivations`
+                WHERE `reserved_derivations`.`p2_puzzle_hash` = `derivations`.`p2_puzzle_hash`
+            )
+            OR EXISTS (
+                SELECT 1 FROM `invoices`
+                WHERE `invoices`.`p2_puzzle_hash` = `derivations`.`p2_puzzle_hash`
+            )
+        )
+        AND `hardened` = ?
+        ",
+        hardened
+    )
+    .fetch_one(conn)
+    .await?;
+    Ok(row.max_index.map(TryInto::try_into).transpose()?)
+}
+
+/// Used addresses which can be reused, excluding those reserved for an invoice.
+async fn max_reusable_derivation_index(
+    conn: impl SqliteExecutor<'_>,
+    hardened: bool,
 ) -> Result<Option<u32>> {
     let row = sqlx::query!(
         "
@@ -124,6 +199,10 @@ async fn max_used_derivation_index(
             WHERE `puzzle_hash` = `p2_puzzle_hash`
             OR `hint` = `p2_puzzle_hash`
         )
+        AND NOT EXISTS (
+            SELECT 1 FROM `invoices`
+            WHERE `invoices`.`p2_puzzle_hash` = `derivations`.`p2_puzzle_hash`
+        )
         AND `hardened` = ?
         ",
         hardened
@@ -133,13 +212,15 @@ async fn max_used_derivation_index(
     Ok(row.max_index.map(TryInto::try_into).transpose()?)
 }
 
-async fn p2_puzzle_hashes(conn: impl SqliteExecutor<'_>) -> Result<Vec<Bytes32>> {
+async fn p2_puzzle_hashes(conn: impl SqliteExecutor<'_>, hardened: bool) -> Result<Vec<Bytes32>> {
     let rows = sqlx::query!(
         "
         SELECT `p2_puzzle_hash`
         FROM `derivations`
-        ORDER BY `index` ASC, `hardened` ASC
-        "
+        WHERE `hardened` = ?
+        ORDER BY `index` ASC
+        ",
+        hardened
     )
     .fetch_all(conn)
     .await?;
@@ -148,8 +229,9 @@ async fn p2_puzzle_hashes(conn: impl SqliteExecutor<'_>) -> Result<Vec<Bytes32>>
         .collect::<Result<_>>()
 }
 
-async fn unhardened_derivations(
+async fn derivations(
     conn: impl SqliteExecutor<'_>,
+    hardened: bool,
     limit: u32,
     offset: u32,
 ) -> Result<Vec<DerivationRow>> {
@@ -157,10 +239,11 @@ async fn unhardened_derivations(
         DerivationSql,
         "
         SELECT * FROM `derivations`
-        WHERE `hardened` = 0
+        WHERE `hardened` = ?
         ORDER BY `index` ASC
         LIMIT ? OFFSET ?
         ",
+        hardened,
         limit,
         offset
     )
@@ -190,25 +273,24 @@ async fn synthetic_key(
     Ok(PublicKey::from_bytes(&to_bytes(bytes)?)?)
 }
 
-async fn synthetic_key_index(
+async fn synthetic_key_derivation(
     conn: impl SqliteExecutor<'_>,
     synthetic_key: PublicKey,
-) -> Result<Option<u32>> {
+) -> Result<Option<DerivationRow>> {
     let synthetic_key = synthetic_key.to_bytes();
     let synthetic_key_ref = synthetic_key.as_ref();
-    Ok(sqlx::query!(
+    sqlx::query_as!(
+        DerivationSql,
         "
-        SELECT `index`
-        FROM `derivations`
+        SELECT * FROM `derivations`
         WHERE `synthetic_key` = ?
-        AND `hardened` = 0
         ",
         synthetic_key_ref
     )
     .fetch_optional(conn)
     .await?
-    .map(|row| row.index.try_into())
-    .transpose()?)
+    .map(into_row)
+    .transpose()
 }
 
 async fn p2_puzzle_hash(
@@ -244,3 +326,142 @@ async fn is_p2_puzzle_hash(conn: impl SqliteExecutor<'_>, p2_puzzle_hash: Bytes3
     .count
         > 0)
 }
+
+async fn derivation(
+    conn: impl SqliteExecutor<'_>,
+    p2_puzzle_hash: Bytes32,
+) -> Result<Option<DerivationRow>> {
+    let p2_puzzle_hash = p2_puzzle_hash.as_ref();
+    sqlx::query_as!(
+        DerivationSql,
+        "
+        SELECT * FROM `derivations`
+        WHERE `p2_puzzle_hash` = ?
+        ",
+        p2_puzzle_hash
+    )
+    .fetch_optional(conn)
+    .await?
+    .map(into_row)
+    .transpose()
+}
+
+async fn reserve_derivation(conn: impl SqliteExecutor<'_>, p2_puzzle_hash: Bytes32) -> Result<()> {
+    let p2_puzzle_hash = p2_puzzle_hash.as_ref();
+    sqlx::query!(
+        "
+        INSERT OR IGNORE INTO `reserved_derivations` (`p2_puzzle_hash`)
+        VALUES (?)
+        ",
+        p2_puzzle_hash
+    )
+    .execute(conn)
+    .await?;
+    Ok(())
+}
+
+async fn derivation_label(
+    conn: impl SqliteExecutor<'_>,
+    p2_puzzle_hash: Bytes32,
+) -> Result<Option<String>> {
+    let p2_puzzle_hash = p2_puzzle_hash.as_ref();
+    Ok(sqlx::query!(
+        "
+        SELECT `label` FROM `derivation_labels` WHERE `p2_puzzle_hash` = ?
+        ",
+        p2_puzzle_hash
+    )
+    .fetch_optional(conn)
+    .await?
+    .map(|row| row.label))
+}
+
+async fn set_derivation_label(
+    conn: impl SqliteExecutor<'_>,
+    p2_puzzle_hash: Bytes32,
+    label: Option<String>,
+) -> Result<()> {
+    let p2_puzzle_hash = p2_puzzle_hash.as_ref();
+
+    if let Some(label) = label {
+        sqlx::query!(
+            "
+            REPLACE INTO `derivation_labels` (`p2_puzzle_hash`, `label`)
+            VALUES (?, ?)
+            ",
+            p2_puzzle_hash,
+            label
+        )
+        .execute(conn)
+        .await?;
+    } else {
+        sqlx::query!(
+            "
+            DELETE FROM `derivation_labels` WHERE `p2_puzzle_hash` = ?
+            ",
+            p2_puzzle_hash
+        )
+        .execute(conn)
+        .await?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use chia::bls::SecretKey;
+    use test_log::test;
+
+    use crate::test_database;
+
+    use super::*;
+
+    #[test(tokio::test)]
+    async fn test_address_derivation() -> anyhow::Result<()> {
+        let db = test_database().await?;
+
+        let unhardened = Bytes32::new([1; 32]);
+        let hardened = Bytes32::new([2; 32]);
+        let unhardened_key = SecretKey::from_seed(&[1; 32]).public_key();
+        let hardened_key = SecretKey::from_seed(&[2; 32]).public_key();
+
+        let mut tx = db.tx().await?;
+        tx.insert_derivation(unhardened, 0, false, unhardened_key)
+            .await?;
+        tx.insert_derivation(hardened, 0, true, hardened_key)
+            .await?;
+        tx.commit().await?;
+
+        // Signing by address looks up the derivation of its synthetic key.
+        assert!(db.is_p2_puzzle_hash(unhardened).await?);
+        assert_eq!(db.synthetic_key(unhardened).await?, unhardened_key);
+
+        let derivation = db
+            .synthetic_key_derivation(unhardened_key)
+            .await?
+            .expect("missing derivation");
+
+        assert_eq!(derivation.p2_puzzle_hash, unhardened);
+        assert_eq!(derivation.index, 0);
+        assert!(!derivation.hardened);
+
+        let derivation = db
+            .synthetic_key_derivation(hardened_key)
+            .await?
+            .expect("missing derivation");
+
+        assert_eq!(derivation.p2_puzzle_hash, hardened);
+        assert!(derivation.hardened);
+
+        // Hardened and unhardened keys are derived separately.
+        assert_eq!(db.p2_puzzle_hashes(false).await?, [unhardened]);
+        assert_eq!(db.p2_puzzle_hashes(true).await?, [hardened]);
+        assert_eq!(db.derivation_index(false).await?, 1);
+        assert_eq!(db.derivation_index(true).await?, 1);
+
+        assert!(!db.is_p2_puzzle_hash(Bytes32::new([3; 32])).await?);
+
+        Ok(())
+    }
+}
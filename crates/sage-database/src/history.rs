@@ -0,0 +1,480 @@
+use chia::protocol::{Bytes, Bytes32, CoinState};
+use sqlx::SqliteExecutor;
+
+use crate::{
+    into_row, CoinKind, CoinStateRow, CoinStateSql, Database, DatabaseTx, HistoryDirection,
+    IntoRow, Result, TransactionHistoryRow, TransactionHistorySql,
+};
+
+/// Narrows down the transaction history by direction, asset and label.
+#[derive(Debug, Default, Clone)]
+pub struct TransactionHistoryFilter {
+    pub direction: Option<HistoryDirection>,
+    pub kind: Option<CoinKind>,
+    pub asset_id: Option<Bytes32>,
+    pub label: Option<String>,
+}
+
+impl Database {
+    pub async fn transaction_history_row(
+        &self,
+        id: Bytes32,
+    ) -> Result<Option<TransactionHistoryRow>> {
+        transaction_history_row(&self.pool, id).await
+    }
+
+    /// Returns a page of the transaction history, with the most recent transactions first.
+    /// Transactions which haven't been assigned a height yet are listed before all others.
+    pub async fn transaction_history(
+        &self,
+        filter: &TransactionHistoryFilter,
+        offset: u32,
+        limit: u32,
+    ) -> Result<Vec<TransactionHistoryRow>> {
+        transaction_history(&self.pool, filter, offset, limit).await
+    }
+
+    /// Returns every transaction confirmed within the range of heights, oldest first.
+    pub async fn confirmed_transaction_history(
+        &self,
+        from_height: u32,
+        to_height: u32,
+    ) -> Result<Vec<TransactionHistoryRow>> {
+        confirmed_transaction_history(&self.pool, from_height, to_height).await
+    }
+
+    pub async fn transaction_history_count(
+        &self,
+        filter: &TransactionHistoryFilter,
+    ) -> Result<u32> {
+        transaction_history_count(&self.pool, filter).await
+    }
+
+    pub async fn transaction_history_coins(
+        &self,
+        id: Bytes32,
+        spent: bool,
+    ) -> Result<Vec<CoinStateRow>> {
+        transaction_history_coins(&self.pool, id, spent).await
+    }
+
+    pub async fn transaction_history_memos(&self, id: Bytes32) -> Result<Vec<Bytes>> {
+        transaction_history_memos(&self.pool, id).await
+    }
+
+    pub async fn set_transaction_label(&self, id: Bytes32, label: Option<String>) -> Result<()> {
+        set_transaction_label(&self.pool, id, label).await
+    }
+
+    /// Returns synced coins that were sent to the wallet by someone else, and which
+    /// haven't been recorded in the transaction history yet.
+    pub async fn unrecorded_incoming_coins(&self, limit: usize) -> Result<Vec<CoinState>> {
+        unrecorded_incoming_coins(&self.pool, limit).await
+    }
+
+    /// Returns coins spent by the wallet outside of any pending transaction it knows of, such
+    /// as before a resync, which haven't been recorded in the transaction history yet. Every
+    /// such coin spent at the earliest heights is returned, up to the given number of heights.
+    pub async fn unrecorded_spent_coins(&self, heights: usize) -> Result<Vec<CoinState>> {
+        unrecorded_spent_coins(&self.pool, heights).await
+    }
+}
+
+impl DatabaseTx<'_> {
+    pub async fn insert_transaction_history(&mut self, row: TransactionHistoryRow) -> Result<()> {
+        insert_transaction_history(&mut *self.tx, row).await
+    }
+
+    pub async fn insert_transaction_history_coin(
+        &mut self,
+        id: Bytes32,
+        coin_id: Bytes32,
+        spent: bool,
+    ) -> Result<()> {
+        insert_transaction_history_coin(&mut *self.tx, id, coin_id, spent).await
+    }
+
+    pub async fn insert_transaction_history_memo(
+        &mut self,
+        id: Bytes32,
+        index: usize,
+        memo: Bytes,
+    ) -> Result<()> {
+        insert_transaction_history_memo(&mut *self.tx, id, index, memo).await
+    }
+
+    pub async fn unrecorded_spent_coins(
+        conn: impl SqliteExecutor<'_>,
+        heights: usize,
+    ) -> Result<Vec<CoinState>> {
+        let heights: i64 = heights.try_into()?;
+
+        // Only XCH, CATs, NFTs and DIDs are spent by the wallet itself. Clawbacks can be spent by
+        // the other party instead.
+        let rows = sqlx::query_as!(
+        CoinStateSql,
+        "
+        SELECT `parent_coin_id`, `puzzle_hash`, `amount`, `created_height`, `spent_height`, `transaction_id`, `kind`
+        FROM `coin_states`
+        WHERE `spent_height` IN (
+            SELECT DISTINCT `spent_height`
+            FROM `coin_states`
+            WHERE `synced` = 1 AND `kind` IN (1, 2, 3, 4) AND `spent_height` IS NOT NULL
+            AND `coin_id` NOT IN (SELECT `coin_id` FROM `transaction_spends`)
+            AND `coin_id` NOT IN (
+                SELECT `coin_id` FROM `transaction_history_coins` WHERE `spent` = 1
+            )
+            ORDER BY `spent_height` ASC
+            LIMIT ?
+        )
+        AND `synced` = 1 AND `kind` IN (1, 2, 3, 4)
+        AND `coin_id` NOT IN (SELECT `coin_id` FROM `transaction_spends`)
+        AND `coin_id` NOT IN (
+            SELECT `coin_id` FROM `transaction_history_coins` WHERE `spent` = 1
+        )
+        ORDER BY `spent_height` ASC, `coin_id` ASC
+        ",
+        heights
+    )
+    .fetch_all(conn)
+    .await?;
+
+        rows.into_iter()
+            .map(|sql| sql.into_row().map(|row| row.coin_state))
+            .collect()
+    }
+
+    async fn update_transaction_history_heights(&mut self) -> Result<()> {
+        update_transaction_history_heights(&mut *self.tx).await
+    }
+}
+
+async fn insert_transaction_history(
+    conn: impl SqliteExecutor<'_>,
+    row: TransactionHistoryRow,
+) -> Result<()> {
+    let id = row.id.as_ref();
+    let direction = row.direction as u8;
+    let fee = row.fee.to_be_bytes();
+    let fee = fee.as_ref();
+    let counterparty_puzzle_hash = row.counterparty_puzzle_hash.as_deref();
+
+    sqlx::query!(
+        "
+        INSERT OR IGNORE INTO `transaction_history` (
+            `id`,
+            `height`,
+            `direction`,
+            `fee`,
+            `counterparty_puzzle_hash`,
+            `label`
+        )
+        VALUES (?, ?, ?, ?, ?, ?)
+        ",
+        id,
+        row.height,
+        direction,
+        fee,
+        counterparty_puzzle_hash,
+        row.label
+    )
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+async fn insert_transaction_history_coin(
+    conn: impl SqliteExecutor<'_>,
+    id: Bytes32,
+    coin_id: Bytes32,
+    spent: bool,
+) -> Result<()> {
+    let id = id.as_ref();
+    let coin_id = coin_id.as_ref();
+
+    sqlx::query!(
+        "
+        INSERT OR IGNORE INTO `transaction_history_coins` (`transaction_id`, `coin_id`, `spent`)
+        VALUES (?, ?, ?)
+        ",
+        id,
+        coin_id,
+        spent
+    )
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+async fn insert_transaction_history_memo(
+    conn: impl SqliteExecutor<'_>,
+    id: Bytes32,
+    index: usize,
+    memo: Bytes,
+) -> Result<()> {
+    let id = id.as_ref();
+    let index: i64 = index.try_into()?;
+    let memo = memo.as_ref();
+
+    sqlx::query!(
+        "
+        INSERT OR IGNORE INTO `transaction_history_memos` (`transaction_id`, `index`, `memo`)
+        VALUES (?, ?, ?)
+        ",
+        id,
+        index,
+        memo
+    )
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+async fn transaction_history_row(
+    conn: impl SqliteExecutor<'_>,
+    id: Bytes32,
+) -> Result<Option<TransactionHistoryRow>> {
+    let id = id.as_ref();
+
+    sqlx::query_as!(
+        TransactionHistorySql,
+        "
+        SELECT `id`, `height`, `direction`, `fee`, `counterparty_puzzle_hash`, `label`
+        FROM `transaction_history`
+        WHERE `id` = ?
+        ",
+        id
+    )
+    .fetch_optional(conn)
+    .await?
+    .map(into_row)
+    .transpose()
+}
+
+async fn transaction_history(
+    conn: impl SqliteExecutor<'_>,
+    filter: &TransactionHistoryFilter,
+    offset: u32,
+    limit: u32,
+) -> Result<Vec<TransactionHistoryRow>> {
+    let direction = filter.direction.map(|direction| direction as u8);
+    let kind = filter.kind.map(|kind| kind as u8);
+    let asset_id = filter.asset_id.as_deref();
+    let label = filter.label.as_deref();
+
+    sqlx::query_as!(
+        TransactionHistorySql,
+        "
+        SELECT `id`, `height`, `direction`, `fee`, `counterparty_puzzle_hash`, `label`
+        FROM `transaction_history`
+        WHERE (? IS NULL OR `direction` = ?)
+        AND (? IS NULL OR `label` LIKE '%' || ? || '%')
+        AND (? IS NULL OR EXISTS (
+            SELECT 1 FROM `transaction_history_coins`
+            INNER JOIN `coin_states` ON `coin_states`.`coin_id` = `transaction_history_coins`.`coin_id`
+            LEFT JOIN `cat_coins` ON `cat_coins`.`coin_id` = `coin_states`.`coin_id`
+            WHERE `transaction_history_coins`.`transaction_id` = `transaction_history`.`id`
+            AND `coin_states`.`kind` = ?
+            AND (? IS NULL OR `cat_coins`.`asset_id` = ?)
+        ))
+        ORDER BY `height` IS NULL DESC, `height` DESC, `id` ASC
+        LIMIT ? OFFSET ?
+        ",
+        direction,
+        direction,
+        label,
+        label,
+        kind,
+        kind,
+        asset_id,
+        asset_id,
+        limit,
+        offset
+    )
+    .fetch_all(conn)
+    .await?
+    .into_iter()
+    .map(into_row)
+    .collect()
+}
+
+async fn confirmed_transaction_history(
+    conn: impl SqliteExecutor<'_>,
+    from_height: u32,
+    to_height: u32,
+) -> Result<Vec<TransactionHistoryRow>> {
+    sqlx::query_as!(
+        TransactionHistorySql,
+        "
+        SELECT `id`, `height`, `direction`, `fee`, `counterparty_puzzle_hash`, `label`
+        FROM `transaction_history`
+        WHERE `height` >= ? AND `height` <= ?
+        ORDER BY `height` ASC, `id` ASC
+        ",
+        from_height,
+        to_height
+    )
+    .fetch_all(conn)
+    .await?
+    .into_iter()
+    .map(into_row)
+    .collect()
+}
+
+async fn transaction_history_count(
+    conn: impl SqliteExecutor<'_>,
+    filter: &TransactionHistoryFilter,
+) -> Result<u32> {
+    let direction = filter.direction.map(|direction| direction as u8);
+    let kind = filter.kind.map(|kind| kind as u8);
+    let asset_id = filter.asset_id.as_deref();
+    let label = filter.label.as_deref();
+
+    let row = sqlx::query!(
+        "
+        SELECT COUNT(*) AS `total`
+        FROM `transaction_history`
+        WHERE (? IS NULL OR `direction` = ?)
+        AND (? IS NULL OR `label` LIKE '%' || ? || '%')
+        AND (? IS NULL OR EXISTS (
+            SELECT 1 FROM `transaction_history_coins`
+            INNER JOIN `coin_states` ON `coin_states`.`coin_id` = `transaction_history_coins`.`coin_id`
+            LEFT JOIN `cat_coins` ON `cat_coins`.`coin_id` = `coin_states`.`coin_id`
+            WHERE `transaction_history_coins`.`transaction_id` = `transaction_history`.`id`
+            AND `coin_states`.`kind` = ?
+            AND (? IS NULL OR `cat_coins`.`asset_id` = ?)
+        ))
+        ",
+        direction,
+        direction,
+        label,
+        label,
+        kind,
+        kind,
+        asset_id,
+        asset_id
+    )
+    .fetch_one(conn)
+    .await?;
+
+    Ok(row.total.try_into()?)
+}
+
+async fn transaction_history_coins(
+    conn: impl SqliteExecutor<'_>,
+    id: Bytes32,
+    spent: bool,
+) -> Result<Vec<CoinStateRow>> {
+    let id = id.as_ref();
+
+    let rows = sqlx::query_as!(
+        CoinStateSql,
+        "
+        SELECT
+            `parent_coin_id`, `puzzle_hash`, `amount`, `created_height`, `spent_height`,
+            `coin_states`.`transaction_id`, `kind`
+        FROM `coin_states`
+        INNER JOIN `transaction_history_coins` ON `transaction_history_coins`.`coin_id` = `coin_states`.`coin_id`
+        WHERE `transaction_history_coins`.`transaction_id` = ? AND `spent` = ?
+        ORDER BY `amount` DESC
+        ",
+        id,
+        spent
+    )
+    .fetch_all(conn)
+    .await?;
+
+    rows.into_iter().map(into_row).collect()
+}
+
+async fn transaction_history_memos(
+    conn: impl SqliteExecutor<'_>,
+    id: Bytes32,
+) -> Result<Vec<Bytes>> {
+    let id = id.as_ref();
+
+    let rows = sqlx::query!(
+        "
+        SELECT `memo`
+        FROM `transaction_history_memos`
+        WHERE `transaction_id` = ?
+        ORDER BY `index` ASC
+        ",
+        id
+    )
+    .fetch_all(conn)
+    .await?;
+
+    Ok(rows.into_iter().map(|row| row.memo.into()).collect())
+}
+
+async fn set_transaction_label(
+    conn: impl SqliteExecutor<'_>,
+    id: Bytes32,
+    label: Option<String>,
+) -> Result<()> {
+    let id = id.as_ref();
+
+    sqlx::query!(
+        "
+        UPDATE `transaction_history` SET `label` = ? WHERE `id` = ?
+        ",
+        label,
+        id
+    )
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+async fn unrecorded_incoming_coins(
+    conn: impl SqliteExecutor<'_>,
+    limit: usize,
+) -> Result<Vec<CoinState>> {
+    let limit: i64 = limit.try_into()?;
+
+    let rows = sqlx::query_as!(
+        CoinStateSql,
+        "
+        SELECT `parent_coin_id`, `puzzle_hash`, `amount`, `created_height`, `spent_height`, `transaction_id`, `kind`
+        FROM `coin_states`
+        WHERE `synced` = 1 AND `kind` != 0 AND `created_height` IS NOT NULL
+        AND `transaction_id` IS NULL
+        AND `parent_coin_id` NOT IN (SELECT `coin_id` FROM `coin_states`)
+        AND `coin_id` NOT IN (SELECT `coin_id` FROM `transaction_history_coins`)
+        ORDER BY `created_height` ASC
+        LIMIT ?
+        ",
+        limit
+    )
+    .fetch_all(conn)
+    .await?;
+
+    rows.into_iter()
+        .map(|sql| sql.into_row().map(|row| row.coin_state))
+        .collect()
+}
+
+async fn update_transaction_history_heights(conn: impl SqliteExecutor<'_>) -> Result<()> {
+    sqlx::query!(
+        "
+        UPDATE `transaction_history`
+        SET `height` = (
+            SELECT MAX(`coin_states`.`spent_height`)
+            FROM `transaction_history_coins`
+            INNER JOIN `coin_states` ON `coin_states`.`coin_id` = `transaction_history_coins`.`coin_id`
+            WHERE `transaction_history_coins`.`transaction_id` = `transaction_history`.`id`
+            AND `transaction_history_coins`.`spent` = 1
+        )
+        WHERE `height` IS NULL
+        "
+    )
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
@@ -0,0 +1,228 @@
+use chia::protocol::Bytes32;
+use sqlx::SqliteExecutor;
+
+use crate::{
+    into_row, Database, DatabaseTx, InvoicePaymentRow, InvoicePaymentSql, InvoiceRow, InvoiceSql,
+    InvoiceStatus, Result,
+};
+
+impl Database {
+    pub async fn invoices(&self, limit: u32, offset: u32) -> Result<Vec<InvoiceRow>> {
+        invoices(&self.pool, limit, offset).await
+    }
+
+    pub async fn invoice_count(&self) -> Result<u32> {
+        invoice_count(&self.pool).await
+    }
+
+    pub async fn invoice(&self, id: u32) -> Result<Option<InvoiceRow>> {
+        invoice(&self.pool, id).await
+    }
+
+    pub async fn invoice_payments(&self, id: u32) -> Result<Vec<InvoicePaymentRow>> {
+        invoice_payments(&self.pool, id).await
+    }
+}
+
+impl DatabaseTx<'_> {
+    /// Inserts a new invoice, ignoring the id of the row, and returns the id it was assigned.
+    pub async fn insert_invoice(&mut self, row: InvoiceRow) -> Result<u32> {
+        insert_invoice(&mut *self.tx, row).await
+    }
+
+    pub async fn invoice_by_p2_puzzle_hash(
+        &mut self,
+        p2_puzzle_hash: Bytes32,
+    ) -> Result<Option<InvoiceRow>> {
+        invoice_by_p2_puzzle_hash(&mut *self.tx, p2_puzzle_hash).await
+    }
+
+    /// Records a coin which paid an invoice, returning false if it had already been recorded.
+    pub async fn insert_invoice_payment(&mut self, row: InvoicePaymentRow) -> Result<bool> {
+        insert_invoice_payment(&mut *self.tx, row).await
+    }
+
+    pub async fn update_invoice_paid_amount(
+        &mut self,
+        id: u32,
+        paid_amount: u64,
+        status: InvoiceStatus,
+    ) -> Result<()> {
+        update_invoice_paid_amount(&mut *self.tx, id, paid_amount, status).await
+    }
+}
+
+async fn invoices(
+    conn: impl SqliteExecutor<'_>,
+    limit: u32,
+    offset: u32,
+) -> Result<Vec<InvoiceRow>> {
+    sqlx::query_as!(
+        InvoiceSql,
+        "
+        SELECT * FROM `invoices`
+        ORDER BY `id` DESC
+        LIMIT ? OFFSET ?
+        ",
+        limit,
+        offset
+    )
+    .fetch_all(conn)
+    .await?
+    .into_iter()
+    .map(into_row)
+    .collect()
+}
+
+async fn invoice_count(conn: impl SqliteExecutor<'_>) -> Result<u32> {
+    let row = sqlx::query!(
+        "
+        SELECT COUNT(*) AS `count` FROM `invoices`
+        "
+    )
+    .fetch_one(conn)
+    .await?;
+
+    Ok(row.count.try_into()?)
+}
+
+async fn invoice(conn: impl SqliteExecutor<'_>, id: u32) -> Result<Option<InvoiceRow>> {
+    sqlx::query_as!(
+        InvoiceSql,
+        "
+        SELECT * FROM `invoices`
+        WHERE `id` = ?
+        ",
+        id
+    )
+    .fetch_optional(conn)
+    .await?
+    .map(into_row)
+    .transpose()
+}
+
+async fn invoice_by_p2_puzzle_hash(
+    conn: impl SqliteExecutor<'_>,
+    p2_puzzle_hash: Bytes32,
+) -> Result<Option<InvoiceRow>> {
+    let p2_puzzle_hash = p2_puzzle_hash.as_ref();
+
+    sqlx::query_as!(
+        InvoiceSql,
+        "
+        SELECT * FROM `invoices`
+        WHERE `p2_puzzle_hash` = ?
+        ",
+        p2_puzzle_hash
+    )
+    .fetch_optional(conn)
+    .await?
+    .map(into_row)
+    .transpose()
+}
+
+async fn invoice_payments(
+    conn: impl SqliteExecutor<'_>,
+    id: u32,
+) -> Result<Vec<InvoicePaymentRow>> {
+    sqlx::query_as!(
+        InvoicePaymentSql,
+        "
+        SELECT * FROM `invoice_payments`
+        WHERE `invoice_id` = ?
+        ORDER BY `height` ASC
+        ",
+        id
+    )
+    .fetch_all(conn)
+    .await?
+    .into_iter()
+    .map(into_row)
+    .collect()
+}
+
+async fn insert_invoice(conn: impl SqliteExecutor<'_>, row: InvoiceRow) -> Result<u32> {
+    let p2_puzzle_hash = row.p2_puzzle_hash.as_ref();
+    let asset_id = row.asset_id.as_deref();
+    let amount = row.amount.to_be_bytes();
+    let amount = amount.as_ref();
+    let paid_amount = row.paid_amount.to_be_bytes();
+    let paid_amount = paid_amount.as_ref();
+    let expires_at = row.expires_at.map(|ts| ts.to_be_bytes().to_vec());
+    let created_at = row.created_at.to_be_bytes();
+    let created_at = created_at.as_ref();
+    let status = row.status as u8;
+
+    let id = sqlx::query!(
+        "
+        INSERT INTO `invoices` (
+            `p2_puzzle_hash`, `asset_id`, `amount`, `paid_amount`,
+            `memo`, `expires_at`, `created_at`, `status`
+        )
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+        ",
+        p2_puzzle_hash,
+        asset_id,
+        amount,
+        paid_amount,
+        row.memo,
+        expires_at,
+        created_at,
+        status
+    )
+    .execute(conn)
+    .await?
+    .last_insert_rowid();
+
+    Ok(id.try_into()?)
+}
+
+async fn insert_invoice_payment(
+    conn: impl SqliteExecutor<'_>,
+    row: InvoicePaymentRow,
+) -> Result<bool> {
+    let coin_id = row.coin_id.as_ref();
+    let amount = row.amount.to_be_bytes();
+    let amount = amount.as_ref();
+
+    let result = sqlx::query!(
+        "
+        INSERT OR IGNORE INTO `invoice_payments` (`coin_id`, `invoice_id`, `amount`, `height`)
+        VALUES (?, ?, ?, ?)
+        ",
+        coin_id,
+        row.invoice_id,
+        amount,
+        row.height
+    )
+    .execute(conn)
+    .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+async fn update_invoice_paid_amount(
+    conn: impl SqliteExecutor<'_>,
+    id: u32,
+    paid_amount: u64,
+    status: InvoiceStatus,
+) -> Result<()> {
+    let paid_amount = paid_amount.to_be_bytes();
+    let paid_amount = paid_amount.as_ref();
+    let status = status as u8;
+
+    sqlx::query!(
+        "
+        UPDATE `invoices`
+        SET `paid_amount` = ?, `status` = ?
+        WHERE `id` = ?
+        ",
+        paid_amount,
+        status,
+        id
+    )
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
@@ -1,5 +1,11 @@
+mod block_timestamps;
+mod clawbacks;
 mod coin_states;
+mod contacts;
 mod derivations;
+mod history;
+mod invoices;
+mod nft_attributes;
 mod offers;
 mod peaks;
 mod primitives;
@@ -7,12 +13,20 @@ mod rows;
 mod transactions;
 mod utils;
 
+#[cfg(test)]
+mod test;
+
+pub use history::*;
+pub use nft_attributes::*;
 pub use primitives::*;
 pub use rows::*;
 pub use transactions::*;
 
 pub(crate) use utils::*;
 
+#[cfg(test)]
+pub(crate) use test::*;
+
 use std::num::TryFromIntError;
 
 use sqlx::{Sqlite, SqlitePool, Transaction};
@@ -72,6 +86,9 @@ pub enum DatabaseError {
 
     #[error("Invalid offer status {0}")]
     InvalidOfferStatus(i64),
+
+    #[error("Invalid invoice status {0}")]
+    InvalidInvoiceStatus(i64),
 }
 
 pub(crate) type Result<T> = std::result::Result<T, DatabaseError>;
@@ -0,0 +1,309 @@
+use chia::protocol::Bytes32;
+use sqlx::SqliteExecutor;
+
+use crate::{into_row, Database, DatabaseTx, NftRow, NftSql, Result};
+
+/// A CHIP-0007 attribute from the offchain metadata of an NFT.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NftAttribute {
+    pub trait_type: String,
+    pub value: String,
+}
+
+/// How many owned NFTs have a given trait.
+#[derive(Debug, Clone)]
+pub struct NftTraitCount {
+    pub trait_type: String,
+    pub value: String,
+    pub count: u32,
+}
+
+/// Narrows down owned NFTs by collection, name and trait.
+#[derive(Debug, Default, Clone)]
+pub struct NftSearchFilter {
+    /// If set, only NFTs in this collection are included, or those without one if it's `None`.
+    pub collection_id: Option<Option<Bytes32>>,
+    pub include_hidden: bool,
+    /// Matches NFTs whose name contains words starting with each of the words in the text.
+    pub name: Option<String>,
+    pub trait_type: Option<String>,
+    /// Only used along with the trait type, to match a specific value of it.
+    pub trait_value: Option<String>,
+}
+
+impl Database {
+    pub async fn nft_attributes(&self, launcher_id: Bytes32) -> Result<Vec<NftAttribute>> {
+        nft_attributes(&self.pool, launcher_id).await
+    }
+
+    pub async fn search_nfts_named(
+        &self,
+        filter: &NftSearchFilter,
+        limit: u32,
+        offset: u32,
+    ) -> Result<Vec<NftRow>> {
+        search_nfts_named(&self.pool, filter, limit, offset).await
+    }
+
+    pub async fn search_nfts_recent(
+        &self,
+        filter: &NftSearchFilter,
+        limit: u32,
+        offset: u32,
+    ) -> Result<Vec<NftRow>> {
+        search_nfts_recent(&self.pool, filter, limit, offset).await
+    }
+
+    /// Counts each trait among owned NFTs, optionally within a collection (or those without one).
+    pub async fn nft_trait_counts(
+        &self,
+        collection_id: Option<Option<Bytes32>>,
+    ) -> Result<Vec<NftTraitCount>> {
+        nft_trait_counts(&self.pool, collection_id).await
+    }
+
+    /// Counts owned NFTs, including hidden ones, to compare trait counts against.
+    pub async fn owned_nft_count(&self, collection_id: Option<Option<Bytes32>>) -> Result<u32> {
+        owned_nft_count(&self.pool, collection_id).await
+    }
+}
+
+impl DatabaseTx<'_> {
+    /// Replaces the attributes of an NFT, for example after its metadata has been fetched.
+    pub async fn set_nft_attributes(
+        &mut self,
+        launcher_id: Bytes32,
+        attributes: Vec<NftAttribute>,
+    ) -> Result<()> {
+        delete_nft_attributes(&mut *self.tx, launcher_id).await?;
+
+        for attribute in attributes {
+            insert_nft_attribute(&mut *self.tx, launcher_id, attribute).await?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Turns free text into an FTS5 query, so that it can't be misinterpreted as query syntax.
+fn search_query(text: &str) -> Option<String> {
+    let words: Vec<String> = text
+        .split_whitespace()
+        .map(|word| format!("\"{}\"*", word.replace('"', "\"\"")))
+        .collect();
+
+    (!words.is_empty()).then(|| words.join(" "))
+}
+
+async fn delete_nft_attributes(conn: impl SqliteExecutor<'_>, launcher_id: Bytes32) -> Result<()> {
+    let launcher_id = launcher_id.as_ref();
+
+    sqlx::query!(
+        "DELETE FROM `nft_attributes` WHERE `launcher_id` = ?",
+        launcher_id
+    )
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+async fn insert_nft_attribute(
+    conn: impl SqliteExecutor<'_>,
+    launcher_id: Bytes32,
+    attribute: NftAttribute,
+) -> Result<()> {
+    let launcher_id = launcher_id.as_ref();
+
+    sqlx::query!(
+        "
+        INSERT OR IGNORE INTO `nft_attributes` (`launcher_id`, `trait_type`, `value`)
+        VALUES (?, ?, ?)
+        ",
+        launcher_id,
+        attribute.trait_type,
+        attribute.value
+    )
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+async fn nft_attributes(
+    conn: impl SqliteExecutor<'_>,
+    launcher_id: Bytes32,
+) -> Result<Vec<NftAttribute>> {
+    let launcher_id = launcher_id.as_ref();
+
+    let rows = sqlx::query!(
+        "
+        SELECT `trait_type`, `value` FROM `nft_attributes`
+        WHERE `launcher_id` = ?
+        ORDER BY `trait_type` ASC, `value` ASC
+        ",
+        launcher_id
+    )
+    .fetch_all(conn)
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| NftAttribute {
+            trait_type: row.trait_type,
+            value: row.value,
+        })
+        .collect())
+}
+
+async fn search_nfts_named(
+    conn: impl SqliteExecutor<'_>,
+    filter: &NftSearchFilter,
+    limit: u32,
+    offset: u32,
+) -> Result<Vec<NftRow>> {
+    let any_collection = filter.collection_id.is_none();
+    let collection_id = filter.collection_id.flatten();
+    let collection_id = collection_id.as_deref();
+    let name = filter.name.as_deref().and_then(search_query);
+    let trait_type = filter.trait_type.as_deref();
+    let trait_value = filter.trait_value.as_deref();
+
+    sqlx::query_as!(
+        NftSql,
+        "
+        SELECT * FROM `nfts`
+        WHERE `is_owned` = 1 AND (? OR `visible` = 1)
+        AND (? OR `collection_id` IS ?)
+        AND (? IS NULL OR `rowid` IN (SELECT `rowid` FROM `nft_search` WHERE `nft_search` MATCH ?))
+        AND (? IS NULL OR EXISTS (
+            SELECT 1 FROM `nft_attributes`
+            WHERE `nft_attributes`.`launcher_id` = `nfts`.`launcher_id`
+            AND `trait_type` = ? AND (? IS NULL OR `value` = ?)
+        ))
+        ORDER BY `visible` DESC, `is_pending` DESC, `is_named` DESC, `name` ASC, `launcher_id` ASC
+        LIMIT ? OFFSET ?
+        ",
+        filter.include_hidden,
+        any_collection,
+        collection_id,
+        name,
+        name,
+        trait_type,
+        trait_type,
+        trait_value,
+        trait_value,
+        limit,
+        offset
+    )
+    .fetch_all(conn)
+    .await?
+    .into_iter()
+    .map(into_row)
+    .collect()
+}
+
+async fn search_nfts_recent(
+    conn: impl SqliteExecutor<'_>,
+    filter: &NftSearchFilter,
+    limit: u32,
+    offset: u32,
+) -> Result<Vec<NftRow>> {
+    let any_collection = filter.collection_id.is_none();
+    let collection_id = filter.collection_id.flatten();
+    let collection_id = collection_id.as_deref();
+    let name = filter.name.as_deref().and_then(search_query);
+    let trait_type = filter.trait_type.as_deref();
+    let trait_value = filter.trait_value.as_deref();
+
+    sqlx::query_as!(
+        NftSql,
+        "
+        SELECT * FROM `nfts`
+        WHERE `is_owned` = 1 AND (? OR `visible` = 1)
+        AND (? OR `collection_id` IS ?)
+        AND (? IS NULL OR `rowid` IN (SELECT `rowid` FROM `nft_search` WHERE `nft_search` MATCH ?))
+        AND (? IS NULL OR EXISTS (
+            SELECT 1 FROM `nft_attributes`
+            WHERE `nft_attributes`.`launcher_id` = `nfts`.`launcher_id`
+            AND `trait_type` = ? AND (? IS NULL OR `value` = ?)
+        ))
+        ORDER BY `visible` DESC, `is_pending` DESC, `created_height` DESC, `launcher_id` ASC
+        LIMIT ? OFFSET ?
+        ",
+        filter.include_hidden,
+        any_collection,
+        collection_id,
+        name,
+        name,
+        trait_type,
+        trait_type,
+        trait_value,
+        trait_value,
+        limit,
+        offset
+    )
+    .fetch_all(conn)
+    .await?
+    .into_iter()
+    .map(into_row)
+    .collect()
+}
+
+async fn nft_trait_counts(
+    conn: impl SqliteExecutor<'_>,
+    collection_id: Option<Option<Bytes32>>,
+) -> Result<Vec<NftTraitCount>> {
+    let any_collection = collection_id.is_none();
+    let collection_id = collection_id.flatten();
+    let collection_id = collection_id.as_deref();
+
+    let rows = sqlx::query!(
+        "
+        SELECT `trait_type`, `value`, COUNT(*) AS `count`
+        FROM `nft_attributes`
+        INNER JOIN `nfts` ON `nfts`.`launcher_id` = `nft_attributes`.`launcher_id`
+        WHERE `is_owned` = 1 AND (? OR `collection_id` IS ?)
+        GROUP BY `trait_type`, `value`
+        ORDER BY `trait_type` ASC, `count` ASC, `value` ASC
+        ",
+        any_collection,
+        collection_id
+    )
+    .fetch_all(conn)
+    .await?;
+
+    let mut counts = Vec::with_capacity(rows.len());
+
+    for row in rows {
+        counts.push(NftTraitCount {
+            trait_type: row.trait_type,
+            value: row.value,
+            count: row.count.try_into()?,
+        });
+    }
+
+    Ok(counts)
+}
+
+async fn owned_nft_count(
+    conn: impl SqliteExecutor<'_>,
+    collection_id: Option<Option<Bytes32>>,
+) -> Result<u32> {
+    let any_collection = collection_id.is_none();
+    let collection_id = collection_id.flatten();
+    let collection_id = collection_id.as_deref();
+
+    let row = sqlx::query!(
+        "
+        SELECT COUNT(*) AS `count` FROM `nfts`
+        WHERE `is_owned` = 1 AND (? OR `collection_id` IS ?)
+        ",
+        any_collection,
+        collection_id
+    )
+    .fetch_one(conn)
+    .await?;
+
+    Ok(row.count.try_into()?)
+}
@@ -70,6 +70,15 @@ impl DatabaseTx<'_> {
     pub async fn insert_offer_cat(&mut self, row: OfferCatRow) -> Result<()> {
         insert_offer_cat(&mut *self.tx, row).await
     }
+
+    /// Marks the active offers that include a coin as being cancelled by our own spend of it.
+    pub async fn set_coin_offers_cancelling(
+        &mut self,
+        coin_id: Bytes32,
+        cancelling: bool,
+    ) -> Result<()> {
+        set_coin_offers_cancelling(&mut *self.tx, coin_id, cancelling).await
+    }
 }
 
 async fn insert_offer(conn: impl SqliteExecutor<'_>, row: OfferRow) -> Result<()> {
@@ -324,6 +333,29 @@ async fn update_offer_status(
     Ok(())
 }
 
+async fn set_coin_offers_cancelling(
+    conn: impl SqliteExecutor<'_>,
+    coin_id: Bytes32,
+    cancelling: bool,
+) -> Result<()> {
+    let coin_id = coin_id.as_ref();
+
+    sqlx::query!(
+        "
+        UPDATE `offers` SET `cancelling` = ?
+        WHERE `status` = 0 AND `offer_id` IN (
+            SELECT `offer_id` FROM `offered_coins` WHERE `coin_id` = ?
+        )
+        ",
+        cancelling,
+        coin_id
+    )
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
 async fn offer_coin_ids(conn: impl SqliteExecutor<'_>, offer_id: Bytes32) -> Result<Vec<Bytes32>> {
     let offer_id = offer_id.as_ref();
 
@@ -1,4 +1,4 @@
-use chia::{protocol::Bytes32, puzzles::LineageProof};
+use chia::{bls::PublicKey, protocol::Bytes32, puzzles::LineageProof};
 use chia_wallet_sdk::Cat;
 use sqlx::SqliteExecutor;
 
@@ -28,6 +28,7 @@ impl Database {
         unfetched_cat(&self.pool).await
     }
 
+    /// Returns the unspent and unlocked coins of the CAT, oldest first.
     pub async fn spendable_cat_coins(&self, asset_id: Bytes32) -> Result<Vec<CatCoinRow>> {
         spendable_cat_coins(&self.pool, asset_id).await
     }
@@ -44,6 +45,21 @@ impl Database {
         refetch_cat(&self.pool, asset_id).await
     }
 
+    /// Records that the CAT was issued by this wallet with an everything with signature TAIL.
+    /// The key is one of the wallet's derivations, so this only saves looking for it again.
+    pub async fn set_cat_issuance_key(
+        &self,
+        asset_id: Bytes32,
+        public_key: PublicKey,
+    ) -> Result<()> {
+        set_cat_issuance_key(&self.pool, asset_id, public_key).await
+    }
+
+    /// Returns the synthetic key that can be used to run the TAIL of the CAT, if it has been recorded.
+    pub async fn cat_issuance_key(&self, asset_id: Bytes32) -> Result<Option<PublicKey>> {
+        cat_issuance_key(&self.pool, asset_id).await
+    }
+
     pub async fn cat_coin_states(&self, asset_id: Bytes32) -> Result<Vec<CoinStateRow>> {
         cat_coin_states(&self.pool, asset_id).await
     }
@@ -63,6 +79,10 @@ impl<'a> DatabaseTx<'a> {
         insert_cat(&mut *self.tx, row).await
     }
 
+    pub async fn cat_coin(&mut self, coin_id: Bytes32) -> Result<Option<Cat>> {
+        cat_coin(&mut *self.tx, coin_id).await
+    }
+
     pub async fn insert_cat_coin(
         &mut self,
         coin_id: Bytes32,
@@ -114,7 +134,7 @@ async fn update_cat(conn: impl SqliteExecutor<'_>, row: CatRow) -> Result<()> {
 
     sqlx::query!(
         "
-        REPLACE INTO `cats` (
+        INSERT INTO `cats` (
             `asset_id`,
             `name`,
             `ticker`,
@@ -123,6 +143,13 @@ async fn update_cat(conn: impl SqliteExecutor<'_>, row: CatRow) -> Result<()> {
             `visible`,
             `fetched`
         ) VALUES (?, ?, ?, ?, ?, ?, ?)
+        ON CONFLICT (`asset_id`) DO UPDATE SET
+            `name` = excluded.`name`,
+            `ticker` = excluded.`ticker`,
+            `description` = excluded.`description`,
+            `icon` = excluded.`icon`,
+            `visible` = excluded.`visible`,
+            `fetched` = excluded.`fetched`
         ",
         asset_id,
         row.name,
@@ -244,6 +271,7 @@ async fn spendable_cat_coins(
         AND `transaction_spends`.`coin_id` IS NULL
         AND (`offered_coins`.`coin_id` IS NULL OR `offers`.`status` > 0)
         AND cs.`transaction_id` IS NULL
+        ORDER BY cs.`created_height` ASC
         ",
         asset_id
     )
@@ -362,3 +390,47 @@ async fn refetch_cat(conn: impl SqliteExecutor<'_>, asset_id: Bytes32) -> Result
 
     Ok(())
 }
+
+async fn set_cat_issuance_key(
+    conn: impl SqliteExecutor<'_>,
+    asset_id: Bytes32,
+    public_key: PublicKey,
+) -> Result<()> {
+    let asset_id = asset_id.as_ref();
+    let public_key = public_key.to_bytes();
+    let public_key = public_key.as_ref();
+
+    sqlx::query!(
+        "
+        UPDATE `cats` SET `issuance_public_key` = ? WHERE `asset_id` = ?
+        ",
+        public_key,
+        asset_id
+    )
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+async fn cat_issuance_key(
+    conn: impl SqliteExecutor<'_>,
+    asset_id: Bytes32,
+) -> Result<Option<PublicKey>> {
+    let asset_id = asset_id.as_ref();
+
+    let row = sqlx::query!(
+        "
+        SELECT `issuance_public_key` FROM `cats` WHERE `asset_id` = ?
+        ",
+        asset_id
+    )
+    .fetch_optional(conn)
+    .await?;
+
+    let Some(bytes) = row.and_then(|row| row.issuance_public_key) else {
+        return Ok(None);
+    };
+
+    Ok(Some(PublicKey::from_bytes(&to_bytes(&bytes)?)?))
+}
@@ -7,7 +7,7 @@ use sqlx::SqliteExecutor;
 
 use crate::{
     into_row, to_bytes32, CoinStateRow, CoinStateSql, CollectionRow, CollectionSql, Database,
-    DatabaseTx, FullNftCoinSql, IntoRow, NftRow, NftSql, Result,
+    DatabaseTx, FullNftCoinSql, IntoRow, NftRow, NftSql, NftUriRow, NftUriSql, Result,
 };
 
 #[derive(Debug, Clone)]
@@ -20,11 +20,24 @@ pub struct NftData {
 pub struct NftUri {
     pub hash: Bytes32,
     pub uri: String,
+    pub attempts: u32,
 }
 
 impl Database {
-    pub async fn unchecked_nft_uris(&self, limit: u32) -> Result<Vec<NftUri>> {
-        unchecked_nft_uris(&self.pool, limit).await
+    pub async fn unchecked_nft_uris(&self, limit: u32, timestamp: u64) -> Result<Vec<NftUri>> {
+        unchecked_nft_uris(&self.pool, limit, timestamp).await
+    }
+
+    pub async fn nft_uris(&self, hash: Bytes32) -> Result<Vec<NftUriRow>> {
+        nft_uris(&self.pool, hash).await
+    }
+
+    pub async fn reset_nft_uris(&self, hash: Bytes32) -> Result<()> {
+        reset_nft_uris(&self.pool, hash).await
+    }
+
+    pub async fn reset_fetched_nft_uris(&self, hash: Bytes32) -> Result<()> {
+        reset_fetched_nft_uris(&self.pool, hash).await
     }
 
     pub async fn set_nft_visible(&self, launcher_id: Bytes32, visible: bool) -> Result<()> {
@@ -39,6 +52,14 @@ impl Database {
         fetch_nft_data(&self.pool, hash).await
     }
 
+    pub async fn nft_media_hashes(&self) -> Result<Vec<Bytes32>> {
+        nft_media_hashes(&self.pool).await
+    }
+
+    pub async fn delete_nft_data(&self, hash: Bytes32) -> Result<()> {
+        delete_nft_data(&self.pool, hash).await
+    }
+
     pub async fn collection(&self, collection_id: Bytes32) -> Result<CollectionRow> {
         collection(&self.pool, collection_id).await
     }
@@ -233,8 +254,24 @@ impl<'a> DatabaseTx<'a> {
         insert_nft_uri(&mut *self.tx, uri, hash).await
     }
 
-    pub async fn set_nft_uri_checked(&mut self, uri: String, hash: Bytes32) -> Result<()> {
-        set_nft_uri_checked(&mut *self.tx, uri, hash).await
+    pub async fn set_nft_uri_checked(
+        &mut self,
+        uri: String,
+        hash: Bytes32,
+        timestamp: u64,
+    ) -> Result<()> {
+        set_nft_uri_checked(&mut *self.tx, uri, hash, timestamp).await
+    }
+
+    pub async fn set_nft_uri_failed(
+        &mut self,
+        uri: String,
+        hash: Bytes32,
+        error: String,
+        timestamp: u64,
+        next_retry: Option<u64>,
+    ) -> Result<()> {
+        set_nft_uri_failed(&mut *self.tx, uri, hash, error, timestamp, next_retry).await
     }
 
     pub async fn insert_nft_data(&mut self, hash: Bytes32, nft_data: NftData) -> Result<()> {
@@ -526,9 +563,23 @@ async fn insert_nft_uri(conn: impl SqliteExecutor<'_>, uri: String, hash: Bytes3
     Ok(())
 }
 
-async fn unchecked_nft_uris(conn: impl SqliteExecutor<'_>, limit: u32) -> Result<Vec<NftUri>> {
+/// URIs which haven't been checked yet, and are due to be tried at the given timestamp.
+async fn unchecked_nft_uris(
+    conn: impl SqliteExecutor<'_>,
+    limit: u32,
+    timestamp: u64,
+) -> Result<Vec<NftUri>> {
+    let timestamp = timestamp.to_be_bytes();
+    let timestamp = timestamp.as_ref();
+
     let rows = sqlx::query!(
-        "SELECT `hash`, `uri` FROM `nft_uris` WHERE `checked` = 0 LIMIT ?",
+        "
+        SELECT `hash`, `uri`, `attempts` FROM `nft_uris`
+        WHERE `checked` = 0 AND (`next_retry` IS NULL OR `next_retry` <= ?)
+        ORDER BY `attempts` ASC
+        LIMIT ?
+        ",
+        timestamp,
         limit
     )
     .fetch_all(conn)
@@ -539,22 +590,120 @@ async fn unchecked_nft_uris(conn: impl SqliteExecutor<'_>, limit: u32) -> Result
     for row in rows {
         let hash = to_bytes32(&row.hash)?;
         let uri = row.uri;
+        let attempts = row.attempts.try_into()?;
 
-        uris.push(NftUri { hash, uri });
+        uris.push(NftUri {
+            hash,
+            uri,
+            attempts,
+        });
     }
 
     Ok(uris)
 }
 
+async fn nft_uris(conn: impl SqliteExecutor<'_>, hash: Bytes32) -> Result<Vec<NftUriRow>> {
+    let hash = hash.as_ref();
+
+    sqlx::query_as!(
+        NftUriSql,
+        "SELECT * FROM `nft_uris` WHERE `hash` = ? ORDER BY `uri` ASC",
+        hash
+    )
+    .fetch_all(conn)
+    .await?
+    .into_iter()
+    .map(into_row)
+    .collect()
+}
+
+async fn reset_nft_uris(conn: impl SqliteExecutor<'_>, hash: Bytes32) -> Result<()> {
+    let hash = hash.as_ref();
+
+    sqlx::query!(
+        "
+        UPDATE `nft_uris`
+        SET `checked` = 0, `attempts` = 0, `last_error` = NULL, `next_retry` = NULL
+        WHERE `hash` = ? AND `hash` NOT IN (SELECT `hash` FROM `nft_data`)
+        ",
+        hash
+    )
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+/// Marks URIs which were fetched successfully as unchecked, so that they're fetched again.
+/// Failed URIs are left alone, so that they keep backing off.
+async fn reset_fetched_nft_uris(conn: impl SqliteExecutor<'_>, hash: Bytes32) -> Result<()> {
+    let hash = hash.as_ref();
+
+    sqlx::query!(
+        "
+        UPDATE `nft_uris` SET `checked` = 0, `next_retry` = NULL
+        WHERE `hash` = ? AND `checked` = 1 AND `last_error` IS NULL
+        ",
+        hash
+    )
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
 async fn set_nft_uri_checked(
     conn: impl SqliteExecutor<'_>,
     uri: String,
     hash: Bytes32,
+    timestamp: u64,
 ) -> Result<()> {
     let hash = hash.as_ref();
+    let timestamp = timestamp.to_be_bytes();
+    let timestamp = timestamp.as_ref();
 
     sqlx::query!(
-        "UPDATE `nft_uris` SET `checked` = 1 WHERE `hash` = ? AND `uri` = ?",
+        "
+        UPDATE `nft_uris`
+        SET `checked` = 1, `attempts` = `attempts` + 1, `last_error` = NULL,
+            `last_attempt` = ?, `next_retry` = NULL
+        WHERE `hash` = ? AND `uri` = ?
+        ",
+        timestamp,
+        hash,
+        uri
+    )
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+async fn set_nft_uri_failed(
+    conn: impl SqliteExecutor<'_>,
+    uri: String,
+    hash: Bytes32,
+    error: String,
+    timestamp: u64,
+    next_retry: Option<u64>,
+) -> Result<()> {
+    let hash = hash.as_ref();
+    let timestamp = timestamp.to_be_bytes();
+    let timestamp = timestamp.as_ref();
+    let checked = next_retry.is_none();
+    let next_retry = next_retry.map(|next_retry| next_retry.to_be_bytes().to_vec());
+
+    sqlx::query!(
+        "
+        UPDATE `nft_uris`
+        SET `checked` = ?, `attempts` = `attempts` + 1, `last_error` = ?,
+            `last_attempt` = ?, `next_retry` = ?
+        WHERE `hash` = ? AND `uri` = ?
+        ",
+        checked,
+        error,
+        timestamp,
+        next_retry,
         hash,
         uri
     )
@@ -601,6 +750,31 @@ async fn fetch_nft_data(conn: impl SqliteExecutor<'_>, hash: Bytes32) -> Result<
     }))
 }
 
+/// The hashes of data which isn't the offchain metadata of any NFT.
+async fn nft_media_hashes(conn: impl SqliteExecutor<'_>) -> Result<Vec<Bytes32>> {
+    let rows = sqlx::query!(
+        "
+        SELECT `hash` FROM `nft_data` WHERE `hash` NOT IN (
+            SELECT `metadata_hash` FROM `nfts` WHERE `metadata_hash` IS NOT NULL
+        )
+        "
+    )
+    .fetch_all(conn)
+    .await?;
+
+    rows.into_iter().map(|row| to_bytes32(&row.hash)).collect()
+}
+
+async fn delete_nft_data(conn: impl SqliteExecutor<'_>, hash: Bytes32) -> Result<()> {
+    let hash = hash.as_ref();
+
+    sqlx::query!("DELETE FROM `nft_data` WHERE `hash` = ?", hash)
+        .execute(conn)
+        .await?;
+
+    Ok(())
+}
+
 async fn insert_nft(conn: impl SqliteExecutor<'_>, row: NftRow) -> Result<()> {
     let launcher_id = row.launcher_id.as_ref();
     let coin_id = row.coin_id.as_ref();
@@ -1306,3 +1480,111 @@ async fn set_nft_created_height(
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use test_log::test;
+
+    use crate::test_database;
+
+    use super::*;
+
+    #[test(tokio::test)]
+    async fn test_retry_failed_nft_uri() -> anyhow::Result<()> {
+        let db = test_database().await?;
+        let hash = Bytes32::new([1; 32]);
+        let uri = "https://example.com/1.png".to_string();
+
+        let mut tx = db.tx().await?;
+        tx.insert_nft_uri(uri.clone(), hash).await?;
+        tx.commit().await?;
+
+        let uris = db.unchecked_nft_uris(10, 1000).await?;
+        assert_eq!(uris.len(), 1);
+        assert_eq!(uris[0].uri, uri);
+        assert_eq!(uris[0].attempts, 0);
+
+        // A failed URI is skipped until it's due to be retried.
+        let mut tx = db.tx().await?;
+        tx.set_nft_uri_failed(uri.clone(), hash, "Timeout".to_string(), 1000, Some(1300))
+            .await?;
+        tx.commit().await?;
+
+        assert!(db.unchecked_nft_uris(10, 1299).await?.is_empty());
+
+        let uris = db.unchecked_nft_uris(10, 1300).await?;
+        assert_eq!(uris.len(), 1);
+        assert_eq!(uris[0].attempts, 1);
+
+        // Once it's given up on, it isn't tried again until a retry is requested.
+        let mut tx = db.tx().await?;
+        tx.set_nft_uri_failed(uri.clone(), hash, "Mismatch".to_string(), 1300, None)
+            .await?;
+        tx.commit().await?;
+
+        assert!(db.unchecked_nft_uris(10, u64::MAX).await?.is_empty());
+
+        let rows = db.nft_uris(hash).await?;
+        assert_eq!(rows.len(), 1);
+        assert!(rows[0].checked);
+        assert_eq!(rows[0].attempts, 2);
+        assert_eq!(rows[0].last_error.as_deref(), Some("Mismatch"));
+        assert_eq!(rows[0].next_retry, None);
+
+        db.reset_nft_uris(hash).await?;
+
+        let uris = db.unchecked_nft_uris(10, 1300).await?;
+        assert_eq!(uris.len(), 1);
+        assert_eq!(uris[0].attempts, 0);
+
+        let rows = db.nft_uris(hash).await?;
+        assert!(!rows[0].checked);
+        assert_eq!(rows[0].last_error, None);
+
+        Ok(())
+    }
+
+    #[test(tokio::test)]
+    async fn test_reset_nft_uris() -> anyhow::Result<()> {
+        let db = test_database().await?;
+        let hash = Bytes32::new([2; 32]);
+        let fetched = "https://example.com/2.png".to_string();
+        let failed = "https://example.org/2.png".to_string();
+
+        let mut tx = db.tx().await?;
+        tx.insert_nft_uri(fetched.clone(), hash).await?;
+        tx.insert_nft_uri(failed.clone(), hash).await?;
+        tx.set_nft_uri_checked(fetched.clone(), hash, 1000).await?;
+        tx.set_nft_uri_failed(failed.clone(), hash, "Not found".to_string(), 1000, None)
+            .await?;
+        tx.commit().await?;
+
+        assert!(db.unchecked_nft_uris(10, 1000).await?.is_empty());
+
+        // Only the URI which was fetched is queued again once the data has been evicted.
+        db.reset_fetched_nft_uris(hash).await?;
+
+        let uris = db.unchecked_nft_uris(10, 1000).await?;
+        assert_eq!(uris.len(), 1);
+        assert_eq!(uris[0].uri, fetched);
+
+        // Retrying isn't necessary once the data is stored.
+        let mut tx = db.tx().await?;
+        tx.set_nft_uri_checked(fetched.clone(), hash, 1000).await?;
+        tx.insert_nft_data(
+            hash,
+            NftData {
+                blob: b"{}".to_vec(),
+                mime_type: "application/json".to_string(),
+            },
+        )
+        .await?;
+        tx.commit().await?;
+
+        db.reset_nft_uris(hash).await?;
+
+        assert!(db.unchecked_nft_uris(10, 1000).await?.is_empty());
+
+        Ok(())
+    }
+}
@@ -6,6 +6,7 @@ use crate::{
 };
 
 impl Database {
+    /// Returns the unspent and unlocked XCH coins, oldest first.
     pub async fn spendable_coins(&self) -> Result<Vec<Coin>> {
         spendable_coins(&self.pool).await
     }
@@ -79,6 +80,7 @@ async fn spendable_coins(conn: impl SqliteExecutor<'_>) -> Result<Vec<Coin>> {
         AND (`offered_coins`.`coin_id` IS NULL OR `offers`.`status` > 0)
         AND `coin_states`.`transaction_id` IS NULL
         AND `kind` = 1
+        ORDER BY `coin_states`.`created_height` ASC
         "
     )
     .fetch_all(conn)
@@ -1,10 +1,14 @@
 mod cat;
 mod cat_coin;
+mod clawback;
 mod coin_state;
 mod collection;
+mod contact;
 mod derivation;
 mod did;
 mod did_coin;
+mod history;
+mod invoice;
 mod nft;
 mod nft_coin;
 mod nft_uri;
@@ -12,13 +16,18 @@ mod offer;
 
 pub use cat::*;
 pub use cat_coin::*;
+pub use clawback::*;
 pub use coin_state::*;
 pub use collection::*;
+pub use contact::*;
 pub use derivation::*;
 pub use did::*;
 pub use did_coin::*;
+pub use history::*;
+pub use invoice::*;
 pub use nft::*;
 pub(crate) use nft_coin::*;
+pub use nft_uri::*;
 pub use offer::*;
 
 use crate::DatabaseError;
@@ -0,0 +1,78 @@
+use chia::{
+    protocol::{Bytes32, Coin, CoinState},
+    puzzles::LineageProof,
+};
+
+use crate::{to_bytes32, to_u64, DatabaseError};
+
+use super::IntoRow;
+
+pub(crate) struct ClawbackCoinSql {
+    pub parent_coin_id: Vec<u8>,
+    pub puzzle_hash: Vec<u8>,
+    pub amount: Vec<u8>,
+    pub created_height: Option<i64>,
+    pub spent_height: Option<i64>,
+    pub sender_puzzle_hash: Vec<u8>,
+    pub recipient_puzzle_hash: Vec<u8>,
+    pub seconds: Vec<u8>,
+    pub asset_id: Option<Vec<u8>>,
+    pub parent_parent_coin_id: Option<Vec<u8>>,
+    pub parent_inner_puzzle_hash: Option<Vec<u8>>,
+    pub parent_amount: Option<Vec<u8>>,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct ClawbackRow {
+    pub sender_puzzle_hash: Bytes32,
+    pub recipient_puzzle_hash: Bytes32,
+    pub seconds: u64,
+    pub asset_id: Option<Bytes32>,
+    pub lineage_proof: Option<LineageProof>,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct ClawbackCoinRow {
+    pub coin_state: CoinState,
+    pub clawback: ClawbackRow,
+}
+
+impl IntoRow for ClawbackCoinSql {
+    type Row = ClawbackCoinRow;
+
+    fn into_row(self) -> Result<ClawbackCoinRow, DatabaseError> {
+        let lineage_proof = match (
+            self.parent_parent_coin_id,
+            self.parent_inner_puzzle_hash,
+            self.parent_amount,
+        ) {
+            (Some(parent_parent_coin_id), Some(parent_inner_puzzle_hash), Some(parent_amount)) => {
+                Some(LineageProof {
+                    parent_parent_coin_info: to_bytes32(&parent_parent_coin_id)?,
+                    parent_inner_puzzle_hash: to_bytes32(&parent_inner_puzzle_hash)?,
+                    parent_amount: to_u64(&parent_amount)?,
+                })
+            }
+            _ => None,
+        };
+
+        Ok(ClawbackCoinRow {
+            coin_state: CoinState {
+                coin: Coin {
+                    parent_coin_info: to_bytes32(&self.parent_coin_id)?,
+                    puzzle_hash: to_bytes32(&self.puzzle_hash)?,
+                    amount: to_u64(&self.amount)?,
+                },
+                spent_height: self.spent_height.map(TryInto::try_into).transpose()?,
+                created_height: self.created_height.map(TryInto::try_into).transpose()?,
+            },
+            clawback: ClawbackRow {
+                sender_puzzle_hash: to_bytes32(&self.sender_puzzle_hash)?,
+                recipient_puzzle_hash: to_bytes32(&self.recipient_puzzle_hash)?,
+                seconds: to_u64(&self.seconds)?,
+                asset_id: self.asset_id.as_deref().map(to_bytes32).transpose()?,
+                lineage_proof,
+            },
+        })
+    }
+}
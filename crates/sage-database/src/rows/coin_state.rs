@@ -27,6 +27,7 @@ pub enum CoinKind {
     Cat,
     Nft,
     Did,
+    Clawback,
 }
 
 impl CoinKind {
@@ -36,6 +37,7 @@ impl CoinKind {
             2 => Self::Cat,
             3 => Self::Nft,
             4 => Self::Did,
+            5 => Self::Clawback,
             _ => Self::Unknown,
         }
     }
@@ -0,0 +1,36 @@
+use chia::protocol::Bytes32;
+
+use crate::{to_bytes32, DatabaseError};
+
+use super::IntoRow;
+
+pub(crate) struct ContactSql {
+    pub id: i64,
+    pub name: String,
+    pub puzzle_hash: Vec<u8>,
+    pub did_id: Option<Vec<u8>>,
+    pub notes: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ContactRow {
+    pub id: u32,
+    pub name: String,
+    pub puzzle_hash: Bytes32,
+    pub did_id: Option<Bytes32>,
+    pub notes: Option<String>,
+}
+
+impl IntoRow for ContactSql {
+    type Row = ContactRow;
+
+    fn into_row(self) -> Result<ContactRow, DatabaseError> {
+        Ok(ContactRow {
+            id: self.id.try_into()?,
+            name: self.name,
+            puzzle_hash: to_bytes32(&self.puzzle_hash)?,
+            did_id: self.did_id.as_deref().map(to_bytes32).transpose()?,
+            notes: self.notes,
+        })
+    }
+}
@@ -0,0 +1,56 @@
+use chia::protocol::Bytes32;
+
+use crate::{to_bytes32, to_u64, DatabaseError};
+
+use super::IntoRow;
+
+pub(crate) struct TransactionHistorySql {
+    pub id: Vec<u8>,
+    pub height: Option<i64>,
+    pub direction: i64,
+    pub fee: Vec<u8>,
+    pub counterparty_puzzle_hash: Option<Vec<u8>>,
+    pub label: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct TransactionHistoryRow {
+    pub id: Bytes32,
+    pub height: Option<u32>,
+    pub direction: HistoryDirection,
+    pub fee: u64,
+    pub counterparty_puzzle_hash: Option<Bytes32>,
+    pub label: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(u8)]
+pub enum HistoryDirection {
+    Incoming = 0,
+    Outgoing = 1,
+    Internal = 2,
+}
+
+impl IntoRow for TransactionHistorySql {
+    type Row = TransactionHistoryRow;
+
+    fn into_row(self) -> Result<TransactionHistoryRow, DatabaseError> {
+        Ok(TransactionHistoryRow {
+            id: to_bytes32(&self.id)?,
+            height: self.height.map(TryInto::try_into).transpose()?,
+            direction: match self.direction {
+                0 => HistoryDirection::Incoming,
+                1 => HistoryDirection::Outgoing,
+                2 => HistoryDirection::Internal,
+                _ => return Err(DatabaseError::InvalidEnumVariant),
+            },
+            fee: to_u64(&self.fee)?,
+            counterparty_puzzle_hash: self
+                .counterparty_puzzle_hash
+                .as_deref()
+                .map(to_bytes32)
+                .transpose()?,
+            label: self.label,
+        })
+    }
+}
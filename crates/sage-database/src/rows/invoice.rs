@@ -0,0 +1,106 @@
+use chia::protocol::Bytes32;
+
+use crate::{to_bytes32, to_u64, DatabaseError};
+
+use super::IntoRow;
+
+pub(crate) struct InvoiceSql {
+    pub id: i64,
+    pub p2_puzzle_hash: Vec<u8>,
+    pub asset_id: Option<Vec<u8>>,
+    pub amount: Vec<u8>,
+    pub paid_amount: Vec<u8>,
+    pub memo: Option<String>,
+    pub expires_at: Option<Vec<u8>>,
+    pub created_at: Vec<u8>,
+    pub status: i64,
+}
+
+/// An invoice is paid to its own p2 puzzle hash, which is never used for anything else.
+#[derive(Debug, Clone)]
+pub struct InvoiceRow {
+    pub id: u32,
+    pub p2_puzzle_hash: Bytes32,
+    pub asset_id: Option<Bytes32>,
+    pub amount: u64,
+    pub paid_amount: u64,
+    pub memo: Option<String>,
+    pub expires_at: Option<u64>,
+    pub created_at: u64,
+    pub status: InvoiceStatus,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(u8)]
+pub enum InvoiceStatus {
+    Unpaid = 0,
+    PartiallyPaid = 1,
+    Paid = 2,
+    Overpaid = 3,
+}
+
+impl InvoiceStatus {
+    pub fn from_amounts(amount: u64, paid_amount: u64) -> Self {
+        if paid_amount == 0 {
+            Self::Unpaid
+        } else if paid_amount < amount {
+            Self::PartiallyPaid
+        } else if paid_amount == amount {
+            Self::Paid
+        } else {
+            Self::Overpaid
+        }
+    }
+}
+
+pub(crate) struct InvoicePaymentSql {
+    pub coin_id: Vec<u8>,
+    pub invoice_id: i64,
+    pub amount: Vec<u8>,
+    pub height: i64,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct InvoicePaymentRow {
+    pub coin_id: Bytes32,
+    pub invoice_id: u32,
+    pub amount: u64,
+    pub height: u32,
+}
+
+impl IntoRow for InvoiceSql {
+    type Row = InvoiceRow;
+
+    fn into_row(self) -> Result<InvoiceRow, DatabaseError> {
+        Ok(InvoiceRow {
+            id: self.id.try_into()?,
+            p2_puzzle_hash: to_bytes32(&self.p2_puzzle_hash)?,
+            asset_id: self.asset_id.as_deref().map(to_bytes32).transpose()?,
+            amount: to_u64(&self.amount)?,
+            paid_amount: to_u64(&self.paid_amount)?,
+            memo: self.memo,
+            expires_at: self.expires_at.as_deref().map(to_u64).transpose()?,
+            created_at: to_u64(&self.created_at)?,
+            status: match self.status {
+                0 => InvoiceStatus::Unpaid,
+                1 => InvoiceStatus::PartiallyPaid,
+                2 => InvoiceStatus::Paid,
+                3 => InvoiceStatus::Overpaid,
+                _ => return Err(DatabaseError::InvalidInvoiceStatus(self.status)),
+            },
+        })
+    }
+}
+
+impl IntoRow for InvoicePaymentSql {
+    type Row = InvoicePaymentRow;
+
+    fn into_row(self) -> Result<InvoicePaymentRow, DatabaseError> {
+        Ok(InvoicePaymentRow {
+            coin_id: to_bytes32(&self.coin_id)?,
+            invoice_id: self.invoice_id.try_into()?,
+            amount: to_u64(&self.amount)?,
+            height: self.height.try_into()?,
+        })
+    }
+}
@@ -1 +1,44 @@
+use chia::protocol::Bytes32;
 
+use crate::{to_bytes32, to_u64, DatabaseError};
+
+use super::IntoRow;
+
+pub(crate) struct NftUriSql {
+    pub uri: String,
+    pub hash: Vec<u8>,
+    pub checked: bool,
+    pub attempts: i64,
+    pub last_error: Option<String>,
+    pub last_attempt: Option<Vec<u8>>,
+    pub next_retry: Option<Vec<u8>>,
+}
+
+/// A URI which the data for a given hash can be fetched from, along with the result of doing so.
+/// URIs which fail are retried with a backoff, until they're given up on and marked as checked.
+#[derive(Debug, Clone)]
+pub struct NftUriRow {
+    pub uri: String,
+    pub hash: Bytes32,
+    pub checked: bool,
+    pub attempts: u32,
+    pub last_error: Option<String>,
+    pub last_attempt: Option<u64>,
+    pub next_retry: Option<u64>,
+}
+
+impl IntoRow for NftUriSql {
+    type Row = NftUriRow;
+
+    fn into_row(self) -> Result<NftUriRow, DatabaseError> {
+        Ok(NftUriRow {
+            uri: self.uri,
+            hash: to_bytes32(&self.hash)?,
+            checked: self.checked,
+            attempts: self.attempts.try_into()?,
+            last_error: self.last_error,
+            last_attempt: self.last_attempt.as_deref().map(to_u64).transpose()?,
+            next_retry: self.next_retry.as_deref().map(to_u64).transpose()?,
+        })
+    }
+}
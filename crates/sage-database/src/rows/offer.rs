@@ -12,6 +12,7 @@ pub(crate) struct OfferSql {
     pub fee: Vec<u8>,
     pub status: i64,
     pub inserted_timestamp: Vec<u8>,
+    pub cancelling: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -23,6 +24,7 @@ pub struct OfferRow {
     pub fee: u64,
     pub status: OfferStatus,
     pub inserted_timestamp: u64,
+    pub cancelling: bool,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -56,6 +58,7 @@ impl IntoRow for OfferSql {
                 _ => return Err(DatabaseError::InvalidOfferStatus(self.status)),
             },
             inserted_timestamp: to_u64(&self.inserted_timestamp)?,
+            cancelling: self.cancelling,
         })
     }
 }
@@ -0,0 +1,15 @@
+use std::sync::atomic::{AtomicU32, Ordering};
+
+use sqlx::{migrate, SqlitePool};
+
+use crate::Database;
+
+static INDEX: AtomicU32 = AtomicU32::new(0);
+
+/// Creates an empty in-memory database with all of the migrations applied.
+pub async fn test_database() -> anyhow::Result<Database> {
+    let index = INDEX.fetch_add(1, Ordering::SeqCst);
+    let pool = SqlitePool::connect(&format!("file:testdb{index}?mode=memory&cache=shared")).await?;
+    migrate!("../../migrations").run(&pool).await?;
+    Ok(Database::new(pool))
+}
@@ -26,6 +26,17 @@ impl Database {
         transactions(&self.pool).await
     }
 
+    pub async fn transaction(&self, transaction_id: Bytes32) -> Result<Option<TransactionRow>> {
+        transaction(&self.pool, transaction_id).await
+    }
+
+    pub async fn transaction_signature(
+        &self,
+        transaction_id: Bytes32,
+    ) -> Result<Option<Signature>> {
+        transaction_signature(&self.pool, transaction_id).await
+    }
+
     pub async fn resubmittable_transactions(
         &self,
         threshold: i64,
@@ -79,6 +90,10 @@ impl<'a> DatabaseTx<'a> {
     pub async fn transaction_coin_ids(&mut self, transaction_id: Bytes32) -> Result<Vec<Bytes32>> {
         transaction_coin_ids(&mut *self.tx, transaction_id).await
     }
+
+    pub async fn coin_spends(&mut self, transaction_id: Bytes32) -> Result<Vec<CoinSpend>> {
+        coin_spends(&mut *self.tx, transaction_id).await
+    }
 }
 
 async fn insert_pending_transaction(
@@ -205,6 +220,61 @@ async fn transactions(conn: impl SqliteExecutor<'_>) -> Result<Vec<TransactionRo
         .collect()
 }
 
+async fn transaction(
+    conn: impl SqliteExecutor<'_>,
+    transaction_id: Bytes32,
+) -> Result<Option<TransactionRow>> {
+    let transaction_id = transaction_id.as_ref();
+
+    let Some(row) = sqlx::query!(
+        "
+        SELECT
+            `transaction_id`,
+            `fee`,
+            `submitted_at`
+        FROM `transactions`
+        WHERE `transaction_id` = ?
+        ",
+        transaction_id
+    )
+    .fetch_optional(conn)
+    .await?
+    else {
+        return Ok(None);
+    };
+
+    Ok(Some(TransactionRow {
+        transaction_id: to_bytes32(&row.transaction_id)?,
+        fee: u64::from_be_bytes(to_bytes(&row.fee)?),
+        submitted_at: row.submitted_at,
+    }))
+}
+
+async fn transaction_signature(
+    conn: impl SqliteExecutor<'_>,
+    transaction_id: Bytes32,
+) -> Result<Option<Signature>> {
+    let transaction_id = transaction_id.as_ref();
+
+    let Some(row) = sqlx::query!(
+        "
+        SELECT `aggregated_signature`
+        FROM `transactions`
+        WHERE `transaction_id` = ?
+        ",
+        transaction_id
+    )
+    .fetch_optional(conn)
+    .await?
+    else {
+        return Ok(None);
+    };
+
+    Ok(Some(Signature::from_bytes(&to_bytes(
+        &row.aggregated_signature,
+    )?)?))
+}
+
 async fn coin_transaction_id(
     conn: impl SqliteExecutor<'_>,
     coin_id: Bytes32,
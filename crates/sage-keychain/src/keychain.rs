@@ -100,6 +100,48 @@ impl Keychain {
         }
     }
 
+    /// Checks that the password decrypts the secret keys. Every secret key in the keychain
+    /// is encrypted with the same password, so only one of them needs to be checked.
+    pub fn verify_password(&self, password: &[u8]) -> Result<(), KeychainError> {
+        let Some(encrypted) = self.keys.values().find_map(|key_data| match key_data {
+            KeyData::Public { .. } => None,
+            KeyData::Secret { encrypted, .. } => Some(encrypted),
+        }) else {
+            return Ok(());
+        };
+
+        decrypt::<SecretKeyData>(encrypted, password)?;
+
+        Ok(())
+    }
+
+    /// Re-encrypts every secret key with a new password. Nothing is changed unless all of the
+    /// keys can be decrypted with the old password.
+    pub fn change_password(
+        &mut self,
+        old_password: &[u8],
+        new_password: &[u8],
+    ) -> Result<(), KeychainError> {
+        let mut reencrypted = Vec::new();
+
+        for (&fingerprint, key_data) in &self.keys {
+            let KeyData::Secret { encrypted, .. } = key_data else {
+                continue;
+            };
+
+            let data = decrypt::<SecretKeyData>(encrypted, old_password)?;
+            reencrypted.push((fingerprint, encrypt(new_password, &mut self.rng, &data)?));
+        }
+
+        for (fingerprint, new_encrypted) in reencrypted {
+            if let Some(KeyData::Secret { encrypted, .. }) = self.keys.get_mut(&fingerprint) {
+                *encrypted = new_encrypted;
+            }
+        }
+
+        Ok(())
+    }
+
     pub fn add_public_key(&mut self, master_pk: &PublicKey) -> Result<u32, KeychainError> {
         let fingerprint = master_pk.get_fingerprint();
 
@@ -129,6 +171,8 @@ impl Keychain {
             return Err(KeychainError::KeyExists);
         }
 
+        self.verify_password(password)?;
+
         let encrypted = encrypt(
             password,
             &mut self.rng,
@@ -162,6 +206,8 @@ impl Keychain {
             return Err(KeychainError::KeyExists);
         }
 
+        self.verify_password(password)?;
+
         let encrypted = encrypt(password, &mut self.rng, &SecretKeyData(entropy))?;
 
         self.keys.insert(
@@ -176,3 +222,63 @@ impl Keychain {
         Ok(fingerprint)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_verify_password() -> Result<(), KeychainError> {
+        let mut keychain = Keychain::default();
+
+        // Without any secret keys, there is nothing for a password to protect.
+        keychain.verify_password(b"anything")?;
+
+        let master_sk = SecretKey::from_seed(&[1; 32]);
+        keychain.add_secret_key(&master_sk, b"password")?;
+
+        keychain.verify_password(b"password")?;
+        assert!(matches!(
+            keychain.verify_password(b"wrong"),
+            Err(KeychainError::Decrypt)
+        ));
+
+        // Every key has to be encrypted with the same password.
+        assert!(matches!(
+            keychain.add_secret_key(&SecretKey::from_seed(&[2; 32]), b"wrong"),
+            Err(KeychainError::Decrypt)
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_change_password() -> Result<(), KeychainError> {
+        let mut keychain = Keychain::default();
+
+        let master_sk = SecretKey::from_seed(&[1; 32]);
+        let fingerprint = keychain.add_secret_key(&master_sk, b"old")?;
+        keychain.add_public_key(&SecretKey::from_seed(&[2; 32]).public_key())?;
+
+        assert!(matches!(
+            keychain.change_password(b"wrong", b"new"),
+            Err(KeychainError::Decrypt)
+        ));
+        keychain.verify_password(b"old")?;
+
+        keychain.change_password(b"old", b"new")?;
+
+        let keychain = Keychain::from_bytes(&keychain.to_bytes()?)?;
+
+        keychain.verify_password(b"new")?;
+        assert!(matches!(
+            keychain.verify_password(b"old"),
+            Err(KeychainError::Decrypt)
+        ));
+
+        let (_mnemonic, secret_key) = keychain.extract_secrets(fingerprint, b"new")?;
+        assert_eq!(secret_key, Some(master_sk));
+
+        Ok(())
+    }
+}
@@ -9,7 +9,7 @@ use chia_wallet_sdk::{
 use clvmr::{Allocator, NodePtr};
 use tracing::{debug_span, warn};
 
-use crate::WalletError;
+use crate::{Clawback, WalletError};
 
 #[derive(Debug, Clone)]
 #[allow(clippy::large_enum_variant)]
@@ -32,6 +32,11 @@ pub enum ChildKind {
         lineage_proof: LineageProof,
         metadata: Option<NftMetadata>,
     },
+    Clawback {
+        clawback: Clawback,
+        asset_id: Option<Bytes32>,
+        lineage_proof: Option<LineageProof>,
+    },
 }
 
 impl ChildKind {
@@ -87,6 +92,12 @@ impl ChildKind {
             return Ok(Self::Unknown { hint: None });
         };
 
+        // The clawback is only used if its puzzle hash matches the coin's (inner) puzzle hash.
+        let clawback = create_coin
+            .memos
+            .as_ref()
+            .and_then(|memos| Clawback::parse_memos(allocator, memos.value));
+
         let hint = if let Some(memos) = create_coin.memos {
             let memos = Memos::<(Bytes32, NodePtr)>::from_clvm(allocator, memos.value).ok();
             memos.map(|memos| memos.value.0)
@@ -115,6 +126,16 @@ impl ChildKind {
                     return Ok(unknown);
                 };
 
+                if let Some(clawback) =
+                    clawback.filter(|clawback| clawback.puzzle_hash() == cat.p2_puzzle_hash.into())
+                {
+                    return Ok(Self::Clawback {
+                        clawback,
+                        asset_id: Some(cat.asset_id),
+                        lineage_proof: Some(lineage_proof),
+                    });
+                }
+
                 return Ok(Self::Cat {
                     asset_id: cat.asset_id,
                     lineage_proof,
@@ -126,6 +147,16 @@ impl ChildKind {
             Ok(None) => {}
         }
 
+        if let Some(clawback) =
+            clawback.filter(|clawback| clawback.puzzle_hash() == coin.puzzle_hash.into())
+        {
+            return Ok(Self::Clawback {
+                clawback,
+                asset_id: None,
+                lineage_proof: None,
+            });
+        }
+
         match Nft::<HashedPtr>::parse_child(allocator, parent_coin, parent_puzzle, parent_solution)
         {
             // If there was an error parsing the NFT, we can exit early.
@@ -201,10 +232,24 @@ impl ChildKind {
             Self::Cat { p2_puzzle_hash, .. } => Some(*p2_puzzle_hash),
             Self::Did { info, .. } => Some(info.p2_puzzle_hash),
             Self::Nft { info, .. } => Some(info.p2_puzzle_hash),
+            Self::Clawback { clawback, .. } => Some(clawback.recipient_puzzle_hash),
+        }
+    }
+
+    /// The p2 puzzle hashes that can spend the coin. It belongs to the wallet if any of them do.
+    pub fn p2_puzzle_hashes(&self) -> Vec<Bytes32> {
+        match self {
+            Self::Clawback { clawback, .. } => {
+                vec![clawback.recipient_puzzle_hash, clawback.sender_puzzle_hash]
+            }
+            _ => self.p2_puzzle_hash().into_iter().collect(),
         }
     }
 
     pub fn subscribe(&self) -> bool {
-        matches!(self, Self::Cat { .. } | Self::Did { .. } | Self::Nft { .. })
+        matches!(
+            self,
+            Self::Cat { .. } | Self::Did { .. } | Self::Nft { .. } | Self::Clawback { .. }
+        )
     }
 }
@@ -0,0 +1,136 @@
+use chia::{
+    clvm_traits::FromClvm,
+    clvm_utils::TreeHash,
+    protocol::{Bytes, Bytes32},
+};
+use chia_wallet_sdk::{
+    AssertBeforeSecondsRelative, AssertSecondsRelative, AugmentedConditionArgs,
+    AugmentedConditionSolution, DriverError, MerkleTree, Mod, P2OneOfManyArgs, P2OneOfManySolution,
+    Spend, SpendContext,
+};
+use clvmr::{Allocator, NodePtr};
+
+/// A payment that the sender can revoke until the timelock expires, after which
+/// only the recipient can claim it.
+///
+/// The puzzle is a merkle tree of two paths, each of which wraps the p2 puzzle of
+/// one of the parties with a timelock condition.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Clawback {
+    pub sender_puzzle_hash: Bytes32,
+    pub recipient_puzzle_hash: Bytes32,
+    pub seconds: u64,
+}
+
+impl Clawback {
+    pub fn new(sender_puzzle_hash: Bytes32, recipient_puzzle_hash: Bytes32, seconds: u64) -> Self {
+        Self {
+            sender_puzzle_hash,
+            recipient_puzzle_hash,
+            seconds,
+        }
+    }
+
+    /// Parses the clawback from the memos of the coin it was created with.
+    /// The memos must start with the recipient puzzle hash as the hint,
+    /// followed by the sender puzzle hash and the number of seconds.
+    pub fn parse_memos(allocator: &Allocator, memos: NodePtr) -> Option<Self> {
+        let memos = Vec::<Bytes>::from_clvm(allocator, memos).ok()?;
+
+        let [recipient_puzzle_hash, sender_puzzle_hash, seconds, ..] = memos.as_slice() else {
+            return None;
+        };
+
+        Some(Self {
+            sender_puzzle_hash: sender_puzzle_hash.as_ref().try_into().ok()?,
+            recipient_puzzle_hash: recipient_puzzle_hash.as_ref().try_into().ok()?,
+            seconds: u64::from_be_bytes(seconds.as_ref().try_into().ok()?),
+        })
+    }
+
+    /// The memos that the clawback coin must be created with, so that the recipient
+    /// can find it and either party can reconstruct its puzzle.
+    pub fn memos(&self, extra_memos: Vec<Bytes>) -> Vec<Bytes> {
+        let mut memos = vec![
+            self.recipient_puzzle_hash.into(),
+            self.sender_puzzle_hash.into(),
+            self.seconds.to_be_bytes().to_vec().into(),
+        ];
+        memos.extend(extra_memos);
+        memos
+    }
+
+    pub fn sender_path_hash(&self) -> TreeHash {
+        AugmentedConditionArgs::new(
+            AssertBeforeSecondsRelative::new(self.seconds),
+            TreeHash::from(self.sender_puzzle_hash),
+        )
+        .curry_tree_hash()
+    }
+
+    pub fn recipient_path_hash(&self) -> TreeHash {
+        AugmentedConditionArgs::new(
+            AssertSecondsRelative::new(self.seconds),
+            TreeHash::from(self.recipient_puzzle_hash),
+        )
+        .curry_tree_hash()
+    }
+
+    pub fn puzzle_hash(&self) -> TreeHash {
+        P2OneOfManyArgs::new(self.merkle_tree().root()).curry_tree_hash()
+    }
+
+    /// Spends the coin back to the sender, which is only valid before the timelock expires.
+    pub fn sender_spend(&self, ctx: &mut SpendContext, spend: Spend) -> Result<Spend, DriverError> {
+        let puzzle = ctx.curry(AugmentedConditionArgs::new(
+            AssertBeforeSecondsRelative::new(self.seconds),
+            spend.puzzle,
+        ))?;
+        self.spend_path(ctx, self.sender_path_hash(), puzzle, spend.solution)
+    }
+
+    /// Spends the coin to the recipient, which is only valid after the timelock expires.
+    pub fn recipient_spend(
+        &self,
+        ctx: &mut SpendContext,
+        spend: Spend,
+    ) -> Result<Spend, DriverError> {
+        let puzzle = ctx.curry(AugmentedConditionArgs::new(
+            AssertSecondsRelative::new(self.seconds),
+            spend.puzzle,
+        ))?;
+        self.spend_path(ctx, self.recipient_path_hash(), puzzle, spend.solution)
+    }
+
+    fn merkle_tree(&self) -> MerkleTree {
+        MerkleTree::new(&[
+            self.sender_path_hash().into(),
+            self.recipient_path_hash().into(),
+        ])
+    }
+
+    fn spend_path(
+        &self,
+        ctx: &mut SpendContext,
+        path_hash: TreeHash,
+        path_puzzle: NodePtr,
+        inner_solution: NodePtr,
+    ) -> Result<Spend, DriverError> {
+        let path_solution = ctx.alloc(&AugmentedConditionSolution::new(inner_solution))?;
+
+        // Both paths are leaves of the tree, so there is always a proof for them.
+        let merkle_proof = self
+            .merkle_tree()
+            .proof(path_hash.into())
+            .expect("missing merkle proof");
+
+        let puzzle = ctx.curry(P2OneOfManyArgs::new(self.merkle_tree().root()))?;
+        let solution = ctx.alloc(&P2OneOfManySolution::new(
+            merkle_proof,
+            path_puzzle,
+            path_solution,
+        ))?;
+
+        Ok(Spend::new(puzzle, solution))
+    }
+}
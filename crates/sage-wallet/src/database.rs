@@ -7,9 +7,15 @@ use chia::{
     bls::Signature,
     protocol::{Bytes32, CoinState},
 };
-use sage_database::{CatRow, CoinKind, Database, DatabaseTx, DidRow, NftRow};
+use sage_database::{
+    CatRow, ClawbackRow, CoinKind, Database, DatabaseTx, DidRow, InvoicePaymentRow, InvoiceRow,
+    InvoiceStatus, NftRow,
+};
 
-use crate::{compute_nft_info, fetch_nft_did, ChildKind, Transaction, WalletError, WalletPeer};
+use crate::{
+    compute_nft_info, fetch_nft_did, insert_outgoing_history, ChildKind, Transaction, WalletError,
+    WalletPeer,
+};
 
 #[derive(Debug, Default, Clone, Copy)]
 pub struct UpsertCounters {
@@ -21,12 +27,13 @@ pub struct UpsertCounters {
     pub delete_puzzle: Duration,
 }
 
+/// Inserts or updates a coin, and returns the invoice it paid if that changed its status.
 pub async fn upsert_coin(
     tx: &mut DatabaseTx<'_>,
     coin_state: CoinState,
     transaction_id: Option<Bytes32>,
     counters: &mut UpsertCounters,
-) -> Result<(), WalletError> {
+) -> Result<Option<InvoiceRow>, WalletError> {
     let coin_id = coin_state.coin.coin_id();
 
     // Check if the coin is plain XCH, rather than an asset that wraps the p2 puzzle hash.
@@ -56,21 +63,82 @@ pub async fn upsert_coin(
         let start = Instant::now();
         tx.insert_p2_coin(coin_id).await?;
         counters.insert_p2_coin += start.elapsed();
-    } else {
-        let start = Instant::now();
-        update_created_puzzle(tx, coin_state).await?;
-        counters.update_created_puzzle += start.elapsed();
+
+        // CAT payments are only recorded once the puzzle has been synced and its lineage is known.
+        return record_invoice_payment(tx, coin_state, coin_state.coin.puzzle_hash, None).await;
     }
 
-    Ok(())
+    let start = Instant::now();
+    update_created_puzzle(tx, coin_state).await?;
+    counters.update_created_puzzle += start.elapsed();
+
+    // The puzzle of a coin created by a pending transaction is inserted before it's confirmed,
+    // so the payment wouldn't be recorded when the puzzle is synced.
+    if let Some(cat) = tx.cat_coin(coin_id).await? {
+        return record_invoice_payment(tx, coin_state, cat.p2_puzzle_hash, Some(cat.asset_id))
+            .await;
+    }
+
+    Ok(None)
 }
 
+/// Records a confirmed coin paid to the address of an invoice, and returns the invoice if its
+/// status changed as a result. Coins of the wrong asset, or which have already been recorded,
+/// are ignored.
+pub async fn record_invoice_payment(
+    tx: &mut DatabaseTx<'_>,
+    coin_state: CoinState,
+    p2_puzzle_hash: Bytes32,
+    asset_id: Option<Bytes32>,
+) -> Result<Option<InvoiceRow>, WalletError> {
+    let Some(height) = coin_state.created_height else {
+        return Ok(None);
+    };
+
+    let Some(mut invoice) = tx.invoice_by_p2_puzzle_hash(p2_puzzle_hash).await? else {
+        return Ok(None);
+    };
+
+    if invoice.asset_id != asset_id {
+        return Ok(None);
+    }
+
+    let inserted = tx
+        .insert_invoice_payment(InvoicePaymentRow {
+            coin_id: coin_state.coin.coin_id(),
+            invoice_id: invoice.id,
+            amount: coin_state.coin.amount,
+            height,
+        })
+        .await?;
+
+    if !inserted {
+        return Ok(None);
+    }
+
+    invoice.paid_amount = invoice.paid_amount.saturating_add(coin_state.coin.amount);
+
+    let status = InvoiceStatus::from_amounts(invoice.amount, invoice.paid_amount);
+
+    tx.update_invoice_paid_amount(invoice.id, invoice.paid_amount, status)
+        .await?;
+
+    if status == invoice.status {
+        return Ok(None);
+    }
+
+    invoice.status = status;
+
+    Ok(Some(invoice))
+}
+
+/// Inserts the synced puzzle of a coin, and returns the invoice it paid if that changed its status.
 pub async fn insert_puzzle(
     tx: &mut DatabaseTx<'_>,
     coin_state: CoinState,
     info: ChildKind,
     minter_did: Option<Bytes32>,
-) -> Result<(), WalletError> {
+) -> Result<Option<InvoiceRow>, WalletError> {
     let coin_id = coin_state.coin.coin_id();
 
     match info {
@@ -94,6 +162,8 @@ pub async fn insert_puzzle(
             .await?;
             tx.insert_cat_coin(coin_id, lineage_proof, p2_puzzle_hash, asset_id)
                 .await?;
+
+            return record_invoice_payment(tx, coin_state, p2_puzzle_hash, Some(asset_id)).await;
         }
         ChildKind::Did {
             lineage_proof,
@@ -106,7 +176,7 @@ pub async fn insert_puzzle(
             tx.insert_did_coin(coin_id, lineage_proof, info).await?;
 
             if coin_state.spent_height.is_some() {
-                return Ok(());
+                return Ok(None);
             }
 
             let name = tx.get_future_did_name(launcher_id).await?;
@@ -158,7 +228,7 @@ pub async fn insert_puzzle(
             .await?;
 
             if coin_state.spent_height.is_some() {
-                return Ok(());
+                return Ok(None);
             }
 
             let mut row = tx.nft_row(launcher_id).await?.unwrap_or(NftRow {
@@ -201,6 +271,9 @@ pub async fn insert_puzzle(
                 tx.insert_collection(collection).await?;
             }
 
+            tx.set_nft_attributes(launcher_id, computed_info.attributes)
+                .await?;
+
             row.owner_did = owner_did;
             row.created_height = coin_state.created_height;
 
@@ -226,9 +299,32 @@ pub async fn insert_puzzle(
                 }
             }
         }
+        ChildKind::Clawback {
+            clawback,
+            asset_id,
+            lineage_proof,
+        } => {
+            tx.sync_coin(
+                coin_id,
+                Some(clawback.recipient_puzzle_hash),
+                CoinKind::Clawback,
+            )
+            .await?;
+            tx.insert_clawback_coin(
+                coin_id,
+                ClawbackRow {
+                    sender_puzzle_hash: clawback.sender_puzzle_hash,
+                    recipient_puzzle_hash: clawback.recipient_puzzle_hash,
+                    seconds: clawback.seconds,
+                    asset_id,
+                    lineage_proof,
+                },
+            )
+            .await?;
+        }
     }
 
-    Ok(())
+    Ok(None)
 }
 
 pub async fn delete_puzzle(tx: &mut DatabaseTx<'_>, coin_id: Bytes32) -> Result<(), WalletError> {
@@ -260,6 +356,55 @@ pub async fn insert_transaction(
     transaction: Transaction,
     aggregated_signature: Signature,
 ) -> Result<Vec<Bytes32>, WalletError> {
+    let minter_dids = fetch_minter_dids(peer, genesis_challenge, &transaction).await?;
+
+    let mut tx = db.tx().await?;
+    let subscriptions = insert_transaction_rows(
+        &mut tx,
+        transaction_id,
+        transaction,
+        aggregated_signature,
+        &minter_dids,
+    )
+    .await?;
+    tx.commit().await?;
+
+    Ok(subscriptions)
+}
+
+/// Replaces a pending transaction with another that spends the same coins, such as one with a
+/// higher fee. Both happen in a single database transaction, so the coins are never unlocked.
+pub async fn replace_transaction(
+    db: &Database,
+    peer: &WalletPeer,
+    genesis_challenge: Bytes32,
+    old_transaction_id: Bytes32,
+    transaction_id: Bytes32,
+    transaction: Transaction,
+    aggregated_signature: Signature,
+) -> Result<Vec<Bytes32>, WalletError> {
+    let minter_dids = fetch_minter_dids(peer, genesis_challenge, &transaction).await?;
+
+    let mut tx = db.tx().await?;
+    safely_remove_transaction(&mut tx, old_transaction_id).await?;
+    let subscriptions = insert_transaction_rows(
+        &mut tx,
+        transaction_id,
+        transaction,
+        aggregated_signature,
+        &minter_dids,
+    )
+    .await?;
+    tx.commit().await?;
+
+    Ok(subscriptions)
+}
+
+async fn fetch_minter_dids(
+    peer: &WalletPeer,
+    genesis_challenge: Bytes32,
+    transaction: &Transaction,
+) -> Result<HashMap<Bytes32, Bytes32>, WalletError> {
     let mut coin_spends = HashMap::new();
 
     for input in &transaction.inputs {
@@ -282,8 +427,16 @@ pub async fn insert_transaction(
         }
     }
 
-    let mut tx = db.tx().await?;
+    Ok(minter_dids)
+}
 
+async fn insert_transaction_rows(
+    tx: &mut DatabaseTx<'_>,
+    transaction_id: Bytes32,
+    transaction: Transaction,
+    aggregated_signature: Signature,
+    minter_dids: &HashMap<Bytes32, Bytes32>,
+) -> Result<Vec<Bytes32>, WalletError> {
     tx.insert_pending_transaction(transaction_id, aggregated_signature, transaction.fee)
         .await?;
 
@@ -292,7 +445,10 @@ pub async fn insert_transaction(
         .iter()
         .map(|input| input.coin_spend.coin.coin_id())
     {
-        delete_puzzle(&mut tx, coin_id).await?;
+        delete_puzzle(tx, coin_id).await?;
+
+        // Spending an offered coin ourselves cancels the offer, rather than it being taken.
+        tx.set_coin_offers_cancelling(coin_id, true).await?;
     }
 
     let mut subscriptions = Vec::new();
@@ -312,11 +468,16 @@ pub async fn insert_transaction(
                 continue;
             }
 
-            let Some(p2_puzzle_hash) = output.kind.p2_puzzle_hash() else {
-                continue;
-            };
+            let mut is_owned = false;
 
-            if !tx.is_p2_puzzle_hash(p2_puzzle_hash).await? {
+            for p2_puzzle_hash in output.kind.p2_puzzle_hashes() {
+                if tx.is_p2_puzzle_hash(p2_puzzle_hash).await? {
+                    is_owned = true;
+                    break;
+                }
+            }
+
+            if !is_owned {
                 continue;
             }
 
@@ -324,12 +485,13 @@ pub async fn insert_transaction(
                 .await?;
             tx.sync_coin(
                 coin_id,
-                Some(p2_puzzle_hash),
+                output.kind.p2_puzzle_hash(),
                 match output.kind {
                     ChildKind::Unknown { .. } | ChildKind::Launcher => CoinKind::Unknown,
                     ChildKind::Cat { .. } => CoinKind::Cat,
                     ChildKind::Did { .. } => CoinKind::Did,
                     ChildKind::Nft { .. } => CoinKind::Nft,
+                    ChildKind::Clawback { .. } => CoinKind::Clawback,
                 },
             )
             .await?;
@@ -339,7 +501,7 @@ pub async fn insert_transaction(
             }
 
             insert_puzzle(
-                &mut tx,
+                tx,
                 coin_state,
                 output.kind,
                 minter_dids.get(&output.coin.coin_id()).copied(),
@@ -348,8 +510,6 @@ pub async fn insert_transaction(
         }
     }
 
-    tx.commit().await?;
-
     Ok(subscriptions)
 }
 
@@ -367,3 +527,193 @@ pub async fn safely_remove_transaction(
 
     Ok(())
 }
+
+/// Inserts the clawback coins sent by the wallet in a transaction that was confirmed without
+/// the wallet knowing about it, such as before a resync. They're hinted to the recipient, so
+/// otherwise the sender would never find them. The ids of the coins are returned, so that they
+/// can be subscribed to in order to find out whether they have been spent since.
+pub async fn insert_sent_clawbacks(
+    tx: &mut DatabaseTx<'_>,
+    transaction: &Transaction,
+    height: Option<u32>,
+) -> Result<Vec<Bytes32>, WalletError> {
+    let mut coin_ids = Vec::new();
+
+    for input in &transaction.inputs {
+        for output in &input.outputs {
+            let ChildKind::Clawback { clawback, .. } = &output.kind else {
+                continue;
+            };
+
+            if !tx.is_p2_puzzle_hash(clawback.sender_puzzle_hash).await? {
+                continue;
+            }
+
+            let coin_state = CoinState::new(output.coin, None, height);
+
+            tx.insert_coin_state(coin_state, true, None).await?;
+            insert_puzzle(tx, coin_state, output.kind.clone(), None).await?;
+
+            coin_ids.push(output.coin.coin_id());
+        }
+    }
+
+    Ok(coin_ids)
+}
+
+/// Removes a transaction once it has been confirmed, and records it in the transaction history.
+/// Its coins may not have been synced yet, so the history can't be left to the sync.
+pub async fn remove_confirmed_transaction(
+    tx: &mut DatabaseTx<'_>,
+    transaction_id: Bytes32,
+) -> Result<(), WalletError> {
+    let transaction = Transaction::from_coin_spends(tx.coin_spends(transaction_id).await?)?;
+    insert_outgoing_history(tx, transaction_id, &transaction).await?;
+
+    tx.confirm_coins(transaction_id).await?;
+    safely_remove_transaction(tx, transaction_id).await
+}
+
+/// Removes a transaction that will never be confirmed, such as one rejected from the mempool.
+/// Any offers it would have cancelled can still be taken, so they are no longer marked.
+pub async fn remove_failed_transaction(
+    tx: &mut DatabaseTx<'_>,
+    transaction_id: Bytes32,
+) -> Result<(), WalletError> {
+    for coin_spend in tx.coin_spends(transaction_id).await? {
+        tx.set_coin_offers_cancelling(coin_spend.coin.coin_id(), false)
+            .await?;
+    }
+
+    safely_remove_transaction(tx, transaction_id).await
+}
+
+#[cfg(test)]
+mod tests {
+    use sage_database::{InvoiceRow, InvoiceStatus};
+    use test_log::test;
+
+    use crate::{CoinSelection, SyncEvent, TestWallet};
+
+    #[test(tokio::test)]
+    async fn test_invoice_payment() -> anyhow::Result<()> {
+        let mut test = TestWallet::new(1000).await?;
+
+        let p2_puzzle_hash = test.wallet.p2_puzzle_hash(false, false).await?;
+
+        let mut tx = test.wallet.db.tx().await?;
+        let invoice_id = tx
+            .insert_invoice(InvoiceRow {
+                id: 0,
+                p2_puzzle_hash,
+                asset_id: None,
+                amount: 1000,
+                paid_amount: 0,
+                memo: None,
+                expires_at: None,
+                created_at: 0,
+                status: InvoiceStatus::Unpaid,
+            })
+            .await?;
+        tx.commit().await?;
+
+        // The address is reserved, so it's no longer handed out.
+        assert_ne!(
+            test.wallet.p2_puzzle_hash(false, false).await?,
+            p2_puzzle_hash
+        );
+
+        let coin_spends = test
+            .wallet
+            .send_xch(
+                p2_puzzle_hash,
+                600,
+                0,
+                Vec::new(),
+                None,
+                &CoinSelection::default(),
+                false,
+                true,
+            )
+            .await?;
+
+        test.transact(coin_spends).await?;
+        test.consume_until(|event| matches!(event, SyncEvent::InvoiceUpdated { .. }))
+            .await;
+
+        // The change is sent elsewhere, so only the payment itself is counted.
+        let invoice = test
+            .wallet
+            .db
+            .invoice(invoice_id)
+            .await?
+            .expect("missing invoice");
+        assert_eq!(invoice.paid_amount, 600);
+        assert_eq!(invoice.status, InvoiceStatus::PartiallyPaid);
+        assert_eq!(test.wallet.db.invoice_payments(invoice_id).await?.len(), 1);
+
+        Ok(())
+    }
+
+    #[test(tokio::test)]
+    async fn test_cat_invoice_payment() -> anyhow::Result<()> {
+        let mut test = TestWallet::new(1000).await?;
+
+        let (coin_spends, asset_id) = test
+            .wallet
+            .issue_cat(1000, 0, None, &CoinSelection::default(), false, true)
+            .await?;
+        test.transact(coin_spends).await?;
+        test.wait_for_coins().await;
+
+        let p2_puzzle_hash = test.wallet.p2_puzzle_hash(false, false).await?;
+
+        let mut tx = test.wallet.db.tx().await?;
+        let invoice_id = tx
+            .insert_invoice(InvoiceRow {
+                id: 0,
+                p2_puzzle_hash,
+                asset_id: Some(asset_id),
+                amount: 1000,
+                paid_amount: 0,
+                memo: None,
+                expires_at: None,
+                created_at: 0,
+                status: InvoiceStatus::Unpaid,
+            })
+            .await?;
+        tx.commit().await?;
+
+        // Paying ourselves inserts the puzzle of the payment before the coin is confirmed.
+        let coin_spends = test
+            .wallet
+            .send_cat(
+                asset_id,
+                p2_puzzle_hash,
+                1000,
+                0,
+                Vec::new(),
+                None,
+                &CoinSelection::default(),
+                false,
+                true,
+            )
+            .await?;
+
+        test.transact(coin_spends).await?;
+        test.consume_until(|event| matches!(event, SyncEvent::InvoiceUpdated { .. }))
+            .await;
+
+        let invoice = test
+            .wallet
+            .db
+            .invoice(invoice_id)
+            .await?
+            .expect("missing invoice");
+        assert_eq!(invoice.paid_amount, 1000);
+        assert_eq!(invoice.status, InvoiceStatus::Paid);
+        assert_eq!(test.wallet.db.invoice_payments(invoice_id).await?.len(), 1);
+
+        Ok(())
+    }
+}
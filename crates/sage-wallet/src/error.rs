@@ -33,6 +33,9 @@ pub enum WalletError {
     #[error("Request error: {0}")]
     Request(#[from] reqwest::Error),
 
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
     #[error("Timeout exceeded")]
     Elapsed(#[from] Elapsed),
 
@@ -78,6 +81,12 @@ pub enum WalletError {
     #[error("SECP is not supported")]
     SecpNotSupported,
 
+    #[error("Coin with id {0} has already been spent")]
+    CoinSpent(Bytes32),
+
+    #[error("Coin with id {0} is locked by a pending transaction or offer")]
+    CoinLocked(Bytes32),
+
     #[error("Missing DID with id {0}")]
     MissingDid(Bytes32),
 
@@ -101,4 +110,40 @@ pub enum WalletError {
 
     #[error("Empty bulk transfer")]
     EmptyBulkTransfer,
+
+    #[error("Empty payment list")]
+    EmptyPayments,
+
+    #[error("Duplicate payment of {1} to {0}")]
+    DuplicatePayment(Bytes32, u64),
+
+    #[error("Offer with id {0} can no longer be cancelled")]
+    UncancellableOffer(Bytes32),
+
+    #[error("Coin with id {0} can't be spent back to the wallet")]
+    UncancellableCoin(Bytes32),
+
+    #[error("Missing clawback coin with id {0}")]
+    MissingClawback(Bytes32),
+
+    #[error("Clawback coin with id {0} wasn't sent to this wallet")]
+    NotClawbackRecipient(Bytes32),
+
+    #[error("Clawback coin with id {0} wasn't sent by this wallet")]
+    NotClawbackSender(Bytes32),
+
+    #[error("CAT with asset id {0} wasn't issued by this wallet")]
+    NotCatIssuer(Bytes32),
+
+    #[error("There are no dust coins to consolidate")]
+    NothingToConsolidate,
+
+    #[error("Empty denomination list")]
+    EmptyDenominations,
+
+    #[error("Invalid denomination of {0}")]
+    InvalidDenomination(u64),
+
+    #[error("Duplicate denomination of {0}")]
+    DuplicateDenomination(u64),
 }
@@ -0,0 +1,296 @@
+use chia::protocol::{Bytes32, CoinState};
+use sage_database::{DatabaseTx, HistoryDirection, TransactionHistoryRow};
+
+use crate::{ChildKind, CoinKind, Transaction, WalletError};
+
+/// Records a transaction sent by the wallet in the transaction history, once it has been confirmed.
+/// Only coins that belong to the wallet are recorded, and the first output which is sent elsewhere
+/// determines the counterparty and memos. If there is no such output, the transaction is internal.
+pub async fn insert_outgoing_history(
+    tx: &mut DatabaseTx<'_>,
+    transaction_id: Bytes32,
+    transaction: &Transaction,
+) -> Result<(), WalletError> {
+    let mut spent = Vec::new();
+    let mut created = Vec::new();
+    let mut counterparty = None;
+
+    for input in &transaction.inputs {
+        let coin_id = input.coin_spend.coin.coin_id();
+
+        if tx.is_p2_coin(coin_id).await?.is_some() {
+            spent.push(coin_id);
+        }
+
+        for output in &input.outputs {
+            if matches!(output.kind, ChildKind::Launcher) {
+                continue;
+            }
+
+            let coin_id = output.coin.coin_id();
+
+            if tx.is_p2_coin(coin_id).await?.is_some() {
+                created.push(coin_id);
+            }
+
+            let p2_puzzle_hash = output
+                .kind
+                .p2_puzzle_hash()
+                .unwrap_or(output.coin.puzzle_hash);
+
+            if counterparty.is_none() && !tx.is_p2_puzzle_hash(p2_puzzle_hash).await? {
+                counterparty = Some((p2_puzzle_hash, output.memos.clone()));
+            }
+        }
+    }
+
+    let direction = if counterparty.is_some() {
+        HistoryDirection::Outgoing
+    } else {
+        HistoryDirection::Internal
+    };
+
+    let (counterparty_puzzle_hash, memos) = counterparty.unzip();
+
+    tx.insert_transaction_history(TransactionHistoryRow {
+        id: transaction_id,
+        height: None,
+        direction,
+        fee: transaction.fee,
+        counterparty_puzzle_hash,
+        label: None,
+    })
+    .await?;
+
+    for coin_id in spent {
+        tx.insert_transaction_history_coin(transaction_id, coin_id, true)
+            .await?;
+    }
+
+    for coin_id in created {
+        tx.insert_transaction_history_coin(transaction_id, coin_id, false)
+            .await?;
+    }
+
+    for (index, memo) in memos.unwrap_or_default().into_iter().enumerate() {
+        tx.insert_transaction_history_memo(transaction_id, index, memo)
+            .await?;
+    }
+
+    // The spent coins have already been synced, so the height is known at this point.
+    tx.update_transaction_history_heights().await?;
+
+    Ok(())
+}
+
+/// Records a coin sent to the wallet by someone else in the transaction history. Coins created by
+/// the same parent spend are grouped into a single entry, whose id is the parent coin id.
+/// The fee is paid by the sender, so it isn't recorded. Coins such as farming rewards don't have
+/// a parent spend, so they are recorded without a counterparty or memos.
+pub async fn insert_incoming_history(
+    tx: &mut DatabaseTx<'_>,
+    coin_state: CoinState,
+    parent: Option<Transaction>,
+) -> Result<(), WalletError> {
+    let id = coin_state.coin.parent_coin_info;
+
+    let mut counterparty_puzzle_hash = None;
+    let mut memos = Vec::new();
+
+    if let Some(input) = parent.and_then(|parent| parent.inputs.into_iter().next()) {
+        counterparty_puzzle_hash = Some(match input.kind {
+            CoinKind::Cat { p2_puzzle_hash, .. } => p2_puzzle_hash,
+            CoinKind::Did { info } => info.p2_puzzle_hash,
+            CoinKind::Nft { info, .. } => info.p2_puzzle_hash,
+            CoinKind::Unknown | CoinKind::Launcher => input.coin_spend.coin.puzzle_hash,
+        });
+
+        if let Some(output) = input
+            .outputs
+            .into_iter()
+            .find(|output| output.coin == coin_state.coin)
+        {
+            memos = output.memos;
+        }
+    }
+
+    tx.insert_transaction_history(TransactionHistoryRow {
+        id,
+        height: coin_state.created_height,
+        direction: HistoryDirection::Incoming,
+        fee: 0,
+        counterparty_puzzle_hash,
+        label: None,
+    })
+    .await?;
+
+    tx.insert_transaction_history_coin(id, coin_state.coin.coin_id(), false)
+        .await?;
+
+    for (index, memo) in memos.into_iter().enumerate() {
+        tx.insert_transaction_history_memo(id, index, memo).await?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use chia::protocol::Bytes;
+    use sage_database::{HistoryDirection, TransactionHistoryFilter};
+    use test_log::test;
+
+    use crate::{CoinSelection, SyncEvent, TestWallet};
+
+    #[test(tokio::test)]
+    async fn test_transaction_history() -> anyhow::Result<()> {
+        let mut alice = TestWallet::new(1000).await?;
+        let mut bob = alice.next(0).await?;
+
+        let memo = Bytes::from(b"rent".to_vec());
+
+        let coin_spends = alice
+            .wallet
+            .send_xch(
+                bob.puzzle_hash,
+                250,
+                100,
+                vec![memo.clone()],
+                None,
+                &CoinSelection::default(),
+                false,
+                true,
+            )
+            .await?;
+
+        alice.transact(coin_spends).await?;
+        alice.wait_for_coins().await;
+
+        let outgoing = TransactionHistoryFilter {
+            direction: Some(HistoryDirection::Outgoing),
+            ..Default::default()
+        };
+
+        let rows = alice
+            .wallet
+            .db
+            .transaction_history(&outgoing, 0, 10)
+            .await?;
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].fee, 100);
+        assert_eq!(rows[0].counterparty_puzzle_hash, Some(bob.puzzle_hash));
+        assert!(rows[0].height.is_some());
+
+        let id = rows[0].id;
+        assert_eq!(
+            alice.wallet.db.transaction_history_memos(id).await?,
+            [memo.clone()]
+        );
+        assert_eq!(
+            alice
+                .wallet
+                .db
+                .transaction_history_coins(id, true)
+                .await?
+                .len(),
+            1
+        );
+
+        let created = alice.wallet.db.transaction_history_coins(id, false).await?;
+        assert_eq!(created.len(), 1);
+        assert_eq!(created[0].coin_state.coin.amount, 650);
+
+        alice
+            .wallet
+            .db
+            .set_transaction_label(id, Some("Rent".to_string()))
+            .await?;
+
+        let labeled = TransactionHistoryFilter {
+            label: Some("Rent".to_string()),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            alice.wallet.db.transaction_history_count(&labeled).await?,
+            1
+        );
+
+        bob.consume_until(|event| matches!(event, SyncEvent::TransactionHistory))
+            .await;
+
+        let incoming = TransactionHistoryFilter {
+            direction: Some(HistoryDirection::Incoming),
+            ..Default::default()
+        };
+
+        let rows = bob.wallet.db.transaction_history(&incoming, 0, 10).await?;
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].fee, 0);
+        assert_eq!(rows[0].counterparty_puzzle_hash, Some(alice.puzzle_hash));
+
+        let id = rows[0].id;
+        assert_eq!(bob.wallet.db.transaction_history_memos(id).await?, [memo]);
+
+        let created = bob.wallet.db.transaction_history_coins(id, false).await?;
+        assert_eq!(created.len(), 1);
+        assert_eq!(created[0].coin_state.coin.amount, 250);
+
+        Ok(())
+    }
+    #[test(tokio::test)]
+    async fn test_restored_transaction_history() -> anyhow::Result<()> {
+        let mut alice = TestWallet::new(1000).await?;
+        let bob = alice.next(0).await?;
+
+        let coin_spends = alice
+            .wallet
+            .send_xch(
+                bob.puzzle_hash,
+                250,
+                100,
+                Vec::new(),
+                None,
+                &CoinSelection::default(),
+                false,
+                true,
+            )
+            .await?;
+
+        alice.transact(coin_spends).await?;
+        alice.wait_for_coins().await;
+
+        // The transaction was never pending in the restored wallet, so it's recorded from the
+        // spends on chain instead.
+        let mut restored = alice.restore().await?;
+
+        restored
+            .consume_until(|event| matches!(event, SyncEvent::TransactionHistory))
+            .await;
+
+        let outgoing = TransactionHistoryFilter {
+            direction: Some(HistoryDirection::Outgoing),
+            ..Default::default()
+        };
+
+        let rows = restored
+            .wallet
+            .db
+            .transaction_history(&outgoing, 0, 10)
+            .await?;
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].fee, 100);
+        assert_eq!(rows[0].counterparty_puzzle_hash, Some(bob.puzzle_hash));
+        assert!(rows[0].height.is_some());
+
+        let created = restored
+            .wallet
+            .db
+            .transaction_history_coins(rows[0].id, false)
+            .await?;
+        assert_eq!(created.len(), 1);
+        assert_eq!(created[0].coin_state.coin.amount, 650);
+
+        Ok(())
+    }
+}
@@ -1,7 +1,9 @@
 mod child_kind;
+mod clawback;
 mod coin_kind;
 mod database;
 mod error;
+mod history;
 mod queues;
 mod sync_manager;
 mod transaction;
@@ -10,9 +12,11 @@ mod wallet;
 mod wallet_peer;
 
 pub use child_kind::*;
+pub use clawback::*;
 pub use coin_kind::*;
 pub use database::*;
 pub use error::*;
+pub use history::*;
 pub use queues::*;
 pub use sync_manager::*;
 pub use transaction::*;
@@ -1,10 +1,12 @@
 mod cat_queue;
+mod history_queue;
 mod nft_uri_queue;
 mod offer_queue;
 mod puzzle_queue;
 mod transaction_queue;
 
 pub use cat_queue::*;
+pub use history_queue::*;
 pub use nft_uri_queue::*;
 pub use offer_queue::*;
 pub use puzzle_queue::*;
@@ -0,0 +1,268 @@
+use std::{net::SocketAddr, sync::Arc, time::Duration};
+
+use chia::protocol::{Bytes32, Coin, CoinSpend, CoinState};
+use futures_util::{stream::FuturesUnordered, StreamExt};
+use itertools::Itertools;
+use sage_database::Database;
+use tokio::{
+    sync::{mpsc, Mutex},
+    task::spawn_blocking,
+    time::{sleep, timeout},
+};
+use tracing::debug;
+
+use crate::{
+    database::insert_sent_clawbacks, insert_incoming_history, insert_outgoing_history, PeerState,
+    SyncCommand, SyncEvent, Transaction, WalletError, WalletPeer,
+};
+
+#[derive(Debug)]
+pub struct HistoryQueue {
+    db: Database,
+    genesis_challenge: Bytes32,
+    state: Arc<Mutex<PeerState>>,
+    sync_sender: mpsc::Sender<SyncEvent>,
+    command_sender: mpsc::Sender<SyncCommand>,
+}
+
+impl HistoryQueue {
+    pub fn new(
+        db: Database,
+        genesis_challenge: Bytes32,
+        state: Arc<Mutex<PeerState>>,
+        sync_sender: mpsc::Sender<SyncEvent>,
+        command_sender: mpsc::Sender<SyncCommand>,
+    ) -> Self {
+        Self {
+            db,
+            genesis_challenge,
+            state,
+            sync_sender,
+            command_sender,
+        }
+    }
+
+    pub async fn start(mut self, delay: Duration) -> Result<(), WalletError> {
+        loop {
+            self.process_batch().await?;
+            sleep(delay).await;
+        }
+    }
+
+    async fn process_batch(&mut self) -> Result<(), WalletError> {
+        let peers = self.state.lock().await.peers();
+
+        if peers.is_empty() {
+            return Ok(());
+        }
+
+        let mut recorded = self.record_incoming(&peers).await?;
+        recorded |= self.record_outgoing(&peers).await?;
+
+        if recorded {
+            self.sync_sender
+                .send(SyncEvent::TransactionHistory)
+                .await
+                .ok();
+        }
+
+        Ok(())
+    }
+
+    async fn record_incoming(&mut self, peers: &[WalletPeer]) -> Result<bool, WalletError> {
+        let coin_states = self.db.unrecorded_incoming_coins(peers.len() * 5).await?;
+
+        if coin_states.is_empty() {
+            return Ok(false);
+        }
+
+        debug!(
+            "Recording a batch of {} incoming coins in the transaction history",
+            coin_states.len()
+        );
+
+        let mut futures = FuturesUnordered::new();
+
+        let mut coin_states_iter = coin_states.into_iter();
+
+        for peer in peers {
+            for _ in 0..5 {
+                let Some(coin_state) = coin_states_iter.next() else {
+                    break;
+                };
+
+                let genesis_challenge = self.genesis_challenge;
+                let addr = peer.socket_addr();
+                let peer = peer.clone();
+
+                futures.push(async move {
+                    let result = fetch_parent(&peer, genesis_challenge, coin_state.coin).await;
+                    (addr, coin_state, result)
+                });
+            }
+        }
+
+        let mut recorded = false;
+
+        while let Some((addr, coin_state, result)) = futures.next().await {
+            match result {
+                Ok(parent) => {
+                    let mut tx = self.db.tx().await?;
+                    insert_incoming_history(&mut tx, coin_state, parent).await?;
+                    tx.commit().await?;
+
+                    recorded = true;
+                }
+                Err(error) => {
+                    debug!(
+                        "Failed to lookup parent spend of {} from peer {}: {}",
+                        coin_state.coin.coin_id(),
+                        addr,
+                        error
+                    );
+
+                    self.ban_on_error(addr, &error).await;
+                }
+            }
+        }
+
+        Ok(recorded)
+    }
+
+    /// Transactions are normally recorded when they're confirmed, but that isn't possible for
+    /// coins that were spent while the wallet didn't know about the transaction, such as after
+    /// a resync. Those are recorded from the spends on chain instead. Which coins were spent
+    /// together can't be told apart, so all of the coins spent at the same height are recorded as
+    /// a single transaction, whose id is the first of their coin ids. Clawbacks sent by the wallet
+    /// are found this way as well.
+    async fn record_outgoing(&mut self, peers: &[WalletPeer]) -> Result<bool, WalletError> {
+        let coin_states = self.db.unrecorded_spent_coins(peers.len()).await?;
+
+        if coin_states.is_empty() {
+            return Ok(false);
+        }
+
+        debug!(
+            "Recording a batch of {} spent coins in the transaction history",
+            coin_states.len()
+        );
+
+        let mut futures = FuturesUnordered::new();
+
+        let groups = coin_states
+            .into_iter()
+            .chunk_by(|coin_state| coin_state.spent_height)
+            .into_iter()
+            .map(|(_, group)| group.collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+
+        for (peer, coin_states) in peers.iter().zip(groups) {
+            let addr = peer.socket_addr();
+            let peer = peer.clone();
+
+            futures.push(async move {
+                let result = fetch_spends(&peer, &coin_states).await;
+                (addr, coin_states, result)
+            });
+        }
+
+        let mut recorded = false;
+        let mut subscriptions = Vec::new();
+
+        while let Some((addr, coin_states, result)) = futures.next().await {
+            match result {
+                Ok(transaction) => {
+                    let transaction_id = coin_states[0].coin.coin_id();
+
+                    let mut tx = self.db.tx().await?;
+                    subscriptions.extend(
+                        insert_sent_clawbacks(&mut tx, &transaction, coin_states[0].spent_height)
+                            .await?,
+                    );
+                    insert_outgoing_history(&mut tx, transaction_id, &transaction).await?;
+                    tx.commit().await?;
+
+                    recorded = true;
+                }
+                Err(error) => {
+                    debug!(
+                        "Failed to lookup spends at height {:?} from peer {}: {}",
+                        coin_states[0].spent_height, addr, error
+                    );
+
+                    self.ban_on_error(addr, &error).await;
+                }
+            }
+        }
+
+        if !subscriptions.is_empty() {
+            self.command_sender
+                .send(SyncCommand::SubscribeCoins {
+                    coin_ids: subscriptions,
+                })
+                .await
+                .ok();
+        }
+
+        Ok(recorded)
+    }
+
+    async fn ban_on_error(&self, addr: SocketAddr, error: &WalletError) {
+        if matches!(
+            error,
+            WalletError::Elapsed(..) | WalletError::PeerMisbehaved | WalletError::Client(..)
+        ) {
+            self.state.lock().await.ban(
+                addr.ip(),
+                Duration::from_secs(300),
+                "failed history spend lookup",
+            );
+        }
+    }
+}
+
+/// Fetches and parses the spend that created a coin, if there is one.
+async fn fetch_parent(
+    peer: &WalletPeer,
+    genesis_challenge: Bytes32,
+    coin: Coin,
+) -> Result<Option<Transaction>, WalletError> {
+    let Some(parent_spend) = timeout(
+        Duration::from_secs(15),
+        peer.fetch_optional_coin_spend(coin.parent_coin_info, genesis_challenge),
+    )
+    .await??
+    else {
+        return Ok(None);
+    };
+
+    let transaction =
+        spawn_blocking(move || Transaction::from_coin_spends(vec![parent_spend])).await??;
+
+    Ok(Some(transaction))
+}
+
+/// Fetches and parses the spends of coins which were spent at the same height.
+async fn fetch_spends(
+    peer: &WalletPeer,
+    coin_states: &[CoinState],
+) -> Result<Transaction, WalletError> {
+    let mut coin_spends = Vec::new();
+
+    for coin_state in coin_states {
+        let coin_id = coin_state.coin.coin_id();
+        let spent_height = coin_state
+            .spent_height
+            .ok_or(WalletError::MissingSpend(coin_id))?;
+
+        let (puzzle_reveal, solution) = timeout(
+            Duration::from_secs(15),
+            peer.fetch_puzzle_solution(coin_id, spent_height),
+        )
+        .await??;
+
+        coin_spends.push(CoinSpend::new(coin_state.coin, puzzle_reveal, solution));
+    }
+
+    Ok(spawn_blocking(move || Transaction::from_coin_spends(coin_spends)).await??)
+}
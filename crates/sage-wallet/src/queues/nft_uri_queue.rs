@@ -1,4 +1,4 @@
-use std::time::Duration;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use futures_lite::StreamExt;
 use futures_util::stream::FuturesUnordered;
@@ -6,17 +6,37 @@ use sage_database::{Database, NftData};
 use tokio::{sync::mpsc, time::sleep};
 use tracing::{debug, info};
 
-use crate::{compute_nft_info, fetch_uri, SyncEvent, WalletError};
+use crate::{
+    compute_nft_info, fetch_uri, MediaCache, SyncEvent, UriError, UriResolver, WalletError,
+};
+
+/// How many times a URI is tried before it's given up on, until a retry is requested.
+const MAX_ATTEMPTS: u32 = 8;
+
+/// The delay before the first retry, which doubles with each failed attempt.
+const BASE_RETRY_DELAY: u64 = 300;
 
 #[derive(Debug)]
 pub struct NftUriQueue {
     db: Database,
     sync_sender: mpsc::Sender<SyncEvent>,
+    resolver: UriResolver,
+    media_cache: MediaCache,
 }
 
 impl NftUriQueue {
-    pub fn new(db: Database, sync_sender: mpsc::Sender<SyncEvent>) -> Self {
-        Self { db, sync_sender }
+    pub fn new(
+        db: Database,
+        sync_sender: mpsc::Sender<SyncEvent>,
+        resolver: UriResolver,
+        media_cache: MediaCache,
+    ) -> Self {
+        Self {
+            db,
+            sync_sender,
+            resolver,
+            media_cache,
+        }
     }
 
     pub async fn start(self, delay: Duration) -> Result<(), WalletError> {
@@ -27,7 +47,9 @@ impl NftUriQueue {
     }
 
     async fn process_batch(&self) -> Result<(), WalletError> {
-        let batch = self.db.unchecked_nft_uris(30).await?;
+        let timestamp = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+
+        let batch = self.db.unchecked_nft_uris(30, timestamp).await?;
 
         if batch.is_empty() {
             return Ok(());
@@ -38,9 +60,17 @@ impl NftUriQueue {
         let mut futures = FuturesUnordered::new();
 
         for item in batch {
+            let resolver = &self.resolver;
+
             futures.push(async move {
-                let result =
-                    fetch_uri(&item.uri, Duration::from_secs(15), Duration::from_secs(15)).await;
+                let result = fetch_uri(
+                    resolver,
+                    &item.uri,
+                    Duration::from_secs(15),
+                    Duration::from_secs(15),
+                    Some(item.hash),
+                )
+                .await;
                 (item, result)
             });
         }
@@ -48,49 +78,85 @@ impl NftUriQueue {
         while let Some((item, result)) = futures.next().await {
             let mut tx = self.db.tx().await?;
 
+            // The content at a URI won't change, so there's no point in trying it again.
+            let mismatch = matches!(result, Err(UriError::HashMismatch { .. }));
+
+            // Offchain metadata is kept in the database, since it's needed to sync NFT names and
+            // collections. Anything else is media, which is cached on disk instead.
+            let result = match result {
+                Ok(data) if tx.nfts_by_metadata_hash(item.hash).await?.is_empty() => self
+                    .media_cache
+                    .insert(
+                        item.hash,
+                        NftData {
+                            mime_type: data.mime_type,
+                            blob: data.blob,
+                        },
+                    )
+                    .await
+                    .map(|()| None)
+                    .map_err(|error| error.to_string()),
+                Ok(data) => Ok(Some(data)),
+                Err(error) => Err(error.to_string()),
+            };
+
             match result {
-                Ok(data) => {
-                    if data.hash == item.hash {
-                        if tx.fetch_nft_data(item.hash).await?.is_none() {
-                            tx.insert_nft_data(
-                                item.hash,
-                                NftData {
-                                    mime_type: data.mime_type,
-                                    blob: data.blob.clone(),
-                                },
-                            )
-                            .await?;
-
-                            let nfts = tx.nfts_by_metadata_hash(item.hash).await?;
-
-                            for mut nft in nfts {
-                                let info = compute_nft_info(nft.minter_did, Some(&data.blob));
-
-                                nft.sensitive_content = info.sensitive_content;
-                                nft.name = info.name;
-                                nft.collection_id =
-                                    info.collection.as_ref().map(|col| col.collection_id);
-
-                                if let Some(collection) = info.collection {
-                                    tx.insert_collection(collection).await?;
-                                }
-
-                                tx.insert_nft(nft).await?;
+                Ok(None) => {
+                    tx.set_nft_uri_checked(item.uri, item.hash, timestamp)
+                        .await?;
+                }
+                Ok(Some(data)) => {
+                    if tx.fetch_nft_data(item.hash).await?.is_none() {
+                        tx.insert_nft_data(
+                            item.hash,
+                            NftData {
+                                mime_type: data.mime_type,
+                                blob: data.blob.clone(),
+                            },
+                        )
+                        .await?;
+
+                        let nfts = tx.nfts_by_metadata_hash(item.hash).await?;
+
+                        for mut nft in nfts {
+                            let info = compute_nft_info(nft.minter_did, Some(&data.blob));
+
+                            nft.sensitive_content = info.sensitive_content;
+                            nft.name = info.name;
+                            nft.collection_id =
+                                info.collection.as_ref().map(|col| col.collection_id);
+
+                            if let Some(collection) = info.collection {
+                                tx.insert_collection(collection).await?;
                             }
+
+                            tx.set_nft_attributes(nft.launcher_id, info.attributes)
+                                .await?;
+
+                            tx.insert_nft(nft).await?;
                         }
-                    } else {
-                        debug!(
-                            "Hash mismatch for URI {} (expected {} but found {})",
-                            item.uri, item.hash, data.hash
-                        );
                     }
+
+                    tx.set_nft_uri_checked(item.uri, item.hash, timestamp)
+                        .await?;
                 }
                 Err(error) => {
                     debug!("{error}");
-                }
-            };
 
-            tx.set_nft_uri_checked(item.uri, item.hash).await?;
+                    tx.set_nft_uri_failed(
+                        item.uri,
+                        item.hash,
+                        error,
+                        timestamp,
+                        if mismatch {
+                            None
+                        } else {
+                            next_retry(timestamp, item.attempts + 1)
+                        },
+                    )
+                    .await?;
+                }
+            }
 
             tx.commit().await?;
         }
@@ -100,3 +166,22 @@ impl NftUriQueue {
         Ok(())
     }
 }
+
+/// When to try a URI again after it has failed the given number of times, if at all.
+fn next_retry(timestamp: u64, attempts: u32) -> Option<u64> {
+    (attempts < MAX_ATTEMPTS).then(|| timestamp + BASE_RETRY_DELAY * 2_u64.pow(attempts - 1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_next_retry() {
+        assert_eq!(next_retry(1000, 1), Some(1300));
+        assert_eq!(next_retry(1000, 2), Some(1600));
+        assert_eq!(next_retry(1000, 3), Some(2200));
+        assert_eq!(next_retry(1000, MAX_ATTEMPTS - 1), Some(1000 + 300 * 64));
+        assert_eq!(next_retry(1000, MAX_ATTEMPTS), None);
+    }
+}
@@ -110,14 +110,19 @@ impl OfferQueue {
                         .into_iter()
                         .all(|coin_id| coin_states.iter().any(|cs| cs.coin.coin_id() == coin_id))
                 {
-                    self.db
-                        .update_offer_status(offer.offer_id, OfferStatus::Completed)
-                        .await?;
+                    // If we spent the coins ourselves to cancel the offer, it was never taken.
+                    let status = if offer.cancelling {
+                        OfferStatus::Cancelled
+                    } else {
+                        OfferStatus::Completed
+                    };
+
+                    self.db.update_offer_status(offer.offer_id, status).await?;
 
                     self.sync_sender
                         .send(SyncEvent::OfferUpdated {
                             offer_id: offer.offer_id,
-                            status: OfferStatus::Completed,
+                            status,
                         })
                         .await
                         .ok();
@@ -104,17 +104,31 @@ impl PuzzleQueue {
                 Ok((info, minter_did)) => {
                     let subscribe = info.subscribe();
 
-                    let remove = match info.p2_puzzle_hash() {
-                        Some(p2_puzzle_hash) => !self.db.is_p2_puzzle_hash(p2_puzzle_hash).await?,
-                        None => true,
-                    };
+                    let mut remove = true;
+
+                    for p2_puzzle_hash in info.p2_puzzle_hashes() {
+                        if self.db.is_p2_puzzle_hash(p2_puzzle_hash).await? {
+                            remove = false;
+                            break;
+                        }
+                    }
 
                     if remove {
                         self.db.delete_coin_state(coin_state.coin.coin_id()).await?;
                     } else {
                         let mut tx = self.db.tx().await?;
-                        insert_puzzle(&mut tx, coin_state, info, minter_did).await?;
+                        let invoice = insert_puzzle(&mut tx, coin_state, info, minter_did).await?;
                         tx.commit().await?;
+
+                        if let Some(invoice) = invoice {
+                            self.sync_sender
+                                .send(SyncEvent::InvoiceUpdated {
+                                    invoice_id: invoice.id,
+                                    status: invoice.status,
+                                })
+                                .await
+                                .ok();
+                        }
                     }
 
                     if subscribe {
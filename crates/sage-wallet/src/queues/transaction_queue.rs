@@ -12,7 +12,8 @@ use tokio::{
 use tracing::{info, warn};
 
 use crate::{
-    safely_remove_transaction, submit_to_peers, PeerState, Status, SyncEvent, WalletError,
+    remove_confirmed_transaction, remove_failed_transaction, submit_to_peers, PeerState, Status,
+    SyncEvent, WalletError,
 };
 
 #[derive(Debug)]
@@ -89,8 +90,7 @@ impl TransactionQueue {
                     info!("Transaction {transaction_id} confirmed, removing and confirming coins");
 
                     let mut tx = self.db.tx().await?;
-                    tx.confirm_coins(transaction_id).await?;
-                    safely_remove_transaction(&mut tx, transaction_id).await?;
+                    remove_confirmed_transaction(&mut tx, transaction_id).await?;
                     tx.commit().await?;
 
                     self.sync_sender
@@ -120,7 +120,7 @@ impl TransactionQueue {
                     );
 
                     let mut tx = self.db.tx().await?;
-                    safely_remove_transaction(&mut tx, transaction_id).await?;
+                    remove_failed_transaction(&mut tx, transaction_id).await?;
                     tx.commit().await?;
 
                     self.sync_sender
@@ -18,10 +18,11 @@ use tokio::{
     time::{sleep, timeout},
 };
 use tracing::{debug, info, warn};
-use wallet_sync::{incremental_sync, sync_wallet};
+use wallet_sync::{incremental_sync, sync_puzzle_hashes, sync_wallet};
 
 use crate::{
-    CatQueue, NftUriQueue, OfferQueue, PuzzleQueue, TransactionQueue, Wallet, WalletError,
+    CatQueue, HistoryQueue, NftUriQueue, OfferQueue, PuzzleQueue, TransactionQueue, Wallet,
+    WalletError,
 };
 
 mod options;
@@ -52,7 +53,9 @@ pub struct SyncManager {
     nft_uri_queue_task: Option<JoinHandle<Result<(), WalletError>>>,
     transaction_queue_task: Option<JoinHandle<Result<(), WalletError>>>,
     offer_queue_task: Option<JoinHandle<Result<(), WalletError>>>,
+    history_queue_task: Option<JoinHandle<Result<(), WalletError>>>,
     pending_coin_subscriptions: Vec<Bytes32>,
+    pending_puzzle_subscriptions: Vec<Bytes32>,
 }
 
 impl fmt::Debug for SyncManager {
@@ -92,6 +95,9 @@ impl Drop for SyncManager {
         if let Some(task) = &mut self.offer_queue_task {
             task.abort();
         }
+        if let Some(task) = &mut self.history_queue_task {
+            task.abort();
+        }
     }
 }
 
@@ -123,7 +129,9 @@ impl SyncManager {
             nft_uri_queue_task: None,
             transaction_queue_task: None,
             offer_queue_task: None,
+            history_queue_task: None,
             pending_coin_subscriptions: Vec::new(),
+            pending_puzzle_subscriptions: Vec::new(),
         };
 
         (manager, command_sender, event_receiver)
@@ -176,6 +184,21 @@ impl SyncManager {
                 SyncCommand::SubscribeCoins { coin_ids } => {
                     self.pending_coin_subscriptions.extend(coin_ids);
                 }
+                SyncCommand::SubscribePuzzles { puzzle_hashes } => {
+                    self.pending_puzzle_subscriptions.extend(puzzle_hashes);
+                }
+                SyncCommand::TransactionReplaced {
+                    transaction_id,
+                    new_transaction_id,
+                } => {
+                    self.event_sender
+                        .send(SyncEvent::TransactionReplaced {
+                            transaction_id,
+                            new_transaction_id,
+                        })
+                        .await
+                        .ok();
+                }
                 SyncCommand::ConnectionClosed(ip) => {
                     self.state
                         .lock()
@@ -194,24 +217,55 @@ impl SyncManager {
     }
 
     async fn subscribe(&mut self) {
-        if self.pending_coin_subscriptions.is_empty() {
+        if self.pending_coin_subscriptions.is_empty()
+            && self.pending_puzzle_subscriptions.is_empty()
+        {
             return;
         }
 
-        if let InitialWalletSync::Subscribed(ip) = self.initial_wallet_sync {
-            if let Some(info) = self.state.lock().await.peer(ip) {
-                // TODO: Handle cases
-                timeout(
-                    Duration::from_secs(3),
-                    info.peer.subscribe_coins(
-                        mem::take(&mut self.pending_coin_subscriptions),
-                        None,
-                        self.network.genesis_challenge,
-                    ),
+        let InitialWalletSync::Subscribed(ip) = self.initial_wallet_sync else {
+            return;
+        };
+
+        let Some(peer) = self
+            .state
+            .lock()
+            .await
+            .peer(ip)
+            .map(|info| info.peer.clone())
+        else {
+            return;
+        };
+
+        if !self.pending_coin_subscriptions.is_empty() {
+            // TODO: Handle cases
+            timeout(
+                Duration::from_secs(3),
+                peer.subscribe_coins(
+                    mem::take(&mut self.pending_coin_subscriptions),
+                    None,
+                    self.network.genesis_challenge,
+                ),
+            )
+            .await
+            .map(Result::ok)
+            .ok();
+        }
+
+        if let Some(wallet) = self.wallet.clone() {
+            for puzzle_hashes in mem::take(&mut self.pending_puzzle_subscriptions).chunks(500) {
+                if let Err(error) = sync_puzzle_hashes(
+                    &wallet,
+                    &peer,
+                    None,
+                    self.network.genesis_challenge,
+                    puzzle_hashes,
+                    self.event_sender.clone(),
                 )
                 .await
-                .map(Result::ok)
-                .ok();
+                {
+                    warn!("Failed to subscribe to puzzle hashes: {error}");
+                }
             }
         }
     }
@@ -237,6 +291,9 @@ impl SyncManager {
         if let Some(task) = &mut self.offer_queue_task.take() {
             task.abort();
         }
+        if let Some(task) = &mut self.history_queue_task.take() {
+            task.abort();
+        }
     }
 
     async fn handle_message(&self, ip: IpAddr, message: Message) -> Result<(), WalletError> {
@@ -395,8 +452,13 @@ impl SyncManager {
 
             if self.nft_uri_queue_task.is_none() && !self.options.testing {
                 let task = tokio::spawn(
-                    NftUriQueue::new(wallet.db.clone(), self.event_sender.clone())
-                        .start(self.options.timeouts.nft_uri_delay),
+                    NftUriQueue::new(
+                        wallet.db.clone(),
+                        self.event_sender.clone(),
+                        self.options.uri_resolver.clone(),
+                        self.options.media_cache.clone(),
+                    )
+                    .start(self.options.timeouts.nft_uri_delay),
                 );
                 self.nft_uri_queue_task = Some(task);
             }
@@ -426,12 +488,27 @@ impl SyncManager {
                 );
                 self.offer_queue_task = Some(task);
             }
+
+            if self.history_queue_task.is_none() {
+                let task = tokio::spawn(
+                    HistoryQueue::new(
+                        wallet.db.clone(),
+                        wallet.genesis_challenge,
+                        self.state.clone(),
+                        self.event_sender.clone(),
+                        self.command_sender.clone(),
+                    )
+                    .start(self.options.timeouts.history_delay),
+                );
+                self.history_queue_task = Some(task);
+            }
         } else {
             self.puzzle_lookup_task = None;
             self.cat_queue_task = None;
             self.nft_uri_queue_task = None;
             self.transaction_queue_task = None;
             self.offer_queue_task = None;
+            self.history_queue_task = None;
         }
     }
 
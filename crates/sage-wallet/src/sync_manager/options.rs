@@ -1,6 +1,8 @@
 use std::time::Duration;
 
-#[derive(Debug, Clone, Copy)]
+use crate::{MediaCache, UriResolver};
+
+#[derive(Debug, Clone)]
 pub struct SyncOptions {
     pub target_peers: usize,
     pub discover_peers: bool,
@@ -8,6 +10,8 @@ pub struct SyncOptions {
     pub connection_batch_size: usize,
     pub max_peer_age_seconds: u64,
     pub timeouts: Timeouts,
+    pub uri_resolver: UriResolver,
+    pub media_cache: MediaCache,
     pub testing: bool,
 }
 
@@ -19,6 +23,7 @@ pub struct Timeouts {
     pub puzzle_delay: Duration,
     pub transaction_delay: Duration,
     pub offer_delay: Duration,
+    pub history_delay: Duration,
     pub connection: Duration,
     pub initial_peak: Duration,
     pub remove_subscription: Duration,
@@ -35,6 +40,7 @@ impl Default for Timeouts {
             puzzle_delay: Duration::from_secs(1),
             transaction_delay: Duration::from_secs(1),
             offer_delay: Duration::from_secs(5),
+            history_delay: Duration::from_secs(1),
             connection: Duration::from_secs(3),
             initial_peak: Duration::from_secs(2),
             remove_subscription: Duration::from_secs(3),
@@ -25,6 +25,13 @@ pub enum SyncCommand {
     SubscribeCoins {
         coin_ids: Vec<Bytes32>,
     },
+    SubscribePuzzles {
+        puzzle_hashes: Vec<Bytes32>,
+    },
+    TransactionReplaced {
+        transaction_id: Bytes32,
+        new_transaction_id: Bytes32,
+    },
     ConnectionClosed(IpAddr),
     SetTargetPeers(usize),
     SetDiscoverPeers(bool),
@@ -1,7 +1,7 @@
 use std::net::IpAddr;
 
 use chia::protocol::{Bytes32, CoinState};
-use sage_database::OfferStatus;
+use sage_database::{InvoiceStatus, OfferStatus};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum SyncEvent {
@@ -11,6 +11,9 @@ pub enum SyncEvent {
     DerivationIndex {
         next_index: u32,
     },
+    /// A coin was received by a hardened address past the last one that had been used.
+    /// Only the app can derive more hardened keys, since it requires the secret key.
+    HardenedDerivationUsed,
     CoinsUpdated {
         coin_states: Vec<CoinState>,
     },
@@ -18,11 +21,20 @@ pub enum SyncEvent {
         transaction_id: Bytes32,
         success: bool,
     },
+    TransactionReplaced {
+        transaction_id: Bytes32,
+        new_transaction_id: Bytes32,
+    },
     OfferUpdated {
         offer_id: Bytes32,
         status: OfferStatus,
     },
+    InvoiceUpdated {
+        invoice_id: u32,
+        status: InvoiceStatus,
+    },
     PuzzleBatchSynced,
+    TransactionHistory,
     CatInfo,
     DidInfo,
     NftData,
@@ -8,6 +8,7 @@ use chia::{
     protocol::{Bytes32, CoinState, CoinStateFilters},
     puzzles::{standard::StandardArgs, DeriveSynthetic},
 };
+use indexmap::IndexSet;
 use rayon::iter::{IntoParallelIterator, ParallelIterator};
 use tokio::{
     sync::{mpsc, Mutex},
@@ -16,7 +17,10 @@ use tokio::{
 };
 use tracing::{debug, info, warn};
 
-use crate::{delete_puzzle, upsert_coin, UpsertCounters, Wallet, WalletError, WalletPeer};
+use crate::{
+    delete_puzzle, insert_outgoing_history, upsert_coin, Transaction, UpsertCounters, Wallet,
+    WalletError, WalletPeer,
+};
 
 use super::{PeerState, SyncEvent};
 
@@ -28,7 +32,8 @@ pub async fn sync_wallet(
 ) -> Result<(), WalletError> {
     info!("Starting sync against peer {}", peer.socket_addr());
 
-    let p2_puzzle_hashes = wallet.db.p2_puzzle_hashes().await?;
+    let p2_puzzle_hashes = wallet.db.p2_puzzle_hashes(false).await?;
+    let hardened_p2_puzzle_hashes = wallet.db.p2_puzzle_hashes(true).await?;
 
     let (start_height, start_header_hash) = wallet.db.latest_peak().await?.map_or_else(
         || (None, wallet.genesis_challenge),
@@ -64,7 +69,21 @@ pub async fn sync_wallet(
         .await?;
     }
 
-    let mut start_index = p2_puzzle_hashes.len() as u32;
+    // Hardened keys are derived separately once one of them is used, so finding coins for them
+    // doesn't derive any more unhardened keys.
+    for batch in hardened_p2_puzzle_hashes.chunks(500) {
+        sync_puzzle_hashes(
+            &wallet,
+            &peer,
+            start_height,
+            start_header_hash,
+            batch,
+            sync_sender.clone(),
+        )
+        .await?;
+    }
+
+    let mut start_index = wallet.db.derivation_index(false).await?;
 
     while derive_more {
         derive_more = false;
@@ -167,7 +186,7 @@ async fn sync_coin_ids(
     Ok(())
 }
 
-async fn sync_puzzle_hashes(
+pub async fn sync_puzzle_hashes(
     wallet: &Wallet,
     peer: &WalletPeer,
     start_height: Option<u32>,
@@ -226,18 +245,39 @@ pub async fn incremental_sync(
 
     let start = Instant::now();
 
+    let max_hardened_index = tx.max_used_derivation_index(true).await?;
+
     let mut counters = UpsertCounters::default();
+    let mut confirmed_transactions = IndexSet::new();
+    let mut invoices = Vec::new();
 
     for &coin_state in &coin_states {
-        upsert_coin(&mut tx, coin_state, None, &mut counters).await?;
+        if let Some(invoice) = upsert_coin(&mut tx, coin_state, None, &mut counters).await? {
+            invoices.push(invoice);
+        }
 
         if coin_state.spent_height.is_some() {
+            let coin_id = coin_state.coin.coin_id();
+
             let start = Instant::now();
-            delete_puzzle(&mut tx, coin_state.coin.coin_id()).await?;
+            delete_puzzle(&mut tx, coin_id).await?;
             counters.delete_puzzle += start.elapsed();
+
+            if let Some(transaction_id) = tx.transaction_for_spent_coin(coin_id).await? {
+                confirmed_transactions.insert(transaction_id);
+            }
         }
     }
 
+    // Our own transactions are recorded in the history once all of their coins have been updated.
+    for transaction_id in confirmed_transactions {
+        let transaction = Transaction::from_coin_spends(tx.coin_spends(transaction_id).await?)?;
+        insert_outgoing_history(&mut tx, transaction_id, &transaction).await?;
+    }
+
+    // Transactions recorded before their coins were synced get their height now.
+    tx.update_transaction_history_heights().await?;
+
     debug!(
         "Upserted {} coins in {:?}, with counters {:?}",
         coin_states.len(),
@@ -265,6 +305,8 @@ pub async fn incremental_sync(
         }
     }
 
+    let hardened_used = tx.max_used_derivation_index(true).await? != max_hardened_index;
+
     tx.commit().await?;
 
     sync_sender
@@ -272,6 +314,13 @@ pub async fn incremental_sync(
         .await
         .ok();
 
+    if hardened_used {
+        sync_sender
+            .send(SyncEvent::HardenedDerivationUsed)
+            .await
+            .ok();
+    }
+
     if derived {
         sync_sender
             .send(SyncEvent::DerivationIndex { next_index })
@@ -279,5 +328,15 @@ pub async fn incremental_sync(
             .ok();
     }
 
+    for invoice in invoices {
+        sync_sender
+            .send(SyncEvent::InvoiceUpdated {
+                invoice_id: invoice.id,
+                status: invoice.status,
+            })
+            .await
+            .ok();
+    }
+
     Ok(())
 }
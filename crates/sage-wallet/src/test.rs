@@ -1,4 +1,4 @@
-use std::{sync::Arc, time::Duration};
+use std::{env, sync::Arc, time::Duration};
 
 use chia::{
     bls::{master_to_wallet_unhardened_intermediate, DerivableKey, SecretKey},
@@ -20,8 +20,8 @@ use tokio::{
 use tracing::debug;
 
 use crate::{
-    insert_transaction, PeerState, SyncCommand, SyncEvent, SyncManager, SyncOptions, Timeouts,
-    Transaction, Wallet,
+    insert_transaction, MediaCache, PeerState, SyncCommand, SyncEvent, SyncManager, SyncOptions,
+    Timeouts, Transaction, UriResolver, Wallet,
 };
 
 static INDEX: Mutex<u32> = Mutex::const_new(0);
@@ -43,16 +43,23 @@ pub struct TestWallet {
 impl TestWallet {
     pub async fn new(balance: u64) -> anyhow::Result<Self> {
         let sim = PeerSimulator::new().await?;
-        Self::with_sim(Arc::new(sim), balance, 0).await
+        Self::with_sim(Arc::new(sim), balance, true, 0).await
     }
 
     pub async fn next(&self, balance: u64) -> anyhow::Result<Self> {
-        Self::with_sim(self.sim.clone(), balance, self.index + 1).await
+        Self::with_sim(self.sim.clone(), balance, true, self.index + 1).await
+    }
+
+    /// Syncs the same key into an empty database, as if the wallet was restored.
+    pub async fn restore(&self) -> anyhow::Result<Self> {
+        let balance = self.wallet.db.balance().await?.try_into()?;
+        Self::with_sim(self.sim.clone(), balance, false, self.index).await
     }
 
     async fn with_sim(
         sim: Arc<PeerSimulator>,
         balance: u64,
+        mint: bool,
         key_index: u32,
     ) -> anyhow::Result<Self> {
         let db_index = {
@@ -75,7 +82,7 @@ impl TestWallet {
         let puzzle_hash =
             StandardArgs::curry_tree_hash(intermediate_pk.derive_unhardened(0).derive_synthetic());
 
-        if balance > 0 {
+        if mint && balance > 0 {
             sim.mint_coin(puzzle_hash.into(), balance).await;
         }
 
@@ -101,8 +108,17 @@ impl TestWallet {
                     puzzle_delay: Duration::from_millis(100),
                     transaction_delay: Duration::from_millis(100),
                     offer_delay: Duration::from_millis(100),
+                    history_delay: Duration::from_millis(100),
                     ..Default::default()
                 },
+                uri_resolver: UriResolver::default(),
+                media_cache: MediaCache::new(
+                    env::temp_dir().join(format!(
+                        "sage-media-cache-{}-{db_index}",
+                        std::process::id()
+                    )),
+                    10 * 1024 * 1024,
+                ),
                 testing: true,
             },
             state.clone(),
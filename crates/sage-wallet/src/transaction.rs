@@ -1,6 +1,6 @@
 use chia::{
     clvm_traits::{FromClvm, ToClvm},
-    protocol::{Coin, CoinSpend, Program},
+    protocol::{Bytes, Coin, CoinSpend, Program},
 };
 use chia_wallet_sdk::{run_puzzle, Condition, Conditions};
 use clvmr::{Allocator, NodePtr};
@@ -24,6 +24,7 @@ pub struct TransactionInput {
 pub struct TransactionOutput {
     pub coin: Coin,
     pub kind: ChildKind,
+    pub memos: Vec<Bytes>,
 }
 
 impl Transaction {
@@ -34,9 +35,14 @@ impl Transaction {
         let mut fee = 0;
 
         for coin_spend in coin_spends {
+            let mut allocator = Allocator::new();
             let mut outputs = Vec::new();
 
-            for condition in run_conditions(&coin_spend.puzzle_reveal, &coin_spend.solution)? {
+            for condition in run_conditions(
+                &mut allocator,
+                &coin_spend.puzzle_reveal,
+                &coin_spend.solution,
+            )? {
                 match condition {
                     Condition::CreateCoin(create_coin) => {
                         let child_coin = Coin::new(
@@ -53,6 +59,12 @@ impl Transaction {
                                 &coin_spend.solution,
                                 child_coin,
                             )?,
+                            memos: create_coin
+                                .memos
+                                .and_then(|memos| {
+                                    Vec::<Bytes>::from_clvm(&allocator, memos.value).ok()
+                                })
+                                .unwrap_or_default(),
                         });
                     }
                     Condition::ReserveFee(cond) => {
@@ -75,13 +87,15 @@ impl Transaction {
     }
 }
 
-fn run_conditions(puzzle_reveal: &Program, solution: &Program) -> Result<Conditions, WalletError> {
-    let mut allocator = Allocator::new();
-
-    let puzzle = puzzle_reveal.to_clvm(&mut allocator)?;
-    let solution = solution.to_clvm(&mut allocator)?;
-    let output = run_puzzle(&mut allocator, puzzle, solution)?;
-    let conditions = Conditions::<NodePtr>::from_clvm(&allocator, output)?;
+fn run_conditions(
+    allocator: &mut Allocator,
+    puzzle_reveal: &Program,
+    solution: &Program,
+) -> Result<Conditions, WalletError> {
+    let puzzle = puzzle_reveal.to_clvm(allocator)?;
+    let solution = solution.to_clvm(allocator)?;
+    let output = run_puzzle(allocator, puzzle, solution)?;
+    let conditions = Conditions::<NodePtr>::from_clvm(allocator, output)?;
 
     Ok(conditions)
 }
@@ -1,11 +1,17 @@
+mod fees;
 mod fetch_nft_did;
 mod fetch_nft_offer_details;
 mod fetch_uri;
+mod media_cache;
 mod offchain_metadata;
 mod submit;
+mod uri_resolver;
 
+pub use fees::*;
 pub use fetch_nft_did::*;
 pub use fetch_nft_offer_details::*;
 pub use fetch_uri::*;
+pub use media_cache::*;
 pub use offchain_metadata::*;
 pub use submit::*;
+pub use uri_resolver::*;
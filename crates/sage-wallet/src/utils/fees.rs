@@ -0,0 +1,155 @@
+use std::time::Duration;
+
+use chia::{
+    clvm_traits::{FromClvm, ToClvm},
+    protocol::CoinSpend,
+};
+use chia_wallet_sdk::Condition;
+use clvmr::{run_program, Allocator, ChiaDialect, NodePtr};
+use futures_util::future::join_all;
+use tokio::time::timeout;
+use tracing::warn;
+
+use crate::{WalletError, WalletPeer};
+
+/// The maximum cost of a single block, which also bounds the cost of running each puzzle.
+const MAX_BLOCK_COST: u64 = 11_000_000_000;
+
+const COST_PER_BYTE: u64 = 12_000;
+const CREATE_COIN_COST: u64 = 1_800_000;
+const AGG_SIG_COST: u64 = 1_200_000;
+
+/// Asks each peer for a fee rate estimate in mojos per cost, and returns the median.
+/// Peers which don't respond in time or can't provide an estimate are ignored.
+pub async fn estimate_fee_rate(
+    peers: &[WalletPeer],
+    target_seconds: u64,
+) -> Result<Option<u64>, WalletError> {
+    let results = join_all(peers.iter().map(|peer| async move {
+        match timeout(Duration::from_secs(3), peer.fee_estimate(target_seconds)).await {
+            Ok(Ok(rate)) => rate,
+            Err(_timeout) => {
+                warn!("Fee estimate timed out for {}", peer.socket_addr());
+                None
+            }
+            Ok(Err(err)) => {
+                warn!("Fee estimate failed for {}: {}", peer.socket_addr(), err);
+                None
+            }
+        }
+    }))
+    .await;
+
+    Ok(median_fee_rate(results.into_iter().flatten().collect()))
+}
+
+/// Returns the median of the fee rates, or the higher of the two middle rates if there's
+/// an even number of them.
+fn median_fee_rate(mut rates: Vec<u64>) -> Option<u64> {
+    if rates.is_empty() {
+        return None;
+    }
+
+    rates.sort_unstable();
+
+    Some(rates[rates.len() / 2])
+}
+
+/// Computes the approximate CLVM cost of a transaction, including the cost of its
+/// serialized size and the conditions it outputs, for the purposes of fee estimation.
+pub fn spend_cost(coin_spends: &[CoinSpend]) -> Result<u64, WalletError> {
+    let mut allocator = Allocator::new();
+    let mut cost = 0;
+
+    for coin_spend in coin_spends {
+        let size = coin_spend.puzzle_reveal.as_ref().len() + coin_spend.solution.as_ref().len();
+        cost += size as u64 * COST_PER_BYTE;
+
+        let puzzle = coin_spend.puzzle_reveal.to_clvm(&mut allocator)?;
+        let solution = coin_spend.solution.to_clvm(&mut allocator)?;
+
+        let reduction = run_program(
+            &mut allocator,
+            &ChiaDialect::new(0),
+            puzzle,
+            solution,
+            MAX_BLOCK_COST,
+        )?;
+
+        cost += reduction.0;
+
+        for condition in Vec::<Condition<NodePtr>>::from_clvm(&allocator, reduction.1)? {
+            cost += match condition {
+                Condition::CreateCoin(..) => CREATE_COIN_COST,
+                Condition::AggSigParent(..)
+                | Condition::AggSigPuzzle(..)
+                | Condition::AggSigAmount(..)
+                | Condition::AggSigPuzzleAmount(..)
+                | Condition::AggSigParentAmount(..)
+                | Condition::AggSigParentPuzzle(..)
+                | Condition::AggSigUnsafe(..)
+                | Condition::AggSigMe(..) => AGG_SIG_COST,
+                _ => 0,
+            };
+        }
+    }
+
+    Ok(cost)
+}
+
+#[cfg(test)]
+mod tests {
+    use chia::protocol::{Bytes32, Coin, Program};
+    use chia_wallet_sdk::{Conditions, SpendContext};
+
+    use super::*;
+
+    fn test_spend(outputs: u64) -> anyhow::Result<CoinSpend> {
+        let mut ctx = SpendContext::new();
+        let mut conditions = Conditions::new();
+
+        for amount in 1..=outputs {
+            conditions = conditions.create_coin(Bytes32::default(), amount, None);
+        }
+
+        // The puzzle `1` returns its solution, so the conditions are passed through as is.
+        Ok(CoinSpend::new(
+            Coin::new(Bytes32::default(), Bytes32::default(), outputs),
+            Program::from(vec![1]),
+            ctx.serialize(&conditions)?,
+        ))
+    }
+
+    #[test]
+    fn test_median_fee_rate() {
+        assert_eq!(median_fee_rate(vec![]), None);
+        assert_eq!(median_fee_rate(vec![7]), Some(7));
+        assert_eq!(median_fee_rate(vec![30, 10, 20]), Some(20));
+        assert_eq!(median_fee_rate(vec![40, 10, 30, 20]), Some(30));
+    }
+
+    #[test]
+    fn test_spend_cost() -> anyhow::Result<()> {
+        assert_eq!(spend_cost(&[])?, 0);
+
+        let empty = test_spend(0)?;
+        let single = test_spend(1)?;
+        let double = test_spend(2)?;
+
+        let empty_cost = spend_cost(&[empty.clone()])?;
+        let single_cost = spend_cost(&[single.clone()])?;
+        let double_cost = spend_cost(&[double])?;
+
+        // The serialized size is always accounted for.
+        assert!(empty_cost >= 2 * COST_PER_BYTE);
+
+        // Each output adds the cost of creating a coin, on top of its size.
+        assert!(single_cost > empty_cost + CREATE_COIN_COST);
+        assert!(double_cost > single_cost + CREATE_COIN_COST);
+
+        // The cost of a transaction is the sum of the cost of its spends.
+        assert_eq!(spend_cost(&[empty, single])?, empty_cost + single_cost);
+
+        Ok(())
+    }
+}
@@ -9,6 +9,8 @@ use thiserror::Error;
 use tokio::time::timeout;
 use tracing::debug;
 
+use crate::UriResolver;
+
 #[derive(Debug, Clone)]
 pub struct Data {
     pub blob: Vec<u8>,
@@ -49,9 +51,57 @@ pub enum UriError {
 
     #[error("No URIs provided")]
     NoUris,
+
+    #[error("No gateways configured for URI {0}")]
+    NoGateways(String),
 }
 
+/// Fetches the data for a URI, trying each gateway it resolves to in order until one of them
+/// returns the expected data. Each gateway is given [`UriResolver::gateway_timeout`] in total,
+/// so that a slow or stalled one doesn't hold up the rest.
 pub async fn fetch_uri(
+    resolver: &UriResolver,
+    uri: &str,
+    request_timeout: Duration,
+    stream_timeout: Duration,
+    expected_hash: Option<Bytes32>,
+) -> Result<Data, UriError> {
+    let is_gateway_uri = UriResolver::is_gateway_uri(uri);
+
+    let mut last_error = None;
+
+    for url in resolver.resolve(uri) {
+        let result = if is_gateway_uri {
+            timeout(
+                resolver.gateway_timeout,
+                fetch_url(&url, request_timeout, stream_timeout),
+            )
+            .await
+            .unwrap_or_else(|_| Err(UriError::FetchTimeout(url.clone())))
+        } else {
+            fetch_url(&url, request_timeout, stream_timeout).await
+        };
+
+        let error = match result {
+            Ok(data) => match expected_hash {
+                Some(expected) if data.hash != expected => UriError::HashMismatch {
+                    uri: url.clone(),
+                    expected,
+                    found: data.hash,
+                },
+                _ => return Ok(data),
+            },
+            Err(error) => error,
+        };
+
+        debug!("Failed to fetch {url} for URI {uri}: {error}");
+        last_error = Some(error);
+    }
+
+    Err(last_error.unwrap_or_else(|| UriError::NoGateways(uri.to_string())))
+}
+
+async fn fetch_url(
     uri: &str,
     request_timeout: Duration,
     stream_timeout: Duration,
@@ -97,6 +147,7 @@ pub async fn fetch_uri(
 }
 
 pub async fn fetch_uris(
+    resolver: &UriResolver,
     uris: Vec<String>,
     request_timeout: Duration,
     stream_timeout: Duration,
@@ -105,7 +156,7 @@ pub async fn fetch_uris(
 
     for uri in uris {
         futures.push(async move {
-            let result = fetch_uri(&uri, request_timeout, stream_timeout).await;
+            let result = fetch_uri(resolver, &uri, request_timeout, stream_timeout, None).await;
             (uri, result)
         });
     }
@@ -144,7 +195,9 @@ pub async fn fetch_uris(
     data.ok_or(UriError::NoUris)
 }
 
+/// Fetches the data from the first URI which has the expected hash.
 pub async fn lookup_from_uris_with_hash(
+    resolver: &UriResolver,
     uris: Vec<String>,
     request_timeout: Duration,
     stream_timeout: Duration,
@@ -154,22 +207,17 @@ pub async fn lookup_from_uris_with_hash(
 
     for uri in uris {
         futures.push(async move {
-            let result = fetch_uri(&uri, request_timeout, stream_timeout).await;
+            let result =
+                fetch_uri(resolver, &uri, request_timeout, stream_timeout, Some(hash)).await;
             (uri, result)
         });
     }
 
     while let Some((uri, result)) = futures.next().await {
-        let Ok(item) = result else {
-            debug!("Failed to fetch NFT URI {uri}, expected hash {hash}");
-            continue;
-        };
-
-        if hash != item.hash {
-            return None;
+        match result {
+            Ok(item) => return Some(item),
+            Err(error) => debug!("Failed to fetch NFT URI {uri}, expected hash {hash}: {error}"),
         }
-
-        return Some(item);
     }
 
     None
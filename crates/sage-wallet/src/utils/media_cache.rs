@@ -0,0 +1,342 @@
+use std::{
+    collections::{BTreeSet, HashMap},
+    fs,
+    io::{self, Cursor},
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex, MutexGuard, PoisonError},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use chia::protocol::Bytes32;
+use image::ImageFormat;
+use sage_database::NftData;
+use tokio::task::spawn_blocking;
+use tracing::debug;
+
+use crate::WalletError;
+
+/// The largest width or height of a generated thumbnail, in pixels.
+pub const THUMBNAIL_SIZE: u32 = 256;
+
+/// A content addressed cache of NFT media on disk, which is shared between wallets.
+/// Each entry is a directory named after the hash of the data, containing the data itself,
+/// its mime type and a thumbnail if it's an image. Once the total size exceeds the quota,
+/// the least recently used entries are evicted.
+#[derive(Debug, Clone)]
+pub struct MediaCache {
+    dir: PathBuf,
+    max_size: u64,
+    index: Arc<Mutex<Option<CacheIndex>>>,
+}
+
+/// The size and last use of each entry in the cache. It's loaded from disk the first time it's
+/// needed and kept up to date afterwards, so that eviction doesn't have to scan the directory.
+#[derive(Debug, Default)]
+struct CacheIndex {
+    entries: HashMap<String, (u64, SystemTime)>,
+    by_last_used: BTreeSet<(SystemTime, String)>,
+    total_size: u64,
+}
+
+impl MediaCache {
+    pub fn new(dir: PathBuf, max_size: u64) -> Self {
+        Self {
+            dir,
+            max_size,
+            index: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    /// Stores the data for a hash, then evicts entries until the cache fits within its quota.
+    pub async fn insert(&self, hash: Bytes32, data: NftData) -> Result<(), WalletError> {
+        let cache = self.clone();
+        spawn_blocking(move || cache.insert_blocking(hash, &data)).await??;
+        Ok(())
+    }
+
+    /// Reads the data for a hash, or its thumbnail if requested and one could be generated.
+    pub async fn get(
+        &self,
+        hash: Bytes32,
+        thumbnail: bool,
+    ) -> Result<Option<NftData>, WalletError> {
+        let cache = self.clone();
+        Ok(spawn_blocking(move || cache.get_blocking(hash, thumbnail)).await??)
+    }
+
+    fn entry_dir(&self, hash: Bytes32) -> PathBuf {
+        self.dir.join(hash.to_string())
+    }
+
+    fn insert_blocking(&self, hash: Bytes32, data: &NftData) -> io::Result<()> {
+        let entry = self.entry_dir(hash);
+
+        fs::create_dir_all(&entry)?;
+        fs::write(entry.join("full"), &data.blob)?;
+
+        if let Some(thumbnail) = thumbnail(&data.blob, &data.mime_type) {
+            fs::write(entry.join("thumbnail"), thumbnail)?;
+        }
+
+        // The mime type is written last, since its presence marks the entry as complete.
+        fs::write(entry.join("mime_type"), &data.mime_type)?;
+
+        let size = entry_size(&entry)?;
+
+        self.with_index(|index| {
+            index.insert(hash.to_string(), size, SystemTime::now());
+            self.evict(index)
+        })
+    }
+
+    fn get_blocking(&self, hash: Bytes32, thumbnail: bool) -> io::Result<Option<NftData>> {
+        let entry = self.entry_dir(hash);
+
+        let Some(mime_type) = not_found_to_none(fs::read_to_string(entry.join("mime_type")))?
+        else {
+            return Ok(None);
+        };
+
+        let thumbnail_path = entry.join("thumbnail");
+
+        let (path, mime_type) = if thumbnail && thumbnail_path.exists() {
+            (thumbnail_path, "image/png".to_string())
+        } else {
+            (entry.join("full"), mime_type)
+        };
+
+        let Some(blob) = not_found_to_none(fs::read(path))? else {
+            return Ok(None);
+        };
+
+        let now = SystemTime::now();
+
+        // The modification time of the mime type keeps track of when the entry was last used,
+        // so that the order is kept when the index is loaded again.
+        not_found_to_none(
+            fs::File::options()
+                .write(true)
+                .open(entry.join("mime_type"))
+                .and_then(|file| file.set_modified(now)),
+        )?;
+
+        if let Some(index) = self.lock_index().as_mut() {
+            index.touch(&hash.to_string(), now);
+        }
+
+        Ok(Some(NftData { blob, mime_type }))
+    }
+
+    fn lock_index(&self) -> MutexGuard<'_, Option<CacheIndex>> {
+        // The index is always left in a consistent state, so it can be used after a panic.
+        self.index.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn with_index<T>(&self, f: impl FnOnce(&mut CacheIndex) -> io::Result<T>) -> io::Result<T> {
+        let mut index = self.lock_index();
+
+        if index.is_none() {
+            *index = Some(CacheIndex::load(&self.dir)?);
+        }
+
+        f(index.as_mut().expect("the index was just loaded"))
+    }
+
+    /// Removes the least recently used entries until the cache fits within its quota.
+    fn evict(&self, index: &mut CacheIndex) -> io::Result<()> {
+        while index.total_size > self.max_size {
+            let Some(name) = index.least_recently_used() else {
+                break;
+            };
+
+            let path = self.dir.join(&name);
+
+            debug!("Evicting {} from the media cache", path.display());
+
+            // The entry may have been removed by another process sharing the cache.
+            not_found_to_none(fs::remove_dir_all(&path))?;
+
+            index.remove(&name);
+        }
+
+        Ok(())
+    }
+}
+
+impl CacheIndex {
+    /// Scans the cache directory for complete entries.
+    fn load(dir: &Path) -> io::Result<Self> {
+        let mut index = Self::default();
+
+        let Some(entries) = not_found_to_none(fs::read_dir(dir))? else {
+            return Ok(index);
+        };
+
+        for entry in entries {
+            let path = entry?.path();
+
+            if !path.is_dir() {
+                continue;
+            }
+
+            let Some(name) = path.file_name().and_then(|name| name.to_str()) else {
+                continue;
+            };
+
+            let Some(size) = not_found_to_none(entry_size(&path))? else {
+                continue;
+            };
+
+            let last_used = fs::metadata(path.join("mime_type"))
+                .and_then(|metadata| metadata.modified())
+                .unwrap_or(UNIX_EPOCH);
+
+            index.insert(name.to_string(), size, last_used);
+        }
+
+        Ok(index)
+    }
+
+    fn insert(&mut self, name: String, size: u64, last_used: SystemTime) {
+        self.remove(&name);
+        self.total_size += size;
+        self.by_last_used.insert((last_used, name.clone()));
+        self.entries.insert(name, (size, last_used));
+    }
+
+    fn touch(&mut self, name: &str, now: SystemTime) {
+        let Some((_, last_used)) = self.entries.get_mut(name) else {
+            return;
+        };
+
+        self.by_last_used.remove(&(*last_used, name.to_string()));
+        self.by_last_used.insert((now, name.to_string()));
+        *last_used = now;
+    }
+
+    fn remove(&mut self, name: &str) {
+        let Some((size, last_used)) = self.entries.remove(name) else {
+            return;
+        };
+
+        self.by_last_used.remove(&(last_used, name.to_string()));
+        self.total_size -= size;
+    }
+
+    fn least_recently_used(&self) -> Option<String> {
+        self.by_last_used.first().map(|(_, name)| name.clone())
+    }
+}
+
+/// Generates a thumbnail on a blocking thread, since decoding and resizing images is CPU bound.
+pub async fn generate_thumbnail(
+    blob: Vec<u8>,
+    mime_type: String,
+) -> Result<Option<Vec<u8>>, WalletError> {
+    Ok(spawn_blocking(move || thumbnail(&blob, &mime_type)).await?)
+}
+
+/// Generates a PNG thumbnail for an image, unless it's in a format that can't be decoded.
+pub fn thumbnail(blob: &[u8], mime_type: &str) -> Option<Vec<u8>> {
+    if !mime_type.starts_with("image/") {
+        return None;
+    }
+
+    let image = image::load_from_memory(blob)
+        .inspect_err(|error| debug!("Failed to decode image for thumbnail: {error}"))
+        .ok()?;
+
+    let mut bytes = Vec::new();
+
+    image
+        .thumbnail(THUMBNAIL_SIZE, THUMBNAIL_SIZE)
+        .write_to(&mut Cursor::new(&mut bytes), ImageFormat::Png)
+        .ok()?;
+
+    Some(bytes)
+}
+
+fn entry_size(path: &Path) -> io::Result<u64> {
+    let mut size = 0;
+
+    for file in fs::read_dir(path)? {
+        size += file?.metadata()?.len();
+    }
+
+    Ok(size)
+}
+
+fn not_found_to_none<T>(result: io::Result<T>) -> io::Result<Option<T>> {
+    match result {
+        Ok(value) => Ok(Some(value)),
+        Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(error) => Err(error),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+
+    use test_log::test;
+
+    use super::*;
+
+    #[test(tokio::test)]
+    async fn test_media_cache() -> anyhow::Result<()> {
+        let dir = env::temp_dir().join(format!("sage-media-cache-test-{}", std::process::id()));
+        let cache = MediaCache::new(dir.clone(), 20);
+
+        let first = Bytes32::new([1; 32]);
+        let second = Bytes32::new([2; 32]);
+
+        cache
+            .insert(
+                first,
+                NftData {
+                    blob: b"hello".to_vec(),
+                    mime_type: "text/plain".to_string(),
+                },
+            )
+            .await?;
+
+        let data = cache.get(first, true).await?.expect("missing data");
+        assert_eq!(data.blob, b"hello");
+        assert_eq!(data.mime_type, "text/plain");
+
+        // Exceeds the quota, so the least recently used entry is evicted.
+        cache
+            .insert(
+                second,
+                NftData {
+                    blob: b"world".to_vec(),
+                    mime_type: "text/plain".to_string(),
+                },
+            )
+            .await?;
+
+        assert!(cache.get(first, false).await?.is_none());
+        assert!(cache.get(second, false).await?.is_some());
+
+        // The index is loaded from disk by a new instance, such as after a restart.
+        let cache = MediaCache::new(dir.clone(), 20);
+        let third = Bytes32::new([3; 32]);
+
+        cache
+            .insert(
+                third,
+                NftData {
+                    blob: b"again".to_vec(),
+                    mime_type: "text/plain".to_string(),
+                },
+            )
+            .await?;
+
+        assert!(cache.get(second, false).await?.is_none());
+        assert!(cache.get(third, false).await?.is_some());
+
+        fs::remove_dir_all(dir)?;
+
+        Ok(())
+    }
+}
@@ -1,7 +1,26 @@
 use chia::{protocol::Bytes32, sha2::Sha256};
-use sage_database::CollectionRow;
+use sage_database::{CollectionRow, NftAttribute};
 use serde::{Deserialize, Serialize};
-use serde_json::Value;
+use serde_json::{Map, Value};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum MetadataError {
+    #[error("Invalid metadata JSON: {0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error("Metadata must be a JSON object")]
+    NotAnObject,
+
+    #[error("Metadata format must be CHIP-0007")]
+    InvalidFormat,
+
+    #[error("Missing metadata field {0}")]
+    MissingField(String),
+
+    #[error("Invalid metadata field {0}")]
+    InvalidField(String),
+}
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 struct OffchainMetadata {
@@ -13,6 +32,10 @@ struct OffchainMetadata {
 
     #[serde(default)]
     collection: Option<Collection>,
+
+    /// Kept as raw JSON, so that a malformed attribute doesn't prevent the rest from being read.
+    #[serde(default)]
+    attributes: Option<Value>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -41,6 +64,7 @@ pub struct ComputedNftInfo {
     pub name: Option<String>,
     pub sensitive_content: bool,
     pub collection: Option<CollectionRow>,
+    pub attributes: Vec<NftAttribute>,
 }
 
 pub fn compute_nft_info(did_id: Option<Bytes32>, blob: Option<&[u8]>) -> ComputedNftInfo {
@@ -85,9 +109,38 @@ pub fn compute_nft_info(did_id: Option<Bytes32>, blob: Option<&[u8]>) -> Compute
             Value::String(value) => !value.is_empty(),
         }),
         collection,
+        attributes: json.attributes.map(nft_attributes).unwrap_or_default(),
     }
 }
 
+/// Reads the CHIP-0007 attributes which have a trait type and a string or numeric value.
+fn nft_attributes(value: Value) -> Vec<NftAttribute> {
+    let Value::Array(items) = value else {
+        return Vec::new();
+    };
+
+    items
+        .into_iter()
+        .filter_map(|item| {
+            let Value::Object(mut item) = item else {
+                return None;
+            };
+
+            let Some(Value::String(trait_type)) = item.remove("trait_type") else {
+                return None;
+            };
+
+            let value = match item.remove("value")? {
+                Value::String(value) => value,
+                Value::Number(value) => value.to_string(),
+                _ => return None,
+            };
+
+            Some(NftAttribute { trait_type, value })
+        })
+        .collect()
+}
+
 fn offchain_metadata(blob: Option<&[u8]>) -> Option<OffchainMetadata> {
     serde_json::from_slice(blob?).ok()
 }
@@ -98,3 +151,195 @@ fn calculate_collection_id(did_id: Bytes32, json_collection_id: &str) -> Bytes32
     hasher.update(json_collection_id);
     hasher.finalize().into()
 }
+
+/// Checks that offchain metadata follows the CHIP-0007 schema, so that wallets and
+/// marketplaces will be able to display the NFT once it's minted.
+pub fn validate_chip0007(blob: &[u8]) -> Result<(), MetadataError> {
+    let Value::Object(json) = serde_json::from_slice(blob)? else {
+        return Err(MetadataError::NotAnObject);
+    };
+
+    if json.get("format").and_then(Value::as_str) != Some("CHIP-0007") {
+        return Err(MetadataError::InvalidFormat);
+    }
+
+    required(&json, "name", "name", Value::is_string)?;
+    optional(&json, "description", "description", Value::is_string)?;
+    optional(&json, "minting_tool", "minting_tool", Value::is_string)?;
+    optional(&json, "series_number", "series_number", Value::is_u64)?;
+    optional(&json, "series_total", "series_total", Value::is_u64)?;
+    optional(&json, "data", "data", Value::is_object)?;
+    optional(&json, "sensitive_content", "sensitive_content", |value| {
+        value.is_boolean()
+            || value
+                .as_array()
+                .is_some_and(|items| items.iter().all(Value::is_string))
+    })?;
+
+    if let Some(attributes) = json.get("attributes") {
+        let Some(attributes) = attributes.as_array() else {
+            return Err(MetadataError::InvalidField("attributes".to_string()));
+        };
+
+        for (index, attribute) in attributes.iter().enumerate() {
+            let path = format!("attributes[{index}]");
+
+            let Some(attribute) = attribute.as_object() else {
+                return Err(MetadataError::InvalidField(path));
+            };
+
+            let is_scalar = |value: &Value| value.is_string() || value.is_number();
+
+            required(
+                attribute,
+                &format!("{path}.trait_type"),
+                "trait_type",
+                is_scalar,
+            )?;
+            required(attribute, &format!("{path}.value"), "value", is_scalar)?;
+            optional(
+                attribute,
+                &format!("{path}.min_value"),
+                "min_value",
+                Value::is_number,
+            )?;
+            optional(
+                attribute,
+                &format!("{path}.max_value"),
+                "max_value",
+                Value::is_number,
+            )?;
+        }
+    }
+
+    if let Some(collection) = json.get("collection") {
+        let Some(collection) = collection.as_object() else {
+            return Err(MetadataError::InvalidField("collection".to_string()));
+        };
+
+        required(collection, "collection.id", "id", Value::is_string)?;
+        required(collection, "collection.name", "name", Value::is_string)?;
+
+        if let Some(attributes) = collection.get("attributes") {
+            let Some(attributes) = attributes.as_array() else {
+                return Err(MetadataError::InvalidField(
+                    "collection.attributes".to_string(),
+                ));
+            };
+
+            for (index, attribute) in attributes.iter().enumerate() {
+                let path = format!("collection.attributes[{index}]");
+
+                let Some(attribute) = attribute.as_object() else {
+                    return Err(MetadataError::InvalidField(path));
+                };
+
+                required(attribute, &format!("{path}.type"), "type", Value::is_string)?;
+                required(attribute, &format!("{path}.value"), "value", |_| true)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn required(
+    object: &Map<String, Value>,
+    path: &str,
+    key: &str,
+    is_valid: impl Fn(&Value) -> bool,
+) -> Result<(), MetadataError> {
+    if !object.contains_key(key) {
+        return Err(MetadataError::MissingField(path.to_string()));
+    }
+
+    optional(object, path, key, is_valid)
+}
+
+fn optional(
+    object: &Map<String, Value>,
+    path: &str,
+    key: &str,
+    is_valid: impl Fn(&Value) -> bool,
+) -> Result<(), MetadataError> {
+    match object.get(key) {
+        Some(value) if !is_valid(value) => Err(MetadataError::InvalidField(path.to_string())),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nft_attributes() {
+        let json = br#"{
+            "name": "Example",
+            "attributes": [
+                { "trait_type": "Color", "value": "Red" },
+                { "trait_type": "Level", "value": 5 },
+                { "trait_type": "Nested", "value": { "a": 1 } },
+                { "value": "Untyped" },
+                "Invalid"
+            ]
+        }"#;
+
+        let info = compute_nft_info(None, Some(json));
+
+        assert_eq!(info.name.as_deref(), Some("Example"));
+        assert_eq!(
+            info.attributes,
+            vec![
+                NftAttribute {
+                    trait_type: "Color".to_string(),
+                    value: "Red".to_string(),
+                },
+                NftAttribute {
+                    trait_type: "Level".to_string(),
+                    value: "5".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_validate_chip0007() {
+        let valid = br#"{
+            "format": "CHIP-0007",
+            "name": "Example",
+            "sensitive_content": false,
+            "series_number": 1,
+            "attributes": [{ "trait_type": "Level", "value": 5 }],
+            "collection": {
+                "id": "2d4c8f2a-7c16-4e5c-9d1b-6a0f1b2c3d4e",
+                "name": "Examples",
+                "attributes": [{ "type": "icon", "value": "https://example.com/icon.png" }]
+            }
+        }"#;
+        assert!(validate_chip0007(valid).is_ok());
+
+        assert!(matches!(
+            validate_chip0007(b"[]"),
+            Err(MetadataError::NotAnObject)
+        ));
+        assert!(matches!(
+            validate_chip0007(br#"{ "name": "Example" }"#),
+            Err(MetadataError::InvalidFormat)
+        ));
+        assert!(matches!(
+            validate_chip0007(br#"{ "format": "CHIP-0007" }"#),
+            Err(MetadataError::MissingField(field)) if field == "name"
+        ));
+        assert!(matches!(
+            validate_chip0007(
+                br#"{ "format": "CHIP-0007", "name": "Example", "attributes": [{ "value": 1 }] }"#
+            ),
+            Err(MetadataError::MissingField(field)) if field == "attributes[0].trait_type"
+        ));
+        assert!(matches!(
+            validate_chip0007(br#"{ "format": "CHIP-0007", "name": "Example", "series_total": -1 }"#),
+            Err(MetadataError::InvalidField(field)) if field == "series_total"
+        ));
+    }
+}
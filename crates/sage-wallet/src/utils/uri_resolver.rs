@@ -0,0 +1,84 @@
+use std::time::Duration;
+
+/// Maps content addressed URIs, such as `ipfs://` and `ar://`, to HTTP gateway URLs.
+/// Other URIs are fetched directly.
+#[derive(Debug, Clone)]
+pub struct UriResolver {
+    pub ipfs_gateways: Vec<String>,
+    pub arweave_gateways: Vec<String>,
+    pub gateway_timeout: Duration,
+}
+
+impl Default for UriResolver {
+    fn default() -> Self {
+        Self {
+            ipfs_gateways: Vec::new(),
+            arweave_gateways: Vec::new(),
+            gateway_timeout: Duration::from_secs(10),
+        }
+    }
+}
+
+impl UriResolver {
+    /// Whether the URI has to be fetched through a gateway.
+    pub fn is_gateway_uri(uri: &str) -> bool {
+        uri.starts_with("ipfs://") || uri.starts_with("ar://")
+    }
+
+    /// The URLs the URI can be fetched from, in the order they should be tried.
+    pub fn resolve(&self, uri: &str) -> Vec<String> {
+        if let Some(path) = uri.strip_prefix("ipfs://") {
+            // Some minters include the path prefix of the gateway in the URI as well.
+            let path = path.strip_prefix("ipfs/").unwrap_or(path);
+            return gateway_urls(&self.ipfs_gateways, path);
+        }
+
+        if let Some(path) = uri.strip_prefix("ar://") {
+            return gateway_urls(&self.arweave_gateways, path);
+        }
+
+        vec![uri.to_string()]
+    }
+}
+
+fn gateway_urls(gateways: &[String], path: &str) -> Vec<String> {
+    gateways
+        .iter()
+        .map(|gateway| format!("{}/{path}", gateway.trim_end_matches('/')))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_uris() {
+        let resolver = UriResolver {
+            ipfs_gateways: vec![
+                "https://ipfs.io/ipfs/".to_string(),
+                "https://dweb.link/ipfs".to_string(),
+            ],
+            arweave_gateways: vec!["https://arweave.net/".to_string()],
+            ..Default::default()
+        };
+
+        assert_eq!(
+            resolver.resolve("ipfs://bafy/1.json"),
+            [
+                "https://ipfs.io/ipfs/bafy/1.json",
+                "https://dweb.link/ipfs/bafy/1.json"
+            ]
+        );
+        assert_eq!(
+            resolver.resolve("ipfs://ipfs/bafy"),
+            ["https://ipfs.io/ipfs/bafy", "https://dweb.link/ipfs/bafy"]
+        );
+        assert_eq!(resolver.resolve("ar://tx"), ["https://arweave.net/tx"]);
+        assert_eq!(
+            resolver.resolve("https://example.com/1.png"),
+            ["https://example.com/1.png"]
+        );
+        assert!(UriResolver::default().resolve("ipfs://bafy").is_empty());
+    }
+}
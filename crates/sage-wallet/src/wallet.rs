@@ -4,7 +4,9 @@ use sage_database::Database;
 mod cat_coin_management;
 mod cat_spends;
 mod cats;
+mod clawbacks;
 mod coin_selection;
+mod consolidation;
 mod derivations;
 mod did_assign;
 mod dids;
@@ -13,10 +15,15 @@ mod offer;
 mod p2_coin_management;
 mod p2_send;
 mod p2_spends;
+mod replacement;
 mod signing;
 
+pub use coin_selection::{CoinSelection, CoinSelectionStrategy};
 pub use nfts::WalletNftMint;
 pub use offer::*;
+pub use p2_coin_management::Denomination;
+pub use p2_send::WalletPayment;
+pub use replacement::MEMPOOL_MIN_FEE_INCREASE;
 
 #[derive(Debug)]
 pub struct Wallet {
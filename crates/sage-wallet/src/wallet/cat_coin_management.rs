@@ -1,9 +1,14 @@
-use chia::protocol::CoinSpend;
+use std::mem;
+
+use chia::protocol::{Bytes32, CoinSpend};
 use chia_wallet_sdk::{Cat, Conditions, SpendContext};
 
 use crate::WalletError;
 
-use super::Wallet;
+use super::{
+    p2_coin_management::{validate_denominations, Denomination},
+    CoinSelection, Wallet,
+};
 
 impl Wallet {
     /// Combines multiple CAT coins into a single coin, with the given fee subtracted from the output.
@@ -11,11 +16,12 @@ impl Wallet {
         &self,
         cats: Vec<Cat>,
         fee: u64,
+        selection: &CoinSelection,
         hardened: bool,
         reuse: bool,
     ) -> Result<Vec<CoinSpend>, WalletError> {
         let fee_coins = if fee > 0 {
-            self.select_p2_coins(fee as u128).await?
+            self.select_p2_coins(fee as u128, selection).await?
         } else {
             Vec::new()
         };
@@ -77,11 +83,12 @@ impl Wallet {
         cats: Vec<Cat>,
         output_count: usize,
         fee: u64,
+        selection: &CoinSelection,
         hardened: bool,
         reuse: bool,
     ) -> Result<Vec<CoinSpend>, WalletError> {
         let fee_coins = if fee > 0 {
-            self.select_p2_coins(fee as u128).await?
+            self.select_p2_coins(fee as u128, selection).await?
         } else {
             Vec::new()
         };
@@ -159,19 +166,101 @@ impl Wallet {
 
         Ok(ctx.take())
     }
+
+    /// Splits the CAT into coins of the given denominations, with the remainder left over as change.
+    pub async fn split_cat_denominations(
+        &self,
+        asset_id: Bytes32,
+        denominations: &[Denomination],
+        fee: u64,
+        selection: &CoinSelection,
+        hardened: bool,
+        reuse: bool,
+    ) -> Result<Vec<CoinSpend>, WalletError> {
+        let total = validate_denominations(denominations)?;
+
+        let fee_coins = if fee > 0 {
+            self.select_p2_coins(fee as u128, selection).await?
+        } else {
+            Vec::new()
+        };
+
+        let cats = self.select_cat_coins(asset_id, total, selection).await?;
+        let cat_selected: u128 = cats.iter().map(|cat| cat.coin.amount as u128).sum();
+        let cat_change: u64 = (cat_selected - total)
+            .try_into()
+            .expect("change amount overflow");
+
+        let puzzle_hashes = self
+            .denomination_puzzle_hashes(denominations, hardened, reuse)
+            .await?;
+        let change_puzzle_hash = puzzle_hashes[puzzle_hashes.len() - 1];
+
+        let mut ctx = SpendContext::new();
+
+        if !fee_coins.is_empty() {
+            let fee_total: u128 = fee_coins.iter().map(|coin| coin.amount as u128).sum();
+
+            let fee_change: u64 = (fee_total - fee as u128)
+                .try_into()
+                .expect("change amount overflow");
+
+            let mut fee_conditions = Conditions::new()
+                .assert_concurrent_spend(cats[0].coin.coin_id())
+                .reserve_fee(fee);
+
+            if fee_change > 0 {
+                fee_conditions = fee_conditions.create_coin(change_puzzle_hash, fee_change, None);
+            }
+
+            self.spend_p2_coins(&mut ctx, fee_coins, fee_conditions)
+                .await?;
+        }
+
+        let mut conditions = Conditions::new();
+
+        for denomination in denominations {
+            for &puzzle_hash in &puzzle_hashes[..denomination.count] {
+                let hint = ctx.hint(puzzle_hash)?;
+                conditions = conditions.create_coin(puzzle_hash, denomination.amount, Some(hint));
+            }
+        }
+
+        if cat_change > 0 {
+            let hint = ctx.hint(change_puzzle_hash)?;
+            conditions = conditions.create_coin(change_puzzle_hash, cat_change, Some(hint));
+        }
+
+        self.spend_cat_coins(
+            &mut ctx,
+            cats.into_iter().enumerate().map(|(i, cat)| {
+                if i != 0 {
+                    return (cat, Conditions::new());
+                }
+
+                (cat, mem::take(&mut conditions))
+            }),
+        )
+        .await?;
+
+        Ok(ctx.take())
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use test_log::test;
 
-    use crate::TestWallet;
+    use crate::{CoinSelection, Denomination, TestWallet};
 
     #[test(tokio::test)]
     async fn test_cat_coin_management() -> anyhow::Result<()> {
         let mut test = TestWallet::new(100).await?;
 
-        let (coin_spends, asset_id) = test.wallet.issue_cat(100, 0, None, false, true).await?;
+        let (coin_spends, asset_id) = test
+            .wallet
+            .issue_cat(100, 0, None, &CoinSelection::default(), false, true)
+            .await?;
         test.transact(coin_spends).await?;
         test.wait_for_coins().await;
 
@@ -180,7 +269,10 @@ mod tests {
         assert_eq!(cats.len(), 1);
 
         let cat = test.wallet.db.cat_coin(cats.remove(0).coin.coin_id()).await?.expect("missing cat");
-        let coin_spends = test.wallet.split_cat(vec![cat], 2, 0, false, true).await?;
+        let coin_spends = test
+            .wallet
+            .split_cat(vec![cat], 2, 0, &CoinSelection::default(), false, true)
+            .await?;
         test.transact(coin_spends).await?;
         test.wait_for_coins().await;
 
@@ -192,7 +284,10 @@ mod tests {
         for cat in cats {
             cat_coins.push(test.wallet.db.cat_coin(cat.coin.coin_id()).await?.expect("missing cat"));
         }
-        let coin_spends = test.wallet.combine_cat(cat_coins, 0, false, true).await?;
+        let coin_spends = test
+            .wallet
+            .combine_cat(cat_coins, 0, &CoinSelection::default(), false, true)
+            .await?;
         test.transact(coin_spends).await?;
         test.wait_for_coins().await;
 
@@ -201,4 +296,56 @@ mod tests {
 
         Ok(())
     }
+
+    #[test(tokio::test)]
+    async fn test_split_cat_denominations() -> anyhow::Result<()> {
+        let mut test = TestWallet::new(1000).await?;
+
+        let (coin_spends, asset_id) = test
+            .wallet
+            .issue_cat(1000, 0, None, &CoinSelection::default(), false, true)
+            .await?;
+        test.transact(coin_spends).await?;
+        test.wait_for_coins().await;
+
+        let denominations = [
+            Denomination {
+                amount: 200,
+                count: 2,
+            },
+            Denomination {
+                amount: 10,
+                count: 5,
+            },
+        ];
+
+        let coin_spends = test
+            .wallet
+            .split_cat_denominations(
+                asset_id,
+                &denominations,
+                0,
+                &CoinSelection::default(),
+                false,
+                true,
+            )
+            .await?;
+        test.transact(coin_spends).await?;
+        test.wait_for_coins().await;
+
+        let mut amounts: Vec<u64> = test
+            .wallet
+            .db
+            .spendable_cat_coins(asset_id)
+            .await?
+            .into_iter()
+            .map(|cat| cat.coin.amount)
+            .collect();
+        amounts.sort_unstable();
+
+        assert_eq!(test.wallet.db.cat_balance(asset_id).await?, 1000);
+        assert_eq!(amounts, [10, 10, 10, 10, 10, 200, 200, 550]);
+
+        Ok(())
+    }
 }
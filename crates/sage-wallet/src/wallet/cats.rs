@@ -3,12 +3,16 @@ use std::mem;
 use chia::{
     bls::PublicKey,
     protocol::{Bytes, Bytes32, CoinSpend},
+    puzzles::cat::EverythingWithSignatureTailArgs,
 };
-use chia_wallet_sdk::{Cat, Conditions, SpendContext};
+use chia_wallet_sdk::{
+    Cat, CatSpend, Conditions, Mod, SpendContext, SpendWithConditions, StandardLayer,
+};
+use clvmr::NodePtr;
 
-use crate::WalletError;
+use crate::{Clawback, WalletError};
 
-use super::Wallet;
+use super::{p2_send::validate_payments, CoinSelection, Wallet, WalletPayment};
 
 impl Wallet {
     pub async fn issue_cat(
@@ -16,11 +20,12 @@ impl Wallet {
         amount: u64,
         fee: u64,
         multi_issuance_key: Option<PublicKey>,
+        selection: &CoinSelection,
         hardened: bool,
         reuse: bool,
     ) -> Result<(Vec<CoinSpend>, Bytes32), WalletError> {
         let total_amount = amount as u128 + fee as u128;
-        let coins = self.select_p2_coins(total_amount).await?;
+        let coins = self.select_p2_coins(total_amount, selection).await?;
         let selected: u128 = coins.iter().map(|coin| coin.amount as u128).sum();
 
         let change: u64 = (selected - total_amount)
@@ -55,6 +60,164 @@ impl Wallet {
         Ok((ctx.take(), eve.asset_id))
     }
 
+    /// Returns the synthetic key that the CAT was issued with, if it was issued by this wallet with an
+    /// everything with signature TAIL. Since the key is one of the wallet's derivations, it can be found
+    /// again by checking each of them if it isn't recorded, such as after the wallet has been restored.
+    pub async fn cat_issuance_key(
+        &self,
+        asset_id: Bytes32,
+    ) -> Result<Option<PublicKey>, WalletError> {
+        if let Some(public_key) = self.db.cat_issuance_key(asset_id).await? {
+            return Ok(Some(public_key));
+        }
+
+        for hardened in [false, true] {
+            let mut offset = 0;
+
+            loop {
+                let derivations = self.db.derivations(hardened, 1000, offset).await?;
+
+                if derivations.is_empty() {
+                    break;
+                }
+
+                for derivation in &derivations {
+                    let tail_args = EverythingWithSignatureTailArgs::new(derivation.synthetic_key);
+
+                    if tail_args.curry_tree_hash() == asset_id.into() {
+                        self.db
+                            .set_cat_issuance_key(asset_id, derivation.synthetic_key)
+                            .await?;
+
+                        return Ok(Some(derivation.synthetic_key));
+                    }
+                }
+
+                offset += derivations.len() as u32;
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Issues more of a CAT that was issued by this wallet with an everything with signature TAIL.
+    pub async fn mint_more_cat(
+        &self,
+        asset_id: Bytes32,
+        amount: u64,
+        fee: u64,
+        selection: &CoinSelection,
+        hardened: bool,
+        reuse: bool,
+    ) -> Result<Vec<CoinSpend>, WalletError> {
+        let Some(public_key) = self.cat_issuance_key(asset_id).await? else {
+            return Err(WalletError::NotCatIssuer(asset_id));
+        };
+
+        // The asset id only depends on the TAIL, so a new eve coin with the same key will match.
+        let (coin_spends, eve_asset_id) = self
+            .issue_cat(amount, fee, Some(public_key), selection, hardened, reuse)
+            .await?;
+
+        if eve_asset_id != asset_id {
+            return Err(WalletError::NotCatIssuer(asset_id));
+        }
+
+        Ok(coin_spends)
+    }
+
+    /// Destroys the given amount of a CAT that was issued by this wallet, by running its TAIL.
+    pub async fn melt_cat(
+        &self,
+        asset_id: Bytes32,
+        amount: u64,
+        fee: u64,
+        selection: &CoinSelection,
+        hardened: bool,
+        reuse: bool,
+    ) -> Result<Vec<CoinSpend>, WalletError> {
+        let Some(public_key) = self.cat_issuance_key(asset_id).await? else {
+            return Err(WalletError::NotCatIssuer(asset_id));
+        };
+
+        let tail_args = EverythingWithSignatureTailArgs::new(public_key);
+
+        if tail_args.curry_tree_hash() != asset_id.into() {
+            return Err(WalletError::NotCatIssuer(asset_id));
+        }
+
+        let fee_coins = if fee > 0 {
+            self.select_p2_coins(fee as u128, selection).await?
+        } else {
+            Vec::new()
+        };
+
+        let cats = self
+            .select_cat_coins(asset_id, amount as u128, selection)
+            .await?;
+        let cat_selected: u128 = cats.iter().map(|cat| cat.coin.amount as u128).sum();
+        let cat_change: u64 = (cat_selected - amount as u128)
+            .try_into()
+            .expect("change amount overflow");
+
+        let change_puzzle_hash = self.p2_puzzle_hash(hardened, reuse).await?;
+
+        let mut ctx = SpendContext::new();
+
+        if fee > 0 {
+            let fee_selected: u128 = fee_coins.iter().map(|coin| coin.amount as u128).sum();
+            let fee_change: u64 = (fee_selected - fee as u128)
+                .try_into()
+                .expect("fee change overflow");
+
+            let mut conditions = Conditions::new()
+                .assert_concurrent_spend(cats[0].coin.coin_id())
+                .reserve_fee(fee);
+
+            if fee_change > 0 {
+                conditions = conditions.create_coin(change_puzzle_hash, fee_change, None);
+            }
+
+            self.spend_p2_coins(&mut ctx, fee_coins, conditions).await?;
+        }
+
+        let tail = ctx.curry(tail_args)?;
+
+        let mut cat_spends = Vec::with_capacity(cats.len());
+
+        for (i, cat) in cats.into_iter().enumerate() {
+            let synthetic_key = self.db.synthetic_key(cat.p2_puzzle_hash).await?;
+            let p2 = StandardLayer::new(synthetic_key);
+
+            if i != 0 {
+                cat_spends.push(CatSpend::new(
+                    cat,
+                    p2.spend_with_conditions(&mut ctx, Conditions::new())?,
+                ));
+                continue;
+            }
+
+            // The first spend runs the TAIL, which allows the melted amount to leave the supply.
+            let mut conditions = Conditions::new().run_cat_tail(tail, NodePtr::NIL);
+
+            if cat_change > 0 {
+                let change_hint = ctx.hint(change_puzzle_hash)?;
+                conditions =
+                    conditions.create_coin(change_puzzle_hash, cat_change, Some(change_hint));
+            }
+
+            cat_spends.push(CatSpend::with_extra_delta(
+                cat,
+                p2.spend_with_conditions(&mut ctx, conditions)?,
+                -(amount as i64),
+            ));
+        }
+
+        Cat::spend_all(&mut ctx, &cat_spends)?;
+
+        Ok(ctx.take())
+    }
+
     /// Sends the given amount of XCH to the given puzzle hash, minus the fee.
     #[allow(clippy::too_many_arguments)]
     pub async fn send_cat(
@@ -64,18 +227,50 @@ impl Wallet {
         amount: u64,
         fee: u64,
         memos: Vec<Bytes>,
+        clawback_seconds: Option<u64>,
+        selection: &CoinSelection,
+        hardened: bool,
+        reuse: bool,
+    ) -> Result<Vec<CoinSpend>, WalletError> {
+        self.send_cat_multi(
+            asset_id,
+            vec![WalletPayment {
+                puzzle_hash,
+                amount,
+                memos,
+                clawback_seconds,
+            }],
+            fee,
+            selection,
+            hardened,
+            reuse,
+        )
+        .await
+    }
+
+    /// Sends the CAT to each of the payments in a single transaction, with one change output.
+    pub async fn send_cat_multi(
+        &self,
+        asset_id: Bytes32,
+        payments: Vec<WalletPayment>,
+        fee: u64,
+        selection: &CoinSelection,
         hardened: bool,
         reuse: bool,
     ) -> Result<Vec<CoinSpend>, WalletError> {
+        validate_payments(&payments)?;
+
+        let amount: u128 = payments.iter().map(|payment| payment.amount as u128).sum();
+
         let fee_coins = if fee > 0 {
-            self.select_p2_coins(fee as u128).await?
+            self.select_p2_coins(fee as u128, selection).await?
         } else {
             Vec::new()
         };
 
-        let cats = self.select_cat_coins(asset_id, amount as u128).await?;
+        let cats = self.select_cat_coins(asset_id, amount, selection).await?;
         let cat_selected: u128 = cats.iter().map(|cat| cat.coin.amount as u128).sum();
-        let cat_change: u64 = (cat_selected - amount as u128)
+        let cat_change: u64 = (cat_selected - amount)
             .try_into()
             .expect("change amount overflow");
 
@@ -107,9 +302,22 @@ impl Wallet {
                 .await?;
         }
 
-        let mut output_memos = vec![puzzle_hash.into()];
-        output_memos.extend(memos);
-        let memos = ctx.memos(&output_memos)?;
+        let mut outputs = Vec::with_capacity(payments.len());
+
+        for payment in payments {
+            let (puzzle_hash, output_memos) = if let Some(seconds) = payment.clawback_seconds {
+                let clawback = Clawback::new(change_puzzle_hash, payment.puzzle_hash, seconds);
+                (clawback.puzzle_hash().into(), clawback.memos(payment.memos))
+            } else {
+                let mut output_memos = vec![payment.puzzle_hash.into()];
+                output_memos.extend(payment.memos);
+                (payment.puzzle_hash, output_memos)
+            };
+
+            let memos = ctx.memos(&output_memos)?;
+            outputs.push((puzzle_hash, payment.amount, memos));
+        }
+
         let change_hint = ctx.hint(change_puzzle_hash)?;
 
         self.spend_cat_coins(
@@ -119,8 +327,11 @@ impl Wallet {
                     return (cat, Conditions::new());
                 }
 
-                let mut conditions =
-                    mem::take(&mut conditions).create_coin(puzzle_hash, amount, Some(memos));
+                let mut conditions = mem::take(&mut conditions);
+
+                for (puzzle_hash, amount, memos) in mem::take(&mut outputs) {
+                    conditions = conditions.create_coin(puzzle_hash, amount, Some(memos));
+                }
 
                 if cat_change > 0 {
                     conditions =
@@ -138,15 +349,19 @@ impl Wallet {
 
 #[cfg(test)]
 mod tests {
+    use chia::protocol::Bytes32;
     use test_log::test;
 
-    use crate::TestWallet;
+    use crate::{CoinSelection, TestWallet, WalletError, WalletPayment};
 
     #[test(tokio::test)]
     async fn test_send_cat() -> anyhow::Result<()> {
         let mut test = TestWallet::new(1500).await?;
 
-        let (coin_spends, asset_id) = test.wallet.issue_cat(1000, 0, None, false, true).await?;
+        let (coin_spends, asset_id) = test
+            .wallet
+            .issue_cat(1000, 0, None, &CoinSelection::default(), false, true)
+            .await?;
         assert_eq!(coin_spends.len(), 2);
 
         test.transact(coin_spends).await?;
@@ -159,7 +374,17 @@ mod tests {
 
         let coin_spends = test
             .wallet
-            .send_cat(asset_id, test.puzzle_hash, 750, 0, Vec::new(), false, true)
+            .send_cat(
+                asset_id,
+                test.puzzle_hash,
+                750,
+                0,
+                Vec::new(),
+                None,
+                &CoinSelection::default(),
+                false,
+                true,
+            )
             .await?;
         assert_eq!(coin_spends.len(), 1);
 
@@ -177,6 +402,8 @@ mod tests {
                 1000,
                 500,
                 Vec::new(),
+                None,
+                &CoinSelection::default(),
                 false,
                 true,
             )
@@ -193,4 +420,120 @@ mod tests {
 
         Ok(())
     }
+
+    #[test(tokio::test)]
+    async fn test_send_cat_multi() -> anyhow::Result<()> {
+        let mut test = TestWallet::new(1500).await?;
+
+        let (coin_spends, asset_id) = test
+            .wallet
+            .issue_cat(1000, 0, None, &CoinSelection::default(), false, true)
+            .await?;
+
+        test.transact(coin_spends).await?;
+        test.wait_for_coins().await;
+
+        let payments = (1..=3)
+            .map(|amount| WalletPayment {
+                puzzle_hash: test.puzzle_hash,
+                amount: amount * 100,
+                memos: Vec::new(),
+                clawback_seconds: None,
+            })
+            .collect();
+
+        let coin_spends = test
+            .wallet
+            .send_cat_multi(
+                asset_id,
+                payments,
+                500,
+                &CoinSelection::default(),
+                false,
+                true,
+            )
+            .await?;
+        assert_eq!(coin_spends.len(), 2);
+
+        test.transact(coin_spends).await?;
+        test.wait_for_coins().await;
+
+        assert_eq!(test.wallet.db.balance().await?, 0);
+        assert_eq!(test.wallet.db.cat_balance(asset_id).await?, 1000);
+        assert_eq!(test.wallet.db.spendable_cat_coins(asset_id).await?.len(), 4);
+
+        Ok(())
+    }
+
+    #[test(tokio::test)]
+    async fn test_mint_more_and_melt_cat() -> anyhow::Result<()> {
+        let mut test = TestWallet::new(1500).await?;
+
+        let public_key = test.wallet.db.synthetic_key(test.puzzle_hash).await?;
+
+        let (coin_spends, asset_id) = test
+            .wallet
+            .issue_cat(
+                1000,
+                0,
+                Some(public_key),
+                &CoinSelection::default(),
+                false,
+                true,
+            )
+            .await?;
+
+        test.transact(coin_spends).await?;
+        test.wait_for_coins().await;
+
+        // The issuance key isn't recorded, but it's found from the derivations and recorded.
+        assert_eq!(test.wallet.db.cat_issuance_key(asset_id).await?, None);
+        assert_eq!(
+            test.wallet.cat_issuance_key(asset_id).await?,
+            Some(public_key)
+        );
+        assert_eq!(
+            test.wallet.db.cat_issuance_key(asset_id).await?,
+            Some(public_key)
+        );
+
+        // CATs that weren't issued by this wallet can't be re-issued.
+        assert!(matches!(
+            test.wallet
+                .mint_more_cat(
+                    Bytes32::default(),
+                    250,
+                    0,
+                    &CoinSelection::default(),
+                    false,
+                    true
+                )
+                .await,
+            Err(WalletError::NotCatIssuer(..))
+        ));
+
+        let coin_spends = test
+            .wallet
+            .mint_more_cat(asset_id, 250, 0, &CoinSelection::default(), false, true)
+            .await?;
+
+        test.transact(coin_spends).await?;
+        test.wait_for_coins().await;
+
+        assert_eq!(test.wallet.db.balance().await?, 250);
+        assert_eq!(test.wallet.db.cat_balance(asset_id).await?, 1250);
+
+        let coin_spends = test
+            .wallet
+            .melt_cat(asset_id, 1100, 0, &CoinSelection::default(), false, true)
+            .await?;
+
+        test.transact(coin_spends).await?;
+        test.wait_for_coins().await;
+
+        assert_eq!(test.wallet.db.cat_balance(asset_id).await?, 150);
+        assert_eq!(test.wallet.db.spendable_cat_coins(asset_id).await?.len(), 1);
+
+        Ok(())
+    }
 }
@@ -0,0 +1,365 @@
+use chia::protocol::{Bytes32, CoinSpend};
+use chia_wallet_sdk::{
+    Cat, CatSpend, Conditions, SpendContext, SpendWithConditions, StandardLayer,
+};
+
+use crate::{Clawback, WalletError};
+
+use super::{CoinSelection, Wallet};
+
+impl Wallet {
+    /// Claims a clawback payment sent to the wallet, which is only valid once the timelock has expired.
+    pub async fn claim_clawback(
+        &self,
+        coin_id: Bytes32,
+        fee: u64,
+        selection: &CoinSelection,
+        hardened: bool,
+        reuse: bool,
+    ) -> Result<Vec<CoinSpend>, WalletError> {
+        self.spend_clawback(coin_id, false, fee, selection, hardened, reuse)
+            .await
+    }
+
+    /// Revokes a clawback payment sent by the wallet, which is only valid until the timelock expires.
+    pub async fn revoke_clawback(
+        &self,
+        coin_id: Bytes32,
+        fee: u64,
+        selection: &CoinSelection,
+        hardened: bool,
+        reuse: bool,
+    ) -> Result<Vec<CoinSpend>, WalletError> {
+        self.spend_clawback(coin_id, true, fee, selection, hardened, reuse)
+            .await
+    }
+
+    /// Spends the clawback coin to the p2 puzzle hash of either the sender or the recipient.
+    async fn spend_clawback(
+        &self,
+        coin_id: Bytes32,
+        revoke: bool,
+        fee: u64,
+        selection: &CoinSelection,
+        hardened: bool,
+        reuse: bool,
+    ) -> Result<Vec<CoinSpend>, WalletError> {
+        let Some(row) = self.db.clawback_coin(coin_id).await? else {
+            return Err(WalletError::MissingClawback(coin_id));
+        };
+
+        if row.coin_state.spent_height.is_some() {
+            return Err(WalletError::CoinSpent(coin_id));
+        }
+
+        if self.db.is_coin_locked(coin_id).await? {
+            return Err(WalletError::CoinLocked(coin_id));
+        }
+
+        let clawback = Clawback::new(
+            row.clawback.sender_puzzle_hash,
+            row.clawback.recipient_puzzle_hash,
+            row.clawback.seconds,
+        );
+
+        let p2_puzzle_hash = if revoke {
+            clawback.sender_puzzle_hash
+        } else {
+            clawback.recipient_puzzle_hash
+        };
+
+        if !self.db.is_p2_puzzle_hash(p2_puzzle_hash).await? {
+            return Err(if revoke {
+                WalletError::NotClawbackSender(coin_id)
+            } else {
+                WalletError::NotClawbackRecipient(coin_id)
+            });
+        }
+
+        let fee_coins = if fee > 0 {
+            self.select_p2_coins(fee as u128, selection).await?
+        } else {
+            Vec::new()
+        };
+
+        let mut ctx = SpendContext::new();
+
+        if fee > 0 {
+            let fee_selected: u128 = fee_coins.iter().map(|coin| coin.amount as u128).sum();
+            let fee_change: u64 = (fee_selected - fee as u128)
+                .try_into()
+                .expect("fee change overflow");
+
+            let mut conditions = Conditions::new()
+                .assert_concurrent_spend(coin_id)
+                .reserve_fee(fee);
+
+            if fee_change > 0 {
+                let change_puzzle_hash = self.p2_puzzle_hash(hardened, reuse).await?;
+                conditions = conditions.create_coin(change_puzzle_hash, fee_change, None);
+            }
+
+            self.spend_p2_coins(&mut ctx, fee_coins, conditions).await?;
+        }
+
+        let coin = row.coin_state.coin;
+
+        // CATs need to be hinted, so that they can be found by the wallet.
+        let hint = if row.clawback.asset_id.is_some() {
+            Some(ctx.hint(p2_puzzle_hash)?)
+        } else {
+            None
+        };
+
+        let conditions = Conditions::new().create_coin(p2_puzzle_hash, coin.amount, hint);
+
+        let synthetic_key = self.db.synthetic_key(p2_puzzle_hash).await?;
+        let inner_spend =
+            StandardLayer::new(synthetic_key).spend_with_conditions(&mut ctx, conditions)?;
+
+        let spend = if revoke {
+            clawback.sender_spend(&mut ctx, inner_spend)?
+        } else {
+            clawback.recipient_spend(&mut ctx, inner_spend)?
+        };
+
+        match (row.clawback.asset_id, row.clawback.lineage_proof) {
+            (Some(asset_id), Some(lineage_proof)) => {
+                let cat = Cat {
+                    coin,
+                    lineage_proof: Some(lineage_proof),
+                    asset_id,
+                    p2_puzzle_hash: clawback.puzzle_hash().into(),
+                };
+
+                Cat::spend_all(&mut ctx, &[CatSpend::new(cat, spend)])?;
+            }
+            _ => ctx.spend(coin, spend)?,
+        }
+
+        Ok(ctx.take())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_log::test;
+
+    use crate::{CoinSelection, SyncEvent, TestWallet, WalletError};
+
+    #[test(tokio::test)]
+    async fn test_revoke_xch_clawback() -> anyhow::Result<()> {
+        let mut alice = TestWallet::new(1000).await?;
+        let mut bob = alice.next(0).await?;
+
+        let coin_spends = alice
+            .wallet
+            .send_xch(
+                bob.puzzle_hash,
+                1000,
+                0,
+                Vec::new(),
+                Some(3600),
+                &CoinSelection::default(),
+                false,
+                true,
+            )
+            .await?;
+
+        alice.transact(coin_spends).await?;
+        alice.wait_for_coins().await;
+        bob.wait_for_puzzles().await;
+
+        assert_eq!(alice.wallet.db.balance().await?, 0);
+        assert_eq!(bob.wallet.db.balance().await?, 0);
+
+        let clawbacks = alice.wallet.db.unspent_clawback_coins().await?;
+        assert_eq!(clawbacks.len(), 1);
+        assert_eq!(bob.wallet.db.unspent_clawback_coins().await?.len(), 1);
+
+        let coin_id = clawbacks[0].coin_state.coin.coin_id();
+
+        // Only the sender can revoke the payment.
+        assert!(matches!(
+            bob.wallet
+                .revoke_clawback(coin_id, 0, &CoinSelection::default(), false, true)
+                .await,
+            Err(WalletError::NotClawbackSender(..))
+        ));
+
+        let coin_spends = alice
+            .wallet
+            .revoke_clawback(coin_id, 0, &CoinSelection::default(), false, true)
+            .await?;
+
+        alice.transact(coin_spends).await?;
+        alice.wait_for_coins().await;
+
+        assert_eq!(alice.wallet.db.balance().await?, 1000);
+        assert!(alice.wallet.db.unspent_clawback_coins().await?.is_empty());
+
+        Ok(())
+    }
+
+    #[test(tokio::test)]
+    async fn test_claim_xch_clawback() -> anyhow::Result<()> {
+        let mut alice = TestWallet::new(1000).await?;
+        let mut bob = alice.next(0).await?;
+
+        // The timelock expires immediately, so the payment can be claimed right away.
+        let coin_spends = alice
+            .wallet
+            .send_xch(
+                bob.puzzle_hash,
+                1000,
+                0,
+                Vec::new(),
+                Some(0),
+                &CoinSelection::default(),
+                false,
+                true,
+            )
+            .await?;
+
+        alice.transact(coin_spends).await?;
+        alice.wait_for_coins().await;
+        bob.wait_for_puzzles().await;
+
+        let clawbacks = bob.wallet.db.unspent_clawback_coins().await?;
+        assert_eq!(clawbacks.len(), 1);
+
+        let coin_id = clawbacks[0].coin_state.coin.coin_id();
+
+        // Only the recipient can claim the payment.
+        assert!(matches!(
+            alice
+                .wallet
+                .claim_clawback(coin_id, 0, &CoinSelection::default(), false, true)
+                .await,
+            Err(WalletError::NotClawbackRecipient(..))
+        ));
+
+        let coin_spends = bob
+            .wallet
+            .claim_clawback(coin_id, 0, &CoinSelection::default(), false, true)
+            .await?;
+
+        bob.transact(coin_spends).await?;
+        bob.wait_for_coins().await;
+
+        assert_eq!(bob.wallet.db.balance().await?, 1000);
+        assert!(bob.wallet.db.unspent_clawback_coins().await?.is_empty());
+
+        Ok(())
+    }
+
+    #[test(tokio::test)]
+    async fn test_revoke_cat_clawback() -> anyhow::Result<()> {
+        let mut alice = TestWallet::new(1000).await?;
+        let mut bob = alice.next(0).await?;
+
+        let (coin_spends, asset_id) = alice
+            .wallet
+            .issue_cat(1000, 0, None, &CoinSelection::default(), false, true)
+            .await?;
+
+        alice.transact(coin_spends).await?;
+        alice.wait_for_coins().await;
+
+        let coin_spends = alice
+            .wallet
+            .send_cat(
+                asset_id,
+                bob.puzzle_hash,
+                1000,
+                0,
+                Vec::new(),
+                Some(3600),
+                &CoinSelection::default(),
+                false,
+                true,
+            )
+            .await?;
+
+        alice.transact(coin_spends).await?;
+        alice.wait_for_coins().await;
+        bob.wait_for_puzzles().await;
+
+        assert_eq!(alice.wallet.db.cat_balance(asset_id).await?, 0);
+        assert_eq!(bob.wallet.db.cat_balance(asset_id).await?, 0);
+
+        let clawbacks = alice.wallet.db.unspent_clawback_coins().await?;
+        assert_eq!(clawbacks.len(), 1);
+        assert_eq!(clawbacks[0].clawback.asset_id, Some(asset_id));
+
+        let coin_spends = alice
+            .wallet
+            .revoke_clawback(
+                clawbacks[0].coin_state.coin.coin_id(),
+                0,
+                &CoinSelection::default(),
+                false,
+                true,
+            )
+            .await?;
+
+        alice.transact(coin_spends).await?;
+        alice.wait_for_coins().await;
+
+        assert_eq!(alice.wallet.db.cat_balance(asset_id).await?, 1000);
+
+        Ok(())
+    }
+
+    #[test(tokio::test)]
+    async fn test_revoke_clawback_after_restore() -> anyhow::Result<()> {
+        let mut alice = TestWallet::new(1000).await?;
+        let bob = alice.next(0).await?;
+
+        let coin_spends = alice
+            .wallet
+            .send_xch(
+                bob.puzzle_hash,
+                1000,
+                0,
+                Vec::new(),
+                Some(3600),
+                &CoinSelection::default(),
+                false,
+                true,
+            )
+            .await?;
+
+        alice.transact(coin_spends).await?;
+        alice.wait_for_coins().await;
+
+        // The clawback is hinted to the recipient, so the sender finds it by looking at the
+        // spends of its own coins instead.
+        let mut restored = alice.restore().await?;
+
+        restored
+            .consume_until(|event| matches!(event, SyncEvent::TransactionHistory))
+            .await;
+
+        let clawbacks = restored.wallet.db.unspent_clawback_coins().await?;
+        assert_eq!(clawbacks.len(), 1);
+
+        let coin_spends = restored
+            .wallet
+            .revoke_clawback(
+                clawbacks[0].coin_state.coin.coin_id(),
+                0,
+                &CoinSelection::default(),
+                false,
+                true,
+            )
+            .await?;
+
+        restored.transact(coin_spends).await?;
+        restored.wait_for_coins().await;
+
+        assert_eq!(restored.wallet.db.balance().await?, 1000);
+
+        Ok(())
+    }
+}
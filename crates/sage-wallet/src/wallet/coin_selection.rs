@@ -1,17 +1,46 @@
-use std::collections::HashMap;
+use std::{cmp::Reverse, collections::HashMap};
 
 use chia::protocol::{Bytes32, Coin};
 use chia_wallet_sdk::{select_coins, Cat};
+use indexmap::IndexMap;
 
 use crate::WalletError;
 
 use super::Wallet;
 
+pub use sage_config::CoinSelectionStrategy;
+
+/// The maximum number of branches explored when searching for an exact match.
+const MAX_EXACT_MATCH_TRIES: usize = 100_000;
+
+/// Controls which coins are used as the inputs of a transaction.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct CoinSelection {
+    /// Coins that must be spent as the inputs of their kind, rather than being selected
+    /// automatically. Kinds of coins that have none pinned are still selected automatically.
+    pub coin_ids: Vec<Bytes32>,
+
+    /// How coins are selected when none of their kind are pinned.
+    pub strategy: CoinSelectionStrategy,
+}
+
 impl Wallet {
     /// Selects one or more unspent p2 coins from the database.
-    pub(crate) async fn select_p2_coins(&self, amount: u128) -> Result<Vec<Coin>, WalletError> {
+    pub(crate) async fn select_p2_coins(
+        &self,
+        amount: u128,
+        selection: &CoinSelection,
+    ) -> Result<Vec<Coin>, WalletError> {
         let spendable_coins = self.db.spendable_coins().await?;
-        Ok(select_coins(spendable_coins, amount)?)
+
+        if let Some(coins) = self
+            .pinned_coins(&spendable_coins, amount, selection)
+            .await?
+        {
+            return Ok(coins);
+        }
+
+        select_with_strategy(spendable_coins, amount, selection.strategy)
     }
 
     /// Selects one or more unspent CAT coins from the database.
@@ -19,6 +48,7 @@ impl Wallet {
         &self,
         asset_id: Bytes32,
         amount: u128,
+        selection: &CoinSelection,
     ) -> Result<Vec<Cat>, WalletError> {
         let cat_coins = self.db.spendable_cat_coins(asset_id).await?;
 
@@ -38,9 +68,443 @@ impl Wallet {
             spendable_coins.push(cat.coin);
         }
 
-        Ok(select_coins(spendable_coins, amount)?
-            .into_iter()
-            .map(|coin| cats[&coin])
-            .collect())
+        let coins = match self
+            .pinned_coins(&spendable_coins, amount, selection)
+            .await?
+        {
+            Some(coins) => coins,
+            None => select_with_strategy(spendable_coins, amount, selection.strategy)?,
+        };
+
+        Ok(coins.into_iter().map(|coin| cats[&coin]).collect())
+    }
+
+    /// Checks that every pinned coin is owned, unspent, and not locked, then returns the
+    /// spendable coins among them, which must cover the amount. If none of the spendable
+    /// coins are pinned, returns [`None`].
+    async fn pinned_coins(
+        &self,
+        spendable_coins: &[Coin],
+        amount: u128,
+        selection: &CoinSelection,
+    ) -> Result<Option<Vec<Coin>>, WalletError> {
+        for &coin_id in &selection.coin_ids {
+            let Some(coin_state) = self.db.coin_state(coin_id).await? else {
+                return Err(WalletError::MissingCoin(coin_id));
+            };
+
+            if coin_state.spent_height.is_some() {
+                return Err(WalletError::CoinSpent(coin_id));
+            }
+
+            if self.db.is_coin_locked(coin_id).await? {
+                return Err(WalletError::CoinLocked(coin_id));
+            }
+        }
+
+        let coins: Vec<Coin> = spendable_coins
+            .iter()
+            .filter(|coin| selection.coin_ids.contains(&coin.coin_id()))
+            .copied()
+            .collect();
+
+        if coins.is_empty() {
+            return Ok(None);
+        }
+
+        let selected: u128 = coins.iter().map(|coin| coin.amount as u128).sum();
+
+        if selected < amount {
+            return Err(WalletError::InsufficientFunds);
+        }
+
+        Ok(Some(coins))
+    }
+}
+
+/// Selects coins from the spendable coins, which are expected to be ordered from oldest to newest.
+fn select_with_strategy(
+    mut spendable_coins: Vec<Coin>,
+    amount: u128,
+    strategy: CoinSelectionStrategy,
+) -> Result<Vec<Coin>, WalletError> {
+    match strategy {
+        CoinSelectionStrategy::Automatic => Ok(select_coins(spendable_coins, amount)?),
+        CoinSelectionStrategy::LargestFirst => {
+            spendable_coins.sort_by_key(|coin| Reverse(coin.amount));
+            select_in_order(spendable_coins, amount)
+        }
+        CoinSelectionStrategy::SmallestFirst => {
+            spendable_coins.sort_by_key(|coin| coin.amount);
+            select_in_order(spendable_coins, amount)
+        }
+        CoinSelectionStrategy::OldestFirst => select_in_order(spendable_coins, amount),
+        CoinSelectionStrategy::ExactMatch => match select_exact_match(&spendable_coins, amount) {
+            Some(coins) => Ok(coins),
+            None => Ok(select_coins(spendable_coins, amount)?),
+        },
+        CoinSelectionStrategy::SingleAddress => {
+            let mut addresses = IndexMap::<Bytes32, Vec<Coin>>::new();
+
+            for &coin in &spendable_coins {
+                addresses.entry(coin.puzzle_hash).or_default().push(coin);
+            }
+
+            // The address with the smallest balance that covers the amount is used,
+            // to leave addresses with larger balances for larger payments.
+            let address_coins = addresses
+                .into_values()
+                .map(|coins| {
+                    let total: u128 = coins.iter().map(|coin| coin.amount as u128).sum();
+                    (total, coins)
+                })
+                .filter(|(total, _)| *total >= amount)
+                .min_by_key(|(total, _)| *total);
+
+            match address_coins {
+                Some((_, coins)) => Ok(select_coins(coins, amount)?),
+                None => Ok(select_coins(spendable_coins, amount)?),
+            }
+        }
+    }
+}
+
+/// Selects coins in the given order until the amount is covered.
+fn select_in_order(coins: Vec<Coin>, amount: u128) -> Result<Vec<Coin>, WalletError> {
+    let mut selected = Vec::new();
+    let mut total = 0;
+
+    for coin in coins {
+        if total >= amount && !selected.is_empty() {
+            break;
+        }
+
+        total += coin.amount as u128;
+        selected.push(coin);
+    }
+
+    if total < amount || selected.is_empty() {
+        return Err(WalletError::InsufficientFunds);
+    }
+
+    Ok(selected)
+}
+
+/// Uses a branch and bound search to find coins that add up to exactly the amount.
+fn select_exact_match(coins: &[Coin], amount: u128) -> Option<Vec<Coin>> {
+    let mut coins = coins.to_vec();
+    coins.sort_by_key(|coin| Reverse(coin.amount));
+
+    // The total amount of the coins from each index onward, used to prune branches
+    // that can no longer reach the amount.
+    let mut remaining = vec![0; coins.len() + 1];
+
+    for i in (0..coins.len()).rev() {
+        remaining[i] = remaining[i + 1] + coins[i].amount as u128;
+    }
+
+    let mut selected = Vec::new();
+    let mut tries = 0;
+
+    if !search_exact_match(&coins, &remaining, 0, amount, &mut selected, &mut tries) {
+        return None;
+    }
+
+    Some(selected.into_iter().map(|i| coins[i]).collect())
+}
+
+fn search_exact_match(
+    coins: &[Coin],
+    remaining: &[u128],
+    index: usize,
+    target: u128,
+    selected: &mut Vec<usize>,
+    tries: &mut usize,
+) -> bool {
+    if target == 0 {
+        return !selected.is_empty();
+    }
+
+    if index == coins.len() || remaining[index] < target || *tries >= MAX_EXACT_MATCH_TRIES {
+        return false;
+    }
+
+    *tries += 1;
+
+    let amount = coins[index].amount as u128;
+
+    if amount <= target {
+        selected.push(index);
+
+        if search_exact_match(
+            coins,
+            remaining,
+            index + 1,
+            target - amount,
+            selected,
+            tries,
+        ) {
+            return true;
+        }
+
+        selected.pop();
+    }
+
+    search_exact_match(coins, remaining, index + 1, target, selected, tries)
+}
+
+#[cfg(test)]
+mod tests {
+    use chia::protocol::Bytes32;
+    use test_log::test;
+
+    use crate::{CoinSelection, CoinSelectionStrategy, TestWallet, WalletError, WalletPayment};
+
+    /// Creates coins of 300, 200, 100, and 400 mojos, from oldest to newest.
+    async fn setup_coins(test: &mut TestWallet) -> anyhow::Result<()> {
+        for amount in [300, 200, 100] {
+            let coins = test.wallet.db.spendable_coins().await?;
+            let largest = coins
+                .iter()
+                .max_by_key(|coin| coin.amount)
+                .expect("missing coin");
+
+            let selection = CoinSelection {
+                coin_ids: vec![largest.coin_id()],
+                ..Default::default()
+            };
+
+            let coin_spends = test
+                .wallet
+                .send_xch(
+                    test.puzzle_hash,
+                    amount,
+                    0,
+                    Vec::new(),
+                    None,
+                    &selection,
+                    false,
+                    true,
+                )
+                .await?;
+
+            test.transact(coin_spends).await?;
+            test.wait_for_coins().await;
+        }
+
+        Ok(())
+    }
+
+    /// Returns the sorted amounts of the coins that would be spent to send the amount.
+    async fn selected_amounts(
+        test: &TestWallet,
+        amount: u64,
+        strategy: CoinSelectionStrategy,
+    ) -> anyhow::Result<Vec<u64>> {
+        let selection = CoinSelection {
+            coin_ids: Vec::new(),
+            strategy,
+        };
+
+        let coin_spends = test
+            .wallet
+            .send_xch(
+                Bytes32::default(),
+                amount,
+                0,
+                Vec::new(),
+                None,
+                &selection,
+                false,
+                true,
+            )
+            .await?;
+
+        let mut amounts: Vec<u64> = coin_spends
+            .iter()
+            .map(|coin_spend| coin_spend.coin.amount)
+            .collect();
+        amounts.sort_unstable();
+
+        Ok(amounts)
+    }
+
+    #[test(tokio::test)]
+    async fn test_pinned_coins() -> anyhow::Result<()> {
+        let mut test = TestWallet::new(1000).await?;
+
+        let coins = test.wallet.db.spendable_coins().await?;
+        let coin_spends = test.wallet.split_xch(&coins, 2, 0, false, true).await?;
+
+        test.transact(coin_spends).await?;
+        test.wait_for_coins().await;
+
+        let coins = test.wallet.db.spendable_coins().await?;
+        assert_eq!(coins.len(), 2);
+
+        let selection = CoinSelection {
+            coin_ids: vec![coins[1].coin_id()],
+            ..Default::default()
+        };
+
+        assert!(matches!(
+            test.wallet
+                .send_xch(
+                    test.puzzle_hash,
+                    600,
+                    0,
+                    Vec::new(),
+                    None,
+                    &selection,
+                    false,
+                    true
+                )
+                .await,
+            Err(WalletError::InsufficientFunds)
+        ));
+
+        let coin_spends = test
+            .wallet
+            .send_xch(
+                test.puzzle_hash,
+                100,
+                0,
+                Vec::new(),
+                None,
+                &selection,
+                false,
+                true,
+            )
+            .await?;
+
+        assert_eq!(coin_spends.len(), 1);
+        assert_eq!(coin_spends[0].coin, coins[1]);
+
+        test.transact(coin_spends).await?;
+        test.wait_for_coins().await;
+
+        assert!(matches!(
+            test.wallet
+                .send_xch(
+                    test.puzzle_hash,
+                    100,
+                    0,
+                    Vec::new(),
+                    None,
+                    &selection,
+                    false,
+                    true
+                )
+                .await,
+            Err(WalletError::CoinSpent(..))
+        ));
+
+        let selection = CoinSelection {
+            coin_ids: vec![Bytes32::default()],
+            ..Default::default()
+        };
+
+        assert!(matches!(
+            test.wallet
+                .send_xch(
+                    test.puzzle_hash,
+                    100,
+                    0,
+                    Vec::new(),
+                    None,
+                    &selection,
+                    false,
+                    true
+                )
+                .await,
+            Err(WalletError::MissingCoin(..))
+        ));
+
+        Ok(())
+    }
+
+    #[test(tokio::test)]
+    async fn test_largest_first() -> anyhow::Result<()> {
+        let mut test = TestWallet::new(1000).await?;
+        setup_coins(&mut test).await?;
+
+        let amounts = selected_amounts(&test, 450, CoinSelectionStrategy::LargestFirst).await?;
+        assert_eq!(amounts, [300, 400]);
+
+        Ok(())
+    }
+
+    #[test(tokio::test)]
+    async fn test_smallest_first() -> anyhow::Result<()> {
+        let mut test = TestWallet::new(1000).await?;
+        setup_coins(&mut test).await?;
+
+        let amounts = selected_amounts(&test, 250, CoinSelectionStrategy::SmallestFirst).await?;
+        assert_eq!(amounts, [100, 200]);
+
+        Ok(())
+    }
+
+    #[test(tokio::test)]
+    async fn test_oldest_first() -> anyhow::Result<()> {
+        let mut test = TestWallet::new(1000).await?;
+        setup_coins(&mut test).await?;
+
+        let amounts = selected_amounts(&test, 450, CoinSelectionStrategy::OldestFirst).await?;
+        assert_eq!(amounts, [200, 300]);
+
+        Ok(())
+    }
+
+    #[test(tokio::test)]
+    async fn test_exact_match() -> anyhow::Result<()> {
+        let mut test = TestWallet::new(1000).await?;
+        setup_coins(&mut test).await?;
+
+        let amounts = selected_amounts(&test, 600, CoinSelectionStrategy::ExactMatch).await?;
+        assert_eq!(amounts, [200, 400]);
+
+        // There's no exact match, so it falls back to automatic selection.
+        let amounts = selected_amounts(&test, 650, CoinSelectionStrategy::ExactMatch).await?;
+        assert!(amounts.iter().sum::<u64>() > 650);
+
+        Ok(())
+    }
+
+    #[test(tokio::test)]
+    async fn test_single_address() -> anyhow::Result<()> {
+        let mut test = TestWallet::new(1000).await?;
+
+        let other_puzzle_hash = test.wallet.p2_puzzle_hash(false, false).await?;
+        assert_ne!(other_puzzle_hash, test.puzzle_hash);
+
+        let payments = vec![
+            WalletPayment {
+                puzzle_hash: test.puzzle_hash,
+                amount: 150,
+                memos: Vec::new(),
+                clawback_seconds: None,
+            },
+            WalletPayment {
+                puzzle_hash: other_puzzle_hash,
+                amount: 600,
+                memos: Vec::new(),
+                clawback_seconds: None,
+            },
+        ];
+
+        let coin_spends = test
+            .wallet
+            .send_xch_multi(payments, 0, &CoinSelection::default(), false, true)
+            .await?;
+
+        test.transact(coin_spends).await?;
+        test.wait_for_coins().await;
+
+        // The first address has 150 and 250 of change, which is the smallest balance that covers the amount.
+        let amounts = selected_amounts(&test, 350, CoinSelectionStrategy::SingleAddress).await?;
+        assert_eq!(amounts, [150, 250]);
+
+        Ok(())
     }
 }
@@ -0,0 +1,187 @@
+use chia::protocol::{Coin, CoinSpend};
+use chia_wallet_sdk::{Cat, Conditions, SpendContext};
+
+use crate::WalletError;
+
+use super::Wallet;
+
+impl Wallet {
+    /// Merges the dust coins of XCH and every CAT into one coin per asset, in a single transaction.
+    /// Only coins with an amount below the dust threshold are merged, smallest first, and at most
+    /// the given number of coins of each asset are spent. The fee is subtracted from the XCH output,
+    /// and if the XCH dust isn't enough to cover it, the smallest other coins are spent as well.
+    pub async fn auto_consolidate(
+        &self,
+        max_coins_per_asset: usize,
+        dust_threshold: u64,
+        fee: u64,
+        hardened: bool,
+        reuse: bool,
+    ) -> Result<Vec<CoinSpend>, WalletError> {
+        let p2_puzzle_hash = self.p2_puzzle_hash(hardened, reuse).await?;
+
+        let mut ctx = SpendContext::new();
+        let mut consolidated = false;
+
+        for cat in self.db.cats_by_name().await? {
+            let asset_id = cat.asset_id;
+
+            let mut cat_coins = self.db.spendable_cat_coins(asset_id).await?;
+            cat_coins.retain(|cat| cat.coin.amount < dust_threshold);
+            cat_coins.sort_by_key(|cat| cat.coin.amount);
+            cat_coins.truncate(max_coins_per_asset);
+
+            if cat_coins.len() < 2 {
+                continue;
+            }
+
+            let cat_total: u128 = cat_coins.iter().map(|cat| cat.coin.amount as u128).sum();
+            let hint = ctx.hint(p2_puzzle_hash)?;
+
+            self.spend_cat_coins(
+                &mut ctx,
+                cat_coins.into_iter().enumerate().map(|(i, cat)| {
+                    let cat = Cat {
+                        coin: cat.coin,
+                        lineage_proof: Some(cat.lineage_proof),
+                        asset_id,
+                        p2_puzzle_hash: cat.p2_puzzle_hash,
+                    };
+
+                    if i != 0 {
+                        return (cat, Conditions::new());
+                    }
+
+                    (
+                        cat,
+                        Conditions::new().create_coin(
+                            p2_puzzle_hash,
+                            cat_total.try_into().expect("output amount overflow"),
+                            Some(hint),
+                        ),
+                    )
+                }),
+            )
+            .await?;
+
+            consolidated = true;
+        }
+
+        let mut spendable_coins = self.db.spendable_coins().await?;
+        spendable_coins.sort_by_key(|coin| coin.amount);
+
+        let (dust, other): (Vec<Coin>, Vec<Coin>) = spendable_coins
+            .into_iter()
+            .partition(|coin| coin.amount < dust_threshold);
+
+        let mut coins: Vec<Coin> = dust.into_iter().take(max_coins_per_asset).collect();
+
+        if coins.len() >= 2 {
+            consolidated = true;
+        } else if fee == 0 || !consolidated {
+            // A single coin can't be merged, so it's only spent if it's needed for the fee.
+            coins.clear();
+        }
+
+        if !consolidated {
+            return Err(WalletError::NothingToConsolidate);
+        }
+
+        let mut total: u128 = coins.iter().map(|coin| coin.amount as u128).sum();
+
+        for coin in other {
+            if total >= fee as u128 {
+                break;
+            }
+
+            total += coin.amount as u128;
+            coins.push(coin);
+        }
+
+        if fee as u128 > total {
+            return Err(WalletError::InsufficientFunds);
+        }
+
+        if !coins.is_empty() {
+            let change: u64 = (total - fee as u128)
+                .try_into()
+                .expect("change amount overflow");
+
+            let mut conditions = Conditions::new();
+
+            if fee > 0 {
+                conditions = conditions.reserve_fee(fee);
+            }
+
+            if change > 0 {
+                conditions = conditions.create_coin(p2_puzzle_hash, change, None);
+            }
+
+            self.spend_p2_coins(&mut ctx, coins, conditions).await?;
+        }
+
+        Ok(ctx.take())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_log::test;
+
+    use crate::{CoinSelection, TestWallet, WalletError};
+
+    #[test(tokio::test)]
+    async fn test_auto_consolidate() -> anyhow::Result<()> {
+        let mut test = TestWallet::new(1000).await?;
+
+        let (coin_spends, asset_id) = test
+            .wallet
+            .issue_cat(400, 0, None, &CoinSelection::default(), false, true)
+            .await?;
+
+        test.transact(coin_spends).await?;
+        test.wait_for_coins().await;
+
+        let coins = test.wallet.db.spendable_coins().await?;
+        let coin_spends = test.wallet.split_xch(&coins, 3, 0, false, true).await?;
+
+        test.transact(coin_spends).await?;
+        test.wait_for_coins().await;
+
+        let cats = test
+            .wallet
+            .select_cat_coins(asset_id, 400, &CoinSelection::default())
+            .await?;
+        let coin_spends = test
+            .wallet
+            .split_cat(cats, 4, 0, &CoinSelection::default(), false, true)
+            .await?;
+
+        test.transact(coin_spends).await?;
+        test.wait_for_coins().await;
+
+        assert_eq!(test.wallet.db.spendable_coins().await?.len(), 3);
+        assert_eq!(test.wallet.db.spendable_cat_coins(asset_id).await?.len(), 4);
+
+        let coin_spends = test
+            .wallet
+            .auto_consolidate(10, 1000, 100, false, true)
+            .await?;
+
+        test.transact(coin_spends).await?;
+        test.wait_for_coins().await;
+
+        assert_eq!(test.wallet.db.balance().await?, 500);
+        assert_eq!(test.wallet.db.spendable_coins().await?.len(), 1);
+        assert_eq!(test.wallet.db.cat_balance(asset_id).await?, 400);
+        assert_eq!(test.wallet.db.spendable_cat_coins(asset_id).await?.len(), 1);
+
+        // There is only one coin of each asset left, so there is nothing to merge.
+        assert!(matches!(
+            test.wallet.auto_consolidate(10, 1000, 0, false, true).await,
+            Err(WalletError::NothingToConsolidate)
+        ));
+
+        Ok(())
+    }
+}
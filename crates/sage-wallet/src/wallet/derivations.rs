@@ -1,7 +1,7 @@
 use std::ops::Range;
 
 use chia::{
-    bls::DerivableKey,
+    bls::{master_to_wallet_hardened_intermediate, DerivableKey, SecretKey},
     protocol::Bytes32,
     puzzles::{standard::StandardArgs, DeriveSynthetic},
 };
@@ -37,46 +37,97 @@ impl Wallet {
         Ok(puzzle_hashes)
     }
 
-    pub async fn p2_puzzle_hashes(
+    /// Inserts a range of hardened derivations to the database.
+    /// Unlike unhardened keys, these can only be derived from the secret key.
+    pub async fn insert_hardened_derivations(
         &self,
-        count: u32,
-        hardened: bool,
-        reuse: bool,
+        tx: &mut DatabaseTx<'_>,
+        intermediate_sk: &SecretKey,
+        range: Range<u32>,
     ) -> Result<Vec<Bytes32>, WalletError> {
-        let mut tx = self.db.tx().await?;
+        let mut puzzle_hashes = Vec::new();
 
-        let max_used = tx.max_used_derivation_index(hardened).await?;
-        let next_index = tx.derivation_index(hardened).await?;
-
-        let (mut start, mut end) = if reuse {
-            let start = max_used.unwrap_or(0);
-            let end = next_index.min(start + count);
-            (start, end)
-        } else {
-            let start = max_used.map_or(0, |i| i + 1);
-            let end = next_index.min(start + count);
-            (start, end)
-        };
-
-        if end - start < count && reuse {
-            start = start.saturating_sub(count - (end - start));
-        }
+        for index in range {
+            let synthetic_key = intermediate_sk
+                .derive_hardened(index)
+                .derive_synthetic()
+                .public_key();
+
+            let p2_puzzle_hash = StandardArgs::curry_tree_hash(synthetic_key).into();
 
-        if end - start < count {
-            end = next_index.min(end + count - (end - start));
+            tx.insert_derivation(p2_puzzle_hash, index, true, synthetic_key)
+                .await?;
+
+            puzzle_hashes.push(p2_puzzle_hash);
         }
 
-        if end - start < count {
-            return Err(WalletError::InsufficientDerivations);
+        Ok(puzzle_hashes)
+    }
+
+    /// Derives unhardened keys until there are `gap` of them past the last one that has been used.
+    /// Returns the puzzle hashes of the newly derived keys, which still need to be subscribed to.
+    pub async fn derive_unhardened_keys(&self, gap: u32) -> Result<Vec<Bytes32>, WalletError> {
+        let mut tx = self.db.tx().await?;
+
+        let next_index = tx.derivation_index(false).await?;
+        let target_index = tx
+            .max_used_derivation_index(false)
+            .await?
+            .map_or(0, |index| index + 1)
+            + gap;
+
+        if next_index >= target_index {
+            return Ok(Vec::new());
         }
 
-        let mut p2_puzzle_hashes = Vec::new();
+        let puzzle_hashes = self
+            .insert_unhardened_derivations(&mut tx, next_index..target_index)
+            .await?;
+
+        tx.commit().await?;
+
+        Ok(puzzle_hashes)
+    }
+
+    /// Derives hardened keys until there are `gap` of them past the last one that has been used.
+    /// Returns the puzzle hashes of the newly derived keys, which still need to be subscribed to.
+    pub async fn derive_hardened_keys(
+        &self,
+        master_sk: &SecretKey,
+        gap: u32,
+    ) -> Result<Vec<Bytes32>, WalletError> {
+        let mut tx = self.db.tx().await?;
+
+        let next_index = tx.derivation_index(true).await?;
+        let target_index = tx
+            .max_used_derivation_index(true)
+            .await?
+            .map_or(0, |index| index + 1)
+            + gap;
 
-        for index in start..end {
-            let p2_puzzle_hash = tx.p2_puzzle_hash(index, hardened).await?;
-            p2_puzzle_hashes.push(p2_puzzle_hash);
+        if next_index >= target_index {
+            return Ok(Vec::new());
         }
 
+        let intermediate_sk = master_to_wallet_hardened_intermediate(master_sk);
+
+        let puzzle_hashes = self
+            .insert_hardened_derivations(&mut tx, &intermediate_sk, next_index..target_index)
+            .await?;
+
+        tx.commit().await?;
+
+        Ok(puzzle_hashes)
+    }
+
+    pub async fn p2_puzzle_hashes(
+        &self,
+        count: u32,
+        hardened: bool,
+        reuse: bool,
+    ) -> Result<Vec<Bytes32>, WalletError> {
+        let mut tx = self.db.tx().await?;
+        let p2_puzzle_hashes = select_p2_puzzle_hashes(&mut tx, count, hardened, reuse).await?;
         tx.commit().await?;
 
         Ok(p2_puzzle_hashes)
@@ -89,4 +140,173 @@ impl Wallet {
     ) -> Result<Bytes32, WalletError> {
         Ok(self.p2_puzzle_hashes(1, hardened, reuse).await?[0])
     }
+
+    /// Returns the first address that hasn't been used, after skipping the given number of unused
+    /// addresses, optionally with a label. If `mark_used` is set, it's reserved so that the next
+    /// call returns a different address. This happens within the given transaction, so that the
+    /// address can't be handed out twice.
+    pub async fn next_p2_puzzle_hash(
+        &self,
+        tx: &mut DatabaseTx<'_>,
+        hardened: bool,
+        skip: u32,
+        mark_used: bool,
+        label: Option<String>,
+    ) -> Result<Bytes32, WalletError> {
+        let p2_puzzle_hash =
+            select_p2_puzzle_hashes(tx, skip + 1, hardened, false).await?[skip as usize];
+
+        if mark_used {
+            tx.reserve_derivation(p2_puzzle_hash).await?;
+        }
+
+        if label.is_some() {
+            tx.set_derivation_label(p2_puzzle_hash, label).await?;
+        }
+
+        Ok(p2_puzzle_hash)
+    }
+}
+
+async fn select_p2_puzzle_hashes(
+    tx: &mut DatabaseTx<'_>,
+    count: u32,
+    hardened: bool,
+    reuse: bool,
+) -> Result<Vec<Bytes32>, WalletError> {
+    let max_used = tx.max_used_derivation_index(hardened).await?;
+    let max_reusable = tx.max_reusable_derivation_index(hardened).await?;
+    let next_index = tx.derivation_index(hardened).await?;
+
+    let (mut start, mut end) = if reuse {
+        let start = max_reusable.unwrap_or(0);
+        let end = next_index.min(start + count);
+        (start, end)
+    } else {
+        let start = max_used.map_or(0, |i| i + 1);
+        let end = next_index.min(start + count);
+        (start, end)
+    };
+
+    if end - start < count && reuse {
+        start = start.saturating_sub(count - (end - start));
+    }
+
+    if end - start < count {
+        end = next_index.min(end + count - (end - start));
+    }
+
+    if end - start < count {
+        return Err(WalletError::InsufficientDerivations);
+    }
+
+    let mut p2_puzzle_hashes = Vec::new();
+    let mut index = start;
+
+    while p2_puzzle_hashes.len() < count as usize {
+        if index >= next_index {
+            return Err(WalletError::InsufficientDerivations);
+        }
+
+        let p2_puzzle_hash = tx.p2_puzzle_hash(index, hardened).await?;
+        index += 1;
+
+        // Addresses reserved for an invoice can only be reached when reusing, and are skipped
+        // so that unrelated coins such as change don't count towards the payment.
+        if reuse
+            && tx
+                .invoice_by_p2_puzzle_hash(p2_puzzle_hash)
+                .await?
+                .is_some()
+        {
+            continue;
+        }
+
+        p2_puzzle_hashes.push(p2_puzzle_hash);
+    }
+
+    Ok(p2_puzzle_hashes)
+}
+
+#[cfg(test)]
+mod tests {
+    use test_log::test;
+
+    use crate::TestWallet;
+
+    #[test(tokio::test)]
+    async fn test_next_p2_puzzle_hash() -> anyhow::Result<()> {
+        let test = TestWallet::new(0).await?;
+
+        let mut tx = test.wallet.db.tx().await?;
+        let first = test
+            .wallet
+            .next_p2_puzzle_hash(&mut tx, false, 0, false, None)
+            .await?;
+        assert_eq!(
+            test.wallet
+                .next_p2_puzzle_hash(&mut tx, false, 0, false, None)
+                .await?,
+            first
+        );
+
+        let reserved = test
+            .wallet
+            .next_p2_puzzle_hash(&mut tx, false, 0, true, Some("Savings".to_string()))
+            .await?;
+        assert_eq!(reserved, first);
+
+        // The reservation is visible within the same transaction.
+        let next = test
+            .wallet
+            .next_p2_puzzle_hash(&mut tx, false, 0, true, None)
+            .await?;
+        assert_ne!(next, reserved);
+        tx.commit().await?;
+
+        assert_eq!(
+            test.wallet.db.derivation_label(reserved).await?.as_deref(),
+            Some("Savings")
+        );
+        assert_eq!(test.wallet.db.derivation_label(next).await?, None);
+
+        // Nothing is reserved if the transaction is rolled back.
+        let mut tx = test.wallet.db.tx().await?;
+        let rolled_back = test
+            .wallet
+            .next_p2_puzzle_hash(&mut tx, false, 0, true, None)
+            .await?;
+        drop(tx);
+
+        let mut tx = test.wallet.db.tx().await?;
+        assert_eq!(
+            test.wallet
+                .next_p2_puzzle_hash(&mut tx, false, 0, false, None)
+                .await?,
+            rolled_back
+        );
+
+        // Skipping the next address reserves the one after it instead.
+        let skipped = test
+            .wallet
+            .next_p2_puzzle_hash(&mut tx, false, 1, true, None)
+            .await?;
+        assert_ne!(skipped, rolled_back);
+        tx.commit().await?;
+
+        assert_eq!(
+            test.wallet
+                .db
+                .derivation(skipped)
+                .await?
+                .map(|row| row.index),
+            test.wallet
+                .db
+                .derivation(rolled_back)
+                .await?
+                .map(|row| row.index + 1)
+        );
+
+        Ok(())
+    }
 }
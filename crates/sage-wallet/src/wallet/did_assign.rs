@@ -3,7 +3,7 @@ use chia_wallet_sdk::{Conditions, DidOwner, HashedPtr, SpendContext, StandardLay
 
 use crate::WalletError;
 
-use super::Wallet;
+use super::{CoinSelection, Wallet};
 
 impl Wallet {
     pub async fn assign_nfts(
@@ -11,6 +11,7 @@ impl Wallet {
         nft_ids: Vec<Bytes32>,
         did_id: Option<Bytes32>,
         fee: u64,
+        selection: &CoinSelection,
         hardened: bool,
         reuse: bool,
     ) -> Result<Vec<CoinSpend>, WalletError> {
@@ -41,7 +42,7 @@ impl Wallet {
         };
 
         let coins = if fee > 0 {
-            self.select_p2_coins(fee as u128).await?
+            self.select_p2_coins(fee as u128, selection).await?
         } else {
             Vec::new()
         };
@@ -140,7 +141,10 @@ mod tests {
     async fn test_assign_nft() -> anyhow::Result<()> {
         let mut test = TestWallet::new(2).await?;
 
-        let (coin_spends, did) = test.wallet.create_did(0, false, true).await?;
+        let (coin_spends, did) = test
+            .wallet
+            .create_did(0, &CoinSelection::default(), false, true)
+            .await?;
         test.transact(coin_spends).await?;
         test.wait_for_coins().await;
 
@@ -154,6 +158,7 @@ mod tests {
                     royalty_puzzle_hash: Some(Bytes32::default()),
                     royalty_ten_thousandths: 300,
                 }],
+                &CoinSelection::default(),
                 false,
                 true,
             )
@@ -165,7 +170,14 @@ mod tests {
 
         let coin_spends = test
             .wallet
-            .assign_nfts(vec![nft.info.launcher_id], None, 0, false, true)
+            .assign_nfts(
+                vec![nft.info.launcher_id],
+                None,
+                0,
+                &CoinSelection::default(),
+                false,
+                true,
+            )
             .await?;
         test.transact(coin_spends).await?;
         test.wait_for_coins().await;
@@ -176,6 +188,7 @@ mod tests {
                 vec![nft.info.launcher_id],
                 Some(did.info.launcher_id),
                 0,
+                &CoinSelection::default(),
                 false,
                 true,
             )
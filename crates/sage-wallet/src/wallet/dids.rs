@@ -3,17 +3,18 @@ use chia_wallet_sdk::{Conditions, Did, HashedPtr, Launcher, SpendContext, Standa
 
 use crate::WalletError;
 
-use super::Wallet;
+use super::{CoinSelection, Wallet};
 
 impl Wallet {
     pub async fn create_did(
         &self,
         fee: u64,
+        selection: &CoinSelection,
         hardened: bool,
         reuse: bool,
     ) -> Result<(Vec<CoinSpend>, Did<()>), WalletError> {
         let total_amount = fee as u128 + 1;
-        let coins = self.select_p2_coins(total_amount).await?;
+        let coins = self.select_p2_coins(total_amount, selection).await?;
         let selected: u128 = coins.iter().map(|coin| coin.amount as u128).sum();
 
         let change: u64 = (selected - total_amount)
@@ -47,6 +48,7 @@ impl Wallet {
         did_ids: Vec<Bytes32>,
         puzzle_hash: Bytes32,
         fee: u64,
+        selection: &CoinSelection,
         hardened: bool,
         reuse: bool,
     ) -> Result<Vec<CoinSpend>, WalletError> {
@@ -65,7 +67,7 @@ impl Wallet {
         }
 
         let coins = if fee > 0 {
-            self.select_p2_coins(fee as u128).await?
+            self.select_p2_coins(fee as u128, selection).await?
         } else {
             Vec::new()
         };
@@ -124,7 +126,7 @@ impl Wallet {
 
 #[cfg(test)]
 mod tests {
-    use crate::TestWallet;
+    use crate::{CoinSelection, TestWallet};
 
     use test_log::test;
 
@@ -132,14 +134,24 @@ mod tests {
     async fn test_create_did() -> anyhow::Result<()> {
         let mut test = TestWallet::new(1).await?;
 
-        let (coin_spends, did) = test.wallet.create_did(0, false, true).await?;
+        let (coin_spends, did) = test
+            .wallet
+            .create_did(0, &CoinSelection::default(), false, true)
+            .await?;
         test.transact(coin_spends).await?;
         test.wait_for_coins().await;
 
         for _ in 0..2 {
             let coin_spends = test
                 .wallet
-                .transfer_dids(vec![did.info.launcher_id], test.puzzle_hash, 0, false, true)
+                .transfer_dids(
+                    vec![did.info.launcher_id],
+                    test.puzzle_hash,
+                    0,
+                    &CoinSelection::default(),
+                    false,
+                    true,
+                )
                 .await?;
             test.transact(coin_spends).await?;
             test.wait_for_coins().await;
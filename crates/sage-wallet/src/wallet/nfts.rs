@@ -9,7 +9,7 @@ use chia_wallet_sdk::{
 
 use crate::WalletError;
 
-use super::Wallet;
+use super::{CoinSelection, Wallet};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct WalletNftMint {
@@ -24,6 +24,7 @@ impl Wallet {
         fee: u64,
         did_id: Bytes32,
         mints: Vec<WalletNftMint>,
+        selection: &CoinSelection,
         hardened: bool,
         reuse: bool,
     ) -> Result<(Vec<CoinSpend>, Vec<Nft<NftMetadata>>, Did<Program>), WalletError> {
@@ -32,7 +33,7 @@ impl Wallet {
         };
 
         let total_amount = fee as u128 + mints.len() as u128;
-        let coins = self.select_p2_coins(total_amount).await?;
+        let coins = self.select_p2_coins(total_amount, selection).await?;
         let selected: u128 = coins.iter().map(|coin| coin.amount as u128).sum();
 
         let change: u64 = (selected - total_amount)
@@ -94,6 +95,7 @@ impl Wallet {
         nft_ids: Vec<Bytes32>,
         puzzle_hash: Bytes32,
         fee: u64,
+        selection: &CoinSelection,
         hardened: bool,
         reuse: bool,
     ) -> Result<Vec<CoinSpend>, WalletError> {
@@ -112,7 +114,7 @@ impl Wallet {
         }
 
         let coins = if fee > 0 {
-            self.select_p2_coins(fee as u128).await?
+            self.select_p2_coins(fee as u128, selection).await?
         } else {
             Vec::new()
         };
@@ -173,6 +175,7 @@ impl Wallet {
         nft_id: Bytes32,
         fee: u64,
         uri: MetadataUpdate,
+        selection: &CoinSelection,
         hardened: bool,
         reuse: bool,
     ) -> Result<(Vec<CoinSpend>, Nft<Program>), WalletError> {
@@ -181,7 +184,7 @@ impl Wallet {
         };
 
         let coins = if fee > 0 {
-            self.select_p2_coins(fee as u128).await?
+            self.select_p2_coins(fee as u128, selection).await?
         } else {
             Vec::new()
         };
@@ -230,6 +233,7 @@ impl Wallet {
 
 #[cfg(test)]
 mod tests {
+    use sage_database::{NftAttribute, NftSearchFilter};
     use test_log::test;
 
     use crate::TestWallet;
@@ -240,7 +244,10 @@ mod tests {
     async fn test_mint_nft() -> anyhow::Result<()> {
         let mut test = TestWallet::new(2).await?;
 
-        let (coin_spends, did) = test.wallet.create_did(0, false, true).await?;
+        let (coin_spends, did) = test
+            .wallet
+            .create_did(0, &CoinSelection::default(), false, true)
+            .await?;
         test.transact(coin_spends).await?;
         test.wait_for_coins().await;
 
@@ -254,6 +261,7 @@ mod tests {
                     royalty_puzzle_hash: Some(Bytes32::default()),
                     royalty_ten_thousandths: 300,
                 }],
+                &CoinSelection::default(),
                 false,
                 true,
             )
@@ -272,7 +280,14 @@ mod tests {
         ] {
             let (coin_spends, _nft) = test
                 .wallet
-                .add_nft_uri(nft.info.launcher_id, 0, item, false, true)
+                .add_nft_uri(
+                    nft.info.launcher_id,
+                    0,
+                    item,
+                    &CoinSelection::default(),
+                    false,
+                    true,
+                )
                 .await?;
             test.transact(coin_spends).await?;
             test.wait_for_coins().await;
@@ -281,7 +296,14 @@ mod tests {
         for _ in 0..2 {
             let coin_spends = test
                 .wallet
-                .transfer_nfts(vec![nft.info.launcher_id], puzzle_hash, 0, false, true)
+                .transfer_nfts(
+                    vec![nft.info.launcher_id],
+                    puzzle_hash,
+                    0,
+                    &CoinSelection::default(),
+                    false,
+                    true,
+                )
                 .await?;
             test.transact(coin_spends).await?;
             test.wait_for_coins().await;
@@ -289,4 +311,82 @@ mod tests {
 
         Ok(())
     }
+
+    #[test(tokio::test)]
+    async fn test_nft_search() -> anyhow::Result<()> {
+        let mut test = TestWallet::new(2).await?;
+
+        let (coin_spends, did) = test
+            .wallet
+            .create_did(0, &CoinSelection::default(), false, true)
+            .await?;
+        test.transact(coin_spends).await?;
+        test.wait_for_coins().await;
+
+        let (coin_spends, mut nfts, _did) = test
+            .wallet
+            .bulk_mint_nfts(
+                0,
+                did.info.launcher_id,
+                vec![WalletNftMint {
+                    metadata: NftMetadata::default(),
+                    royalty_puzzle_hash: Some(Bytes32::default()),
+                    royalty_ten_thousandths: 300,
+                }],
+                &CoinSelection::default(),
+                false,
+                true,
+            )
+            .await?;
+        test.transact(coin_spends).await?;
+        test.wait_for_coins().await;
+
+        let launcher_id = nfts.remove(0).info.launcher_id;
+
+        let mut row = test
+            .wallet
+            .db
+            .nft_row(launcher_id)
+            .await?
+            .expect("missing nft");
+        row.name = Some("Cool Dragon #1".to_string());
+
+        let mut tx = test.wallet.db.tx().await?;
+        tx.insert_nft(row.clone()).await?;
+        tx.set_nft_attributes(
+            launcher_id,
+            vec![NftAttribute {
+                trait_type: "Color".to_string(),
+                value: "Red".to_string(),
+            }],
+        )
+        .await?;
+        // Replacing the row again shouldn't affect the search index or attributes.
+        tx.insert_nft(row).await?;
+        tx.commit().await?;
+
+        for (name, trait_value, expected) in [
+            (Some("drag"), None, 1),
+            (Some("cool dragon"), Some("Red"), 1),
+            (Some("cat"), None, 0),
+            (None, Some("Red"), 1),
+            (None, Some("Blue"), 0),
+        ] {
+            let filter = NftSearchFilter {
+                name: name.map(ToString::to_string),
+                trait_type: Some("Color".to_string()),
+                trait_value: trait_value.map(ToString::to_string),
+                ..Default::default()
+            };
+            let nfts = test.wallet.db.search_nfts_named(&filter, 10, 0).await?;
+            assert_eq!(nfts.len(), expected);
+        }
+
+        let counts = test.wallet.db.nft_trait_counts(None).await?;
+        assert_eq!(counts.len(), 1);
+        assert_eq!(counts[0].count, 1);
+        assert_eq!(test.wallet.db.owned_nft_count(None).await?, 1);
+
+        Ok(())
+    }
 }
@@ -1,3 +1,4 @@
+mod cancel_offer;
 mod lock_assets;
 mod make_offer;
 mod offer_coins;
@@ -18,14 +19,18 @@ pub use unlock_assets::*;
 mod tests {
     use chia::{
         clvm_traits::{FromClvm, ToClvm},
-        protocol::{Bytes32, Program},
+        protocol::{Bytes32, Program, SpendBundle},
         puzzles::nft::NftMetadata,
     };
     use clvmr::Allocator;
     use indexmap::{indexmap, IndexMap};
+    use sage_database::{OfferRow, OfferStatus};
     use test_log::test;
 
-    use crate::{MakerSide, RequestedNft, TakerSide, TestWallet, WalletNftMint};
+    use crate::{
+        parse_locked_coins, CoinSelection, MakerSide, RequestedNft, SyncEvent, TakerSide,
+        TestWallet, WalletNftMint,
+    };
 
     #[test(tokio::test)]
     async fn test_offer_xch_for_cat() -> anyhow::Result<()> {
@@ -33,7 +38,10 @@ mod tests {
         let mut bob = alice.next(1000).await?;
 
         // Issue CAT
-        let (coin_spends, asset_id) = bob.wallet.issue_cat(1000, 0, None, false, true).await?;
+        let (coin_spends, asset_id) = bob
+            .wallet
+            .issue_cat(1000, 0, None, &CoinSelection::default(), false, true)
+            .await?;
         bob.transact(coin_spends).await?;
         bob.wait_for_coins().await;
 
@@ -53,6 +61,7 @@ mod tests {
                     nfts: IndexMap::new(),
                 },
                 None,
+                &CoinSelection::default(),
                 false,
                 true,
             )
@@ -63,7 +72,10 @@ mod tests {
             .await?;
 
         // Take offer
-        let offer = bob.wallet.take_offer(offer, 0, false, true).await?;
+        let offer = bob
+            .wallet
+            .take_offer(offer, 0, &CoinSelection::default(), false, true)
+            .await?;
         let spend_bundle = bob
             .wallet
             .sign_take_offer(offer, &bob.agg_sig, bob.master_sk.clone())
@@ -86,7 +98,10 @@ mod tests {
         let mut alice = TestWallet::new(1030).await?;
         let mut bob = alice.next(2).await?;
 
-        let (coin_spends, did) = bob.wallet.create_did(0, false, true).await?;
+        let (coin_spends, did) = bob
+            .wallet
+            .create_did(0, &CoinSelection::default(), false, true)
+            .await?;
         bob.transact(coin_spends).await?;
         bob.wait_for_coins().await;
 
@@ -100,6 +115,7 @@ mod tests {
                     royalty_puzzle_hash: Some(Bytes32::default()),
                     royalty_ten_thousandths: 300,
                 }],
+                &CoinSelection::default(),
                 false,
                 true,
             )
@@ -136,6 +152,7 @@ mod tests {
                     },
                 },
                 None,
+                &CoinSelection::default(),
                 false,
                 true,
             )
@@ -146,7 +163,10 @@ mod tests {
             .await?;
 
         // Take offer
-        let offer = bob.wallet.take_offer(offer, 0, false, true).await?;
+        let offer = bob
+            .wallet
+            .take_offer(offer, 0, &CoinSelection::default(), false, true)
+            .await?;
         let spend_bundle = bob
             .wallet
             .sign_take_offer(offer, &bob.agg_sig, bob.master_sk.clone())
@@ -172,7 +192,10 @@ mod tests {
         let mut alice = TestWallet::new(2).await?;
         let mut bob = alice.next(1030).await?;
 
-        let (coin_spends, did) = alice.wallet.create_did(0, false, true).await?;
+        let (coin_spends, did) = alice
+            .wallet
+            .create_did(0, &CoinSelection::default(), false, true)
+            .await?;
         alice.transact(coin_spends).await?;
         alice.wait_for_coins().await;
 
@@ -186,6 +209,7 @@ mod tests {
                     royalty_puzzle_hash: Some(Bytes32::default()),
                     royalty_ten_thousandths: 300,
                 }],
+                &CoinSelection::default(),
                 false,
                 true,
             )
@@ -211,6 +235,7 @@ mod tests {
                     nfts: IndexMap::new(),
                 },
                 None,
+                &CoinSelection::default(),
                 false,
                 true,
             )
@@ -221,7 +246,10 @@ mod tests {
             .await?;
 
         // Take offer
-        let offer = bob.wallet.take_offer(offer, 0, false, true).await?;
+        let offer = bob
+            .wallet
+            .take_offer(offer, 0, &CoinSelection::default(), false, true)
+            .await?;
         let spend_bundle = bob
             .wallet
             .sign_take_offer(offer, &bob.agg_sig, bob.master_sk.clone())
@@ -247,7 +275,10 @@ mod tests {
         let mut alice = TestWallet::new(3).await?;
         let mut bob = alice.next(1030).await?;
 
-        let (coin_spends, did) = alice.wallet.create_did(0, false, true).await?;
+        let (coin_spends, did) = alice
+            .wallet
+            .create_did(0, &CoinSelection::default(), false, true)
+            .await?;
         alice.transact(coin_spends).await?;
         alice.wait_for_coins().await;
 
@@ -268,6 +299,7 @@ mod tests {
                         royalty_ten_thousandths: 300,
                     },
                 ],
+                &CoinSelection::default(),
                 false,
                 true,
             )
@@ -297,6 +329,7 @@ mod tests {
                     nfts: IndexMap::new(),
                 },
                 None,
+                &CoinSelection::default(),
                 false,
                 true,
             )
@@ -307,7 +340,10 @@ mod tests {
             .await?;
 
         // Take offer
-        let offer = bob.wallet.take_offer(offer, 0, false, true).await?;
+        let offer = bob
+            .wallet
+            .take_offer(offer, 0, &CoinSelection::default(), false, true)
+            .await?;
         let spend_bundle = bob
             .wallet
             .sign_take_offer(offer, &bob.agg_sig, bob.master_sk.clone())
@@ -343,7 +379,10 @@ mod tests {
         let mut alice = TestWallet::new(3).await?;
         let mut bob = alice.next(1030).await?;
 
-        let (coin_spends, did) = alice.wallet.create_did(0, false, true).await?;
+        let (coin_spends, did) = alice
+            .wallet
+            .create_did(0, &CoinSelection::default(), false, true)
+            .await?;
         alice.transact(coin_spends).await?;
         alice.wait_for_coins().await;
 
@@ -364,6 +403,7 @@ mod tests {
                         royalty_ten_thousandths: 300,
                     },
                 ],
+                &CoinSelection::default(),
                 false,
                 true,
             )
@@ -375,7 +415,10 @@ mod tests {
         let nft_id_second = nfts.remove(0);
 
         // Issue CAT
-        let (coin_spends, asset_id) = bob.wallet.issue_cat(1030, 0, None, false, true).await?;
+        let (coin_spends, asset_id) = bob
+            .wallet
+            .issue_cat(1030, 0, None, &CoinSelection::default(), false, true)
+            .await?;
         bob.transact(coin_spends).await?;
         bob.wait_for_coins().await;
 
@@ -398,6 +441,7 @@ mod tests {
                     nfts: IndexMap::new(),
                 },
                 None,
+                &CoinSelection::default(),
                 false,
                 true,
             )
@@ -408,7 +452,10 @@ mod tests {
             .await?;
 
         // Take offer
-        let offer = bob.wallet.take_offer(offer, 0, false, true).await?;
+        let offer = bob
+            .wallet
+            .take_offer(offer, 0, &CoinSelection::default(), false, true)
+            .await?;
         let spend_bundle = bob
             .wallet
             .sign_take_offer(offer, &bob.agg_sig, bob.master_sk.clone())
@@ -444,7 +491,10 @@ mod tests {
         let mut alice = TestWallet::new(3).await?;
         let mut bob = alice.next(1030).await?;
 
-        let (coin_spends, did) = alice.wallet.create_did(0, false, true).await?;
+        let (coin_spends, did) = alice
+            .wallet
+            .create_did(0, &CoinSelection::default(), false, true)
+            .await?;
         alice.transact(coin_spends).await?;
         alice.wait_for_coins().await;
 
@@ -465,6 +515,7 @@ mod tests {
                         royalty_ten_thousandths: 0,
                     },
                 ],
+                &CoinSelection::default(),
                 false,
                 true,
             )
@@ -494,6 +545,7 @@ mod tests {
                     nfts: IndexMap::new(),
                 },
                 None,
+                &CoinSelection::default(),
                 false,
                 true,
             )
@@ -504,7 +556,10 @@ mod tests {
             .await?;
 
         // Take offer
-        let offer = bob.wallet.take_offer(offer, 0, false, true).await?;
+        let offer = bob
+            .wallet
+            .take_offer(offer, 0, &CoinSelection::default(), false, true)
+            .await?;
         let spend_bundle = bob
             .wallet
             .sign_take_offer(offer, &bob.agg_sig, bob.master_sk.clone())
@@ -534,4 +589,98 @@ mod tests {
 
         Ok(())
     }
+
+    #[test(tokio::test)]
+    async fn test_cancel_offer() -> anyhow::Result<()> {
+        let mut alice = TestWallet::new(1000).await?;
+
+        // Create offer
+        let offer = alice
+            .wallet
+            .make_offer(
+                MakerSide {
+                    xch: 750,
+                    cats: IndexMap::new(),
+                    nfts: Vec::new(),
+                    fee: 0,
+                },
+                TakerSide {
+                    xch: 1000,
+                    cats: IndexMap::new(),
+                    nfts: IndexMap::new(),
+                },
+                None,
+                &CoinSelection::default(),
+                false,
+                true,
+            )
+            .await?;
+        let offer = alice
+            .wallet
+            .sign_make_offer(offer, &alice.agg_sig, alice.master_sk.clone())
+            .await?;
+
+        // Store the offer and its locked coins
+        let encoded_offer = offer.encode()?;
+        let offer_id = SpendBundle::from(offer.clone()).name();
+
+        let mut allocator = Allocator::new();
+        let parsed_offer = offer.parse(&mut allocator)?;
+        let (_locked, coin_ids) = parse_locked_coins(&mut allocator, &parsed_offer)?;
+
+        let mut tx = alice.wallet.db.tx().await?;
+
+        tx.insert_offer(OfferRow {
+            offer_id,
+            encoded_offer,
+            expiration_height: None,
+            expiration_timestamp: None,
+            fee: 0,
+            status: OfferStatus::Active,
+            inserted_timestamp: 0,
+            cancelling: false,
+        })
+        .await?;
+
+        for coin_id in coin_ids {
+            tx.insert_offered_coin(offer_id, coin_id).await?;
+        }
+
+        tx.commit().await?;
+
+        assert_eq!(alice.wallet.cancellable_offers().await?, vec![offer_id]);
+
+        // Cancel offer
+        let coin_spends = alice
+            .wallet
+            .cancel_offer(offer_id, 50, &CoinSelection::default(), false, true)
+            .await?;
+
+        // The offer is only marked as cancelling once the cancellation is submitted
+        let is_cancelling = |offer: Option<OfferRow>| offer.is_some_and(|offer| offer.cancelling);
+        assert!(!is_cancelling(alice.wallet.db.get_offer(offer_id).await?));
+
+        alice.transact(coin_spends).await?;
+        assert!(is_cancelling(alice.wallet.db.get_offer(offer_id).await?));
+        alice.wait_for_coins().await;
+
+        assert_eq!(alice.wallet.db.balance().await?, 950);
+
+        // The offer queue should notice the coins were spent by the cancellation
+        alice
+            .consume_until(|event| {
+                matches!(
+                    event,
+                    SyncEvent::OfferUpdated {
+                        status: OfferStatus::Cancelled,
+                        ..
+                    }
+                )
+            })
+            .await;
+
+        assert!(alice.wallet.cancellable_offers().await?.is_empty());
+
+        Ok(())
+    }
 }
@@ -0,0 +1,239 @@
+use std::mem;
+
+use chia::protocol::{Bytes32, CoinSpend};
+use chia_wallet_sdk::{Conditions, HashedPtr, SpendContext, StandardLayer};
+use indexmap::IndexMap;
+
+use crate::{CoinSelection, Wallet, WalletError};
+
+use super::OfferCoins;
+
+impl Wallet {
+    /// Spends the coins locked by an offer back to the wallet, which invalidates the offer.
+    pub async fn cancel_offer(
+        &self,
+        offer_id: Bytes32,
+        fee: u64,
+        selection: &CoinSelection,
+        hardened: bool,
+        reuse: bool,
+    ) -> Result<Vec<CoinSpend>, WalletError> {
+        self.cancel_offers(vec![offer_id], fee, selection, hardened, reuse)
+            .await
+    }
+
+    /// Cancels multiple offers in a single transaction, so the fee only needs to be paid once.
+    pub async fn cancel_offers(
+        &self,
+        offer_ids: Vec<Bytes32>,
+        fee: u64,
+        selection: &CoinSelection,
+        hardened: bool,
+        reuse: bool,
+    ) -> Result<Vec<CoinSpend>, WalletError> {
+        if offer_ids.is_empty() {
+            return Err(WalletError::EmptyBulkTransfer);
+        }
+
+        let mut coins = OfferCoins {
+            xch: Vec::new(),
+            cats: IndexMap::new(),
+            nfts: IndexMap::new(),
+            dids: IndexMap::new(),
+        };
+
+        for offer_id in offer_ids {
+            let Some(offer_coins) = self.cancellable_offer_coins(offer_id).await? else {
+                return Err(WalletError::UncancellableOffer(offer_id));
+            };
+
+            coins.xch.extend(offer_coins.xch);
+
+            for (asset_id, cats) in offer_coins.cats {
+                coins.cats.entry(asset_id).or_default().extend(cats);
+            }
+
+            coins.nfts.extend(offer_coins.nfts);
+            coins.dids.extend(offer_coins.dids);
+        }
+
+        self.reclaim_coins(coins, fee, selection, hardened, reuse)
+            .await
+    }
+
+    /// Spends the coins back to the wallet, paying the fee from the XCH among them.
+    /// If that doesn't cover the fee, additional coins are selected from the wallet.
+    pub(crate) async fn reclaim_coins(
+        &self,
+        mut coins: OfferCoins,
+        fee: u64,
+        selection: &CoinSelection,
+        hardened: bool,
+        reuse: bool,
+    ) -> Result<Vec<CoinSpend>, WalletError> {
+        let offered_xch: u128 = coins.xch.iter().map(|coin| coin.amount as u128).sum();
+
+        if fee as u128 > offered_xch {
+            coins.xch.extend(
+                self.select_p2_coins(fee as u128 - offered_xch, selection)
+                    .await?,
+            );
+        }
+
+        let p2_puzzle_hash = self.p2_puzzle_hash(hardened, reuse).await?;
+
+        // Each primary coin asserts that the previous one is spent, so the spends can't be separated.
+        let primary_coins = coins.primary_coin_ids();
+
+        let mut primary_conditions = (0..primary_coins.len())
+            .map(|i| {
+                if primary_coins.len() == 1 {
+                    Conditions::new()
+                } else {
+                    let previous = (i + primary_coins.len() - 1) % primary_coins.len();
+                    Conditions::new().assert_concurrent_spend(primary_coins[previous])
+                }
+            })
+            .collect::<Vec<_>>()
+            .into_iter();
+
+        let mut ctx = SpendContext::new();
+
+        // Spend the XCH, which pays the fee.
+        if !coins.xch.is_empty() {
+            let total: u128 = coins.xch.iter().map(|coin| coin.amount as u128).sum();
+
+            let change: u64 = (total - fee as u128)
+                .try_into()
+                .expect("change amount overflow");
+
+            let mut conditions = primary_conditions.next().unwrap_or_default();
+
+            if fee > 0 {
+                conditions = conditions.reserve_fee(fee);
+            }
+
+            if change > 0 {
+                conditions = conditions.create_coin(p2_puzzle_hash, change, None);
+            }
+
+            self.spend_p2_coins(&mut ctx, coins.xch, conditions).await?;
+        }
+
+        // Spend the CATs.
+        for cats in coins.cats.into_values() {
+            let total: u64 = cats
+                .iter()
+                .map(|cat| cat.coin.amount as u128)
+                .sum::<u128>()
+                .try_into()
+                .expect("output amount overflow");
+
+            let hint = ctx.hint(p2_puzzle_hash)?;
+
+            let mut conditions = primary_conditions.next().unwrap_or_default().create_coin(
+                p2_puzzle_hash,
+                total,
+                Some(hint),
+            );
+
+            self.spend_cat_coins(
+                &mut ctx,
+                cats.into_iter()
+                    .map(|cat| (cat, mem::take(&mut conditions))),
+            )
+            .await?;
+        }
+
+        // Spend the NFTs.
+        for nft in coins.nfts.into_values() {
+            let metadata_ptr = ctx.alloc(&nft.info.metadata)?;
+            let nft = nft.with_metadata(HashedPtr::from_ptr(&ctx.allocator, metadata_ptr));
+
+            let synthetic_key = self.db.synthetic_key(nft.info.p2_puzzle_hash).await?;
+            let p2 = StandardLayer::new(synthetic_key);
+
+            let conditions = primary_conditions.next().unwrap_or_default();
+
+            let _nft = nft.transfer(&mut ctx, &p2, p2_puzzle_hash, conditions)?;
+        }
+
+        // Spend the DIDs.
+        for did in coins.dids.into_values() {
+            let metadata_ptr = ctx.alloc(&did.info.metadata)?;
+            let did = did.with_metadata(HashedPtr::from_ptr(&ctx.allocator, metadata_ptr));
+
+            let synthetic_key = self.db.synthetic_key(did.info.p2_puzzle_hash).await?;
+            let p2 = StandardLayer::new(synthetic_key);
+
+            let conditions = primary_conditions.next().unwrap_or_default();
+
+            let _did = did.transfer(&mut ctx, &p2, p2_puzzle_hash, conditions)?;
+        }
+
+        Ok(ctx.take())
+    }
+
+    /// Returns the ids of active offers whose locked coins are all unspent and owned by the wallet.
+    pub async fn cancellable_offers(&self) -> Result<Vec<Bytes32>, WalletError> {
+        let mut offer_ids = Vec::new();
+
+        for offer in self.db.active_offers().await? {
+            if self
+                .cancellable_offer_coins(offer.offer_id)
+                .await?
+                .is_some()
+            {
+                offer_ids.push(offer.offer_id);
+            }
+        }
+
+        Ok(offer_ids)
+    }
+
+    async fn cancellable_offer_coins(
+        &self,
+        offer_id: Bytes32,
+    ) -> Result<Option<OfferCoins>, WalletError> {
+        let coin_ids = self.db.offer_coin_ids(offer_id).await?;
+
+        if coin_ids.is_empty() {
+            return Ok(None);
+        }
+
+        let mut coins = OfferCoins {
+            xch: Vec::new(),
+            cats: IndexMap::new(),
+            nfts: IndexMap::new(),
+            dids: IndexMap::new(),
+        };
+
+        for coin_id in coin_ids {
+            // Imported offers made by someone else will reference coins we don't have.
+            let Some(coin_state) = self.db.coin_state(coin_id).await? else {
+                return Ok(None);
+            };
+
+            // If any of the coins have already been spent, the offer can no longer be taken.
+            if coin_state.spent_height.is_some() {
+                return Ok(None);
+            }
+
+            if let Some(cat) = self.db.cat_coin(coin_id).await? {
+                coins.cats.entry(cat.asset_id).or_default().push(cat);
+            } else if let Some(nft) = self.db.nft_by_coin_id(coin_id).await? {
+                coins.nfts.insert(nft.info.launcher_id, nft);
+            } else if self
+                .db
+                .is_p2_puzzle_hash(coin_state.coin.puzzle_hash)
+                .await?
+            {
+                coins.xch.push(coin_state.coin);
+            } else {
+                return Ok(None);
+            }
+        }
+
+        Ok(Some(coins))
+    }
+}
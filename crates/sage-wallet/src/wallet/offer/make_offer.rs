@@ -8,7 +8,7 @@ use chia::{
 use chia_wallet_sdk::{Conditions, Layer, NftInfo, OfferBuilder, Partial, SpendContext};
 use indexmap::IndexMap;
 
-use crate::{Wallet, WalletError};
+use crate::{CoinSelection, Wallet, WalletError};
 
 use super::{
     calculate_royalties, calculate_trade_prices, lock_assets::OfferSpend, NftRoyaltyInfo,
@@ -51,6 +51,7 @@ impl Wallet {
         maker: MakerSide,
         taker: TakerSide,
         expires_at: Option<u64>,
+        selection: &CoinSelection,
         hardened: bool,
         reuse: bool,
     ) -> Result<UnsignedMakeOffer, WalletError> {
@@ -79,7 +80,7 @@ impl Wallet {
                 cats: IndexMap::new(),
             };
         let maker_coins = self
-            .fetch_offer_coins(&total_amounts, maker.nfts.clone())
+            .fetch_offer_coins(&total_amounts, maker.nfts.clone(), selection)
             .await?;
         let p2_puzzle_hash = self.p2_puzzle_hash(hardened, reuse).await?;
 
@@ -1,10 +1,10 @@
 use std::ops::Add;
 
 use chia::protocol::{Bytes32, Coin, Program};
-use chia_wallet_sdk::{Cat, Nft, Offer};
+use chia_wallet_sdk::{Cat, Did, Nft, Offer};
 use indexmap::IndexMap;
 
-use crate::{Wallet, WalletError};
+use crate::{CoinSelection, Wallet, WalletError};
 
 #[derive(Debug, Default, Clone)]
 pub struct OfferAmounts {
@@ -34,6 +34,7 @@ pub struct OfferCoins {
     pub xch: Vec<Coin>,
     pub cats: IndexMap<Bytes32, Vec<Cat>>,
     pub nfts: IndexMap<Bytes32, Nft<Program>>,
+    pub dids: IndexMap<Bytes32, Did<Program>>,
 }
 
 impl OfferCoins {
@@ -54,6 +55,10 @@ impl OfferCoins {
             coin_ids.push(nft.coin.coin_id());
         }
 
+        for did in self.dids.values() {
+            coin_ids.push(did.coin.coin_id());
+        }
+
         Offer::nonce(coin_ids)
     }
 
@@ -74,6 +79,10 @@ impl OfferCoins {
             primary_coins.push(nft.coin.coin_id());
         }
 
+        for did in self.dids.values() {
+            primary_coins.push(did.coin.coin_id());
+        }
+
         primary_coins
     }
 }
@@ -83,10 +92,12 @@ impl Wallet {
         &self,
         total_amounts: &OfferAmounts,
         nft_ids: Vec<Bytes32>,
+        selection: &CoinSelection,
     ) -> Result<OfferCoins, WalletError> {
         // Select XCH coins.
         let xch = if total_amounts.xch > 0 {
-            self.select_p2_coins(total_amounts.xch as u128).await?
+            self.select_p2_coins(total_amounts.xch as u128, selection)
+                .await?
         } else {
             Vec::new()
         };
@@ -101,7 +112,8 @@ impl Wallet {
 
             cats.insert(
                 asset_id,
-                self.select_cat_coins(asset_id, amount as u128).await?,
+                self.select_cat_coins(asset_id, amount as u128, selection)
+                    .await?,
             );
         }
 
@@ -116,6 +128,11 @@ impl Wallet {
             nfts.insert(nft_id, nft);
         }
 
-        Ok(OfferCoins { xch, cats, nfts })
+        Ok(OfferCoins {
+            xch,
+            cats,
+            nfts,
+            dids: IndexMap::new(),
+        })
     }
 }
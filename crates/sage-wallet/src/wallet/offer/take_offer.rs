@@ -4,8 +4,8 @@ use indexmap::IndexMap;
 
 use crate::{
     calculate_royalties, calculate_trade_prices, complete_requested_payments, parse_locked_coins,
-    parse_offer_payments, unlock_assets, NftRoyaltyInfo, OfferAmounts, OfferSpend, Wallet,
-    WalletError,
+    parse_offer_payments, unlock_assets, CoinSelection, NftRoyaltyInfo, OfferAmounts, OfferSpend,
+    Wallet, WalletError,
 };
 
 #[derive(Debug)]
@@ -19,6 +19,7 @@ impl Wallet {
         &self,
         offer: Offer,
         fee: u64,
+        selection: &CoinSelection,
         hardened: bool,
         reuse: bool,
     ) -> Result<UnsignedTakeOffer, WalletError> {
@@ -55,6 +56,7 @@ impl Wallet {
             .fetch_offer_coins(
                 &total_amounts,
                 requested_payments.nfts.keys().copied().collect(),
+                selection,
             )
             .await?;
         let p2_puzzle_hash = self.p2_puzzle_hash(hardened, reuse).await?;
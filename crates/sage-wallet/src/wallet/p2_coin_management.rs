@@ -5,9 +5,63 @@ use chia_wallet_sdk::{select_coins, Conditions, SpendContext};
 
 use crate::WalletError;
 
-use super::Wallet;
+use super::{CoinSelection, Wallet};
+
+/// A number of coins of the same amount to create when splitting into denominations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Denomination {
+    pub amount: u64,
+    pub count: usize,
+}
+
+/// Checks that there is at least one denomination, that none of them are empty,
+/// and that no two of them have the same amount. Returns the total amount of the outputs.
+pub(crate) fn validate_denominations(denominations: &[Denomination]) -> Result<u128, WalletError> {
+    if denominations.is_empty() {
+        return Err(WalletError::EmptyDenominations);
+    }
+
+    let mut amounts = HashSet::new();
+    let mut total = 0;
+
+    for denomination in denominations {
+        if denomination.amount == 0 || denomination.count == 0 {
+            return Err(WalletError::InvalidDenomination(denomination.amount));
+        }
+
+        if !amounts.insert(denomination.amount) {
+            return Err(WalletError::DuplicateDenomination(denomination.amount));
+        }
+
+        total += denomination.amount as u128 * denomination.count as u128;
+    }
+
+    Ok(total)
+}
 
 impl Wallet {
+    /// Returns enough puzzle hashes that the outputs of each denomination can be sent to different ones,
+    /// followed by one that is reserved for the change. This prevents any two outputs from having the same coin id.
+    /// The keys aren't derived here, since they need to be subscribed to, so the caller derives enough of them first.
+    pub(crate) async fn denomination_puzzle_hashes(
+        &self,
+        denominations: &[Denomination],
+        hardened: bool,
+        reuse: bool,
+    ) -> Result<Vec<Bytes32>, WalletError> {
+        let max_count = denominations
+            .iter()
+            .map(|denomination| denomination.count)
+            .max()
+            .unwrap_or(0);
+
+        let count: u32 = (max_count + 1)
+            .try_into()
+            .expect("derivation count overflow");
+
+        self.p2_puzzle_hashes(count, hardened, reuse).await
+    }
+
     /// Combines multiple p2 coins into a single coin, with the given fee subtracted from the output.
     pub async fn combine_xch(
         &self,
@@ -119,6 +173,50 @@ impl Wallet {
         Ok(ctx.take())
     }
 
+    /// Splits XCH into coins of the given denominations, with the remainder left over as change.
+    pub async fn split_xch_denominations(
+        &self,
+        denominations: &[Denomination],
+        fee: u64,
+        selection: &CoinSelection,
+        hardened: bool,
+        reuse: bool,
+    ) -> Result<Vec<CoinSpend>, WalletError> {
+        let total = validate_denominations(denominations)?;
+
+        let coins = self.select_p2_coins(total + fee as u128, selection).await?;
+        let selected: u128 = coins.iter().map(|coin| coin.amount as u128).sum();
+
+        let change: u64 = (selected - total - fee as u128)
+            .try_into()
+            .expect("change amount overflow");
+
+        let puzzle_hashes = self
+            .denomination_puzzle_hashes(denominations, hardened, reuse)
+            .await?;
+
+        let mut conditions = Conditions::new();
+
+        if fee > 0 {
+            conditions = conditions.reserve_fee(fee);
+        }
+
+        for denomination in denominations {
+            for &puzzle_hash in &puzzle_hashes[..denomination.count] {
+                conditions = conditions.create_coin(puzzle_hash, denomination.amount, None);
+            }
+        }
+
+        if change > 0 {
+            let change_puzzle_hash = puzzle_hashes[puzzle_hashes.len() - 1];
+            conditions = conditions.create_coin(change_puzzle_hash, change, None);
+        }
+
+        let mut ctx = SpendContext::new();
+        self.spend_p2_coins(&mut ctx, coins, conditions).await?;
+        Ok(ctx.take())
+    }
+
     /// Creates a transaction that transfers the given coins to the given puzzle hash, minus the fee as needed.
     /// Since the parent coins are all unique, there are no coin id conflicts in the output.
     pub async fn transfer_xch(
@@ -185,7 +283,7 @@ impl Wallet {
 mod tests {
     use test_log::test;
 
-    use crate::TestWallet;
+    use crate::{CoinSelection, Denomination, SyncCommand, TestWallet, WalletError};
 
     #[test(tokio::test)]
     async fn test_xch_coin_management() -> anyhow::Result<()> {
@@ -213,4 +311,124 @@ mod tests {
 
         Ok(())
     }
+
+    #[test(tokio::test)]
+    async fn test_coin_locked() -> anyhow::Result<()> {
+        let mut test = TestWallet::new(1000).await?;
+
+        let coins = test.wallet.db.spendable_coins().await?;
+        let coin_id = coins[0].coin_id();
+
+        assert!(!test.wallet.db.is_coin_locked(coin_id).await?);
+
+        let coin_spends = test.wallet.split_xch(&coins, 2, 0, false, true).await?;
+        test.transact(coin_spends).await?;
+
+        assert!(test.wallet.db.is_coin_locked(coin_id).await?);
+
+        test.wait_for_coins().await;
+
+        let coins = test.wallet.db.spendable_coins().await?;
+        assert_eq!(coins.len(), 2);
+
+        for coin in coins {
+            assert!(!test.wallet.db.is_coin_locked(coin.coin_id()).await?);
+        }
+
+        Ok(())
+    }
+
+    #[test(tokio::test)]
+    async fn test_split_xch_denominations() -> anyhow::Result<()> {
+        let mut test = TestWallet::new(1000).await?;
+
+        let denominations = [
+            Denomination {
+                amount: 100,
+                count: 3,
+            },
+            Denomination {
+                amount: 50,
+                count: 4,
+            },
+        ];
+
+        let coin_spends = test
+            .wallet
+            .split_xch_denominations(&denominations, 100, &CoinSelection::default(), false, true)
+            .await?;
+
+        test.transact(coin_spends).await?;
+        test.wait_for_coins().await;
+
+        let mut amounts: Vec<u64> = test
+            .wallet
+            .db
+            .spendable_coins()
+            .await?
+            .into_iter()
+            .map(|coin| coin.amount)
+            .collect();
+        amounts.sort_unstable();
+
+        assert_eq!(amounts, [50, 50, 50, 50, 100, 100, 100, 400]);
+
+        // Two denominations with the same amount would be ambiguous.
+        assert!(matches!(
+            test.wallet
+                .split_xch_denominations(
+                    &[denominations[0], denominations[0]],
+                    0,
+                    &CoinSelection::default(),
+                    false,
+                    true,
+                )
+                .await,
+            Err(WalletError::DuplicateDenomination(100))
+        ));
+
+        Ok(())
+    }
+
+    #[test(tokio::test)]
+    async fn test_split_xch_denominations_past_derivations() -> anyhow::Result<()> {
+        let mut test = TestWallet::new(10_000).await?;
+
+        // Every output goes to a different address, which is one more than has been derived.
+        let count = test.wallet.db.derivation_index(false).await?;
+
+        let denominations = [Denomination {
+            amount: 1,
+            count: count as usize,
+        }];
+
+        assert!(matches!(
+            test.wallet
+                .split_xch_denominations(&denominations, 0, &CoinSelection::default(), false, true)
+                .await,
+            Err(WalletError::InsufficientDerivations)
+        ));
+
+        let puzzle_hashes = test.wallet.derive_unhardened_keys(count + 1).await?;
+        assert!(!puzzle_hashes.is_empty());
+
+        test.sender
+            .send(SyncCommand::SubscribePuzzles { puzzle_hashes })
+            .await?;
+
+        let coin_spends = test
+            .wallet
+            .split_xch_denominations(&denominations, 0, &CoinSelection::default(), false, true)
+            .await?;
+
+        test.transact(coin_spends).await?;
+        test.wait_for_coins().await;
+
+        assert_eq!(
+            test.wallet.db.spendable_coins().await?.len(),
+            count as usize + 1
+        );
+
+        Ok(())
+    }
 }
@@ -1,23 +1,84 @@
+use std::collections::HashSet;
+
 use chia::protocol::{Bytes, Bytes32, CoinSpend};
 use chia_wallet_sdk::{Conditions, Memos, SpendContext};
 
-use crate::WalletError;
+use crate::{Clawback, WalletError};
+
+use super::{CoinSelection, Wallet};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WalletPayment {
+    pub puzzle_hash: Bytes32,
+    pub amount: u64,
+    pub memos: Vec<Bytes>,
+    /// If set, the payment can be revoked by the sender for this many seconds.
+    pub clawback_seconds: Option<u64>,
+}
+
+/// Checks that there is at least one payment, and that no two payments would create the same coin.
+pub(crate) fn validate_payments(payments: &[WalletPayment]) -> Result<(), WalletError> {
+    if payments.is_empty() {
+        return Err(WalletError::EmptyPayments);
+    }
 
-use super::Wallet;
+    let mut outputs = HashSet::new();
+
+    for payment in payments {
+        if !outputs.insert((payment.puzzle_hash, payment.amount)) {
+            return Err(WalletError::DuplicatePayment(
+                payment.puzzle_hash,
+                payment.amount,
+            ));
+        }
+    }
+
+    Ok(())
+}
 
 impl Wallet {
     /// Sends the given amount of XCH to the given puzzle hash, minus the fee.
+    #[allow(clippy::too_many_arguments)]
     pub async fn send_xch(
         &self,
         puzzle_hash: Bytes32,
         amount: u64,
         fee: u64,
         memos: Vec<Bytes>,
+        clawback_seconds: Option<u64>,
+        selection: &CoinSelection,
         hardened: bool,
         reuse: bool,
     ) -> Result<Vec<CoinSpend>, WalletError> {
-        let total = amount as u128 + fee as u128;
-        let coins = self.select_p2_coins(total).await?;
+        self.send_xch_multi(
+            vec![WalletPayment {
+                puzzle_hash,
+                amount,
+                memos,
+                clawback_seconds,
+            }],
+            fee,
+            selection,
+            hardened,
+            reuse,
+        )
+        .await
+    }
+
+    /// Sends XCH to each of the payments in a single spend, with one change output.
+    pub async fn send_xch_multi(
+        &self,
+        payments: Vec<WalletPayment>,
+        fee: u64,
+        selection: &CoinSelection,
+        hardened: bool,
+        reuse: bool,
+    ) -> Result<Vec<CoinSpend>, WalletError> {
+        validate_payments(&payments)?;
+
+        let amount: u128 = payments.iter().map(|payment| payment.amount as u128).sum();
+        let total = amount + fee as u128;
+        let coins = self.select_p2_coins(total, selection).await?;
         let selected: u128 = coins.iter().map(|coin| coin.amount as u128).sum();
 
         let change_puzzle_hash = self.p2_puzzle_hash(hardened, reuse).await?;
@@ -28,11 +89,22 @@ impl Wallet {
 
         let mut ctx = SpendContext::new();
 
-        let mut conditions = Conditions::new().create_coin(
-            puzzle_hash,
-            amount,
-            Some(Memos::new(ctx.alloc(&memos)?)),
-        );
+        let mut conditions = Conditions::new();
+
+        for payment in payments {
+            let (puzzle_hash, memos) = if let Some(seconds) = payment.clawback_seconds {
+                let clawback = Clawback::new(change_puzzle_hash, payment.puzzle_hash, seconds);
+                (clawback.puzzle_hash().into(), clawback.memos(payment.memos))
+            } else {
+                (payment.puzzle_hash, payment.memos)
+            };
+
+            conditions = conditions.create_coin(
+                puzzle_hash,
+                payment.amount,
+                Some(Memos::new(ctx.alloc(&memos)?)),
+            );
+        }
 
         if fee > 0 {
             conditions = conditions.reserve_fee(fee);
@@ -50,9 +122,10 @@ impl Wallet {
 
 #[cfg(test)]
 mod tests {
+    use chia::protocol::Bytes32;
     use test_log::test;
 
-    use crate::TestWallet;
+    use crate::{CoinSelection, SyncCommand, TestWallet, WalletError, WalletPayment};
 
     #[test(tokio::test)]
     async fn test_send_xch() -> anyhow::Result<()> {
@@ -60,7 +133,16 @@ mod tests {
 
         let coin_spends = test
             .wallet
-            .send_xch(test.puzzle_hash, 1000, 0, Vec::new(), false, true)
+            .send_xch(
+                test.puzzle_hash,
+                1000,
+                0,
+                Vec::new(),
+                None,
+                &CoinSelection::default(),
+                false,
+                true,
+            )
             .await?;
 
         assert_eq!(coin_spends.len(), 1);
@@ -80,7 +162,16 @@ mod tests {
 
         let coin_spends = test
             .wallet
-            .send_xch(test.puzzle_hash, 250, 250, Vec::new(), false, true)
+            .send_xch(
+                test.puzzle_hash,
+                250,
+                250,
+                Vec::new(),
+                None,
+                &CoinSelection::default(),
+                false,
+                true,
+            )
             .await?;
 
         assert_eq!(coin_spends.len(), 1);
@@ -93,4 +184,113 @@ mod tests {
 
         Ok(())
     }
+
+    #[test(tokio::test)]
+    async fn test_send_xch_multi() -> anyhow::Result<()> {
+        let mut test = TestWallet::new(1000).await?;
+
+        let payments = (1..=3)
+            .map(|amount| WalletPayment {
+                puzzle_hash: test.puzzle_hash,
+                amount: amount * 100,
+                memos: Vec::new(),
+                clawback_seconds: None,
+            })
+            .collect();
+
+        let coin_spends = test
+            .wallet
+            .send_xch_multi(payments, 50, &CoinSelection::default(), false, true)
+            .await?;
+
+        assert_eq!(coin_spends.len(), 1);
+
+        test.transact(coin_spends).await?;
+        test.wait_for_coins().await;
+
+        assert_eq!(test.wallet.db.balance().await?, 950);
+        assert_eq!(test.wallet.db.spendable_coins().await?.len(), 4);
+
+        let payment = WalletPayment {
+            puzzle_hash: Bytes32::default(),
+            amount: 100,
+            memos: Vec::new(),
+            clawback_seconds: None,
+        };
+
+        assert!(matches!(
+            test.wallet
+                .send_xch_multi(
+                    vec![payment.clone(), payment],
+                    0,
+                    &CoinSelection::default(),
+                    false,
+                    true
+                )
+                .await,
+            Err(WalletError::DuplicatePayment(..))
+        ));
+
+        Ok(())
+    }
+
+    #[test(tokio::test)]
+    async fn test_send_xch_hardened() -> anyhow::Result<()> {
+        let mut test = TestWallet::new(1000).await?;
+
+        let puzzle_hashes = test
+            .wallet
+            .derive_hardened_keys(&test.master_sk, 100)
+            .await?;
+
+        assert_eq!(puzzle_hashes.len(), 100);
+
+        test.sender
+            .send(SyncCommand::SubscribePuzzles { puzzle_hashes })
+            .await?;
+
+        let hardened_puzzle_hash = test.wallet.p2_puzzle_hash(true, false).await?;
+
+        let coin_spends = test
+            .wallet
+            .send_xch(
+                hardened_puzzle_hash,
+                1000,
+                0,
+                Vec::new(),
+                None,
+                &CoinSelection::default(),
+                true,
+                true,
+            )
+            .await?;
+
+        test.transact(coin_spends).await?;
+        test.wait_for_coins().await;
+
+        assert_eq!(test.wallet.db.balance().await?, 1000);
+
+        // Spending the coin requires signing with the hardened key.
+        let coin_spends = test
+            .wallet
+            .send_xch(
+                test.puzzle_hash,
+                1000,
+                0,
+                Vec::new(),
+                None,
+                &CoinSelection::default(),
+                false,
+                true,
+            )
+            .await?;
+
+        test.transact(coin_spends).await?;
+        test.wait_for_coins().await;
+
+        assert_eq!(test.wallet.db.balance().await?, 1000);
+        assert_eq!(test.wallet.db.spendable_coins().await?.len(), 1);
+
+        Ok(())
+    }
 }
@@ -0,0 +1,266 @@
+use std::collections::HashSet;
+
+use chia::protocol::{Bytes32, CoinSpend};
+use chia_wallet_sdk::{select_coins, Conditions, SpendContext};
+use indexmap::IndexMap;
+
+use crate::WalletError;
+
+use super::{CoinSelection, OfferCoins, Wallet};
+
+/// The minimum amount by which the fee must increase for the mempool to accept a replacement.
+pub const MEMPOOL_MIN_FEE_INCREASE: u64 = 10_000_000;
+
+impl Wallet {
+    /// Creates an additional spend which increases the fee of a pending transaction.
+    /// The original spends are kept as is, so the combined spend bundle is a superset
+    /// of the original and can replace it in the mempool.
+    pub async fn increase_fee(
+        &self,
+        coin_spends: &[CoinSpend],
+        fee_increase: u64,
+        hardened: bool,
+        reuse: bool,
+    ) -> Result<Vec<CoinSpend>, WalletError> {
+        let input_ids: HashSet<Bytes32> = coin_spends
+            .iter()
+            .map(|coin_spend| coin_spend.coin.coin_id())
+            .collect();
+
+        // The pending transaction's coins can't be used to pay the additional fee.
+        let spendable_coins = self
+            .db
+            .spendable_coins()
+            .await?
+            .into_iter()
+            .filter(|coin| !input_ids.contains(&coin.coin_id()))
+            .collect();
+
+        let coins = select_coins(spendable_coins, fee_increase as u128)?;
+        let selected: u128 = coins.iter().map(|coin| coin.amount as u128).sum();
+
+        let change_puzzle_hash = self.p2_puzzle_hash(hardened, reuse).await?;
+
+        let change: u64 = (selected - fee_increase as u128)
+            .try_into()
+            .expect("change amount overflow");
+
+        let mut conditions = Conditions::new().reserve_fee(fee_increase);
+
+        // Tie the fee spend to the original transaction, so it can't be included on its own.
+        if let Some(coin_spend) = coin_spends.first() {
+            conditions = conditions.assert_concurrent_spend(coin_spend.coin.coin_id());
+        }
+
+        if change > 0 {
+            conditions = conditions.create_coin(change_puzzle_hash, change, None);
+        }
+
+        let mut ctx = SpendContext::new();
+
+        self.spend_p2_coins(&mut ctx, coins, conditions).await?;
+
+        Ok(ctx.take())
+    }
+
+    /// Spends the inputs of a pending transaction back to the wallet instead. Because every
+    /// coin of the original is spent, this can replace it in the mempool if the fee is higher.
+    pub async fn cancel_transaction(
+        &self,
+        coin_spends: &[CoinSpend],
+        fee: u64,
+        selection: &CoinSelection,
+        hardened: bool,
+        reuse: bool,
+    ) -> Result<Vec<CoinSpend>, WalletError> {
+        let input_ids: HashSet<Bytes32> = coin_spends
+            .iter()
+            .map(|coin_spend| coin_spend.coin.coin_id())
+            .collect();
+
+        let mut coins = OfferCoins {
+            xch: Vec::new(),
+            cats: IndexMap::new(),
+            nfts: IndexMap::new(),
+            dids: IndexMap::new(),
+        };
+
+        let mut clawback_spends = Vec::new();
+
+        for coin_spend in coin_spends {
+            let coin_id = coin_spend.coin.coin_id();
+
+            // Coins created and spent within the transaction, such as singleton launchers,
+            // won't exist once the inputs are spent differently.
+            if input_ids.contains(&coin_spend.coin.parent_coin_info) {
+                continue;
+            }
+
+            if let Some(cat) = self.db.cat_coin(coin_id).await? {
+                coins.cats.entry(cat.asset_id).or_default().push(cat);
+            } else if let Some(nft) = self.db.nft_by_coin_id(coin_id).await? {
+                coins.nfts.insert(nft.info.launcher_id, nft);
+            } else if let Some(did) = self.db.did_by_coin_id(coin_id).await? {
+                coins.dids.insert(did.info.launcher_id, did);
+            } else if self.db.clawback_coin(coin_id).await?.is_some() {
+                // The wallet only spends clawback coins to its own address, so the claim or
+                // revocation can be kept as is.
+                clawback_spends.push(coin_spend.clone());
+            } else if self
+                .db
+                .is_p2_puzzle_hash(coin_spend.coin.puzzle_hash)
+                .await?
+            {
+                coins.xch.push(coin_spend.coin);
+            } else {
+                return Err(WalletError::UncancellableCoin(coin_id));
+            }
+        }
+
+        let mut cancel_spends = self
+            .reclaim_coins(coins, fee, selection, hardened, reuse)
+            .await?;
+
+        cancel_spends.extend(clawback_spends);
+
+        Ok(cancel_spends)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chia::protocol::Bytes32;
+    use test_log::test;
+
+    use crate::{CoinSelection, TestWallet};
+
+    #[test(tokio::test)]
+    async fn test_increase_fee() -> anyhow::Result<()> {
+        let mut test = TestWallet::new(1000).await?;
+
+        let coins = test.wallet.db.spendable_coins().await?;
+        let coin_spends = test.wallet.split_xch(&coins, 2, 0, false, true).await?;
+
+        test.transact(coin_spends).await?;
+        test.wait_for_coins().await;
+
+        assert_eq!(test.wallet.db.spendable_coins().await?.len(), 2);
+
+        let mut coin_spends = test
+            .wallet
+            .send_xch(
+                test.puzzle_hash,
+                400,
+                0,
+                Vec::new(),
+                None,
+                &CoinSelection::default(),
+                false,
+                true,
+            )
+            .await?;
+
+        assert_eq!(coin_spends.len(), 1);
+
+        let fee_spends = test
+            .wallet
+            .increase_fee(&coin_spends, 100, false, true)
+            .await?;
+
+        assert_eq!(fee_spends.len(), 1);
+        assert_ne!(fee_spends[0].coin, coin_spends[0].coin);
+
+        coin_spends.extend(fee_spends);
+
+        test.transact(coin_spends).await?;
+        test.wait_for_coins().await;
+
+        assert_eq!(test.wallet.db.balance().await?, 900);
+
+        Ok(())
+    }
+
+    #[test(tokio::test)]
+    async fn test_cancel_transaction() -> anyhow::Result<()> {
+        let mut test = TestWallet::new(1000).await?;
+
+        let coin_spends = test
+            .wallet
+            .send_xch(
+                Bytes32::default(),
+                1000,
+                0,
+                Vec::new(),
+                None,
+                &CoinSelection::default(),
+                false,
+                true,
+            )
+            .await?;
+
+        let cancel_spends = test
+            .wallet
+            .cancel_transaction(&coin_spends, 100, &CoinSelection::default(), false, true)
+            .await?;
+
+        assert_eq!(cancel_spends.len(), 1);
+        assert_eq!(cancel_spends[0].coin, coin_spends[0].coin);
+
+        test.transact(cancel_spends).await?;
+        test.wait_for_coins().await;
+
+        assert_eq!(test.wallet.db.balance().await?, 900);
+
+        Ok(())
+    }
+
+    #[test(tokio::test)]
+    async fn test_cancel_did_transfer() -> anyhow::Result<()> {
+        let mut test = TestWallet::new(1).await?;
+
+        let (coin_spends, did) = test
+            .wallet
+            .create_did(0, &CoinSelection::default(), false, true)
+            .await?;
+        test.transact(coin_spends).await?;
+        test.wait_for_coins().await;
+
+        let coin_spends = test
+            .wallet
+            .transfer_dids(
+                vec![did.info.launcher_id],
+                Bytes32::default(),
+                0,
+                &CoinSelection::default(),
+                false,
+                true,
+            )
+            .await?;
+
+        let cancel_spends = test
+            .wallet
+            .cancel_transaction(&coin_spends, 0, &CoinSelection::default(), false, true)
+            .await?;
+
+        assert_eq!(cancel_spends.len(), 1);
+        assert_eq!(cancel_spends[0].coin, coin_spends[0].coin);
+
+        test.transact(cancel_spends).await?;
+        test.wait_for_coins().await;
+
+        let did = test
+            .wallet
+            .db
+            .spendable_did(did.info.launcher_id)
+            .await?
+            .expect("missing did");
+        assert!(
+            test.wallet
+                .db
+                .is_p2_puzzle_hash(did.info.p2_puzzle_hash)
+                .await?
+        );
+
+        Ok(())
+    }
+}
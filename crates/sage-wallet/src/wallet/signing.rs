@@ -2,8 +2,8 @@ use std::collections::HashMap;
 
 use chia::{
     bls::{
-        master_to_wallet_unhardened_intermediate, sign, DerivableKey, PublicKey, SecretKey,
-        Signature,
+        master_to_wallet_hardened_intermediate, master_to_wallet_unhardened_intermediate, sign,
+        DerivableKey, PublicKey, SecretKey, Signature,
     },
     protocol::{CoinSpend, SpendBundle},
     puzzles::DeriveSynthetic,
@@ -73,22 +73,27 @@ impl Wallet {
                 return Err(WalletError::SecpNotSupported);
             };
             let pk = required.public_key;
-            let Some(index) = self.db.synthetic_key_index(pk).await? else {
+            let Some(derivation) = self.db.synthetic_key_derivation(pk).await? else {
                 if partial {
                     continue;
                 }
                 return Err(WalletError::UnknownPublicKey);
             };
-            indices.insert(pk, index);
+            indices.insert(pk, (derivation.index, derivation.hardened));
         }
 
-        let intermediate_sk = master_to_wallet_unhardened_intermediate(&master_sk);
+        let unhardened_intermediate_sk = master_to_wallet_unhardened_intermediate(&master_sk);
+        let hardened_intermediate_sk = master_to_wallet_hardened_intermediate(&master_sk);
 
         let secret_keys: HashMap<PublicKey, SecretKey> = indices
             .iter()
-            .map(|(pk, index)| {
-                let sk = intermediate_sk.derive_unhardened(*index).derive_synthetic();
-                (*pk, sk)
+            .map(|(pk, &(index, hardened))| {
+                let sk = if hardened {
+                    hardened_intermediate_sk.derive_hardened(index)
+                } else {
+                    unhardened_intermediate_sk.derive_unhardened(index)
+                };
+                (*pk, sk.derive_synthetic())
             })
             .collect();
 
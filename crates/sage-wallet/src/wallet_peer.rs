@@ -2,8 +2,8 @@ use std::net::SocketAddr;
 
 use chia::protocol::{
     Bytes32, CoinSpend, CoinState, CoinStateFilters, Program, RejectStateReason,
-    RequestBlockHeader, RespondBlockHeader, RespondPeers, RespondPuzzleState, SpendBundle,
-    TransactionAck,
+    RequestBlockHeader, RequestFeeEstimates, RespondBlockHeader, RespondFeeEstimates, RespondPeers,
+    RespondPuzzleState, SpendBundle, TransactionAck,
 };
 use chia_wallet_sdk::Peer;
 
@@ -202,4 +202,25 @@ impl WalletPeer {
             .foliage_transaction_block
             .map(|block| block.timestamp))
     }
+
+    /// Requests the estimated fee rate in mojos per cost for inclusion within the target time.
+    pub async fn fee_estimate(&self, target_seconds: u64) -> Result<Option<u64>, WalletError> {
+        let response = self
+            .peer
+            .request_infallible::<RespondFeeEstimates, _>(RequestFeeEstimates::new(vec![
+                target_seconds,
+            ]))
+            .await?;
+
+        if response.estimates.error.is_some() {
+            return Ok(None);
+        }
+
+        Ok(response
+            .estimates
+            .estimates
+            .into_iter()
+            .find(|estimate| estimate.error.is_none() && estimate.time_target == target_seconds)
+            .map(|estimate| estimate.estimated_fee_rate.mojos_per_clvm_cost))
+    }
 }
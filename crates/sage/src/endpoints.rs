@@ -1,5 +1,7 @@
 mod actions;
+mod contacts;
 mod data;
+mod invoices;
 mod keys;
 mod offers;
 mod settings;
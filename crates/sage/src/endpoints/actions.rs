@@ -1,10 +1,18 @@
+use chia::{
+    clvm_traits::{FromClvm, ToClvm},
+    puzzles::nft::NftMetadata,
+};
+use clvmr::Allocator;
 use sage_api::{
-    RemoveCat, RemoveCatResponse, UpdateCat, UpdateCatResponse, UpdateDid, UpdateDidResponse,
-    UpdateNft, UpdateNftResponse,
+    RemoveCat, RemoveCatResponse, RetryNftData, RetryNftDataResponse, UpdateCat, UpdateCatResponse,
+    UpdateDerivation, UpdateDerivationResponse, UpdateDid, UpdateDidResponse, UpdateNft,
+    UpdateNftResponse, UpdateTransaction, UpdateTransactionResponse,
 };
 use sage_database::{CatRow, DidRow};
 
-use crate::{parse_asset_id, parse_did_id, parse_nft_id, Error, Result, Sage};
+use crate::{
+    parse_asset_id, parse_did_id, parse_nft_id, parse_transaction_id, Error, Result, Sage,
+};
 
 impl Sage {
     pub async fn remove_cat(&self, req: RemoveCat) -> Result<RemoveCatResponse> {
@@ -69,4 +77,71 @@ impl Sage {
 
         Ok(UpdateNftResponse {})
     }
+
+    pub async fn retry_nft_data(&self, req: RetryNftData) -> Result<RetryNftDataResponse> {
+        let wallet = self.wallet()?;
+
+        let nft_id = parse_nft_id(req.nft_id)?;
+
+        let Some(nft) = wallet.db.nft(nft_id).await? else {
+            return Err(Error::MissingNft(nft_id));
+        };
+
+        let mut allocator = Allocator::new();
+        let metadata_ptr = nft.info.metadata.to_clvm(&mut allocator)?;
+
+        if let Ok(metadata) = NftMetadata::from_clvm(&allocator, metadata_ptr) {
+            for hash in [
+                metadata.data_hash,
+                metadata.metadata_hash,
+                metadata.license_hash,
+            ]
+            .into_iter()
+            .flatten()
+            {
+                wallet.db.reset_nft_uris(hash).await?;
+            }
+        }
+
+        Ok(RetryNftDataResponse {})
+    }
+
+    pub async fn update_transaction(
+        &self,
+        req: UpdateTransaction,
+    ) -> Result<UpdateTransactionResponse> {
+        let wallet = self.wallet()?;
+
+        let id = parse_transaction_id(req.id)?;
+
+        if wallet.db.transaction_history_row(id).await?.is_none() {
+            return Err(Error::MissingTransaction(id));
+        }
+
+        let label = req.label.filter(|label| !label.trim().is_empty());
+        wallet.db.set_transaction_label(id, label).await?;
+
+        Ok(UpdateTransactionResponse {})
+    }
+
+    pub async fn update_derivation(
+        &self,
+        req: UpdateDerivation,
+    ) -> Result<UpdateDerivationResponse> {
+        let wallet = self.wallet()?;
+
+        let p2_puzzle_hash = self.parse_address(req.address.clone())?;
+
+        if !wallet.db.is_p2_puzzle_hash(p2_puzzle_hash).await? {
+            return Err(Error::UnknownAddress(req.address));
+        }
+
+        let label = req.label.filter(|label| !label.trim().is_empty());
+        wallet
+            .db
+            .set_derivation_label(p2_puzzle_hash, label)
+            .await?;
+
+        Ok(UpdateDerivationResponse {})
+    }
 }
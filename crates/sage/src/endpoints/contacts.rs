@@ -0,0 +1,111 @@
+use chia_wallet_sdk::encode_address;
+use sage_api::{
+    AddContact, AddContactResponse, ContactRecord, DeleteContact, DeleteContactResponse,
+    GetContacts, GetContactsResponse, UpdateContact, UpdateContactResponse,
+};
+use sage_database::ContactRow;
+
+use crate::{parse_did_id, Error, Result, Sage};
+
+impl Sage {
+    pub async fn get_contacts(&self, _req: GetContacts) -> Result<GetContactsResponse> {
+        let wallet = self.wallet()?;
+
+        let contacts = wallet
+            .db
+            .contacts()
+            .await?
+            .into_iter()
+            .map(|row| self.contact_record(row))
+            .collect::<Result<_>>()?;
+
+        Ok(GetContactsResponse { contacts })
+    }
+
+    pub async fn add_contact(&self, req: AddContact) -> Result<AddContactResponse> {
+        let wallet = self.wallet()?;
+
+        let puzzle_hash = self.parse_address(req.address.clone())?;
+        let did_id = req.did_id.map(parse_did_id).transpose()?;
+
+        if wallet
+            .db
+            .contact_by_puzzle_hash(puzzle_hash)
+            .await?
+            .is_some()
+        {
+            return Err(Error::ContactExists(req.address));
+        }
+
+        let id = wallet
+            .db
+            .insert_contact(req.name.clone(), puzzle_hash, did_id, req.notes.clone())
+            .await?;
+
+        Ok(AddContactResponse {
+            contact: self.contact_record(ContactRow {
+                id,
+                name: req.name,
+                puzzle_hash,
+                did_id,
+                notes: req.notes,
+            })?,
+        })
+    }
+
+    pub async fn update_contact(&self, req: UpdateContact) -> Result<UpdateContactResponse> {
+        let wallet = self.wallet()?;
+
+        if wallet.db.contact(req.id).await?.is_none() {
+            return Err(Error::MissingContact(req.id));
+        }
+
+        let puzzle_hash = self.parse_address(req.address.clone())?;
+        let did_id = req.did_id.map(parse_did_id).transpose()?;
+
+        if let Some(existing) = wallet.db.contact_by_puzzle_hash(puzzle_hash).await? {
+            if existing.id != req.id {
+                return Err(Error::ContactExists(req.address));
+            }
+        }
+
+        let row = ContactRow {
+            id: req.id,
+            name: req.name,
+            puzzle_hash,
+            did_id,
+            notes: req.notes,
+        };
+
+        wallet.db.update_contact(row.clone()).await?;
+
+        Ok(UpdateContactResponse {
+            contact: self.contact_record(row)?,
+        })
+    }
+
+    pub async fn delete_contact(&self, req: DeleteContact) -> Result<DeleteContactResponse> {
+        let wallet = self.wallet()?;
+
+        if wallet.db.contact(req.id).await?.is_none() {
+            return Err(Error::MissingContact(req.id));
+        }
+
+        wallet.db.delete_contact(req.id).await?;
+
+        Ok(DeleteContactResponse {})
+    }
+
+    fn contact_record(&self, row: ContactRow) -> Result<ContactRecord> {
+        Ok(ContactRecord {
+            id: row.id,
+            name: row.name,
+            address: encode_address(row.puzzle_hash.to_bytes(), &self.network().address_prefix)?,
+            did_id: row
+                .did_id
+                .map(|did_id| encode_address(did_id.to_bytes(), "did:chia:"))
+                .transpose()?,
+            notes: row.notes,
+        })
+    }
+}
@@ -7,21 +7,33 @@ use chia::{
 use chia_wallet_sdk::{encode_address, Nft};
 use clvmr::Allocator;
 use hex_literal::hex;
+use itertools::Itertools;
 use sage_api::{
-    Amount, AssetKind, CatRecord, CoinRecord, DerivationRecord, DidRecord, GetCat, GetCatCoins,
-    GetCatCoinsResponse, GetCatResponse, GetCats, GetCatsResponse, GetDerivations,
-    GetDerivationsResponse, GetDids, GetDidsResponse, GetNft, GetNftCollection,
-    GetNftCollectionResponse, GetNftCollections, GetNftCollectionsResponse, GetNftData,
-    GetNftDataResponse, GetNftResponse, GetNftStatus, GetNftStatusResponse, GetNfts,
-    GetNftsResponse, GetPendingTransactions, GetPendingTransactionsResponse, GetSyncStatus,
-    GetSyncStatusResponse, GetTransactions, GetTransactionsResponse, GetXchCoins,
-    GetXchCoinsResponse, NftCollectionRecord, NftData, NftRecord, NftSortMode,
-    PendingTransactionRecord, TransactionCoin, TransactionRecord,
+    Amount, AssetKind, CatRecord, ClawbackRecord, CoinRecord, DerivationRecord, DidRecord,
+    ExportFormat, ExportTransactions, ExportTransactionsResponse, GetCat, GetCatCoins,
+    GetCatCoinsResponse, GetCatResponse, GetCats, GetCatsResponse, GetClawbacks,
+    GetClawbacksResponse, GetCoinsByIds, GetCoinsByIdsResponse, GetDerivations,
+    GetDerivationsResponse, GetDids, GetDidsResponse, GetNextAddress, GetNextAddressResponse,
+    GetNft, GetNftCollection, GetNftCollectionResponse, GetNftCollections,
+    GetNftCollectionsResponse, GetNftData, GetNftDataResponse, GetNftResponse, GetNftStatus,
+    GetNftStatusResponse, GetNftTraits, GetNftTraitsResponse, GetNfts, GetNftsResponse,
+    GetPendingTransactions, GetPendingTransactionsResponse, GetSyncStatus, GetSyncStatusResponse,
+    GetTransaction, GetTransactionResponse, GetTransactions, GetTransactionsResponse, GetXchCoins,
+    GetXchCoinsResponse, NftAttributeRecord, NftCollectionRecord, NftData, NftDataSize, NftRecord,
+    NftSortMode, NftTraitRecord, NftUriRecord, NftUriStatus, PendingTransactionRecord,
+    TransactionCoin, TransactionDirection, TransactionRecord,
+};
+use sage_database::{
+    CoinKind, CoinStateRow, Database, DerivationRow, HistoryDirection, NftRow, NftSearchFilter,
+    TransactionHistoryFilter, TransactionHistoryRow,
 };
-use sage_database::{CoinKind, CoinStateRow, Database, NftRow};
 use sage_wallet::WalletError;
 
-use crate::{parse_asset_id, parse_collection_id, parse_nft_id, Result, Sage};
+use crate::{
+    asset_totals, export_csv, export_kind, format_amount, format_timestamp, parse_asset_id,
+    parse_coin_id, parse_collection_id, parse_nft_id, parse_transaction_id, ExportAsset, ExportRow,
+    Result, Sage,
+};
 
 impl Sage {
     pub async fn get_sync_status(&self, _req: GetSyncStatus) -> Result<GetSyncStatusResponse> {
@@ -31,7 +43,10 @@ impl Sage {
         let total_coins = wallet.db.total_coin_count().await?;
         let synced_coins = wallet.db.synced_coin_count().await?;
 
-        let puzzle_hash = match wallet.p2_puzzle_hash(false, false).await {
+        let puzzle_hash = match wallet
+            .p2_puzzle_hash(self.hardened_addresses(), false)
+            .await
+        {
             Ok(puzzle_hash) => Some(puzzle_hash),
             Err(WalletError::InsufficientDerivations) => None,
             Err(error) => return Err(error.into()),
@@ -59,26 +74,39 @@ impl Sage {
     pub async fn get_derivations(&self, req: GetDerivations) -> Result<GetDerivationsResponse> {
         let wallet = self.wallet()?;
 
-        let derivations = wallet
+        let mut derivations = Vec::new();
+
+        for row in wallet
             .db
-            .unhardened_derivations(req.limit, req.offset)
+            .derivations(req.hardened, req.limit, req.offset)
             .await?
-            .into_iter()
-            .map(|row| {
-                Ok(DerivationRecord {
-                    index: row.index,
-                    public_key: hex::encode(row.synthetic_key.to_bytes()),
-                    address: encode_address(
-                        row.p2_puzzle_hash.to_bytes(),
-                        &self.network().address_prefix,
-                    )?,
-                })
-            })
-            .collect::<Result<Vec<_>>>()?;
+        {
+            derivations.push(self.derivation_record(&wallet.db, row).await?);
+        }
 
         Ok(GetDerivationsResponse { derivations })
     }
 
+    pub async fn get_next_address(&self, req: GetNextAddress) -> Result<GetNextAddressResponse> {
+        let wallet = self.wallet()?;
+
+        let hardened = self.hardened_addresses();
+        let label = req.label.filter(|label| !label.trim().is_empty());
+
+        let (tx, p2_puzzle_hash) = self
+            .next_p2_puzzle_hash(&wallet, hardened, 0, req.mark_used, label)
+            .await?;
+        tx.commit().await?;
+
+        let Some(row) = wallet.db.derivation(p2_puzzle_hash).await? else {
+            return Err(WalletError::InsufficientDerivations.into());
+        };
+
+        Ok(GetNextAddressResponse {
+            derivation: self.derivation_record(&wallet.db, row).await?,
+        })
+    }
+
     pub async fn get_xch_coins(&self, _req: GetXchCoins) -> Result<GetXchCoinsResponse> {
         let wallet = self.wallet()?;
 
@@ -160,6 +188,48 @@ impl Sage {
         Ok(GetCatCoinsResponse { coins })
     }
 
+    pub async fn get_coins_by_ids(&self, req: GetCoinsByIds) -> Result<GetCoinsByIdsResponse> {
+        let wallet = self.wallet()?;
+
+        let mut coins = Vec::new();
+
+        for coin_id in req.coin_ids {
+            let Some(row) = wallet.db.coin_state_row(parse_coin_id(coin_id)?).await? else {
+                continue;
+            };
+
+            let cs = row.coin_state;
+
+            let spend_transaction_id = wallet
+                .db
+                .coin_transaction_id(cs.coin.coin_id())
+                .await?
+                .map(hex::encode);
+
+            let offer_id = wallet
+                .db
+                .coin_offer_id(cs.coin.coin_id())
+                .await?
+                .map(hex::encode);
+
+            coins.push(CoinRecord {
+                coin_id: hex::encode(cs.coin.coin_id()),
+                address: encode_address(
+                    cs.coin.puzzle_hash.to_bytes(),
+                    &self.network().address_prefix,
+                )?,
+                amount: Amount::u64(cs.coin.amount),
+                created_height: cs.created_height,
+                spent_height: cs.spent_height,
+                create_transaction_id: row.transaction_id.map(hex::encode),
+                spend_transaction_id,
+                offer_id,
+            });
+        }
+
+        Ok(GetCoinsByIdsResponse { coins })
+    }
+
     pub async fn get_cats(&self, _req: GetCats) -> Result<GetCatsResponse> {
         let wallet = self.wallet()?;
         let cats = wallet.db.cats_by_name().await?;
@@ -259,43 +329,173 @@ impl Sage {
         Ok(GetPendingTransactionsResponse { transactions })
     }
 
+    pub async fn get_clawbacks(&self, _req: GetClawbacks) -> Result<GetClawbacksResponse> {
+        let wallet = self.wallet()?;
+
+        let mut clawbacks = Vec::new();
+
+        for row in wallet.db.unspent_clawback_coins().await? {
+            let clawback = row.clawback;
+
+            clawbacks.push(ClawbackRecord {
+                coin_id: hex::encode(row.coin_state.coin.coin_id()),
+                asset_id: clawback.asset_id.map(hex::encode),
+                amount: Amount::u64(row.coin_state.coin.amount),
+                sender_address: encode_address(
+                    clawback.sender_puzzle_hash.to_bytes(),
+                    &self.network().address_prefix,
+                )?,
+                recipient_address: encode_address(
+                    clawback.recipient_puzzle_hash.to_bytes(),
+                    &self.network().address_prefix,
+                )?,
+                seconds: clawback.seconds,
+                created_height: row.coin_state.created_height,
+                incoming: wallet
+                    .db
+                    .is_p2_puzzle_hash(clawback.recipient_puzzle_hash)
+                    .await?,
+            });
+        }
+
+        Ok(GetClawbacksResponse { clawbacks })
+    }
+
     pub async fn get_transactions(&self, req: GetTransactions) -> Result<GetTransactionsResponse> {
         let wallet = self.wallet()?;
 
+        let (kind, asset_id) = match req.asset_id {
+            None => (None, None),
+            Some(asset_id) if asset_id == "xch" => (Some(CoinKind::Xch), None),
+            Some(asset_id) => (Some(CoinKind::Cat), Some(parse_asset_id(asset_id)?)),
+        };
+
+        let filter = TransactionHistoryFilter {
+            direction: req.direction.map(|direction| match direction {
+                TransactionDirection::Incoming => HistoryDirection::Incoming,
+                TransactionDirection::Outgoing => HistoryDirection::Outgoing,
+                TransactionDirection::Internal => HistoryDirection::Internal,
+            }),
+            kind,
+            asset_id,
+            label: req.label,
+        };
+
         let mut transactions = Vec::new();
 
-        let heights = wallet.db.get_block_heights().await?;
+        for row in wallet
+            .db
+            .transaction_history(&filter, req.offset, req.limit)
+            .await?
+        {
+            transactions.push(self.transaction_record(&wallet.db, row).await?);
+        }
+
+        Ok(GetTransactionsResponse {
+            transactions,
+            total: wallet.db.transaction_history_count(&filter).await?,
+        })
+    }
+
+    pub async fn get_transaction(&self, req: GetTransaction) -> Result<GetTransactionResponse> {
+        let wallet = self.wallet()?;
+
+        let id = parse_transaction_id(req.id)?;
+
+        let transaction = match wallet.db.transaction_history_row(id).await? {
+            Some(row) => Some(self.transaction_record(&wallet.db, row).await?),
+            None => None,
+        };
 
-        for &height in heights
-            .iter()
-            .skip(req.offset.try_into()?)
-            .take(req.limit.try_into()?)
+        Ok(GetTransactionResponse { transaction })
+    }
+
+    pub async fn export_transactions(
+        &self,
+        req: ExportTransactions,
+    ) -> Result<ExportTransactionsResponse> {
+        let wallet = self.wallet()?;
+
+        let asset_filter = match req.asset_id {
+            None => None,
+            Some(asset_id) if asset_id == "xch" => Some(None),
+            Some(asset_id) => Some(Some(parse_asset_id(asset_id)?)),
+        };
+
+        let mut rows = Vec::new();
+
+        for transaction in wallet
+            .db
+            .confirmed_transaction_history(
+                req.from_height.unwrap_or(0),
+                req.to_height.unwrap_or(u32::MAX),
+            )
+            .await?
         {
-            let spent_rows = wallet.db.get_coin_states_by_spent_height(height).await?;
-            let created_rows = wallet.db.get_coin_states_by_created_height(height).await?;
+            let Some(height) = transaction.height else {
+                continue;
+            };
 
-            let mut spent = Vec::new();
-            let mut created = Vec::new();
+            let timestamp = self.block_timestamp(&wallet.db, height).await?;
 
-            for row in spent_rows {
-                spent.push(self.transaction_coin(&wallet.db, row).await?);
-            }
+            let counterparty = transaction
+                .counterparty_puzzle_hash
+                .map(|puzzle_hash| {
+                    encode_address(puzzle_hash.to_bytes(), &self.network().address_prefix)
+                })
+                .transpose()?;
+
+            let mut coins = Vec::new();
+
+            for spent in [true, false] {
+                for coin in wallet
+                    .db
+                    .transaction_history_coins(transaction.id, spent)
+                    .await?
+                {
+                    let Some(asset) = self.export_asset(&wallet.db, &coin).await? else {
+                        continue;
+                    };
 
-            for row in created_rows {
-                created.push(self.transaction_coin(&wallet.db, row).await?);
+                    if asset_filter.is_some_and(|asset_id| asset_id != asset.id) {
+                        continue;
+                    }
+
+                    let amount = i128::from(coin.coin_state.coin.amount);
+                    coins.push((asset, if spent { -amount } else { amount }));
+                }
             }
 
-            transactions.push(TransactionRecord {
-                height,
-                spent,
-                created,
-            });
+            for (asset, sent, received) in asset_totals(coins) {
+                let fee = if asset.id.is_none() {
+                    transaction.fee
+                } else {
+                    0
+                };
+
+                rows.push(ExportRow {
+                    transaction_id: hex::encode(transaction.id),
+                    height,
+                    timestamp,
+                    date: timestamp.and_then(format_timestamp),
+                    kind: export_kind(sent, received, fee),
+                    ticker: asset.ticker,
+                    asset_id: asset.display_id,
+                    sent: format_amount(sent, asset.decimals),
+                    received: format_amount(received, asset.decimals),
+                    fee: format_amount(fee, self.unit.decimals),
+                    counterparty: counterparty.clone(),
+                    label: transaction.label.clone(),
+                });
+            }
         }
 
-        Ok(GetTransactionsResponse {
-            transactions,
-            total: heights.len().try_into()?,
-        })
+        let content = match req.format {
+            ExportFormat::Csv => export_csv(&rows),
+            ExportFormat::Json => serde_json::to_string_pretty(&rows)?,
+        };
+
+        Ok(ExportTransactionsResponse { content })
     }
 
     pub async fn get_nft_status(&self, _req: GetNftStatus) -> Result<GetNftStatusResponse> {
@@ -418,8 +618,37 @@ impl Sage {
 
         let mut records = Vec::new();
 
-        if req.collection_id.as_deref() == Some("all") {
-            let nfts = match (req.sort_mode, req.include_hidden) {
+        let nfts = if req.search.is_some() || req.trait_type.is_some() {
+            let collection_id = if req.collection_id.as_deref() == Some("all") {
+                None
+            } else {
+                Some(req.collection_id.map(parse_collection_id).transpose()?)
+            };
+
+            let filter = NftSearchFilter {
+                collection_id,
+                include_hidden: req.include_hidden,
+                name: req.search,
+                trait_type: req.trait_type,
+                trait_value: req.trait_value,
+            };
+
+            match req.sort_mode {
+                NftSortMode::Name => {
+                    wallet
+                        .db
+                        .search_nfts_named(&filter, req.limit, req.offset)
+                        .await?
+                }
+                NftSortMode::Recent => {
+                    wallet
+                        .db
+                        .search_nfts_recent(&filter, req.limit, req.offset)
+                        .await?
+                }
+            }
+        } else if req.collection_id.as_deref() == Some("all") {
+            match (req.sort_mode, req.include_hidden) {
                 (NftSortMode::Name, true) => wallet.db.nfts_named(req.limit, req.offset).await?,
                 (NftSortMode::Name, false) => {
                     wallet.db.nfts_visible_named(req.limit, req.offset).await?
@@ -428,25 +657,11 @@ impl Sage {
                 (NftSortMode::Recent, false) => {
                     wallet.db.nfts_visible_recent(req.limit, req.offset).await?
                 }
-            };
-
-            for nft_row in nfts {
-                let Some(nft) = wallet.db.nft(nft_row.launcher_id).await? else {
-                    continue;
-                };
-
-                let collection_name = if let Some(collection_id) = nft_row.collection_id {
-                    wallet.db.collection_name(collection_id).await?
-                } else {
-                    None
-                };
-
-                records.push(self.nft_record(nft_row, nft, collection_name)?);
             }
         } else {
             let collection_id = req.collection_id.map(parse_collection_id).transpose()?;
 
-            let nfts = match (req.sort_mode, req.include_hidden, collection_id) {
+            match (req.sort_mode, req.include_hidden, collection_id) {
                 (NftSortMode::Name, true, Some(collection_id)) => {
                     wallet
                         .db
@@ -495,26 +710,60 @@ impl Sage {
                         .no_collection_nfts_visible_recent(req.limit, req.offset)
                         .await?
                 }
-            };
+            }
+        };
 
-            for nft_row in nfts {
-                let Some(nft) = wallet.db.nft(nft_row.launcher_id).await? else {
-                    continue;
-                };
+        for nft_row in nfts {
+            let Some(nft) = wallet.db.nft(nft_row.launcher_id).await? else {
+                continue;
+            };
 
-                let collection_name = if let Some(collection_id) = nft_row.collection_id {
-                    wallet.db.collection_name(collection_id).await?
-                } else {
-                    None
-                };
+            let collection_name = if let Some(collection_id) = nft_row.collection_id {
+                wallet.db.collection_name(collection_id).await?
+            } else {
+                None
+            };
 
-                records.push(self.nft_record(nft_row, nft, collection_name)?);
-            }
+            records.push(
+                self.nft_record(&wallet.db, nft_row, nft, collection_name)
+                    .await?,
+            );
         }
 
         Ok(GetNftsResponse { nfts: records })
     }
 
+    pub async fn get_nft_traits(&self, req: GetNftTraits) -> Result<GetNftTraitsResponse> {
+        let wallet = self.wallet()?;
+
+        let collection_id = if req.collection_id.as_deref() == Some("all") {
+            None
+        } else {
+            Some(req.collection_id.map(parse_collection_id).transpose()?)
+        };
+
+        let total = wallet.db.owned_nft_count(collection_id).await?;
+
+        let traits = wallet
+            .db
+            .nft_trait_counts(collection_id)
+            .await?
+            .into_iter()
+            .map(|row| NftTraitRecord {
+                trait_type: row.trait_type,
+                value: row.value,
+                count: row.count,
+                rarity: if total == 0 {
+                    0.0
+                } else {
+                    f64::from(row.count) / f64::from(total)
+                },
+            })
+            .collect();
+
+        Ok(GetNftTraitsResponse { total, traits })
+    }
+
     pub async fn get_nft(&self, req: GetNft) -> Result<GetNftResponse> {
         let wallet = self.wallet()?;
 
@@ -535,7 +784,10 @@ impl Sage {
         };
 
         Ok(GetNftResponse {
-            nft: Some(self.nft_record(nft_row, nft, collection_name)?),
+            nft: Some(
+                self.nft_record(&wallet.db, nft_row, nft, collection_name)
+                    .await?,
+            ),
         })
     }
 
@@ -555,8 +807,20 @@ impl Sage {
         let data_hash = metadata.as_ref().and_then(|m| m.data_hash);
         let metadata_hash = metadata.as_ref().and_then(|m| m.metadata_hash);
 
+        // Media that isn't cached is fetched in the background by the sync manager, rather than
+        // holding up the request on the network. If it was fetched before, it has since been
+        // evicted from the cache, so its URIs need to be checked again.
         let data = if let Some(hash) = data_hash {
-            wallet.db.fetch_nft_data(hash).await?
+            let data = self
+                .media_cache()
+                .get(hash, req.size == NftDataSize::Thumbnail)
+                .await?;
+
+            if data.is_none() {
+                wallet.db.reset_fetched_nft_uris(hash).await?;
+            }
+
+            data
         } else {
             None
         };
@@ -582,8 +846,9 @@ impl Sage {
         })
     }
 
-    fn nft_record(
+    async fn nft_record(
         &self,
+        db: &Database,
         nft_row: NftRow,
         nft: Nft<Program>,
         collection_name: Option<String>,
@@ -596,6 +861,41 @@ impl Sage {
         let metadata_hash = metadata.as_ref().and_then(|m| m.metadata_hash);
         let license_hash = metadata.as_ref().and_then(|m| m.license_hash);
 
+        let attributes = db
+            .nft_attributes(nft_row.launcher_id)
+            .await?
+            .into_iter()
+            .map(|attribute| NftAttributeRecord {
+                trait_type: attribute.trait_type,
+                value: attribute.value,
+            })
+            .collect();
+
+        let mut uri_health = Vec::new();
+
+        for hash in [data_hash, metadata_hash, license_hash]
+            .into_iter()
+            .flatten()
+            .unique()
+        {
+            for row in db.nft_uris(hash).await? {
+                uri_health.push(NftUriRecord {
+                    uri: row.uri,
+                    hash: hex::encode(row.hash),
+                    status: match (row.checked, &row.last_error) {
+                        (true, None) => NftUriStatus::Fetched,
+                        (true, Some(_)) => NftUriStatus::Failed,
+                        (false, _) if row.attempts == 0 => NftUriStatus::Pending,
+                        (false, _) => NftUriStatus::Retrying,
+                    },
+                    attempts: row.attempts,
+                    last_error: row.last_error,
+                    last_attempt: row.last_attempt,
+                    next_retry: row.next_retry,
+                });
+            }
+        }
+
         Ok(NftRecord {
             launcher_id: encode_address(nft_row.launcher_id.to_bytes(), "nft")?,
             collection_id: nft_row
@@ -648,9 +948,141 @@ impl Sage {
                 .map(|m| m.edition_total.try_into())
                 .transpose()?,
             created_height: nft_row.created_height,
+            uri_health,
+            attributes,
         })
     }
 
+    async fn transaction_record(
+        &self,
+        db: &Database,
+        row: TransactionHistoryRow,
+    ) -> Result<TransactionRecord> {
+        let mut spent = Vec::new();
+        let mut created = Vec::new();
+
+        for coin in db.transaction_history_coins(row.id, true).await? {
+            spent.push(self.transaction_coin(db, coin).await?);
+        }
+
+        for coin in db.transaction_history_coins(row.id, false).await? {
+            created.push(self.transaction_coin(db, coin).await?);
+        }
+
+        Ok(TransactionRecord {
+            id: hex::encode(row.id),
+            height: row.height,
+            direction: match row.direction {
+                HistoryDirection::Incoming => TransactionDirection::Incoming,
+                HistoryDirection::Outgoing => TransactionDirection::Outgoing,
+                HistoryDirection::Internal => TransactionDirection::Internal,
+            },
+            fee: Amount::u64(row.fee),
+            counterparty_address: row
+                .counterparty_puzzle_hash
+                .map(|puzzle_hash| {
+                    encode_address(puzzle_hash.to_bytes(), &self.network().address_prefix)
+                })
+                .transpose()?,
+            memos: db
+                .transaction_history_memos(row.id)
+                .await?
+                .into_iter()
+                .map(hex::encode)
+                .collect(),
+            label: row.label,
+            spent,
+            created,
+        })
+    }
+
+    /// Looks up the timestamp of a block, caching it so that peers are only asked once.
+    async fn block_timestamp(&self, db: &Database, height: u32) -> Result<Option<u64>> {
+        if let Some(timestamp) = db.block_timestamp(height).await? {
+            return Ok(Some(timestamp));
+        }
+
+        let Some(peer) = self.peer_state.lock().await.acquire_peer() else {
+            return Ok(None);
+        };
+
+        let Some(timestamp) = peer.block_timestamp(height).await? else {
+            return Ok(None);
+        };
+
+        db.insert_block_timestamp(height, timestamp).await?;
+
+        Ok(Some(timestamp))
+    }
+
+    async fn export_asset(
+        &self,
+        db: &Database,
+        coin: &CoinStateRow,
+    ) -> Result<Option<ExportAsset>> {
+        let coin_id = coin.coin_state.coin.coin_id();
+
+        let asset_id = match coin.kind {
+            CoinKind::Unknown => return Ok(None),
+            CoinKind::Xch => None,
+            CoinKind::Cat => {
+                let Some(cat) = db.cat_coin(coin_id).await? else {
+                    return Ok(None);
+                };
+                Some(cat.asset_id)
+            }
+            CoinKind::Clawback => {
+                let Some(row) = db.clawback_coin(coin_id).await? else {
+                    return Ok(None);
+                };
+                row.clawback.asset_id
+            }
+            CoinKind::Nft => {
+                let Some(nft) = db.nft_by_coin_id(coin_id).await? else {
+                    return Ok(None);
+                };
+
+                return Ok(Some(ExportAsset {
+                    id: Some(nft.info.launcher_id),
+                    display_id: Some(encode_address(nft.info.launcher_id.to_bytes(), "nft")?),
+                    ticker: None,
+                    decimals: 0,
+                }));
+            }
+            CoinKind::Did => {
+                let Some(did) = db.did_by_coin_id(coin_id).await? else {
+                    return Ok(None);
+                };
+
+                return Ok(Some(ExportAsset {
+                    id: Some(did.info.launcher_id),
+                    display_id: Some(encode_address(
+                        did.info.launcher_id.to_bytes(),
+                        "did:chia:",
+                    )?),
+                    ticker: None,
+                    decimals: 0,
+                }));
+            }
+        };
+
+        let Some(asset_id) = asset_id else {
+            return Ok(Some(ExportAsset {
+                id: None,
+                display_id: None,
+                ticker: Some(self.unit.ticker.clone()),
+                decimals: self.unit.decimals,
+            }));
+        };
+
+        Ok(Some(ExportAsset {
+            id: Some(asset_id),
+            display_id: Some(hex::encode(asset_id)),
+            ticker: db.cat(asset_id).await?.and_then(|cat| cat.ticker),
+            decimals: 3,
+        }))
+    }
+
     async fn transaction_coin(&self, db: &Database, coin: CoinStateRow) -> Result<TransactionCoin> {
         let coin_id = coin.coin_state.coin.coin_id();
 
@@ -695,7 +1127,7 @@ impl Sage {
                     let data_hash = metadata.as_ref().and_then(|m| m.data_hash);
 
                     let data = if let Some(hash) = data_hash {
-                        db.fetch_nft_data(hash).await?
+                        self.media_cache().get(hash, true).await?
                     } else {
                         None
                     };
@@ -715,6 +1147,25 @@ impl Sage {
                     (AssetKind::Unknown, None)
                 }
             }
+            CoinKind::Clawback => {
+                if let Some(row) = db.clawback_coin(coin_id).await? {
+                    let kind = if let Some(asset_id) = row.clawback.asset_id {
+                        let cat = db.cat(asset_id).await?;
+                        AssetKind::Cat {
+                            asset_id: hex::encode(asset_id),
+                            name: cat.as_ref().and_then(|cat| cat.name.clone()),
+                            ticker: cat.as_ref().and_then(|cat| cat.ticker.clone()),
+                            icon_url: cat.and_then(|cat| cat.icon),
+                        }
+                    } else {
+                        AssetKind::Xch
+                    };
+
+                    (kind, Some(row.clawback.recipient_puzzle_hash))
+                } else {
+                    (AssetKind::Unknown, None)
+                }
+            }
             CoinKind::Did => {
                 if let Some(did) = db.did_by_coin_id(coin_id).await? {
                     let row = db.did_row(did.info.launcher_id).await?;
@@ -734,6 +1185,14 @@ impl Sage {
             }
         };
 
+        let contact = match p2_puzzle_hash {
+            Some(p2_puzzle_hash) => db
+                .contact_by_puzzle_hash(p2_puzzle_hash)
+                .await?
+                .map(|row| row.name),
+            None => None,
+        };
+
         Ok(TransactionCoin {
             coin_id: hex::encode(coin_id),
             address: p2_puzzle_hash
@@ -741,8 +1200,26 @@ impl Sage {
                     encode_address(p2_puzzle_hash.to_bytes(), &self.network().address_prefix)
                 })
                 .transpose()?,
+            contact,
             amount: Amount::u64(coin.coin_state.coin.amount),
             kind,
         })
     }
+
+    async fn derivation_record(
+        &self,
+        db: &Database,
+        row: DerivationRow,
+    ) -> Result<DerivationRecord> {
+        Ok(DerivationRecord {
+            index: row.index,
+            hardened: row.hardened,
+            public_key: hex::encode(row.synthetic_key.to_bytes()),
+            address: encode_address(
+                row.p2_puzzle_hash.to_bytes(),
+                &self.network().address_prefix,
+            )?,
+            label: db.derivation_label(row.p2_puzzle_hash).await?,
+        })
+    }
 }
@@ -0,0 +1,162 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use chia_wallet_sdk::encode_address;
+use sage_api::{
+    Amount, CreateInvoice, CreateInvoiceResponse, GetInvoice, GetInvoiceResponse, GetInvoices,
+    GetInvoicesResponse, InvoicePaymentRecord, InvoiceRecord, InvoiceRecordStatus, ParsePaymentUri,
+    ParsePaymentUriResponse,
+};
+use sage_database::{InvoiceRow, InvoiceStatus};
+
+use crate::{parse_asset_id, parse_cat_amount, Error, PaymentUri, Result, Sage};
+
+impl Sage {
+    pub async fn create_invoice(&self, req: CreateInvoice) -> Result<CreateInvoiceResponse> {
+        let wallet = self.wallet()?;
+
+        let (asset_id, amount) = if req.asset == "xch" {
+            (None, self.parse_amount(req.amount)?)
+        } else {
+            (
+                Some(parse_asset_id(req.asset)?),
+                parse_cat_amount(req.amount)?,
+            )
+        };
+
+        if amount == 0 {
+            return Err(Error::InvalidAmount(amount.to_string()));
+        }
+
+        let created_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("system time is before the UNIX epoch")
+            .as_secs();
+
+        // The first unused address is shown as the receive address, and may have been shared
+        // already, so the invoice gets the one after it. The address is reserved in the same
+        // transaction the invoice is inserted in, so that it's never handed out twice.
+        let (mut tx, p2_puzzle_hash) = self
+            .next_p2_puzzle_hash(&wallet, self.hardened_addresses(), 1, true, None)
+            .await?;
+
+        let mut row = InvoiceRow {
+            id: 0,
+            p2_puzzle_hash,
+            asset_id,
+            amount,
+            paid_amount: 0,
+            memo: req.memo,
+            expires_at: req.expires_at,
+            created_at,
+            status: InvoiceStatus::Unpaid,
+        };
+
+        row.id = tx.insert_invoice(row.clone()).await?;
+        tx.commit().await?;
+
+        Ok(CreateInvoiceResponse {
+            invoice: self.invoice_record(row)?,
+        })
+    }
+
+    pub async fn get_invoices(&self, req: GetInvoices) -> Result<GetInvoicesResponse> {
+        let wallet = self.wallet()?;
+
+        let invoices = wallet
+            .db
+            .invoices(req.limit, req.offset)
+            .await?
+            .into_iter()
+            .map(|row| self.invoice_record(row))
+            .collect::<Result<_>>()?;
+
+        Ok(GetInvoicesResponse {
+            invoices,
+            total: wallet.db.invoice_count().await?,
+        })
+    }
+
+    pub async fn get_invoice(&self, req: GetInvoice) -> Result<GetInvoiceResponse> {
+        let wallet = self.wallet()?;
+
+        let Some(row) = wallet.db.invoice(req.id).await? else {
+            return Ok(GetInvoiceResponse {
+                invoice: None,
+                payments: Vec::new(),
+            });
+        };
+
+        let payments = wallet
+            .db
+            .invoice_payments(req.id)
+            .await?
+            .into_iter()
+            .map(|payment| InvoicePaymentRecord {
+                coin_id: hex::encode(payment.coin_id),
+                amount: Amount::u64(payment.amount),
+                height: payment.height,
+            })
+            .collect();
+
+        Ok(GetInvoiceResponse {
+            invoice: Some(self.invoice_record(row)?),
+            payments,
+        })
+    }
+
+    pub fn parse_payment_uri(&self, req: ParsePaymentUri) -> Result<ParsePaymentUriResponse> {
+        let payment = PaymentUri::decode(&req.uri, self.unit.decimals)?;
+
+        self.parse_address(payment.address.clone())?;
+
+        Ok(ParsePaymentUriResponse {
+            address: payment.address,
+            asset_id: payment.asset_id.map(hex::encode),
+            amount: payment.amount.map(Amount::u64),
+            memo: payment.memo,
+        })
+    }
+
+    fn invoice_record(&self, row: InvoiceRow) -> Result<InvoiceRecord> {
+        let address = encode_address(
+            row.p2_puzzle_hash.to_bytes(),
+            &self.network().address_prefix,
+        )?;
+
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("system time is before the UNIX epoch")
+            .as_secs();
+
+        let expired = row.expires_at.is_some_and(|expires_at| expires_at <= now);
+
+        let uri = PaymentUri {
+            address: address.clone(),
+            asset_id: row.asset_id,
+            amount: Some(row.amount),
+            memo: row.memo.clone(),
+        }
+        .encode(self.unit.decimals);
+
+        Ok(InvoiceRecord {
+            id: row.id,
+            address,
+            asset_id: row.asset_id.map(hex::encode),
+            amount: Amount::u64(row.amount),
+            paid_amount: Amount::u64(row.paid_amount),
+            memo: row.memo,
+            expires_at: row.expires_at,
+            created_at: row.created_at,
+            status: match row.status {
+                InvoiceStatus::Unpaid | InvoiceStatus::PartiallyPaid if expired => {
+                    InvoiceRecordStatus::Expired
+                }
+                InvoiceStatus::Unpaid => InvoiceRecordStatus::Unpaid,
+                InvoiceStatus::PartiallyPaid => InvoiceRecordStatus::PartiallyPaid,
+                InvoiceStatus::Paid => InvoiceRecordStatus::Paid,
+                InvoiceStatus::Overpaid => InvoiceRecordStatus::Overpaid,
+            },
+            uri,
+        })
+    }
+}
@@ -6,10 +6,12 @@ use itertools::Itertools;
 use rand::{Rng, SeedableRng};
 use rand_chacha::ChaCha20Rng;
 use sage_api::{
-    DeleteKey, DeleteKeyResponse, GenerateMnemonic, GenerateMnemonicResponse, GetKey,
-    GetKeyResponse, GetKeys, GetKeysResponse, GetSecretKey, GetSecretKeyResponse, ImportKey,
-    ImportKeyResponse, KeyInfo, KeyKind, Login, LoginResponse, Logout, LogoutResponse, RenameKey,
-    RenameKeyResponse, Resync, ResyncResponse, SecretKeyInfo,
+    ChangePassword, ChangePasswordResponse, DeleteKey, DeleteKeyResponse, GenerateMnemonic,
+    GenerateMnemonicResponse, GetKey, GetKeyResponse, GetKeys, GetKeysResponse, GetSecretKey,
+    GetSecretKeyResponse, ImportKey, ImportKeyResponse, KeyInfo, KeyKind, LockKeychain,
+    LockKeychainResponse, Login, LoginResponse, Logout, LogoutResponse, RenameKey,
+    RenameKeyResponse, Resync, ResyncResponse, SecretKeyInfo, UnlockKeychain,
+    UnlockKeychainResponse,
 };
 
 use crate::{Error, Result, Sage};
@@ -88,6 +90,8 @@ impl Sage {
             key_hex = &key_hex[2..];
         }
 
+        let password = req.password.into_bytes();
+
         let fingerprint = if let Ok(bytes) = hex::decode(key_hex) {
             if let Ok(master_pk) = bytes.clone().try_into() {
                 let master_pk = PublicKey::from_bytes(&master_pk)?;
@@ -96,7 +100,7 @@ impl Sage {
                 let master_sk = SecretKey::from_bytes(&master_sk)?;
 
                 if req.save_secrets {
-                    self.keychain.add_secret_key(&master_sk, b"")?
+                    self.keychain.add_secret_key(&master_sk, &password)?
                 } else {
                     self.keychain.add_public_key(&master_sk.public_key())?
                 }
@@ -107,7 +111,7 @@ impl Sage {
             let mnemonic = Mnemonic::from_str(&req.key)?;
 
             if req.save_secrets {
-                self.keychain.add_mnemonic(&mnemonic, b"")?
+                self.keychain.add_mnemonic(&mnemonic, &password)?
             } else {
                 let master_sk = SecretKey::from_seed(&mnemonic.to_seed(""));
                 self.keychain.add_public_key(&master_sk.public_key())?
@@ -121,6 +125,10 @@ impl Sage {
         self.save_keychain()?;
         self.save_config()?;
 
+        if self.keychain.has_secret_key(fingerprint) {
+            self.session.unlock(password);
+        }
+
         if req.login {
             self.switch_wallet().await?;
         }
@@ -128,6 +136,30 @@ impl Sage {
         Ok(ImportKeyResponse { fingerprint })
     }
 
+    pub async fn unlock_keychain(&self, req: UnlockKeychain) -> Result<UnlockKeychainResponse> {
+        let password = req.password.into_bytes();
+        self.keychain.verify_password(&password)?;
+        self.session.unlock(password);
+        self.sync_hardened_derivations().await?;
+        Ok(UnlockKeychainResponse {})
+    }
+
+    pub fn lock_keychain(&self, _req: LockKeychain) -> Result<LockKeychainResponse> {
+        self.session.lock();
+        Ok(LockKeychainResponse {})
+    }
+
+    pub fn change_password(&mut self, req: ChangePassword) -> Result<ChangePasswordResponse> {
+        let new_password = req.new_password.into_bytes();
+
+        self.keychain
+            .change_password(req.old_password.as_bytes(), &new_password)?;
+        self.save_keychain()?;
+        self.session.unlock(new_password);
+
+        Ok(ChangePasswordResponse {})
+    }
+
     pub fn delete_key(&mut self, req: DeleteKey) -> Result<DeleteKeyResponse> {
         self.keychain.remove(req.fingerprint);
 
@@ -186,8 +218,7 @@ impl Sage {
     }
 
     pub fn get_secret_key(&self, req: GetSecretKey) -> Result<GetSecretKeyResponse> {
-        let (mnemonic, Some(secret_key)) = self.keychain.extract_secrets(req.fingerprint, b"")?
-        else {
+        let (mnemonic, Some(secret_key)) = self.extract_secrets(req.fingerprint)? else {
             return Ok(GetSecretKeyResponse { secrets: None });
         };
 
@@ -10,10 +10,11 @@ use chrono::{Local, TimeZone};
 use clvmr::Allocator;
 use indexmap::IndexMap;
 use sage_api::{
-    Amount, CatAmount, DeleteOffer, DeleteOfferResponse, GetOffer, GetOfferResponse, GetOffers,
-    GetOffersResponse, ImportOffer, ImportOfferResponse, MakeOffer, MakeOfferResponse, OfferAssets,
-    OfferCat, OfferNft, OfferRecord, OfferRecordStatus, OfferSummary, OfferXch, TakeOffer,
-    TakeOfferResponse, ViewOffer, ViewOfferResponse,
+    Amount, CancelOffer, CancelOffers, CatAmount, DeleteOffer, DeleteOfferResponse, GetOffer,
+    GetOfferResponse, GetOffers, GetOffersResponse, ImportOffer, ImportOfferResponse, MakeOffer,
+    MakeOfferResponse, OfferAssets, OfferCat, OfferNft, OfferRecord, OfferRecordStatus,
+    OfferSummary, OfferXch, TakeOffer, TakeOfferResponse, TransactionResponse, ViewOffer,
+    ViewOfferResponse,
 };
 use sage_database::{OfferCatRow, OfferNftRow, OfferRow, OfferStatus, OfferXchRow};
 use sage_wallet::{
@@ -75,29 +76,31 @@ impl Sage {
         }
 
         let fee = self.parse_amount(req.fee)?;
-
-        let unsigned = wallet
-            .make_offer(
-                MakerSide {
-                    xch: offered_xch,
-                    cats: offered_cats,
-                    nfts: offered_nfts,
-                    fee,
-                },
-                TakerSide {
-                    xch: requested_xch,
-                    cats: requested_cats,
-                    nfts: requested_nfts,
-                },
-                req.expires_at_second,
-                false,
-                true,
-            )
+        let selection = self.coin_selection(req.coin_ids, req.coin_selection_strategy)?;
+
+        let unsigned = self
+            .build_with_fee(fee, req.fee_target_seconds, |fee| {
+                wallet.make_offer(
+                    MakerSide {
+                        xch: offered_xch,
+                        cats: offered_cats.clone(),
+                        nfts: offered_nfts.clone(),
+                        fee,
+                    },
+                    TakerSide {
+                        xch: requested_xch,
+                        cats: requested_cats.clone(),
+                        nfts: requested_nfts.clone(),
+                    },
+                    req.expires_at_second,
+                    &selection,
+                    self.hardened_addresses(),
+                    true,
+                )
+            })
             .await?;
 
-        let (_mnemonic, Some(master_sk)) =
-            self.keychain.extract_secrets(wallet.fingerprint, b"")?
-        else {
+        let (_mnemonic, Some(master_sk)) = self.extract_secrets(wallet.fingerprint)? else {
             return Err(Error::NoSigningKey);
         };
 
@@ -120,12 +123,21 @@ impl Sage {
 
         let offer = Offer::decode(&req.offer)?;
         let fee = self.parse_amount(req.fee)?;
+        let selection = self.default_coin_selection();
 
-        let unsigned = wallet.take_offer(offer, fee, false, true).await?;
+        let unsigned = self
+            .build_with_fee(fee, req.fee_target_seconds, |fee| {
+                wallet.take_offer(
+                    offer.clone(),
+                    fee,
+                    &selection,
+                    self.hardened_addresses(),
+                    true,
+                )
+            })
+            .await?;
 
-        let (_mnemonic, Some(master_sk)) =
-            self.keychain.extract_secrets(wallet.fingerprint, b"")?
-        else {
+        let (_mnemonic, Some(master_sk)) = self.extract_secrets(wallet.fingerprint)? else {
             return Err(Error::NoSigningKey);
         };
 
@@ -274,6 +286,7 @@ impl Sage {
 
                 if let Some(hash) = metadata.data_hash {
                     if let Some(data) = lookup_from_uris_with_hash(
+                        &self.uri_resolver(),
                         metadata.data_uris.clone(),
                         Duration::from_secs(10),
                         Duration::from_secs(5),
@@ -287,6 +300,7 @@ impl Sage {
 
                 if let Some(hash) = metadata.metadata_hash {
                     if let Some(data) = lookup_from_uris_with_hash(
+                        &self.uri_resolver(),
                         metadata.metadata_uris.clone(),
                         Duration::from_secs(10),
                         Duration::from_secs(5),
@@ -300,6 +314,7 @@ impl Sage {
 
                 extract_nft_data(
                     Some(&wallet.db),
+                    &self.media_cache(),
                     Some(metadata),
                     &ConfirmationInfo::default(),
                 )
@@ -350,6 +365,7 @@ impl Sage {
 
                     if let Some(hash) = metadata.data_hash {
                         if let Some(data) = lookup_from_uris_with_hash(
+                            &self.uri_resolver(),
                             metadata.data_uris.clone(),
                             Duration::from_secs(10),
                             Duration::from_secs(5),
@@ -363,6 +379,7 @@ impl Sage {
 
                     if let Some(hash) = metadata.metadata_hash {
                         if let Some(data) = lookup_from_uris_with_hash(
+                            &self.uri_resolver(),
                             metadata.metadata_uris.clone(),
                             Duration::from_secs(10),
                             Duration::from_secs(5),
@@ -376,6 +393,7 @@ impl Sage {
 
                     extract_nft_data(
                         Some(&wallet.db),
+                        &self.media_cache(),
                         Some(metadata),
                         &ConfirmationInfo::default(),
                     )
@@ -416,6 +434,7 @@ impl Sage {
             fee: maker.fee,
             status: OfferStatus::Active,
             inserted_timestamp,
+            cancelling: false,
         })
         .await?;
 
@@ -493,6 +512,51 @@ impl Sage {
         Ok(DeleteOfferResponse {})
     }
 
+    pub async fn cancel_offer(&self, req: CancelOffer) -> Result<TransactionResponse> {
+        let wallet = self.wallet()?;
+        let offer_id = parse_offer_id(req.offer_id)?;
+        let fee = self.parse_amount(req.fee)?;
+        let selection = self.default_coin_selection();
+
+        let offer = wallet
+            .db
+            .get_offer(offer_id)
+            .await?
+            .ok_or(Error::MissingOffer(offer_id))?;
+
+        if offer.status != OfferStatus::Active {
+            return Err(Error::OfferNotActive(offer_id));
+        }
+
+        let coin_spends = self
+            .build_with_fee(fee, req.fee_target_seconds, |fee| {
+                wallet.cancel_offer(offer_id, fee, &selection, self.hardened_addresses(), true)
+            })
+            .await?;
+        self.transact(coin_spends, req.auto_submit).await
+    }
+
+    pub async fn cancel_offers(&self, req: CancelOffers) -> Result<TransactionResponse> {
+        let wallet = self.wallet()?;
+        let fee = self.parse_amount(req.fee)?;
+        let selection = self.default_coin_selection();
+
+        let offer_ids = wallet.cancellable_offers().await?;
+
+        let coin_spends = self
+            .build_with_fee(fee, req.fee_target_seconds, |fee| {
+                wallet.cancel_offers(
+                    offer_ids.clone(),
+                    fee,
+                    &selection,
+                    self.hardened_addresses(),
+                    true,
+                )
+            })
+            .await?;
+        self.transact(coin_spends, req.auto_submit).await
+    }
+
     async fn offer_record(&self, wallet: &Wallet, offer: OfferRow) -> Result<OfferRecord> {
         let xch = wallet.db.offer_xch(offer.offer_id).await?;
         let cats = wallet.db.offer_cats(offer.offer_id).await?;
@@ -3,14 +3,15 @@ use std::time::Duration;
 use itertools::Itertools;
 use sage_api::{
     AddPeer, AddPeerResponse, GetNetworks, GetNetworksResponse, GetPeers, GetPeersResponse,
-    PeerRecord, RemovePeer, RemovePeerResponse, SetDerivationBatchSize,
+    PeerRecord, RemovePeer, RemovePeerResponse, SetAutoConsolidate, SetAutoConsolidateResponse,
+    SetCoinSelectionStrategy, SetCoinSelectionStrategyResponse, SetDerivationBatchSize,
     SetDerivationBatchSizeResponse, SetDeriveAutomatically, SetDeriveAutomaticallyResponse,
-    SetDiscoverPeers, SetDiscoverPeersResponse, SetNetworkId, SetNetworkIdResponse, SetTargetPeers,
-    SetTargetPeersResponse,
+    SetDiscoverPeers, SetDiscoverPeersResponse, SetHardenedAddresses, SetHardenedAddressesResponse,
+    SetNetworkId, SetNetworkIdResponse, SetTargetPeers, SetTargetPeersResponse,
 };
 use sage_wallet::SyncCommand;
 
-use crate::{parse_genesis_challenge, Result, Sage};
+use crate::{parse_genesis_challenge, Error, Result, Sage};
 
 impl Sage {
     pub async fn get_peers(&self, _req: GetPeers) -> Result<GetPeersResponse> {
@@ -134,6 +135,56 @@ impl Sage {
         Ok(SetDerivationBatchSizeResponse {})
     }
 
+    pub async fn set_hardened_addresses(
+        &mut self,
+        req: SetHardenedAddresses,
+    ) -> Result<SetHardenedAddressesResponse> {
+        // Hardened keys can only be derived from the secret key, so it has to be available.
+        if req.hardened_addresses {
+            if !self.keychain.has_secret_key(req.fingerprint) {
+                return Err(Error::NoSigningKey);
+            }
+
+            self.password()?;
+        }
+
+        let config = self.try_wallet_config_mut(req.fingerprint);
+        config.hardened_addresses = req.hardened_addresses;
+        self.save_config()?;
+
+        self.sync_hardened_derivations().await?;
+
+        Ok(SetHardenedAddressesResponse {})
+    }
+
+    pub fn set_coin_selection_strategy(
+        &mut self,
+        req: SetCoinSelectionStrategy,
+    ) -> Result<SetCoinSelectionStrategyResponse> {
+        let config = self.try_wallet_config_mut(req.fingerprint);
+
+        if config.coin_selection_strategy != req.coin_selection_strategy {
+            config.coin_selection_strategy = req.coin_selection_strategy;
+            self.save_config()?;
+        }
+
+        Ok(SetCoinSelectionStrategyResponse {})
+    }
+
+    pub fn set_auto_consolidate(
+        &mut self,
+        req: SetAutoConsolidate,
+    ) -> Result<SetAutoConsolidateResponse> {
+        let config = self.try_wallet_config_mut(req.fingerprint);
+
+        if config.auto_consolidate != req.auto_consolidate {
+            config.auto_consolidate = req.auto_consolidate;
+            self.save_config()?;
+        }
+
+        Ok(SetAutoConsolidateResponse {})
+    }
+
     pub fn get_networks(&mut self, _req: GetNetworks) -> Result<GetNetworksResponse> {
         Ok(GetNetworksResponse {
             networks: self.networks.clone(),
@@ -1,22 +1,29 @@
 use std::time::Duration;
 
 use chia::{
-    protocol::{Bytes, CoinSpend},
+    protocol::{Bytes32, CoinSpend, SpendBundle},
     puzzles::nft::NftMetadata,
 };
 use chia_wallet_sdk::MetadataUpdate;
 use sage_api::{
-    AddNftUri, AssignNftsToDid, BulkMintNfts, CombineCat, CombineXch, CreateDid, IssueCat,
-    NftUriKind, SendCat, SendXch, SignCoinSpends, SignCoinSpendsResponse, SplitCat, SplitXch,
-    SubmitTransaction, SubmitTransactionResponse, TransactionResponse, TransferDids, TransferNfts,
-    ViewCoinSpends, ViewCoinSpendsResponse,
+    AddNftUri, Amount, AssignNftsToDid, AutoConsolidate, BulkMintNfts, CancelTransaction,
+    CancelTransactionResponse, ClaimClawback, CombineCat, CombineXch, CreateDid, EstimateFee,
+    EstimateFeeResponse, IncreaseTransactionFee, IncreaseTransactionFeeResponse, IssueCat, MeltCat,
+    MintMoreCat, NftUriKind, PrepareNftMints, PrepareNftMintsResponse, RevokeClawback, SendCat,
+    SendCatMulti, SendXch, SendXchMulti, SignCoinSpends, SignCoinSpendsResponse, SplitCat,
+    SplitIntoDenominations, SplitXch, SubmitTransaction, SubmitTransactionResponse,
+    TransactionResponse, TransferDids, TransferNfts, ViewCoinSpends, ViewCoinSpendsResponse,
 };
 use sage_database::CatRow;
-use sage_wallet::{fetch_uris, WalletNftMint};
+use sage_wallet::{
+    fetch_uris, submit_to_peers, validate_chip0007, Data, Denomination, Status, WalletNftMint,
+    WalletPayment, MEMPOOL_MIN_FEE_INCREASE,
+};
 
 use crate::{
     fetch_cats, fetch_coins, json_bundle, json_spend, parse_asset_id, parse_cat_amount,
-    parse_did_id, parse_nft_id, rust_bundle, rust_spend, ConfirmationInfo, Result, Sage,
+    parse_coin_id, parse_did_id, parse_hash, parse_memos, parse_nft_id, parse_transaction_id,
+    rust_bundle, rust_spend, ConfirmationInfo, Error, Result, Sage,
 };
 
 impl Sage {
@@ -25,15 +32,53 @@ impl Sage {
         let puzzle_hash = self.parse_address(req.address)?;
         let amount = self.parse_amount(req.amount)?;
         let fee = self.parse_amount(req.fee)?;
+        let selection = self.coin_selection(req.coin_ids, req.coin_selection_strategy)?;
+
+        let memos = parse_memos(req.memos)?;
+
+        let coin_spends = self
+            .build_with_fee(fee, req.fee_target_seconds, |fee| {
+                wallet.send_xch(
+                    puzzle_hash,
+                    amount,
+                    fee,
+                    memos.clone(),
+                    req.clawback_seconds,
+                    &selection,
+                    self.hardened_addresses(),
+                    true,
+                )
+            })
+            .await?;
+        self.transact(coin_spends, req.auto_submit).await
+    }
+
+    pub async fn send_xch_multi(&self, req: SendXchMulti) -> Result<TransactionResponse> {
+        let wallet = self.wallet()?;
+        let fee = self.parse_amount(req.fee)?;
+        let selection = self.coin_selection(req.coin_ids, req.coin_selection_strategy)?;
 
-        let mut memos = Vec::new();
+        let mut payments = Vec::new();
 
-        for memo in req.memos {
-            memos.push(Bytes::from(hex::decode(memo)?));
+        for payment in req.payments {
+            payments.push(WalletPayment {
+                puzzle_hash: self.parse_address(payment.address)?,
+                amount: self.parse_amount(payment.amount)?,
+                memos: parse_memos(payment.memos)?,
+                clawback_seconds: None,
+            });
         }
 
-        let coin_spends = wallet
-            .send_xch(puzzle_hash, amount, fee, memos, false, true)
+        let coin_spends = self
+            .build_with_fee(fee, req.fee_target_seconds, |fee| {
+                wallet.send_xch_multi(
+                    payments.clone(),
+                    fee,
+                    &selection,
+                    self.hardened_addresses(),
+                    true,
+                )
+            })
             .await?;
         self.transact(coin_spends, req.auto_submit).await
     }
@@ -43,7 +88,30 @@ impl Sage {
         let fee = self.parse_amount(req.fee)?;
         let coins = fetch_coins(&wallet, req.coin_ids).await?;
 
-        let coin_spends = wallet.combine_xch(coins, fee, false, true).await?;
+        let coin_spends = self
+            .build_with_fee(fee, req.fee_target_seconds, |fee| {
+                wallet.combine_xch(coins.clone(), fee, self.hardened_addresses(), true)
+            })
+            .await?;
+        self.transact(coin_spends, req.auto_submit).await
+    }
+
+    pub async fn auto_consolidate(&self, req: AutoConsolidate) -> Result<TransactionResponse> {
+        let wallet = self.wallet()?;
+        let dust_threshold = self.parse_amount(req.dust_threshold)?;
+        let fee = self.parse_amount(req.fee)?;
+
+        let coin_spends = self
+            .build_with_fee(fee, req.fee_target_seconds, |fee| {
+                wallet.auto_consolidate(
+                    req.max_coins_per_asset as usize,
+                    dust_threshold,
+                    fee,
+                    self.hardened_addresses(),
+                    true,
+                )
+            })
+            .await?;
         self.transact(coin_spends, req.auto_submit).await
     }
 
@@ -52,8 +120,16 @@ impl Sage {
         let fee = self.parse_amount(req.fee)?;
         let coins = fetch_coins(&wallet, req.coin_ids).await?;
 
-        let coin_spends = wallet
-            .split_xch(&coins, req.output_count as usize, fee, false, true)
+        let coin_spends = self
+            .build_with_fee(fee, req.fee_target_seconds, |fee| {
+                wallet.split_xch(
+                    &coins,
+                    req.output_count as usize,
+                    fee,
+                    self.hardened_addresses(),
+                    true,
+                )
+            })
             .await?;
         self.transact(coin_spends, req.auto_submit).await
     }
@@ -61,20 +137,102 @@ impl Sage {
     pub async fn combine_cat(&self, req: CombineCat) -> Result<TransactionResponse> {
         let wallet = self.wallet()?;
         let fee = self.parse_amount(req.fee)?;
+        let selection = self.default_coin_selection();
         let cats = fetch_cats(&wallet, req.coin_ids).await?;
 
-        let coin_spends = wallet.combine_cat(cats, fee, false, true).await?;
+        let coin_spends = self
+            .build_with_fee(fee, req.fee_target_seconds, |fee| {
+                wallet.combine_cat(
+                    cats.clone(),
+                    fee,
+                    &selection,
+                    self.hardened_addresses(),
+                    true,
+                )
+            })
+            .await?;
         self.transact(coin_spends, req.auto_submit).await
     }
 
     pub async fn split_cat(&self, req: SplitCat) -> Result<TransactionResponse> {
         let wallet = self.wallet()?;
         let fee = self.parse_amount(req.fee)?;
+        let selection = self.default_coin_selection();
         let cats = fetch_cats(&wallet, req.coin_ids).await?;
 
-        let coin_spends = wallet
-            .split_cat(cats, req.output_count as usize, fee, false, true)
+        let coin_spends = self
+            .build_with_fee(fee, req.fee_target_seconds, |fee| {
+                wallet.split_cat(
+                    cats.clone(),
+                    req.output_count as usize,
+                    fee,
+                    &selection,
+                    self.hardened_addresses(),
+                    true,
+                )
+            })
+            .await?;
+        self.transact(coin_spends, req.auto_submit).await
+    }
+
+    pub async fn split_into_denominations(
+        &self,
+        req: SplitIntoDenominations,
+    ) -> Result<TransactionResponse> {
+        let wallet = self.wallet()?;
+        let asset_id = req.asset_id.map(parse_asset_id).transpose()?;
+        let fee = self.parse_amount(req.fee)?;
+        let selection = self.coin_selection(req.coin_ids, req.coin_selection_strategy)?;
+
+        // Each output of a denomination is sent to a different address, plus one for the change.
+        let address_count = req
+            .outputs
+            .iter()
+            .map(|output| output.count)
+            .max()
+            .unwrap_or(0)
+            .saturating_add(1);
+
+        self.derive_unused_keys(&wallet, self.hardened_addresses(), address_count)
             .await?;
+
+        let mut denominations = Vec::new();
+
+        for output in req.outputs {
+            denominations.push(Denomination {
+                amount: if asset_id.is_some() {
+                    parse_cat_amount(output.amount)?
+                } else {
+                    self.parse_amount(output.amount)?
+                },
+                count: output.count as usize,
+            });
+        }
+
+        let coin_spends = if let Some(asset_id) = asset_id {
+            self.build_with_fee(fee, req.fee_target_seconds, |fee| {
+                wallet.split_cat_denominations(
+                    asset_id,
+                    &denominations,
+                    fee,
+                    &selection,
+                    self.hardened_addresses(),
+                    true,
+                )
+            })
+            .await?
+        } else {
+            self.build_with_fee(fee, req.fee_target_seconds, |fee| {
+                wallet.split_xch_denominations(
+                    &denominations,
+                    fee,
+                    &selection,
+                    self.hardened_addresses(),
+                    true,
+                )
+            })
+            .await?
+        };
         self.transact(coin_spends, req.auto_submit).await
     }
 
@@ -82,8 +240,33 @@ impl Sage {
         let wallet = self.wallet()?;
         let amount = parse_cat_amount(req.amount)?;
         let fee = self.parse_amount(req.fee)?;
+        let selection = self.coin_selection(req.coin_ids, req.coin_selection_strategy)?;
+
+        // Multi-issuance CATs are tied to one of our synthetic keys, so that more can be issued later.
+        // Each one reserves a different address, since the asset id only depends on the key.
+        let multi_issuance_key = if req.multi_issuance {
+            let (mut tx, p2_puzzle_hash) = self
+                .next_p2_puzzle_hash(&wallet, self.hardened_addresses(), 0, true, None)
+                .await?;
+            let synthetic_key = tx.synthetic_key(p2_puzzle_hash).await?;
+            tx.commit().await?;
+            Some(synthetic_key)
+        } else {
+            None
+        };
 
-        let (coin_spends, asset_id) = wallet.issue_cat(amount, fee, None, false, true).await?;
+        let (coin_spends, asset_id) = self
+            .build_with_fee(fee, req.fee_target_seconds, |fee| {
+                wallet.issue_cat(
+                    amount,
+                    fee,
+                    multi_issuance_key,
+                    &selection,
+                    self.hardened_addresses(),
+                    true,
+                )
+            })
+            .await?;
         wallet
             .db
             .insert_cat(CatRow {
@@ -97,6 +280,54 @@ impl Sage {
             })
             .await?;
 
+        if let Some(public_key) = multi_issuance_key {
+            wallet.db.set_cat_issuance_key(asset_id, public_key).await?;
+        }
+
+        self.transact(coin_spends, req.auto_submit).await
+    }
+
+    pub async fn mint_more_cat(&self, req: MintMoreCat) -> Result<TransactionResponse> {
+        let wallet = self.wallet()?;
+        let asset_id = parse_asset_id(req.asset_id)?;
+        let amount = parse_cat_amount(req.amount)?;
+        let fee = self.parse_amount(req.fee)?;
+        let selection = self.coin_selection(req.coin_ids, req.coin_selection_strategy)?;
+
+        let coin_spends = self
+            .build_with_fee(fee, req.fee_target_seconds, |fee| {
+                wallet.mint_more_cat(
+                    asset_id,
+                    amount,
+                    fee,
+                    &selection,
+                    self.hardened_addresses(),
+                    true,
+                )
+            })
+            .await?;
+        self.transact(coin_spends, req.auto_submit).await
+    }
+
+    pub async fn melt_cat(&self, req: MeltCat) -> Result<TransactionResponse> {
+        let wallet = self.wallet()?;
+        let asset_id = parse_asset_id(req.asset_id)?;
+        let amount = parse_cat_amount(req.amount)?;
+        let fee = self.parse_amount(req.fee)?;
+        let selection = self.coin_selection(req.coin_ids, req.coin_selection_strategy)?;
+
+        let coin_spends = self
+            .build_with_fee(fee, req.fee_target_seconds, |fee| {
+                wallet.melt_cat(
+                    asset_id,
+                    amount,
+                    fee,
+                    &selection,
+                    self.hardened_addresses(),
+                    true,
+                )
+            })
+            .await?;
         self.transact(coin_spends, req.auto_submit).await
     }
 
@@ -106,15 +337,56 @@ impl Sage {
         let puzzle_hash = self.parse_address(req.address)?;
         let amount = parse_cat_amount(req.amount)?;
         let fee = self.parse_amount(req.fee)?;
+        let selection = self.coin_selection(req.coin_ids, req.coin_selection_strategy)?;
+
+        let memos = parse_memos(req.memos)?;
+
+        let coin_spends = self
+            .build_with_fee(fee, req.fee_target_seconds, |fee| {
+                wallet.send_cat(
+                    asset_id,
+                    puzzle_hash,
+                    amount,
+                    fee,
+                    memos.clone(),
+                    req.clawback_seconds,
+                    &selection,
+                    self.hardened_addresses(),
+                    true,
+                )
+            })
+            .await?;
+        self.transact(coin_spends, req.auto_submit).await
+    }
+
+    pub async fn send_cat_multi(&self, req: SendCatMulti) -> Result<TransactionResponse> {
+        let wallet = self.wallet()?;
+        let asset_id = parse_asset_id(req.asset_id)?;
+        let fee = self.parse_amount(req.fee)?;
+        let selection = self.coin_selection(req.coin_ids, req.coin_selection_strategy)?;
 
-        let mut memos = Vec::new();
+        let mut payments = Vec::new();
 
-        for memo in req.memos {
-            memos.push(Bytes::from(hex::decode(memo)?));
+        for payment in req.payments {
+            payments.push(WalletPayment {
+                puzzle_hash: self.parse_address(payment.address)?,
+                amount: parse_cat_amount(payment.amount)?,
+                memos: parse_memos(payment.memos)?,
+                clawback_seconds: None,
+            });
         }
 
-        let coin_spends = wallet
-            .send_cat(asset_id, puzzle_hash, amount, fee, memos, false, true)
+        let coin_spends = self
+            .build_with_fee(fee, req.fee_target_seconds, |fee| {
+                wallet.send_cat_multi(
+                    asset_id,
+                    payments.clone(),
+                    fee,
+                    &selection,
+                    self.hardened_addresses(),
+                    true,
+                )
+            })
             .await?;
         self.transact(coin_spends, req.auto_submit).await
     }
@@ -122,8 +394,13 @@ impl Sage {
     pub async fn create_did(&self, req: CreateDid) -> Result<TransactionResponse> {
         let wallet = self.wallet()?;
         let fee = self.parse_amount(req.fee)?;
+        let selection = self.coin_selection(req.coin_ids, req.coin_selection_strategy)?;
 
-        let (coin_spends, did) = wallet.create_did(fee, false, true).await?;
+        let (coin_spends, did) = self
+            .build_with_fee(fee, req.fee_target_seconds, |fee| {
+                wallet.create_did(fee, &selection, self.hardened_addresses(), true)
+            })
+            .await?;
         wallet
             .db
             .set_future_did_name(did.info.launcher_id, req.name.clone())
@@ -137,6 +414,7 @@ impl Sage {
     pub async fn bulk_mint_nfts(&self, req: BulkMintNfts) -> Result<TransactionResponse> {
         let wallet = self.wallet()?;
         let fee = self.parse_amount(req.fee)?;
+        let selection = self.coin_selection(req.coin_ids, req.coin_selection_strategy)?;
         let did_id = parse_did_id(req.did_id)?;
 
         let mut mints = Vec::with_capacity(req.mints.len());
@@ -150,54 +428,33 @@ impl Sage {
 
             let royalty_ten_thousandths = item.royalty_ten_thousandths;
 
-            let data_hash = if item.data_uris.is_empty() {
-                None
-            } else {
-                let data = fetch_uris(
-                    item.data_uris.clone(),
-                    Duration::from_secs(15),
+            let data_hash = self
+                .nft_mint_hash(
+                    &item.data_uris,
+                    item.data_hash,
                     Duration::from_secs(5),
+                    &mut info,
                 )
                 .await?;
 
-                let hash = data.hash;
-                info.nft_data.insert(hash, data);
-
-                Some(hash)
-            };
-
-            let metadata_hash = if item.metadata_uris.is_empty() {
-                None
-            } else {
-                let metadata = fetch_uris(
-                    item.metadata_uris.clone(),
-                    Duration::from_secs(15),
+            let metadata_hash = self
+                .nft_mint_hash(
+                    &item.metadata_uris,
+                    item.metadata_hash,
                     Duration::from_secs(15),
+                    &mut info,
                 )
                 .await?;
 
-                let hash = metadata.hash;
-                info.nft_data.insert(hash, metadata);
-
-                Some(hash)
-            };
-
-            let license_hash = if item.license_uris.is_empty() {
-                None
-            } else {
-                let data = fetch_uris(
-                    item.license_uris.clone(),
-                    Duration::from_secs(15),
+            let license_hash = self
+                .nft_mint_hash(
+                    &item.license_uris,
+                    item.license_hash,
                     Duration::from_secs(15),
+                    &mut info,
                 )
                 .await?;
 
-                let hash = data.hash;
-                info.nft_data.insert(hash, data);
-
-                Some(hash)
-            };
-
             mints.push(WalletNftMint {
                 metadata: NftMetadata {
                     edition_number: item.edition_number.map_or(1, Into::into),
@@ -214,12 +471,99 @@ impl Sage {
             });
         }
 
-        let (coin_spends, _nfts, _did) = wallet
-            .bulk_mint_nfts(fee, did_id, mints, false, true)
+        let (coin_spends, _nfts, _did) = self
+            .build_with_fee(fee, req.fee_target_seconds, |fee| {
+                wallet.bulk_mint_nfts(
+                    fee,
+                    did_id,
+                    mints.clone(),
+                    &selection,
+                    self.hardened_addresses(),
+                    true,
+                )
+            })
             .await?;
         self.transact_with(coin_spends, req.auto_submit, info).await
     }
 
+    pub async fn prepare_nft_mints(&self, req: PrepareNftMints) -> Result<PrepareNftMintsResponse> {
+        let mut mints = Vec::with_capacity(req.mints.len());
+
+        for mut item in req.mints {
+            if let Some(data) = self
+                .fetch_nft_mint_uris(item.data_uris.clone(), Duration::from_secs(5))
+                .await?
+            {
+                item.data_hash = Some(hex::encode(data.hash));
+            }
+
+            if let Some(metadata) = self
+                .fetch_nft_mint_uris(item.metadata_uris.clone(), Duration::from_secs(15))
+                .await?
+            {
+                validate_chip0007(&metadata.blob)?;
+                item.metadata_hash = Some(hex::encode(metadata.hash));
+            }
+
+            if let Some(license) = self
+                .fetch_nft_mint_uris(item.license_uris.clone(), Duration::from_secs(15))
+                .await?
+            {
+                item.license_hash = Some(hex::encode(license.hash));
+            }
+
+            mints.push(item);
+        }
+
+        Ok(PrepareNftMintsResponse { mints })
+    }
+
+    /// Uses the hash given for a mint if there is one, and otherwise fetches the URIs to compute it.
+    async fn nft_mint_hash(
+        &self,
+        uris: &[String],
+        hash: Option<String>,
+        stream_timeout: Duration,
+        info: &mut ConfirmationInfo,
+    ) -> Result<Option<Bytes32>> {
+        if let Some(hash) = hash {
+            return Ok(Some(parse_hash(hash)?));
+        }
+
+        let Some(data) = self
+            .fetch_nft_mint_uris(uris.to_vec(), stream_timeout)
+            .await?
+        else {
+            return Ok(None);
+        };
+
+        let hash = data.hash;
+        info.nft_data.insert(hash, data);
+
+        Ok(Some(hash))
+    }
+
+    /// Fetches data from every URI, making sure that all of the mirrors agree.
+    async fn fetch_nft_mint_uris(
+        &self,
+        uris: Vec<String>,
+        stream_timeout: Duration,
+    ) -> Result<Option<Data>> {
+        if uris.is_empty() {
+            return Ok(None);
+        }
+
+        Ok(Some(
+            fetch_uris(
+                &self.uri_resolver(),
+                uris,
+                Duration::from_secs(15),
+                stream_timeout,
+            )
+            .await?,
+        ))
+    }
+
     pub async fn transfer_nfts(&self, req: TransferNfts) -> Result<TransactionResponse> {
         let wallet = self.wallet()?;
         let nft_ids = req
@@ -229,9 +573,19 @@ impl Sage {
             .collect::<Result<Vec<_>>>()?;
         let puzzle_hash = self.parse_address(req.address)?;
         let fee = self.parse_amount(req.fee)?;
-
-        let coin_spends = wallet
-            .transfer_nfts(nft_ids, puzzle_hash, fee, false, true)
+        let selection = self.default_coin_selection();
+
+        let coin_spends = self
+            .build_with_fee(fee, req.fee_target_seconds, |fee| {
+                wallet.transfer_nfts(
+                    nft_ids.clone(),
+                    puzzle_hash,
+                    fee,
+                    &selection,
+                    self.hardened_addresses(),
+                    true,
+                )
+            })
             .await?;
         self.transact(coin_spends, req.auto_submit).await
     }
@@ -240,6 +594,7 @@ impl Sage {
         let wallet = self.wallet()?;
         let nft_id = parse_nft_id(req.nft_id)?;
         let fee = self.parse_amount(req.fee)?;
+        let selection = self.default_coin_selection();
 
         let uri = match req.kind {
             NftUriKind::Data => MetadataUpdate::NewDataUri(req.uri),
@@ -247,7 +602,18 @@ impl Sage {
             NftUriKind::License => MetadataUpdate::NewLicenseUri(req.uri),
         };
 
-        let (coin_spends, _new_nft) = wallet.add_nft_uri(nft_id, fee, uri, false, true).await?;
+        let (coin_spends, _new_nft) = self
+            .build_with_fee(fee, req.fee_target_seconds, |fee| {
+                wallet.add_nft_uri(
+                    nft_id,
+                    fee,
+                    uri.clone(),
+                    &selection,
+                    self.hardened_addresses(),
+                    true,
+                )
+            })
+            .await?;
         self.transact(coin_spends, req.auto_submit).await
     }
 
@@ -260,9 +626,19 @@ impl Sage {
             .collect::<Result<Vec<_>>>()?;
         let did_id = req.did_id.map(parse_did_id).transpose()?;
         let fee = self.parse_amount(req.fee)?;
-
-        let coin_spends = wallet
-            .assign_nfts(nft_ids, did_id, fee, false, true)
+        let selection = self.default_coin_selection();
+
+        let coin_spends = self
+            .build_with_fee(fee, req.fee_target_seconds, |fee| {
+                wallet.assign_nfts(
+                    nft_ids.clone(),
+                    did_id,
+                    fee,
+                    &selection,
+                    self.hardened_addresses(),
+                    true,
+                )
+            })
             .await?;
         self.transact(coin_spends, req.auto_submit).await
     }
@@ -276,9 +652,47 @@ impl Sage {
             .collect::<Result<Vec<_>>>()?;
         let puzzle_hash = self.parse_address(req.address)?;
         let fee = self.parse_amount(req.fee)?;
+        let selection = self.default_coin_selection();
+
+        let coin_spends = self
+            .build_with_fee(fee, req.fee_target_seconds, |fee| {
+                wallet.transfer_dids(
+                    did_ids.clone(),
+                    puzzle_hash,
+                    fee,
+                    &selection,
+                    self.hardened_addresses(),
+                    true,
+                )
+            })
+            .await?;
+        self.transact(coin_spends, req.auto_submit).await
+    }
+
+    pub async fn claim_clawback(&self, req: ClaimClawback) -> Result<TransactionResponse> {
+        let wallet = self.wallet()?;
+        let coin_id = parse_coin_id(req.coin_id)?;
+        let fee = self.parse_amount(req.fee)?;
+        let selection = self.default_coin_selection();
+
+        let coin_spends = self
+            .build_with_fee(fee, req.fee_target_seconds, |fee| {
+                wallet.claim_clawback(coin_id, fee, &selection, self.hardened_addresses(), true)
+            })
+            .await?;
+        self.transact(coin_spends, req.auto_submit).await
+    }
+
+    pub async fn revoke_clawback(&self, req: RevokeClawback) -> Result<TransactionResponse> {
+        let wallet = self.wallet()?;
+        let coin_id = parse_coin_id(req.coin_id)?;
+        let fee = self.parse_amount(req.fee)?;
+        let selection = self.default_coin_selection();
 
-        let coin_spends = wallet
-            .transfer_dids(did_ids, puzzle_hash, fee, false, true)
+        let coin_spends = self
+            .build_with_fee(fee, req.fee_target_seconds, |fee| {
+                wallet.revoke_clawback(coin_id, fee, &selection, self.hardened_addresses(), true)
+            })
             .await?;
         self.transact(coin_spends, req.auto_submit).await
     }
@@ -325,7 +739,117 @@ impl Sage {
         Ok(SubmitTransactionResponse {})
     }
 
-    async fn transact(
+    pub async fn increase_transaction_fee(
+        &self,
+        req: IncreaseTransactionFee,
+    ) -> Result<IncreaseTransactionFeeResponse> {
+        let wallet = self.wallet()?;
+        let transaction_id = parse_transaction_id(req.transaction_id)?;
+        let new_fee = self.parse_amount(req.new_fee)?;
+
+        let (Some(transaction), Some(mut aggregated_signature)) = (
+            wallet.db.transaction(transaction_id).await?,
+            wallet.db.transaction_signature(transaction_id).await?,
+        ) else {
+            return Err(Error::MissingTransaction(transaction_id));
+        };
+
+        if new_fee < transaction.fee.saturating_add(MEMPOOL_MIN_FEE_INCREASE) {
+            return Err(Error::InsufficientFeeIncrease(MEMPOOL_MIN_FEE_INCREASE));
+        }
+
+        // The original spends are kept intact, so the new spend bundle can replace it in the mempool.
+        let mut coin_spends = wallet.db.coin_spends(transaction_id).await?;
+
+        let fee_spends = wallet
+            .increase_fee(
+                &coin_spends,
+                new_fee - transaction.fee,
+                self.hardened_addresses(),
+                true,
+            )
+            .await?;
+        let fee_bundle = self.sign(fee_spends, false).await?;
+
+        coin_spends.extend(fee_bundle.coin_spends);
+        aggregated_signature += &fee_bundle.aggregated_signature;
+
+        let spend_bundle = SpendBundle::new(coin_spends, aggregated_signature);
+        let new_transaction_id = self.replace(transaction_id, spend_bundle.clone()).await?;
+
+        Ok(IncreaseTransactionFeeResponse {
+            transaction_id: hex::encode(new_transaction_id),
+            summary: self
+                .summarize(spend_bundle.coin_spends, ConfirmationInfo::default())
+                .await?,
+        })
+    }
+
+    pub async fn cancel_transaction(
+        &self,
+        req: CancelTransaction,
+    ) -> Result<CancelTransactionResponse> {
+        let wallet = self.wallet()?;
+        let transaction_id = parse_transaction_id(req.transaction_id)?;
+        let fee = self.parse_amount(req.fee)?;
+        let selection = self.default_coin_selection();
+
+        let Some(transaction) = wallet.db.transaction(transaction_id).await? else {
+            return Err(Error::MissingTransaction(transaction_id));
+        };
+
+        if fee < transaction.fee.saturating_add(MEMPOOL_MIN_FEE_INCREASE) {
+            return Err(Error::InsufficientFeeIncrease(MEMPOOL_MIN_FEE_INCREASE));
+        }
+
+        let coin_spends = wallet.db.coin_spends(transaction_id).await?;
+        let coin_spends = self
+            .build_with_fee(fee, req.fee_target_seconds, |fee| {
+                wallet.cancel_transaction(
+                    &coin_spends,
+                    fee,
+                    &selection,
+                    self.hardened_addresses(),
+                    true,
+                )
+            })
+            .await?;
+        let spend_bundle = self.sign(coin_spends, false).await?;
+
+        let peers = self.peer_state.lock().await.peers();
+
+        if peers.is_empty() {
+            return Err(Error::NoPeers);
+        }
+
+        // The original transaction is only removed once the replacement is in the mempool.
+        match submit_to_peers(&peers, wallet.genesis_challenge, spend_bundle.clone()).await? {
+            Status::Pending => {}
+            Status::Success => return Err(Error::TransactionConfirmed(transaction_id)),
+            Status::Failed(..) | Status::Unknown => {
+                return Err(Error::ReplacementRejected(transaction_id));
+            }
+        }
+
+        let new_transaction_id = self.replace(transaction_id, spend_bundle.clone()).await?;
+
+        Ok(CancelTransactionResponse {
+            transaction_id: hex::encode(new_transaction_id),
+            summary: self
+                .summarize(spend_bundle.coin_spends, ConfirmationInfo::default())
+                .await?,
+        })
+    }
+
+    pub async fn estimate_fee(&self, req: EstimateFee) -> Result<EstimateFeeResponse> {
+        let fee_rate = self.fee_rate(req.target_seconds).await?;
+
+        Ok(EstimateFeeResponse {
+            fee: Amount::u64(fee_rate.saturating_mul(req.cost)),
+        })
+    }
+
+    pub(crate) async fn transact(
         &self,
         coin_spends: Vec<CoinSpend>,
         auto_submit: bool,
@@ -334,22 +858,27 @@ impl Sage {
             .await
     }
 
-    async fn transact_with(
+    pub(crate) async fn transact_with(
         &self,
         coin_spends: Vec<CoinSpend>,
         auto_submit: bool,
         info: ConfirmationInfo,
     ) -> Result<TransactionResponse> {
-        if auto_submit {
+        let transaction_id = if auto_submit {
             let spend_bundle = self.sign(coin_spends.clone(), false).await?;
+            let transaction_id = spend_bundle.name();
             self.submit(spend_bundle).await?;
-        }
+            Some(hex::encode(transaction_id))
+        } else {
+            None
+        };
 
         let json_spends = coin_spends.iter().map(json_spend).collect();
 
         Ok(TransactionResponse {
             summary: self.summarize(coin_spends, info).await?,
             coin_spends: json_spends,
+            transaction_id,
         })
     }
 }
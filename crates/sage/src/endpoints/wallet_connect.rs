@@ -1,22 +1,23 @@
 use chia::{
-    bls::{master_to_wallet_unhardened, sign},
-    clvm_utils::ToTreeHash,
-    protocol::{Bytes, Coin, CoinSpend, SpendBundle},
-    puzzles::{cat::CatArgs, standard::StandardArgs, DeriveSynthetic, Proof},
+    protocol::{Bytes32, Coin, CoinSpend, SpendBundle},
+    puzzles::{cat::CatArgs, standard::StandardArgs, Proof},
 };
 use chia_wallet_sdk::{Layer, SpendContext};
-use sage_api::wallet_connect::{
-    self, AssetCoinType, FilterUnlockedCoins, FilterUnlockedCoinsResponse, GetAssetCoins,
-    GetAssetCoinsResponse, LineageProof, SendTransactionImmediately,
-    SendTransactionImmediatelyResponse, SignMessageWithPublicKey, SignMessageWithPublicKeyResponse,
-    SpendableCoin,
+use sage_api::{
+    wallet_connect::{
+        self, AssetCoinType, FilterUnlockedCoins, FilterUnlockedCoinsResponse, GetAssetCoins,
+        GetAssetCoinsResponse, LineageProof, SendTransactionImmediately,
+        SendTransactionImmediatelyResponse, SignMessageWithPublicKey,
+        SignMessageWithPublicKeyResponse, SpendableCoin,
+    },
+    SignMessageByAddress, SignMessageByAddressResponse, VerifySignature, VerifySignatureResponse,
 };
 use sage_wallet::{insert_transaction, submit_to_peers, Status, SyncCommand, Transaction};
 use tracing::{debug, info, warn};
 
 use crate::{
-    parse_asset_id, parse_coin_id, parse_did_id, parse_nft_id, parse_program, parse_public_key,
-    parse_puzzle_hash, parse_signature, Error, Result, Sage,
+    parse_asset_id, parse_coin_id, parse_did_id, parse_message, parse_nft_id, parse_program,
+    parse_public_key, parse_puzzle_hash, parse_signature, verify_message, Error, Result, Sage,
 };
 
 impl Sage {
@@ -330,29 +331,80 @@ impl Sage {
         let wallet = self.wallet()?;
 
         let public_key = parse_public_key(req.public_key)?;
-        let Some(index) = wallet.db.synthetic_key_index(public_key).await? else {
+        let Some(derivation) = wallet.db.synthetic_key_derivation(public_key).await? else {
             return Err(Error::InvalidKey);
         };
 
-        let (_mnemonic, Some(master_sk)) =
-            self.keychain.extract_secrets(wallet.fingerprint, b"")?
-        else {
-            return Err(Error::NoSigningKey);
-        };
+        let signature = self.sign_message(&derivation, parse_message(req.message, true)?)?;
 
-        let secret_key = master_to_wallet_unhardened(&master_sk, index).derive_synthetic();
+        Ok(SignMessageWithPublicKeyResponse {
+            signature: hex::encode(signature.to_bytes()),
+        })
+    }
 
-        let decoded_message = Bytes::from(hex::decode(&req.message)?);
-        let signature = sign(
-            &secret_key,
-            ("Chia Signed Message", decoded_message).tree_hash(),
-        );
+    pub async fn sign_message_by_address(
+        &self,
+        req: SignMessageByAddress,
+    ) -> Result<SignMessageByAddressResponse> {
+        let wallet = self.wallet()?;
 
-        Ok(SignMessageWithPublicKeyResponse {
+        let puzzle_hash = self.parse_address(req.address.clone())?;
+
+        if !wallet.db.is_p2_puzzle_hash(puzzle_hash).await? {
+            return Err(Error::UnknownAddress(req.address));
+        }
+
+        let synthetic_key = wallet.db.synthetic_key(puzzle_hash).await?;
+
+        let Some(derivation) = wallet.db.synthetic_key_derivation(synthetic_key).await? else {
+            return Err(Error::UnknownAddress(req.address));
+        };
+
+        let signature = self.sign_message(&derivation, parse_message(req.message, req.is_hex)?)?;
+
+        Ok(SignMessageByAddressResponse {
+            public_key: hex::encode(synthetic_key.to_bytes()),
             signature: hex::encode(signature.to_bytes()),
         })
     }
 
+    pub async fn verify_signature(&self, req: VerifySignature) -> Result<VerifySignatureResponse> {
+        let message = parse_message(req.message, req.is_hex)?;
+        let signature = parse_signature(req.signature)?;
+
+        let public_key = match (req.public_key, req.address) {
+            (Some(public_key), address) => {
+                let public_key = parse_public_key(public_key)?;
+
+                if let Some(address) = address {
+                    let puzzle_hash = self.parse_address(address)?;
+
+                    if Bytes32::from(StandardArgs::curry_tree_hash(public_key)) != puzzle_hash {
+                        return Ok(VerifySignatureResponse { is_valid: false });
+                    }
+                }
+
+                public_key
+            }
+            (None, Some(address)) => {
+                let wallet = self.wallet()?;
+
+                let puzzle_hash = self.parse_address(address.clone())?;
+
+                if !wallet.db.is_p2_puzzle_hash(puzzle_hash).await? {
+                    return Err(Error::UnknownAddress(address));
+                }
+
+                wallet.db.synthetic_key(puzzle_hash).await?
+            }
+            (None, None) => return Err(Error::MissingPublicKey),
+        };
+
+        Ok(VerifySignatureResponse {
+            is_valid: verify_message(&public_key, message, &signature),
+        })
+    }
+
     pub async fn send_transaction_immediately(
         &self,
         req: SendTransactionImmediately,
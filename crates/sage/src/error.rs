@@ -15,7 +15,7 @@ use hex::FromHexError;
 use sage_api::ErrorKind;
 use sage_database::DatabaseError;
 use sage_keychain::KeychainError;
-use sage_wallet::{SyncCommand, UriError, WalletError};
+use sage_wallet::{MetadataError, SyncCommand, UriError, WalletError};
 use sqlx::migrate::MigrateError;
 use thiserror::Error;
 use tokio::sync::mpsc::error::SendError;
@@ -51,6 +51,9 @@ pub enum Error {
     #[error("URI error: {0}")]
     Uri(#[from] UriError),
 
+    #[error("Metadata error: {0}")]
+    Metadata(#[from] MetadataError),
+
     #[error("To CLVM error: {0}")]
     ToClvm(#[from] ToClvmError),
 
@@ -81,6 +84,9 @@ pub enum Error {
     #[error("Bincode error: {0}")]
     Bincode(#[from] bincode::Error),
 
+    #[error("JSON error: {0}")]
+    Json(#[from] serde_json::Error),
+
     #[error("Logging initialization error: {0}")]
     LogSubscriber(#[from] TryInitError),
 
@@ -138,6 +144,9 @@ pub enum Error {
     #[error("Invalid puzzle hash: {0}")]
     InvalidPuzzleHash(String),
 
+    #[error("Invalid hash: {0}")]
+    InvalidHash(String),
+
     #[error("Invalid coin id: {0}")]
     InvalidCoinId(String),
 
@@ -156,6 +165,9 @@ pub enum Error {
     #[error("Invalid offer id: {0}")]
     InvalidOfferId(String),
 
+    #[error("Invalid transaction id: {0}")]
+    InvalidTransactionId(String),
+
     #[error("Invalid percentage: {0}")]
     InvalidPercentage(String),
 
@@ -165,6 +177,18 @@ pub enum Error {
     #[error("Invalid public key: {0}")]
     InvalidPublicKey(String),
 
+    #[error("Invalid payment URI: {0}")]
+    InvalidPaymentUri(String),
+
+    #[error("Address is not owned by this wallet: {0}")]
+    UnknownAddress(String),
+
+    #[error("A public key or address is required")]
+    MissingPublicKey,
+
+    #[error("Keychain is locked")]
+    KeychainLocked,
+
     #[error("Wallet is cold and cannot be used for signing")]
     NoSigningKey,
 
@@ -183,15 +207,39 @@ pub enum Error {
     #[error("Missing offer: {0}")]
     MissingOffer(Bytes32),
 
+    #[error("Missing transaction: {0}")]
+    MissingTransaction(Bytes32),
+
+    #[error("Missing contact: {0}")]
+    MissingContact(u32),
+
+    #[error("A contact already exists with address: {0}")]
+    ContactExists(String),
+
     #[error("Coin already spent: {0}")]
     CoinSpent(Bytes32),
 
+    #[error("Offer is not active: {0}")]
+    OfferNotActive(Bytes32),
+
+    #[error("Fee must increase by at least {0} mojos to replace the transaction")]
+    InsufficientFeeIncrease(u64),
+
+    #[error("Transaction has already been confirmed: {0}")]
+    TransactionConfirmed(Bytes32),
+
+    #[error("Replacement for transaction was rejected by the mempool: {0}")]
+    ReplacementRejected(Bytes32),
+
     #[error("IP addr parse error: {0}")]
     IpAddrParse(#[from] AddrParseError),
 
     #[error("No peers are currently available")]
     NoPeers,
 
+    #[error("No peers were able to provide a fee estimate")]
+    NoFeeEstimate,
+
     #[error("Could not fetch NFT with id: {0}")]
     CouldNotFetchNft(Bytes32),
 
@@ -207,6 +255,7 @@ impl Error {
         match self {
             Self::Wallet(..) => ErrorKind::Wallet,
             Self::NotLoggedIn | Self::NoSigningKey => ErrorKind::Unauthorized,
+            Self::KeychainLocked => ErrorKind::Locked,
             Self::Keychain(error) => match error {
                 KeychainError::Decrypt => ErrorKind::Unauthorized,
                 KeychainError::KeyExists
@@ -233,6 +282,7 @@ impl Error {
             | Self::ToClvm(..)
             | Self::FromClvm(..)
             | Self::Bincode(..)
+            | Self::Json(..)
             | Self::Eval(..)
             | Self::Driver(..) => ErrorKind::Internal,
             Self::UnknownFingerprint
@@ -241,7 +291,9 @@ impl Error {
             | Self::MissingCatCoin(..)
             | Self::MissingDid(..)
             | Self::MissingNft(..)
-            | Self::MissingOffer(..) => ErrorKind::NotFound,
+            | Self::MissingOffer(..)
+            | Self::MissingTransaction(..)
+            | Self::MissingContact(..) => ErrorKind::NotFound,
             Self::Bls(..)
             | Self::Hex(..)
             | Self::InvalidKey
@@ -258,18 +310,30 @@ impl Error {
             | Self::InvalidGenesisChallenge(..)
             | Self::InvalidCoinId(..)
             | Self::InvalidPuzzleHash(..)
+            | Self::InvalidHash(..)
             | Self::InvalidAssetId(..)
             | Self::InvalidOfferId(..)
+            | Self::InvalidTransactionId(..)
             | Self::InvalidPercentage(..)
             | Self::InvalidSignature(..)
             | Self::InvalidPublicKey(..)
+            | Self::InvalidPaymentUri(..)
             | Self::CoinSpent(..)
+            | Self::OfferNotActive(..)
+            | Self::InsufficientFeeIncrease(..)
+            | Self::TransactionConfirmed(..)
+            | Self::ReplacementRejected(..)
             | Self::Uri(..)
+            | Self::Metadata(..)
             | Self::IpAddrParse(..)
             | Self::Offer(..)
             | Self::NoPeers
+            | Self::NoFeeEstimate
             | Self::CouldNotFetchNft(..)
-            | Self::MissingAssetId => ErrorKind::Api,
+            | Self::MissingAssetId
+            | Self::ContactExists(..)
+            | Self::UnknownAddress(..)
+            | Self::MissingPublicKey => ErrorKind::Api,
         }
     }
 }
@@ -0,0 +1,64 @@
+use sage_api::{InvoiceRecordStatus, OfferRecordStatus, SyncEvent as ApiEvent};
+use sage_database::{InvoiceStatus, OfferStatus};
+use sage_wallet::SyncEvent;
+
+/// Whether the app has to derive more hardened keys in response to the event, by calling
+/// [`Sage::sync_hardened_derivations`](crate::Sage::sync_hardened_derivations).
+pub fn needs_hardened_derivations(event: &SyncEvent) -> bool {
+    matches!(event, SyncEvent::HardenedDerivationUsed)
+}
+
+/// Converts a sync event emitted by the wallet into the event sent to API consumers.
+pub fn api_event(event: SyncEvent) -> ApiEvent {
+    match event {
+        SyncEvent::Start(ip) => ApiEvent::Start { ip: ip.to_string() },
+        SyncEvent::Stop => ApiEvent::Stop,
+        SyncEvent::Subscribed => ApiEvent::Subscribed,
+        SyncEvent::DerivationIndex { .. } | SyncEvent::HardenedDerivationUsed => {
+            ApiEvent::Derivation
+        }
+        SyncEvent::CoinsUpdated { coin_states } => ApiEvent::CoinState {
+            coin_ids: coin_states
+                .iter()
+                .map(|coin_state| hex::encode(coin_state.coin.coin_id()))
+                .collect(),
+        },
+        SyncEvent::TransactionEnded {
+            transaction_id,
+            success,
+        } => ApiEvent::TransactionEnded {
+            transaction_id: hex::encode(transaction_id),
+            success,
+        },
+        SyncEvent::TransactionReplaced {
+            transaction_id,
+            new_transaction_id,
+        } => ApiEvent::TransactionReplaced {
+            transaction_id: hex::encode(transaction_id),
+            new_transaction_id: hex::encode(new_transaction_id),
+        },
+        SyncEvent::OfferUpdated { offer_id, status } => ApiEvent::OfferUpdated {
+            offer_id: hex::encode(offer_id),
+            status: match status {
+                OfferStatus::Active => OfferRecordStatus::Active,
+                OfferStatus::Completed => OfferRecordStatus::Completed,
+                OfferStatus::Cancelled => OfferRecordStatus::Cancelled,
+                OfferStatus::Expired => OfferRecordStatus::Expired,
+            },
+        },
+        SyncEvent::InvoiceUpdated { invoice_id, status } => ApiEvent::InvoiceUpdated {
+            invoice_id,
+            status: match status {
+                InvoiceStatus::Unpaid => InvoiceRecordStatus::Unpaid,
+                InvoiceStatus::PartiallyPaid => InvoiceRecordStatus::PartiallyPaid,
+                InvoiceStatus::Paid => InvoiceRecordStatus::Paid,
+                InvoiceStatus::Overpaid => InvoiceRecordStatus::Overpaid,
+            },
+        },
+        SyncEvent::PuzzleBatchSynced => ApiEvent::PuzzleBatchSynced,
+        SyncEvent::TransactionHistory => ApiEvent::TransactionHistory,
+        SyncEvent::CatInfo => ApiEvent::CatInfo,
+        SyncEvent::DidInfo => ApiEvent::DidInfo,
+        SyncEvent::NftData => ApiEvent::NftData,
+    }
+}
@@ -2,11 +2,15 @@
 
 mod endpoints;
 mod error;
+mod events;
 mod peers;
 mod sage;
+mod session;
 mod utils;
 
 pub use error::*;
+pub use events::*;
 pub use sage::*;
+pub use session::*;
 
 pub(crate) use utils::*;
@@ -6,14 +6,23 @@ use std::{
     time::{Duration, SystemTime, UNIX_EPOCH},
 };
 
-use chia::{bls::master_to_wallet_unhardened_intermediate, protocol::Bytes32};
+use bip39::Mnemonic;
+use chia::{
+    bls::{master_to_wallet_unhardened_intermediate, SecretKey},
+    protocol::{Bytes32, SpendBundle},
+};
 use chia_wallet_sdk::{create_rustls_connector, decode_address, load_ssl_cert, Connector};
 use indexmap::{indexmap, IndexMap};
 use sage_api::{Amount, Unit, XCH};
-use sage_config::{Config, Network, WalletConfig, MAINNET, TESTNET11};
-use sage_database::Database;
+use sage_config::{
+    AutoConsolidateConfig, CoinSelectionStrategy, Config, Network, WalletConfig, MAINNET, TESTNET11,
+};
+use sage_database::{Database, DatabaseTx};
 use sage_keychain::Keychain;
-use sage_wallet::{PeerState, SyncCommand, SyncEvent, SyncManager, SyncOptions, Timeouts, Wallet};
+use sage_wallet::{
+    MediaCache, PeerState, SyncCommand, SyncEvent, SyncManager, SyncOptions, Timeouts, UriResolver,
+    Wallet, WalletError,
+};
 use sqlx::{
     sqlite::{SqliteConnectOptions, SqliteJournalMode, SqlitePoolOptions, SqliteSynchronous},
     ConnectOptions, SqlitePool,
@@ -25,18 +34,30 @@ use tracing_subscriber::{
     filter::filter_fn, fmt, layer::SubscriberExt, EnvFilter, Layer, Registry,
 };
 
-use crate::{peers::Peers, Error, Result};
+use crate::{peers::Peers, Error, Result, Session};
 
 #[derive(Debug)]
 pub struct Sage {
     pub path: PathBuf,
     pub config: Config,
     pub keychain: Keychain,
+    pub session: Session,
     pub networks: IndexMap<String, Network>,
     pub wallet: Option<Arc<Wallet>>,
     pub peer_state: Arc<Mutex<PeerState>>,
     pub command_sender: mpsc::Sender<SyncCommand>,
     pub unit: Unit,
+    media_cache: MediaCache,
+}
+
+/// A signed transaction along with what's needed to submit it, so that it can be submitted
+/// by background work without holding onto [`Sage`] in the meantime.
+#[derive(Debug)]
+pub struct Submission {
+    pub(crate) wallet: Arc<Wallet>,
+    pub(crate) peer_state: Arc<Mutex<PeerState>>,
+    pub(crate) command_sender: mpsc::Sender<SyncCommand>,
+    pub(crate) spend_bundle: SpendBundle,
 }
 
 impl Sage {
@@ -45,6 +66,7 @@ impl Sage {
             path: path.to_path_buf(),
             config: Config::default(),
             keychain: Keychain::default(),
+            session: Session::default(),
             networks: indexmap! {
                 "mainnet".to_string() => MAINNET.clone(),
                 "testnet11".to_string() => TESTNET11.clone(),
@@ -53,6 +75,7 @@ impl Sage {
             peer_state: Arc::new(Mutex::new(PeerState::default())),
             command_sender: mpsc::channel(1).0,
             unit: XCH.clone(),
+            media_cache: media_cache(path, &Config::default()),
         }
     }
 
@@ -128,6 +151,11 @@ impl Sage {
             fs::write(&key_path, self.keychain.to_bytes()?)?;
         }
 
+        // Keychains that were never given a password don't need to be unlocked.
+        if self.keychain.verify_password(b"").is_ok() {
+            self.session.unlock(Vec::new());
+        }
+
         Ok(())
     }
 
@@ -141,6 +169,8 @@ impl Sage {
             fs::write(&config_path, toml::to_string_pretty(&self.config)?)?;
         };
 
+        self.media_cache = media_cache(&self.path, &self.config);
+
         Ok(())
     }
 
@@ -193,6 +223,8 @@ impl Sage {
                 dns_batch_size: 10,
                 connection_batch_size: 30,
                 timeouts: Timeouts::default(),
+                uri_resolver: self.uri_resolver(),
+                media_cache: self.media_cache(),
                 testing: false,
             },
             self.peer_state.clone(),
@@ -251,6 +283,8 @@ impl Sage {
             })
             .await?;
 
+        self.sync_hardened_derivations().await?;
+
         Ok(())
     }
 
@@ -366,9 +400,37 @@ impl Sage {
 
         sqlx::migrate!("../../migrations").run(&pool).await?;
 
+        // Moving NFT media out of the database leaves most of the file unused, so it's compacted
+        // afterwards. This only happens once, when upgrading from before the cache existed.
+        if self.move_nft_media(&Database::new(pool.clone())).await? > 0 {
+            info!("Compacting the database after moving NFT media to the cache");
+            sqlx::query("VACUUM").execute(&pool).await?;
+        }
+
         Ok(pool)
     }
 
+    /// NFT media used to be stored in the database, so any that's left there is written to the
+    /// cache before it's deleted. Each item is deleted separately, so that the move can resume if
+    /// it's interrupted.
+    async fn move_nft_media(&self, db: &Database) -> Result<usize> {
+        let hashes = db.nft_media_hashes().await?;
+
+        if !hashes.is_empty() {
+            info!("Moving {} NFT media items to the cache", hashes.len());
+        }
+
+        for &hash in &hashes {
+            if let Some(data) = db.fetch_nft_data(hash).await? {
+                self.media_cache.insert(hash, data).await?;
+            }
+
+            db.delete_nft_data(hash).await?;
+        }
+
+        Ok(hashes.len())
+    }
+
     fn wallet_db_path(&self, fingerprint: u32) -> Result<PathBuf> {
         let path = self.path.join("wallets").join(fingerprint.to_string());
         fs::create_dir_all(&path)?;
@@ -401,6 +463,262 @@ impl Sage {
         Ok(wallet.clone())
     }
 
+    /// Whether the active wallet receives and takes change on hardened addresses.
+    pub fn hardened_addresses(&self) -> bool {
+        self.config
+            .app
+            .active_fingerprint
+            .and_then(|fingerprint| self.config.wallets.get(&fingerprint.to_string()))
+            .is_some_and(|config| config.hardened_addresses)
+    }
+
+    /// Resolves `ipfs://` and `ar://` URIs using the configured gateways.
+    pub fn uri_resolver(&self) -> UriResolver {
+        UriResolver {
+            ipfs_gateways: self.config.gateways.ipfs.clone(),
+            arweave_gateways: self.config.gateways.arweave.clone(),
+            gateway_timeout: Duration::from_secs(self.config.gateways.timeout_seconds),
+        }
+    }
+
+    /// The on-disk cache of NFT media, which is shared between all wallets.
+    pub fn media_cache(&self) -> MediaCache {
+        self.media_cache.clone()
+    }
+
+    /// The coin selection strategy the active wallet uses when a request doesn't specify one.
+    pub fn coin_selection_strategy(&self) -> CoinSelectionStrategy {
+        self.config
+            .app
+            .active_fingerprint
+            .and_then(|fingerprint| self.config.wallets.get(&fingerprint.to_string()))
+            .map(|config| config.coin_selection_strategy)
+            .unwrap_or_default()
+    }
+
+    /// How the active wallet merges its dust coins in the background.
+    pub fn auto_consolidate_config(&self) -> AutoConsolidateConfig {
+        self.config
+            .app
+            .active_fingerprint
+            .and_then(|fingerprint| self.config.wallets.get(&fingerprint.to_string()))
+            .map(|config| config.auto_consolidate)
+            .unwrap_or_default()
+    }
+
+    /// Signs a transaction merging the dust coins of the active wallet, if auto consolidation
+    /// is enabled and there's anything to merge. Nothing is signed for cold wallets or while the
+    /// keychain is locked. The result can be submitted after releasing any lock on [`Sage`].
+    pub async fn prepare_auto_consolidation(&self) -> Result<Option<Submission>> {
+        let Some(wallet) = self.wallet.clone() else {
+            return Ok(None);
+        };
+
+        let config = self.auto_consolidate_config();
+
+        if !config.enabled || !self.is_unlocked() {
+            return Ok(None);
+        }
+
+        let Some(master_sk) = self.background_secret_key(wallet.fingerprint)? else {
+            return Ok(None);
+        };
+
+        let coin_spends = match wallet
+            .auto_consolidate(
+                config.max_coins_per_asset as usize,
+                config.dust_threshold,
+                config.fee,
+                self.hardened_addresses(),
+                true,
+            )
+            .await
+        {
+            Ok(coin_spends) => coin_spends,
+            Err(WalletError::NothingToConsolidate) => return Ok(None),
+            Err(error) => return Err(error.into()),
+        };
+
+        let spend_bundle = self.sign_with_key(coin_spends, master_sk, false).await?;
+
+        info!(
+            "Submitting auto consolidation for wallet {}",
+            wallet.fingerprint
+        );
+
+        Ok(Some(self.submission(spend_bundle)?))
+    }
+
+    /// Derives enough hardened keys to maintain the gap limit past the last used one.
+    /// Without the secret key, which isn't available for cold wallets or while the keychain is
+    /// locked, this is left until the next time a transaction is signed.
+    pub async fn sync_hardened_derivations(&self) -> Result<()> {
+        let Some(wallet) = self.wallet.clone() else {
+            return Ok(());
+        };
+
+        let Some(master_sk) = self.background_secret_key(wallet.fingerprint)? else {
+            return Ok(());
+        };
+
+        self.derive_hardened_keys(&wallet, &master_sk).await
+    }
+
+    pub(crate) async fn derive_hardened_keys(
+        &self,
+        wallet: &Wallet,
+        master_sk: &SecretKey,
+    ) -> Result<()> {
+        let gap = self.derivation_batch_size(wallet);
+
+        let puzzle_hashes = wallet.derive_hardened_keys(master_sk, gap).await?;
+
+        if !puzzle_hashes.is_empty() {
+            self.command_sender
+                .send(SyncCommand::SubscribePuzzles { puzzle_hashes })
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    pub(crate) async fn derive_unhardened_keys(&self, wallet: &Wallet) -> Result<()> {
+        let gap = self.derivation_batch_size(wallet);
+
+        let puzzle_hashes = wallet.derive_unhardened_keys(gap).await?;
+
+        if !puzzle_hashes.is_empty() {
+            self.command_sender
+                .send(SyncCommand::SubscribePuzzles { puzzle_hashes })
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    /// Derives enough keys that there are at least `count` unused addresses, so that a transaction
+    /// can send its outputs to that many different ones. Hardened keys need the secret key, so
+    /// they are left as they are if it isn't available.
+    pub(crate) async fn derive_unused_keys(
+        &self,
+        wallet: &Wallet,
+        hardened: bool,
+        count: u32,
+    ) -> Result<()> {
+        let gap = self.derivation_batch_size(wallet).max(count);
+
+        let puzzle_hashes = if hardened {
+            let Some(master_sk) = self.background_secret_key(wallet.fingerprint)? else {
+                return Ok(());
+            };
+
+            wallet.derive_hardened_keys(&master_sk, gap).await?
+        } else {
+            wallet.derive_unhardened_keys(gap).await?
+        };
+
+        if !puzzle_hashes.is_empty() {
+            self.command_sender
+                .send(SyncCommand::SubscribePuzzles { puzzle_hashes })
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns the next unused address within a new transaction, which the caller commits.
+    /// If every derived address has been used up, the next batch is derived first.
+    /// Hardened keys need the secret key, which may not be available.
+    pub(crate) async fn next_p2_puzzle_hash<'a>(
+        &self,
+        wallet: &'a Wallet,
+        hardened: bool,
+        skip: u32,
+        mark_used: bool,
+        label: Option<String>,
+    ) -> Result<(DatabaseTx<'a>, Bytes32)> {
+        let mut tx = wallet.db.tx().await?;
+
+        match wallet
+            .next_p2_puzzle_hash(&mut tx, hardened, skip, mark_used, label.clone())
+            .await
+        {
+            Ok(p2_puzzle_hash) => return Ok((tx, p2_puzzle_hash)),
+            Err(WalletError::InsufficientDerivations) => {}
+            Err(error) => return Err(error.into()),
+        }
+
+        // Deriving keys commits its own transaction, so this one is rolled back first.
+        drop(tx);
+
+        if hardened {
+            self.sync_hardened_derivations().await?;
+        } else {
+            self.derive_unhardened_keys(wallet).await?;
+        }
+
+        let mut tx = wallet.db.tx().await?;
+
+        let p2_puzzle_hash = wallet
+            .next_p2_puzzle_hash(&mut tx, hardened, skip, mark_used, label)
+            .await?;
+
+        Ok((tx, p2_puzzle_hash))
+    }
+
+    fn derivation_batch_size(&self, wallet: &Wallet) -> u32 {
+        self.config
+            .wallets
+            .get(&wallet.fingerprint.to_string())
+            .map_or(500, |config| config.derivation_batch_size)
+    }
+
+    /// Returns the keychain password, provided the session hasn't been locked or gone idle.
+    pub fn password(&self) -> Result<Vec<u8>> {
+        self.session
+            .password(self.idle_timeout())
+            .ok_or(Error::KeychainLocked)
+    }
+
+    /// Whether the keychain is unlocked, without counting the check as activity.
+    pub fn is_unlocked(&self) -> bool {
+        self.session.is_unlocked(self.idle_timeout())
+    }
+
+    fn idle_timeout(&self) -> Option<Duration> {
+        let minutes = self.config.app.auto_lock_minutes;
+        (minutes > 0).then(|| Duration::from_secs(u64::from(minutes) * 60))
+    }
+
+    /// Extracts the secret key of a wallet for background work, which shouldn't keep the
+    /// keychain unlocked. Returns `None` for cold wallets or while the keychain is locked.
+    pub(crate) fn background_secret_key(&self, fingerprint: u32) -> Result<Option<SecretKey>> {
+        if !self.keychain.has_secret_key(fingerprint) {
+            return Ok(None);
+        }
+
+        let Some(password) = self.session.background_password(self.idle_timeout()) else {
+            return Ok(None);
+        };
+
+        let (_mnemonic, master_sk) = self.keychain.extract_secrets(fingerprint, &password)?;
+
+        Ok(master_sk)
+    }
+
+    pub fn extract_secrets(
+        &self,
+        fingerprint: u32,
+    ) -> Result<(Option<Mnemonic>, Option<SecretKey>)> {
+        if !self.keychain.has_secret_key(fingerprint) {
+            return Ok((None, None));
+        }
+
+        Ok(self
+            .keychain
+            .extract_secrets(fingerprint, &self.password()?)?)
+    }
+
     pub fn try_wallet_config(&mut self, fingerprint: u32) -> &WalletConfig {
         self.config
             .wallets
@@ -433,3 +751,8 @@ impl Sage {
         Ok(())
     }
 }
+
+/// The cache is created once, so that its index of entries is shared by everything using it.
+fn media_cache(path: &Path, config: &Config) -> MediaCache {
+    MediaCache::new(path.join("media"), config.cache.media_size_mb * 1024 * 1024)
+}
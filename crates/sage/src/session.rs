@@ -0,0 +1,154 @@
+use std::{
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+/// Holds the keychain password in memory while the keychain is unlocked.
+#[derive(Debug, Default)]
+pub struct Session {
+    unlocked: Mutex<Option<Unlocked>>,
+}
+
+#[derive(Debug)]
+struct Unlocked {
+    password: Vec<u8>,
+    last_used: Instant,
+}
+
+impl Session {
+    pub fn unlock(&self, password: Vec<u8>) {
+        self.unlock_at(password, Instant::now());
+    }
+
+    fn unlock_at(&self, password: Vec<u8>, now: Instant) {
+        *self.unlocked.lock().expect("session poisoned") = Some(Unlocked {
+            password,
+            last_used: now,
+        });
+    }
+
+    pub fn lock(&self) {
+        *self.unlocked.lock().expect("session poisoned") = None;
+    }
+
+    /// Returns the password if the session is still unlocked, and resets the idle timer.
+    /// An empty password doesn't protect anything, so it never times out.
+    pub fn password(&self, idle_timeout: Option<Duration>) -> Option<Vec<u8>> {
+        self.read(idle_timeout, true, Instant::now())
+    }
+
+    /// Returns the password without resetting the idle timer, so that background work
+    /// doesn't keep the session unlocked on its own.
+    pub fn background_password(&self, idle_timeout: Option<Duration>) -> Option<Vec<u8>> {
+        self.read(idle_timeout, false, Instant::now())
+    }
+
+    /// Checks whether the session is still unlocked, without resetting the idle timer.
+    pub fn is_unlocked(&self, idle_timeout: Option<Duration>) -> bool {
+        self.read(idle_timeout, false, Instant::now()).is_some()
+    }
+
+    fn read(&self, idle_timeout: Option<Duration>, touch: bool, now: Instant) -> Option<Vec<u8>> {
+        let mut unlocked = self.unlocked.lock().expect("session poisoned");
+
+        let session = unlocked.as_mut()?;
+
+        if let Some(idle_timeout) = idle_timeout {
+            if !session.password.is_empty()
+                && now.saturating_duration_since(session.last_used) >= idle_timeout
+            {
+                *unlocked = None;
+                return None;
+            }
+        }
+
+        if touch {
+            session.last_used = now;
+        }
+
+        Some(session.password.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const IDLE_TIMEOUT: Option<Duration> = Some(Duration::from_secs(300));
+
+    fn unlocked_at(start: Instant) -> Session {
+        let session = Session::default();
+        session.unlock_at(b"password".to_vec(), start);
+        session
+    }
+
+    #[test]
+    fn test_lock_unlock() {
+        let session = Session::default();
+        assert_eq!(session.password(None), None);
+
+        session.unlock(b"password".to_vec());
+        assert_eq!(session.password(None), Some(b"password".to_vec()));
+
+        session.lock();
+        assert_eq!(session.password(None), None);
+    }
+
+    #[test]
+    fn test_idle_timeout() {
+        let start = Instant::now();
+        let session = unlocked_at(start);
+
+        assert!(session
+            .read(IDLE_TIMEOUT, true, start + Duration::from_secs(299))
+            .is_some());
+
+        // Reading the password after the idle timeout locks the session.
+        assert_eq!(
+            session.read(IDLE_TIMEOUT, true, start + Duration::from_secs(599)),
+            None
+        );
+        assert_eq!(session.read(None, true, start), None);
+    }
+
+    #[test]
+    fn test_reads_reset_idle_timer() {
+        let start = Instant::now();
+        let session = unlocked_at(start);
+
+        assert!(session
+            .read(IDLE_TIMEOUT, true, start + Duration::from_secs(200))
+            .is_some());
+        assert!(session
+            .read(IDLE_TIMEOUT, true, start + Duration::from_secs(400))
+            .is_some());
+        assert!(session
+            .read(IDLE_TIMEOUT, true, start + Duration::from_secs(600))
+            .is_some());
+    }
+
+    #[test]
+    fn test_background_reads_keep_idle_timer() {
+        let start = Instant::now();
+        let session = unlocked_at(start);
+
+        assert!(session
+            .read(IDLE_TIMEOUT, false, start + Duration::from_secs(200))
+            .is_some());
+
+        // Background reads don't count as activity, so the session still locks on time.
+        assert_eq!(
+            session.read(IDLE_TIMEOUT, false, start + Duration::from_secs(300)),
+            None
+        );
+        assert_eq!(session.read(None, true, start), None);
+    }
+
+    #[test]
+    fn test_empty_password_never_expires() {
+        let session = Session::default();
+        session.unlock(Vec::new());
+
+        assert_eq!(session.password(Some(Duration::ZERO)), Some(Vec::new()));
+    }
+}
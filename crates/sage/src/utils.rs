@@ -1,11 +1,19 @@
 mod coins;
 mod confirmation;
+mod export;
+mod fees;
+mod messages;
 mod offer_status;
 mod offer_summary;
 mod parse;
+mod payment_uri;
 mod spends;
 
 pub use coins::*;
 pub use confirmation::*;
+pub use export::*;
+pub use fees::*;
+pub use messages::*;
 pub use offer_status::*;
 pub use parse::*;
+pub use payment_uri::*;
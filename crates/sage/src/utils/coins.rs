@@ -1,11 +1,38 @@
 use chia::protocol::{Bytes32, Coin};
 use chia_wallet_sdk::Cat;
-use sage_wallet::Wallet;
+use sage_config::CoinSelectionStrategy;
+use sage_wallet::{CoinSelection, Wallet};
 
-use crate::{Error, Result};
+use crate::{Error, Result, Sage};
 
 use super::parse_coin_id;
 
+impl Sage {
+    /// Pins the given coins as the inputs of a transaction, if there are any.
+    /// Otherwise, coins are chosen with the given strategy or the wallet's default.
+    pub(crate) fn coin_selection(
+        &self,
+        coin_ids: Vec<String>,
+        strategy: Option<CoinSelectionStrategy>,
+    ) -> Result<CoinSelection> {
+        Ok(CoinSelection {
+            coin_ids: coin_ids
+                .into_iter()
+                .map(parse_coin_id)
+                .collect::<Result<Vec<Bytes32>>>()?,
+            strategy: strategy.unwrap_or_else(|| self.coin_selection_strategy()),
+        })
+    }
+
+    /// Selects coins with the wallet's default strategy.
+    pub(crate) fn default_coin_selection(&self) -> CoinSelection {
+        CoinSelection {
+            coin_ids: Vec::new(),
+            strategy: self.coin_selection_strategy(),
+        }
+    }
+}
+
 pub async fn fetch_coins(wallet: &Wallet, coin_ids: Vec<String>) -> Result<Vec<Coin>> {
     let coin_ids = coin_ids
         .into_iter()
@@ -12,7 +12,9 @@ use sage_api::{
     TransactionOutput, TransactionSummary,
 };
 use sage_database::Database;
-use sage_wallet::{compute_nft_info, ChildKind, CoinKind, Data, Transaction};
+use sage_wallet::{
+    compute_nft_info, generate_thumbnail, ChildKind, CoinKind, Data, MediaCache, Transaction,
+};
 
 use crate::{Error, Result, Sage};
 
@@ -77,7 +79,9 @@ impl Sage {
                     (kind, info.p2_puzzle_hash)
                 }
                 CoinKind::Nft { info, metadata } => {
-                    let extracted = extract_nft_data(Some(&wallet.db), metadata, &cache).await?;
+                    let extracted =
+                        extract_nft_data(Some(&wallet.db), &self.media_cache(), metadata, &cache)
+                            .await?;
 
                     let kind = AssetKind::Nft {
                         launcher_id: encode_address(info.launcher_id.into(), "nft")?,
@@ -101,15 +105,23 @@ impl Sage {
                     ChildKind::Cat { p2_puzzle_hash, .. } => p2_puzzle_hash,
                     ChildKind::Did { info, .. } => info.p2_puzzle_hash,
                     ChildKind::Nft { info, .. } => info.p2_puzzle_hash,
+                    ChildKind::Clawback { clawback, .. } => clawback.recipient_puzzle_hash,
                 };
 
                 let address =
                     encode_address(p2_puzzle_hash.into(), &self.network().address_prefix)?;
 
+                let contact = wallet
+                    .db
+                    .contact_by_puzzle_hash(p2_puzzle_hash)
+                    .await?
+                    .map(|row| row.name);
+
                 outputs.push(TransactionOutput {
                     coin_id: hex::encode(output.coin.coin_id()),
                     amount: Amount::u64(output.coin.amount),
                     address,
+                    contact,
                     receiving: wallet.db.is_p2_puzzle_hash(p2_puzzle_hash).await?,
                     burning: p2_puzzle_hash.to_bytes()
                         == hex!("000000000000000000000000000000000000000000000000000000000000dead"),
@@ -141,6 +153,7 @@ pub struct ExtractedNftData {
 
 pub async fn extract_nft_data(
     db: Option<&Database>,
+    media_cache: &MediaCache,
     onchain_metadata: Option<NftMetadata>,
     cache: &ConfirmationInfo,
 ) -> Result<ExtractedNftData> {
@@ -152,13 +165,18 @@ pub async fn extract_nft_data(
 
     if let Some(data_hash) = onchain_metadata.data_hash {
         if let Some(data) = cache.nft_data.get(&data_hash) {
-            result.image_data = Some(BASE64_STANDARD.encode(&data.blob));
-            result.image_mime_type = Some(data.mime_type.clone());
-        } else if let Some(db) = &db {
-            if let Some(data) = db.fetch_nft_data(data_hash).await? {
+            if let Some(thumbnail) =
+                generate_thumbnail(data.blob.clone(), data.mime_type.clone()).await?
+            {
+                result.image_data = Some(BASE64_STANDARD.encode(thumbnail));
+                result.image_mime_type = Some("image/png".to_string());
+            } else {
                 result.image_data = Some(BASE64_STANDARD.encode(&data.blob));
-                result.image_mime_type = Some(data.mime_type);
+                result.image_mime_type = Some(data.mime_type.clone());
             }
+        } else if let Some(data) = media_cache.get(data_hash, true).await? {
+            result.image_data = Some(BASE64_STANDARD.encode(&data.blob));
+            result.image_mime_type = Some(data.mime_type);
         }
     }
 
@@ -0,0 +1,280 @@
+use std::cmp::Ordering;
+
+use chia::protocol::Bytes32;
+use chrono::{TimeZone, Utc};
+use indexmap::IndexMap;
+use serde::Serialize;
+
+/// The asset of an exported coin. The id is used to filter by asset.
+#[derive(Debug, Clone)]
+pub struct ExportAsset {
+    pub id: Option<Bytes32>,
+    pub display_id: Option<String>,
+    pub ticker: Option<String>,
+    pub decimals: u8,
+}
+
+/// The amounts of an asset spent and created by a transaction, which is written as a single row
+/// of an export. Change is included in the amount received, so that received minus sent minus
+/// the fee is always the change in balance. The fee is only listed on the XCH row.
+#[derive(Debug, Clone, Serialize)]
+pub struct ExportRow {
+    pub transaction_id: String,
+    pub height: u32,
+    pub timestamp: Option<u64>,
+    pub date: Option<String>,
+    pub kind: &'static str,
+    pub ticker: Option<String>,
+    pub asset_id: Option<String>,
+    pub sent: String,
+    pub received: String,
+    pub fee: String,
+    pub counterparty: Option<String>,
+    pub label: Option<String>,
+}
+
+impl ExportRow {
+    const HEADER: [&'static str; 12] = [
+        "transaction_id",
+        "height",
+        "timestamp",
+        "date",
+        "kind",
+        "ticker",
+        "asset_id",
+        "sent",
+        "received",
+        "fee",
+        "counterparty",
+        "label",
+    ];
+
+    fn fields(&self) -> [String; 12] {
+        [
+            self.transaction_id.clone(),
+            self.height.to_string(),
+            self.timestamp
+                .map(|timestamp| timestamp.to_string())
+                .unwrap_or_default(),
+            self.date.clone().unwrap_or_default(),
+            self.kind.to_string(),
+            self.ticker.clone().unwrap_or_default(),
+            self.asset_id.clone().unwrap_or_default(),
+            self.sent.clone(),
+            self.received.clone(),
+            self.fee.clone(),
+            self.counterparty.clone().unwrap_or_default(),
+            self.label.clone().unwrap_or_default(),
+        ]
+    }
+}
+
+/// Formats an amount of mojos in display units, without trailing zeros.
+pub fn format_amount(amount: impl Into<u128>, decimals: u8) -> String {
+    let amount = amount.into();
+    let divisor = 10u128.pow(decimals.into());
+    let whole = amount / divisor;
+    let fraction = amount % divisor;
+
+    if fraction == 0 {
+        return whole.to_string();
+    }
+
+    let fraction = format!("{fraction:0width$}", width = usize::from(decimals));
+    format!("{whole}.{}", fraction.trim_end_matches('0'))
+}
+
+/// Sums the amounts of the coins spent (negative) and created (positive) by a transaction into
+/// the amount sent and received of each asset, in the order they first appear.
+pub fn asset_totals(coins: Vec<(ExportAsset, i128)>) -> Vec<(ExportAsset, u128, u128)> {
+    let mut assets: IndexMap<Option<Bytes32>, (ExportAsset, u128, u128)> = IndexMap::new();
+
+    for (asset, amount) in coins {
+        let totals = assets.entry(asset.id).or_insert_with(|| (asset, 0, 0));
+
+        if amount < 0 {
+            totals.1 += amount.unsigned_abs();
+        } else {
+            totals.2 += amount.unsigned_abs();
+        }
+    }
+
+    assets.into_values().collect()
+}
+
+/// Whether a transaction sent or received an asset overall, or only moved it within the wallet.
+/// The fee is left out, so that combining coins with a fee still counts as an internal move.
+pub fn export_kind(sent: u128, received: u128, fee: u64) -> &'static str {
+    match received.cmp(&sent.saturating_sub(fee.into())) {
+        Ordering::Greater => "received",
+        Ordering::Less => "sent",
+        Ordering::Equal => "internal",
+    }
+}
+
+/// Formats a block timestamp as an RFC 3339 date in UTC.
+pub fn format_timestamp(timestamp: u64) -> Option<String> {
+    Utc.timestamp_opt(timestamp.try_into().ok()?, 0)
+        .single()
+        .map(|date| date.to_rfc3339())
+}
+
+/// Writes the rows as CSV, with a header row naming each column.
+pub fn export_csv(rows: &[ExportRow]) -> String {
+    let mut csv = ExportRow::HEADER.join(",");
+    csv.push('\n');
+
+    for row in rows {
+        let fields: Vec<String> = row.fields().iter().map(|field| escape_csv(field)).collect();
+        csv.push_str(&fields.join(","));
+        csv.push('\n');
+    }
+
+    csv
+}
+
+fn escape_csv(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn asset(id: Option<Bytes32>) -> ExportAsset {
+        ExportAsset {
+            id,
+            display_id: id.map(hex::encode),
+            ticker: None,
+            decimals: 3,
+        }
+    }
+
+    fn totals(coins: Vec<(ExportAsset, i128)>) -> Vec<(Option<Bytes32>, u128, u128)> {
+        asset_totals(coins)
+            .into_iter()
+            .map(|(asset, sent, received)| (asset.id, sent, received))
+            .collect()
+    }
+
+    #[test]
+    fn test_format_amount() {
+        assert_eq!(format_amount(0u64, 12), "0");
+        assert_eq!(format_amount(1_000_000_000_000u64, 12), "1");
+        assert_eq!(format_amount(1_500_000_000_000u64, 12), "1.5");
+        assert_eq!(format_amount(1u64, 12), "0.000000000001");
+        assert_eq!(format_amount(1_230u64, 3), "1.23");
+        assert_eq!(format_amount(42u64, 0), "42");
+        assert_eq!(format_amount(u64::MAX, 12), "18446744.073709551615");
+    }
+
+    #[test]
+    fn test_escape_csv() {
+        assert_eq!(escape_csv(""), "");
+        assert_eq!(escape_csv("plain"), "plain");
+        assert_eq!(escape_csv("a,b"), "\"a,b\"");
+        assert_eq!(escape_csv("say \"hi\""), "\"say \"\"hi\"\"\"");
+        assert_eq!(escape_csv("line\nbreak"), "\"line\nbreak\"");
+        assert_eq!(escape_csv("carriage\rreturn"), "\"carriage\rreturn\"");
+    }
+
+    #[test]
+    fn test_export_csv() {
+        let rows = [
+            ExportRow {
+                transaction_id: "ab".to_string(),
+                height: 10,
+                timestamp: Some(0),
+                date: format_timestamp(0),
+                kind: "sent",
+                ticker: Some("XCH".to_string()),
+                asset_id: None,
+                sent: "1.5".to_string(),
+                received: "0.49".to_string(),
+                fee: "0.01".to_string(),
+                counterparty: Some("xch1abc".to_string()),
+                label: Some("Rent, March".to_string()),
+            },
+            ExportRow {
+                transaction_id: "cd".to_string(),
+                height: 11,
+                timestamp: None,
+                date: None,
+                kind: "received",
+                ticker: None,
+                asset_id: Some("ef".to_string()),
+                sent: "0".to_string(),
+                received: "2".to_string(),
+                fee: "0".to_string(),
+                counterparty: None,
+                label: None,
+            },
+        ];
+
+        assert_eq!(
+            export_csv(&[]),
+            format!("{}\n", ExportRow::HEADER.join(","))
+        );
+        assert_eq!(
+            export_csv(&rows),
+            "transaction_id,height,timestamp,date,kind,ticker,asset_id,sent,received,fee,counterparty,label\n\
+             ab,10,0,1970-01-01T00:00:00+00:00,sent,XCH,,1.5,0.49,0.01,xch1abc,\"Rent, March\"\n\
+             cd,11,,,received,,ef,0,2,0,,\n"
+        );
+    }
+
+    #[test]
+    fn test_asset_totals() {
+        let xch = None;
+        let cat = Some(Bytes32::new([1; 32]));
+
+        // Change is counted as received, rather than cancelling out part of the inputs.
+        assert_eq!(
+            totals(vec![(asset(xch), -1000), (asset(xch), 690)]),
+            [(xch, 1000, 690)]
+        );
+
+        // Receiving coins sums them.
+        assert_eq!(
+            totals(vec![(asset(cat), 5), (asset(cat), 7)]),
+            [(cat, 0, 12)]
+        );
+
+        // Each asset is summed separately, in the order they first appear.
+        assert_eq!(
+            totals(vec![
+                (asset(cat), -100),
+                (asset(xch), -50),
+                (asset(cat), 40),
+                (asset(xch), 45),
+            ]),
+            [(cat, 100, 40), (xch, 50, 45)]
+        );
+
+        // Amounts are summed without overflowing.
+        assert_eq!(
+            totals(vec![
+                (asset(xch), u64::MAX.into()),
+                (asset(xch), u64::MAX.into())
+            ]),
+            [(xch, 0, u128::from(u64::MAX) * 2)]
+        );
+    }
+
+    #[test]
+    fn test_export_kind() {
+        // Sending XCH with change and a fee.
+        assert_eq!(export_kind(1000, 690, 10), "sent");
+
+        // Combining coins with a fee doesn't send anything to anyone else.
+        assert_eq!(export_kind(1000, 990, 10), "internal");
+        assert_eq!(export_kind(100, 100, 0), "internal");
+
+        assert_eq!(export_kind(0, 12, 0), "received");
+        assert_eq!(export_kind(100, 40, 0), "sent");
+    }
+}
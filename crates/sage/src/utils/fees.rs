@@ -0,0 +1,154 @@
+use std::future::Future;
+
+use chia::protocol::CoinSpend;
+use sage_wallet::{
+    estimate_fee_rate, spend_cost, UnsignedMakeOffer, UnsignedTakeOffer, WalletError,
+};
+
+use crate::{Error, Result, Sage};
+
+/// The result of building a transaction, from which the coin spends can be costed.
+pub(crate) trait BuiltSpends {
+    fn coin_spends(&self) -> &[CoinSpend];
+}
+
+impl BuiltSpends for Vec<CoinSpend> {
+    fn coin_spends(&self) -> &[CoinSpend] {
+        self
+    }
+}
+
+impl BuiltSpends for UnsignedMakeOffer {
+    fn coin_spends(&self) -> &[CoinSpend] {
+        &self.coin_spends
+    }
+}
+
+impl BuiltSpends for UnsignedTakeOffer {
+    fn coin_spends(&self) -> &[CoinSpend] {
+        &self.coin_spends
+    }
+}
+
+impl<A> BuiltSpends for (Vec<CoinSpend>, A) {
+    fn coin_spends(&self) -> &[CoinSpend] {
+        &self.0
+    }
+}
+
+impl<A, B> BuiltSpends for (Vec<CoinSpend>, A, B) {
+    fn coin_spends(&self) -> &[CoinSpend] {
+        &self.0
+    }
+}
+
+impl Sage {
+    /// Returns the median fee rate in mojos per cost reported by the connected peers.
+    pub(crate) async fn fee_rate(&self, target_seconds: u64) -> Result<u64> {
+        let peers = self.peer_state.lock().await.peers();
+
+        if peers.is_empty() {
+            return Err(Error::NoPeers);
+        }
+
+        estimate_fee_rate(&peers, target_seconds)
+            .await?
+            .ok_or(Error::NoFeeEstimate)
+    }
+
+    /// Builds a transaction with the given fee. If a fee target is set, the fee for the cost
+    /// of the resulting coin spends is estimated, and the transaction is rebuilt with it if
+    /// it's higher than the fee that was specified.
+    pub(crate) async fn build_with_fee<T, F, Fut>(
+        &self,
+        fee: u64,
+        fee_target_seconds: Option<u64>,
+        build: F,
+    ) -> Result<T>
+    where
+        T: BuiltSpends,
+        F: Fn(u64) -> Fut,
+        Fut: Future<Output = std::result::Result<T, WalletError>>,
+    {
+        let fee_rate = match fee_target_seconds {
+            Some(target_seconds) => Some(self.fee_rate(target_seconds).await?),
+            None => None,
+        };
+
+        build_with_fee_rate(fee, fee_rate, build).await
+    }
+}
+
+/// Builds a transaction with the given fee, and rebuilds it with the fee for its cost at the
+/// given fee rate if that's higher.
+async fn build_with_fee_rate<T, F, Fut>(fee: u64, fee_rate: Option<u64>, build: F) -> Result<T>
+where
+    T: BuiltSpends,
+    F: Fn(u64) -> Fut,
+    Fut: Future<Output = std::result::Result<T, WalletError>>,
+{
+    let built = build(fee).await?;
+
+    let Some(fee_rate) = fee_rate else {
+        return Ok(built);
+    };
+
+    let estimated_fee = fee_rate.saturating_mul(spend_cost(built.coin_spends())?);
+
+    if estimated_fee <= fee {
+        return Ok(built);
+    }
+
+    Ok(build(estimated_fee).await?)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+
+    use chia::protocol::{Bytes32, Coin, Program};
+
+    use super::*;
+
+    fn test_spend(fee: u64) -> CoinSpend {
+        // The puzzle `1` returns its solution, which is nil, so the cost is the same for any fee.
+        CoinSpend::new(
+            Coin::new(Bytes32::default(), Bytes32::default(), fee),
+            Program::from(vec![1]),
+            Program::from(vec![0x80]),
+        )
+    }
+
+    async fn build(fee: u64, fee_rate: Option<u64>) -> Result<Vec<u64>> {
+        let fees = RefCell::new(Vec::new());
+
+        build_with_fee_rate(fee, fee_rate, |fee| {
+            fees.borrow_mut().push(fee);
+            async move { Ok(vec![test_spend(fee)]) }
+        })
+        .await?;
+
+        Ok(fees.into_inner())
+    }
+
+    #[tokio::test]
+    async fn test_build_with_fee_rate() -> Result<()> {
+        let cost = spend_cost(&[test_spend(0)])?;
+
+        // Without a fee target, the fee is used as is.
+        assert_eq!(build(100, None).await?, [100]);
+
+        // The specified fee is enough.
+        assert_eq!(build(cost * 5, Some(5)).await?, [cost * 5]);
+        assert_eq!(build(cost * 10, Some(5)).await?, [cost * 10]);
+
+        // The specified fee is too low, so it's rebuilt with the estimated fee.
+        assert_eq!(build(0, Some(5)).await?, [0, cost * 5]);
+        assert_eq!(
+            build(cost * 5 - 1, Some(5)).await?,
+            [cost * 5 - 1, cost * 5]
+        );
+
+        Ok(())
+    }
+}
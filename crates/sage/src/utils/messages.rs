@@ -0,0 +1,128 @@
+use chia::{
+    bls::{
+        master_to_wallet_hardened, master_to_wallet_unhardened, sign, verify, PublicKey, SecretKey,
+        Signature,
+    },
+    clvm_utils::{ToTreeHash, TreeHash},
+    protocol::Bytes,
+    puzzles::DeriveSynthetic,
+};
+use sage_database::DerivationRow;
+
+use crate::{Error, Result, Sage};
+
+/// Decodes a message to sign or verify, which is either hex encoded or UTF-8 text.
+pub fn parse_message(message: String, is_hex: bool) -> Result<Bytes> {
+    if is_hex {
+        Ok(Bytes::from(hex::decode(message)?))
+    } else {
+        Ok(Bytes::from(message.into_bytes()))
+    }
+}
+
+/// The hash which is signed for a message, as specified by CHIP-0002.
+pub fn signed_message_hash(message: Bytes) -> TreeHash {
+    ("Chia Signed Message", message).tree_hash()
+}
+
+pub fn verify_message(public_key: &PublicKey, message: Bytes, signature: &Signature) -> bool {
+    verify(signature, public_key, signed_message_hash(message))
+}
+
+/// Signs a message with the synthetic secret key of a derivation of the master secret key.
+pub fn sign_with_derivation(
+    master_sk: &SecretKey,
+    derivation: &DerivationRow,
+    message: Bytes,
+) -> Signature {
+    let secret_key = if derivation.hardened {
+        master_to_wallet_hardened(master_sk, derivation.index)
+    } else {
+        master_to_wallet_unhardened(master_sk, derivation.index)
+    }
+    .derive_synthetic();
+
+    sign(&secret_key, signed_message_hash(message))
+}
+
+impl Sage {
+    /// Signs a message with the synthetic secret key of one of the wallet's derivations.
+    pub(crate) fn sign_message(
+        &self,
+        derivation: &DerivationRow,
+        message: Bytes,
+    ) -> Result<Signature> {
+        let wallet = self.wallet()?;
+
+        let (_mnemonic, Some(master_sk)) = self.extract_secrets(wallet.fingerprint)? else {
+            return Err(Error::NoSigningKey);
+        };
+
+        Ok(sign_with_derivation(&master_sk, derivation, message))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chia::puzzles::standard::StandardArgs;
+
+    use super::*;
+
+    fn test_derivation(master_sk: &SecretKey, index: u32, hardened: bool) -> DerivationRow {
+        let synthetic_key = if hardened {
+            master_to_wallet_hardened(master_sk, index)
+        } else {
+            master_to_wallet_unhardened(master_sk, index)
+        }
+        .derive_synthetic()
+        .public_key();
+
+        DerivationRow {
+            p2_puzzle_hash: StandardArgs::curry_tree_hash(synthetic_key).into(),
+            index,
+            hardened,
+            synthetic_key,
+        }
+    }
+
+    #[test]
+    fn test_parse_message() -> Result<()> {
+        assert_eq!(
+            parse_message("68656c6c6f".to_string(), true)?,
+            parse_message("hello".to_string(), false)?
+        );
+        assert!(parse_message("hello".to_string(), true).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_sign_and_verify() {
+        let master_sk = SecretKey::from_seed(&[1; 32]);
+        let message = Bytes::from(b"hello".to_vec());
+
+        for hardened in [false, true] {
+            let derivation = test_derivation(&master_sk, 5, hardened);
+            let signature = sign_with_derivation(&master_sk, &derivation, message.clone());
+
+            assert!(verify_message(
+                &derivation.synthetic_key,
+                message.clone(),
+                &signature
+            ));
+            assert!(!verify_message(
+                &derivation.synthetic_key,
+                Bytes::from(b"goodbye".to_vec()),
+                &signature
+            ));
+
+            // The signature only verifies against the key of the address it was signed by.
+            let other = test_derivation(&master_sk, 5, !hardened);
+            assert!(!verify_message(
+                &other.synthetic_key,
+                message.clone(),
+                &signature
+            ));
+        }
+    }
+}
@@ -88,6 +88,7 @@ impl Sage {
 
                 if let Some(hash) = metadata.data_hash {
                     if let Some(data) = lookup_from_uris_with_hash(
+                        &self.uri_resolver(),
                         metadata.data_uris.clone(),
                         Duration::from_secs(10),
                         Duration::from_secs(5),
@@ -101,6 +102,7 @@ impl Sage {
 
                 if let Some(hash) = metadata.metadata_hash {
                     if let Some(data) = lookup_from_uris_with_hash(
+                        &self.uri_resolver(),
                         metadata.metadata_uris.clone(),
                         Duration::from_secs(10),
                         Duration::from_secs(5),
@@ -112,7 +114,13 @@ impl Sage {
                     }
                 }
 
-                extract_nft_data(Some(&wallet.db), Some(metadata), &confirmation_info).await?
+                extract_nft_data(
+                    Some(&wallet.db),
+                    &self.media_cache(),
+                    Some(metadata),
+                    &confirmation_info,
+                )
+                .await?
             } else {
                 ExtractedNftData::default()
             };
@@ -160,6 +168,7 @@ impl Sage {
             let metadata = NftMetadata::from_clvm(&ctx.allocator, nft.metadata.ptr())?;
             let info = extract_nft_data(
                 Some(&wallet.db),
+                &self.media_cache(),
                 Some(metadata),
                 &ConfirmationInfo::default(),
             )
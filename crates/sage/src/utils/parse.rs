@@ -2,7 +2,7 @@
 
 use chia::{
     bls::{PublicKey, Signature},
-    protocol::{Bytes32, Program},
+    protocol::{Bytes, Bytes32, Program},
 };
 use chia_wallet_sdk::decode_address;
 use sage_api::Amount;
@@ -73,6 +73,13 @@ pub fn parse_offer_id(input: String) -> Result<Bytes32> {
     Ok(asset_id.into())
 }
 
+pub fn parse_transaction_id(input: String) -> Result<Bytes32> {
+    let transaction_id: [u8; 32] = hex::decode(&input)?
+        .try_into()
+        .map_err(|_| Error::InvalidTransactionId(input))?;
+    Ok(transaction_id.into())
+}
+
 pub fn parse_cat_amount(input: Amount) -> Result<u64> {
     let Some(amount) = input.to_u64() else {
         return Err(Error::InvalidAmount(input.to_string()));
@@ -93,6 +100,18 @@ pub fn parse_puzzle_hash(input: String) -> Result<Bytes32> {
         .map_err(|_| Error::InvalidPuzzleHash(input))
 }
 
+pub fn parse_hash(input: String) -> Result<Bytes32> {
+    let stripped = if let Some(stripped) = input.strip_prefix("0x") {
+        stripped
+    } else {
+        &input
+    };
+
+    hex::decode(stripped)?
+        .try_into()
+        .map_err(|_| Error::InvalidHash(input))
+}
+
 pub fn parse_signature(input: String) -> Result<Signature> {
     let stripped = if let Some(stripped) = input.strip_prefix("0x") {
         stripped
@@ -130,3 +149,13 @@ pub fn parse_program(input: String) -> Result<Program> {
 
     Ok(hex::decode(stripped)?.into())
 }
+
+pub fn parse_memos(input: Vec<String>) -> Result<Vec<Bytes>> {
+    let mut memos = Vec::new();
+
+    for memo in input {
+        memos.push(Bytes::from(hex::decode(memo)?));
+    }
+
+    Ok(memos)
+}
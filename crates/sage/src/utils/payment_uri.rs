@@ -0,0 +1,218 @@
+use chia::protocol::Bytes32;
+use url::{form_urlencoded, Url};
+
+use crate::{format_amount, parse_asset_id, Error, Result};
+
+/// A request for payment, which is shared as a `chia:` URI such as
+/// `chia:xch1...?amount=1.5&memo=Order%2042`.
+///
+/// The amount is written in display units, with 3 decimals if there is a CAT asset id.
+#[derive(Debug, Clone)]
+pub struct PaymentUri {
+    pub address: String,
+    pub asset_id: Option<Bytes32>,
+    pub amount: Option<u64>,
+    pub memo: Option<String>,
+}
+
+impl PaymentUri {
+    pub fn encode(&self, xch_decimals: u8) -> String {
+        let mut query = form_urlencoded::Serializer::new(String::new());
+
+        if let Some(asset_id) = self.asset_id {
+            query.append_pair("asset_id", &hex::encode(asset_id));
+        }
+
+        if let Some(amount) = self.amount {
+            query.append_pair(
+                "amount",
+                &format_amount(amount, self.decimals(xch_decimals)),
+            );
+        }
+
+        if let Some(memo) = &self.memo {
+            query.append_pair("memo", memo);
+        }
+
+        let query = query.finish();
+
+        if query.is_empty() {
+            format!("chia:{}", self.address)
+        } else {
+            format!("chia:{}?{query}", self.address)
+        }
+    }
+
+    pub fn decode(uri: &str, xch_decimals: u8) -> Result<Self> {
+        let invalid = || Error::InvalidPaymentUri(uri.to_string());
+
+        let url = Url::parse(uri).map_err(|_| invalid())?;
+
+        if url.scheme() != "chia" || url.path().is_empty() {
+            return Err(invalid());
+        }
+
+        let mut payment = Self {
+            address: url.path().to_string(),
+            asset_id: None,
+            amount: None,
+            memo: None,
+        };
+
+        let mut amount = None;
+
+        for (key, value) in url.query_pairs() {
+            match key.as_ref() {
+                "asset_id" => payment.asset_id = Some(parse_asset_id(value.into_owned())?),
+                "amount" => amount = Some(value.into_owned()),
+                "memo" => payment.memo = Some(value.into_owned()),
+                _ => {}
+            }
+        }
+
+        if let Some(amount) = amount {
+            payment.amount = Some(
+                parse_display_amount(&amount, payment.decimals(xch_decimals))
+                    .ok_or_else(invalid)?,
+            );
+        }
+
+        Ok(payment)
+    }
+
+    fn decimals(&self, xch_decimals: u8) -> u8 {
+        if self.asset_id.is_some() {
+            3
+        } else {
+            xch_decimals
+        }
+    }
+}
+
+/// Parses an amount in display units into mojos, rejecting more decimals than the asset has.
+pub fn parse_display_amount(amount: &str, decimals: u8) -> Option<u64> {
+    let (whole, fraction) = amount.split_once('.').unwrap_or((amount, ""));
+
+    if (whole.is_empty() && fraction.is_empty()) || fraction.len() > usize::from(decimals) {
+        return None;
+    }
+
+    if !whole
+        .chars()
+        .chain(fraction.chars())
+        .all(|c| c.is_ascii_digit())
+    {
+        return None;
+    }
+
+    let whole = if whole.is_empty() {
+        0
+    } else {
+        whole.parse::<u64>().ok()?
+    };
+
+    let fraction = format!("{fraction:0<width$}", width = usize::from(decimals));
+    let fraction = if fraction.is_empty() {
+        0
+    } else {
+        fraction.parse::<u64>().ok()?
+    };
+
+    whole
+        .checked_mul(10u64.checked_pow(decimals.into())?)?
+        .checked_add(fraction)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_display_amount() {
+        assert_eq!(parse_display_amount("1", 12), Some(1_000_000_000_000));
+        assert_eq!(parse_display_amount("1.5", 12), Some(1_500_000_000_000));
+        assert_eq!(parse_display_amount(".5", 3), Some(500));
+        assert_eq!(parse_display_amount("5.", 3), Some(5000));
+        assert_eq!(parse_display_amount("0.001", 3), Some(1));
+        assert_eq!(parse_display_amount("42", 0), Some(42));
+
+        // Amounts are never rounded, so extra decimals are rejected.
+        assert_eq!(parse_display_amount("0.0001", 3), None);
+        assert_eq!(parse_display_amount("1.0", 0), None);
+
+        assert_eq!(parse_display_amount("", 3), None);
+        assert_eq!(parse_display_amount(".", 3), None);
+        assert_eq!(parse_display_amount("-1", 3), None);
+        assert_eq!(parse_display_amount("1e3", 3), None);
+        assert_eq!(parse_display_amount(" 1", 3), None);
+        assert_eq!(parse_display_amount("1.2.3", 3), None);
+    }
+
+    #[test]
+    fn test_parse_display_amount_overflow() {
+        assert_eq!(
+            parse_display_amount("18446744.073709551615", 12),
+            Some(u64::MAX)
+        );
+        assert_eq!(parse_display_amount("18446744.073709551616", 12), None);
+        assert_eq!(parse_display_amount("18446744073709551616", 0), None);
+        assert_eq!(parse_display_amount("1", 20), None);
+    }
+
+    #[test]
+    fn test_payment_uri_round_trip() -> Result<()> {
+        let payment = PaymentUri {
+            address: "xch1test".to_string(),
+            asset_id: None,
+            amount: Some(1_500_000_000_000),
+            memo: Some("Order 42&more".to_string()),
+        };
+
+        let uri = payment.encode(12);
+        assert_eq!(uri, "chia:xch1test?amount=1.5&memo=Order+42%26more");
+
+        let decoded = PaymentUri::decode(&uri, 12)?;
+        assert_eq!(decoded.address, payment.address);
+        assert_eq!(decoded.asset_id, None);
+        assert_eq!(decoded.amount, payment.amount);
+        assert_eq!(decoded.memo, payment.memo);
+
+        let payment = PaymentUri {
+            address: "xch1test".to_string(),
+            asset_id: Some(Bytes32::new([1; 32])),
+            amount: Some(u64::MAX),
+            memo: None,
+        };
+
+        // CAT amounts always have 3 decimals, regardless of the XCH unit.
+        let uri = payment.encode(12);
+        assert_eq!(
+            uri,
+            format!(
+                "chia:xch1test?asset_id={}&amount=18446744073709551.615",
+                hex::encode([1; 32])
+            )
+        );
+
+        let decoded = PaymentUri::decode(&uri, 12)?;
+        assert_eq!(decoded.asset_id, payment.asset_id);
+        assert_eq!(decoded.amount, Some(u64::MAX));
+
+        let decoded = PaymentUri::decode(&PaymentUri::decode("chia:xch1test", 12)?.encode(12), 12)?;
+        assert_eq!(decoded.address, "xch1test");
+        assert_eq!(decoded.amount, None);
+        assert_eq!(decoded.memo, None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid_payment_uri() {
+        assert!(PaymentUri::decode("xch1test", 12).is_err());
+        assert!(PaymentUri::decode("https://example.com?amount=1", 12).is_err());
+        assert!(PaymentUri::decode("chia:", 12).is_err());
+        assert!(PaymentUri::decode("chia:xch1test?amount=0.0000000000001", 12).is_err());
+        assert!(PaymentUri::decode("chia:xch1test?amount=18446744.073709551616", 12).is_err());
+        assert!(PaymentUri::decode("chia:xch1test?asset_id=abc", 12).is_err());
+    }
+}
@@ -1,8 +1,11 @@
-use chia::protocol::{CoinSpend, SpendBundle};
+use chia::{
+    bls::SecretKey,
+    protocol::{Bytes32, CoinSpend, SpendBundle},
+};
 use chia_wallet_sdk::AggSigConstants;
-use sage_wallet::{insert_transaction, SyncCommand, Transaction};
+use sage_wallet::{insert_transaction, replace_transaction, SyncCommand, Transaction};
 
-use crate::{Error, Result, Sage};
+use crate::{Error, Result, Sage, Submission};
 
 use super::parse_genesis_challenge;
 
@@ -14,12 +17,25 @@ impl Sage {
     ) -> Result<SpendBundle> {
         let wallet = self.wallet()?;
 
-        let (_mnemonic, Some(master_sk)) =
-            self.keychain.extract_secrets(wallet.fingerprint, b"")?
-        else {
+        let (_mnemonic, Some(master_sk)) = self.extract_secrets(wallet.fingerprint)? else {
             return Err(Error::NoSigningKey);
         };
 
+        self.sign_with_key(coin_spends, master_sk, partial).await
+    }
+
+    /// Signs with a secret key that has already been extracted, such as by a background job.
+    pub(crate) async fn sign_with_key(
+        &self,
+        coin_spends: Vec<CoinSpend>,
+        master_sk: SecretKey,
+        partial: bool,
+    ) -> Result<SpendBundle> {
+        let wallet = self.wallet()?;
+
+        // Signing is a good opportunity to top up hardened derivations, since the key is available.
+        self.derive_hardened_keys(&wallet, &master_sk).await?;
+
         let spend_bundle = wallet
             .sign_transaction(
                 coin_spends,
@@ -33,6 +49,24 @@ impl Sage {
     }
 
     pub(crate) async fn submit(&self, spend_bundle: SpendBundle) -> Result<()> {
+        self.submission(spend_bundle)?.submit().await
+    }
+
+    pub(crate) fn submission(&self, spend_bundle: SpendBundle) -> Result<Submission> {
+        Ok(Submission {
+            wallet: self.wallet()?,
+            peer_state: self.peer_state.clone(),
+            command_sender: self.command_sender.clone(),
+            spend_bundle,
+        })
+    }
+
+    /// Swaps a pending transaction for one that replaces it in the mempool, and returns its id.
+    pub(crate) async fn replace(
+        &self,
+        transaction_id: Bytes32,
+        spend_bundle: SpendBundle,
+    ) -> Result<Bytes32> {
         let wallet = self.wallet()?;
         let peer = self
             .peer_state
@@ -41,16 +75,56 @@ impl Sage {
             .acquire_peer()
             .ok_or(Error::NoPeers)?;
 
-        let subscriptions = insert_transaction(
+        let new_transaction_id = spend_bundle.name();
+
+        let subscriptions = replace_transaction(
             &wallet.db,
             &peer,
             wallet.genesis_challenge,
-            spend_bundle.name(),
+            transaction_id,
+            new_transaction_id,
             Transaction::from_coin_spends(spend_bundle.coin_spends)?,
             spend_bundle.aggregated_signature,
         )
         .await?;
 
+        self.command_sender
+            .send(SyncCommand::SubscribeCoins {
+                coin_ids: subscriptions,
+            })
+            .await?;
+
+        self.command_sender
+            .send(SyncCommand::TransactionReplaced {
+                transaction_id,
+                new_transaction_id,
+            })
+            .await?;
+
+        Ok(new_transaction_id)
+    }
+}
+
+impl Submission {
+    /// Sends the transaction to a peer and starts tracking it in the wallet.
+    pub async fn submit(self) -> Result<()> {
+        let peer = self
+            .peer_state
+            .lock()
+            .await
+            .acquire_peer()
+            .ok_or(Error::NoPeers)?;
+
+        let subscriptions = insert_transaction(
+            &self.wallet.db,
+            &peer,
+            self.wallet.genesis_challenge,
+            self.spend_bundle.name(),
+            Transaction::from_coin_spends(self.spend_bundle.coin_spends)?,
+            self.spend_bundle.aggregated_signature,
+        )
+        .await?;
+
         self.command_sender
             .send(SyncCommand::SubscribeCoins {
                 coin_ids: subscriptions,
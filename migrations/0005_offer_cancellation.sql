@@ -0,0 +1 @@
+ALTER TABLE `offers` ADD COLUMN `cancelling` BOOLEAN NOT NULL DEFAULT 0;
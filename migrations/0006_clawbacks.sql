@@ -0,0 +1,14 @@
+CREATE TABLE `clawback_coins` (
+    `coin_id` BLOB NOT NULL PRIMARY KEY,
+    `sender_puzzle_hash` BLOB NOT NULL,
+    `recipient_puzzle_hash` BLOB NOT NULL,
+    `seconds` BLOB NOT NULL,
+    `asset_id` BLOB,
+    `parent_parent_coin_id` BLOB,
+    `parent_inner_puzzle_hash` BLOB,
+    `parent_amount` BLOB,
+    FOREIGN KEY (`coin_id`) REFERENCES `coin_states` (`coin_id`) ON DELETE CASCADE
+);
+
+CREATE INDEX `clawback_sender` ON `clawback_coins` (`sender_puzzle_hash`);
+CREATE INDEX `clawback_recipient` ON `clawback_coins` (`recipient_puzzle_hash`);
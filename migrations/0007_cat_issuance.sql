@@ -0,0 +1 @@
+ALTER TABLE `cats` ADD COLUMN `issuance_public_key` BLOB;
@@ -0,0 +1,29 @@
+CREATE TABLE `transaction_history` (
+    `id` BLOB NOT NULL PRIMARY KEY,
+    `height` INTEGER,
+    `direction` INTEGER NOT NULL,
+    `fee` BLOB NOT NULL,
+    `counterparty_puzzle_hash` BLOB,
+    `label` TEXT
+);
+
+CREATE INDEX `history_height` ON `transaction_history` (`height` DESC);
+CREATE INDEX `history_direction` ON `transaction_history` (`direction`);
+
+CREATE TABLE `transaction_history_coins` (
+    `transaction_id` BLOB NOT NULL,
+    `coin_id` BLOB NOT NULL,
+    `spent` BOOLEAN NOT NULL,
+    PRIMARY KEY (`transaction_id`, `coin_id`),
+    FOREIGN KEY (`transaction_id`) REFERENCES `transaction_history` (`id`) ON DELETE CASCADE
+);
+
+CREATE INDEX `history_coin_id` ON `transaction_history_coins` (`coin_id`);
+
+CREATE TABLE `transaction_history_memos` (
+    `transaction_id` BLOB NOT NULL,
+    `index` INTEGER NOT NULL,
+    `memo` BLOB NOT NULL,
+    PRIMARY KEY (`transaction_id`, `index`),
+    FOREIGN KEY (`transaction_id`) REFERENCES `transaction_history` (`id`) ON DELETE CASCADE
+);
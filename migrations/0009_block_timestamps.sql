@@ -0,0 +1,4 @@
+CREATE TABLE `block_timestamps` (
+    `height` INTEGER NOT NULL PRIMARY KEY,
+    `timestamp` BLOB NOT NULL
+);
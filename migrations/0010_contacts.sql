@@ -0,0 +1,9 @@
+CREATE TABLE `contacts` (
+    `id` INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+    `name` TEXT NOT NULL,
+    `puzzle_hash` BLOB NOT NULL UNIQUE,
+    `did_id` BLOB,
+    `notes` TEXT
+);
+
+CREATE INDEX `contact_name` ON `contacts` (`name`);
@@ -0,0 +1,23 @@
+CREATE TABLE `invoices` (
+    `id` INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+    `p2_puzzle_hash` BLOB NOT NULL UNIQUE,
+    `asset_id` BLOB,
+    `amount` BLOB NOT NULL,
+    `paid_amount` BLOB NOT NULL,
+    `memo` TEXT,
+    `expires_at` BLOB,
+    `created_at` BLOB NOT NULL,
+    `status` INTEGER NOT NULL
+);
+
+CREATE INDEX `invoice_status` ON `invoices` (`status`);
+
+CREATE TABLE `invoice_payments` (
+    `coin_id` BLOB NOT NULL PRIMARY KEY,
+    `invoice_id` INTEGER NOT NULL,
+    `amount` BLOB NOT NULL,
+    `height` INTEGER NOT NULL,
+    FOREIGN KEY (`invoice_id`) REFERENCES `invoices` (`id`) ON DELETE CASCADE
+);
+
+CREATE INDEX `invoice_payment_invoice_id` ON `invoice_payments` (`invoice_id`);
@@ -0,0 +1,10 @@
+CREATE TABLE `derivation_labels` (
+    `p2_puzzle_hash` BLOB NOT NULL PRIMARY KEY,
+    `label` TEXT NOT NULL,
+    FOREIGN KEY (`p2_puzzle_hash`) REFERENCES `derivations` (`p2_puzzle_hash`) ON DELETE CASCADE
+);
+
+CREATE TABLE `reserved_derivations` (
+    `p2_puzzle_hash` BLOB NOT NULL PRIMARY KEY,
+    FOREIGN KEY (`p2_puzzle_hash`) REFERENCES `derivations` (`p2_puzzle_hash`) ON DELETE CASCADE
+);
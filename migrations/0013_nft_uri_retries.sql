@@ -0,0 +1,9 @@
+ALTER TABLE `nft_uris` ADD COLUMN `attempts` INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE `nft_uris` ADD COLUMN `last_error` TEXT;
+ALTER TABLE `nft_uris` ADD COLUMN `last_attempt` BLOB;
+ALTER TABLE `nft_uris` ADD COLUMN `next_retry` BLOB;
+
+CREATE INDEX `nft_uri_next_retry` ON `nft_uris` (`checked`, `next_retry`);
+
+-- Failed URIs used to be marked as checked as well, so give them another chance.
+UPDATE `nft_uris` SET `checked` = 0 WHERE `hash` NOT IN (SELECT `hash` FROM `nft_data`);
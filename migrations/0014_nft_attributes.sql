@@ -0,0 +1,55 @@
+CREATE TABLE `nft_attributes` (
+    `launcher_id` BLOB NOT NULL,
+    `trait_type` TEXT NOT NULL,
+    `value` TEXT NOT NULL,
+    PRIMARY KEY (`launcher_id`, `trait_type`, `value`)
+);
+
+CREATE INDEX `nft_attribute_trait` ON `nft_attributes` (`trait_type`, `value`);
+
+-- NFTs are replaced rather than updated, which doesn't fire delete triggers, so attributes
+-- are only removed along with the NFT itself and are otherwise kept up to date by the wallet.
+CREATE TRIGGER `nft_attributes_delete` AFTER DELETE ON `nfts` BEGIN
+    DELETE FROM `nft_attributes` WHERE `launcher_id` = OLD.`launcher_id`;
+END;
+
+-- Parse the attributes of NFTs whose offchain metadata has already been fetched.
+INSERT OR IGNORE INTO `nft_attributes` (`launcher_id`, `trait_type`, `value`)
+SELECT
+    `nfts`.`launcher_id`,
+    json_extract(`attribute`.`value`, '$.trait_type'),
+    CAST(json_extract(`attribute`.`value`, '$.value') AS TEXT)
+FROM `nfts`
+INNER JOIN `nft_data` ON `nft_data`.`hash` = `nfts`.`metadata_hash`
+INNER JOIN json_each(CAST(`nft_data`.`data` AS TEXT), '$.attributes') AS `attribute`
+WHERE CASE WHEN json_valid(CAST(`nft_data`.`data` AS TEXT))
+    THEN json_type(CAST(`nft_data`.`data` AS TEXT), '$.attributes') = 'array'
+END
+AND CASE WHEN `attribute`.`type` = 'object'
+    THEN json_type(`attribute`.`value`, '$.trait_type') = 'text'
+    AND json_type(`attribute`.`value`, '$.value') IN ('text', 'integer', 'real')
+END;
+
+CREATE VIRTUAL TABLE `nft_search` USING fts5(`name`);
+
+-- The search index shares rowids with the NFTs it refers to.
+CREATE TRIGGER `nft_search_replace` BEFORE INSERT ON `nfts` BEGIN
+    DELETE FROM `nft_search` WHERE `rowid` IN (
+        SELECT `rowid` FROM `nfts` WHERE `launcher_id` = NEW.`launcher_id`
+    );
+END;
+
+CREATE TRIGGER `nft_search_insert` AFTER INSERT ON `nfts` WHEN NEW.`name` IS NOT NULL BEGIN
+    INSERT INTO `nft_search` (`rowid`, `name`) VALUES (NEW.`rowid`, NEW.`name`);
+END;
+
+CREATE TRIGGER `nft_search_update` AFTER UPDATE OF `name` ON `nfts` BEGIN
+    DELETE FROM `nft_search` WHERE `rowid` = OLD.`rowid`;
+    INSERT INTO `nft_search` (`rowid`, `name`) SELECT NEW.`rowid`, NEW.`name` WHERE NEW.`name` IS NOT NULL;
+END;
+
+CREATE TRIGGER `nft_search_delete` AFTER DELETE ON `nfts` BEGIN
+    DELETE FROM `nft_search` WHERE `rowid` = OLD.`rowid`;
+END;
+
+INSERT INTO `nft_search` (`rowid`, `name`) SELECT `rowid`, `name` FROM `nfts` WHERE `name` IS NOT NULL;
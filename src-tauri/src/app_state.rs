@@ -4,11 +4,10 @@ use std::{
     sync::Arc,
 };
 
-use sage::Sage;
-use sage_api::SyncEvent as ApiEvent;
-use sage_wallet::SyncEvent;
-use tauri::{AppHandle, Emitter};
+use sage::{api_event, needs_hardened_derivations, Sage};
+use tauri::{AppHandle, Emitter, Manager};
 use tokio::sync::Mutex;
+use tracing::error;
 
 use crate::error::Result;
 
@@ -55,20 +54,18 @@ impl AppStateInner {
 
         tokio::spawn(async move {
             while let Some(event) = receiver.recv().await {
-                let event = match event {
-                    SyncEvent::Start(ip) => ApiEvent::Start { ip: ip.to_string() },
-                    SyncEvent::Stop => ApiEvent::Stop,
-                    SyncEvent::Subscribed => ApiEvent::Subscribed,
-                    SyncEvent::DerivationIndex { .. } => ApiEvent::Derivation,
-                    // TODO: New event?
-                    SyncEvent::CoinsUpdated { .. }
-                    | SyncEvent::TransactionEnded { .. }
-                    | SyncEvent::OfferUpdated { .. } => ApiEvent::CoinState,
-                    SyncEvent::PuzzleBatchSynced => ApiEvent::PuzzleBatchSynced,
-                    SyncEvent::CatInfo => ApiEvent::CatInfo,
-                    SyncEvent::DidInfo => ApiEvent::DidInfo,
-                    SyncEvent::NftData => ApiEvent::NftData,
-                };
+                // This runs separately so that events keep draining while a command holds the lock.
+                if needs_hardened_derivations(&event) {
+                    let state = app_handle.state::<AppState>().inner().clone();
+
+                    tokio::spawn(async move {
+                        if let Err(error) = state.lock().await.sync_hardened_derivations().await {
+                            error!("Error while deriving hardened keys: {error:?}");
+                        }
+                    });
+                }
+
+                let event = api_event(event);
                 if app_handle.emit("sync-event", event).is_err() {
                     break;
                 }
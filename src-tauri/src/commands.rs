@@ -33,6 +33,39 @@ pub async fn initialize(state: State<'_, AppState>) -> Result<()> {
         }
     });
 
+    let app_state = (*state).clone();
+
+    tokio::spawn(async move {
+        loop {
+            let interval = app_state
+                .lock()
+                .await
+                .sage
+                .auto_consolidate_config()
+                .interval_seconds;
+
+            sleep(Duration::from_secs(interval.max(60))).await;
+
+            // The transaction is submitted after the lock is released, since that waits on a peer.
+            let result = app_state
+                .lock()
+                .await
+                .sage
+                .prepare_auto_consolidation()
+                .await;
+
+            let result = match result {
+                Ok(Some(submission)) => submission.submit().await,
+                Ok(None) => Ok(()),
+                Err(error) => Err(error),
+            };
+
+            if let Err(error) = result {
+                error!("Error while consolidating coins: {error:?}");
+            }
+        }
+    });
+
     Ok(())
 }
 
@@ -93,6 +126,33 @@ pub async fn get_secret_key(
     Ok(state.lock().await.get_secret_key(req)?)
 }
 
+#[command]
+#[specta]
+pub async fn unlock_keychain(
+    state: State<'_, AppState>,
+    req: UnlockKeychain,
+) -> Result<UnlockKeychainResponse> {
+    Ok(state.lock().await.unlock_keychain(req).await?)
+}
+
+#[command]
+#[specta]
+pub async fn lock_keychain(
+    state: State<'_, AppState>,
+    req: LockKeychain,
+) -> Result<LockKeychainResponse> {
+    Ok(state.lock().await.lock_keychain(req)?)
+}
+
+#[command]
+#[specta]
+pub async fn change_password(
+    state: State<'_, AppState>,
+    req: ChangePassword,
+) -> Result<ChangePasswordResponse> {
+    Ok(state.lock().await.change_password(req)?)
+}
+
 #[command]
 #[specta]
 pub async fn generate_mnemonic(
@@ -118,6 +178,15 @@ pub async fn send_xch(state: State<'_, AppState>, req: SendXch) -> Result<Transa
     Ok(state.lock().await.send_xch(req).await?)
 }
 
+#[command]
+#[specta]
+pub async fn send_xch_multi(
+    state: State<'_, AppState>,
+    req: SendXchMulti,
+) -> Result<TransactionResponse> {
+    Ok(state.lock().await.send_xch_multi(req).await?)
+}
+
 #[command]
 #[specta]
 pub async fn combine_xch(
@@ -127,6 +196,24 @@ pub async fn combine_xch(
     Ok(state.lock().await.combine_xch(req).await?)
 }
 
+#[command]
+#[specta]
+pub async fn auto_consolidate(
+    state: State<'_, AppState>,
+    req: AutoConsolidate,
+) -> Result<TransactionResponse> {
+    Ok(state.lock().await.auto_consolidate(req).await?)
+}
+
+#[command]
+#[specta]
+pub async fn split_into_denominations(
+    state: State<'_, AppState>,
+    req: SplitIntoDenominations,
+) -> Result<TransactionResponse> {
+    Ok(state.lock().await.split_into_denominations(req).await?)
+}
+
 #[command]
 #[specta]
 pub async fn split_xch(state: State<'_, AppState>, req: SplitXch) -> Result<TransactionResponse> {
@@ -154,12 +241,36 @@ pub async fn issue_cat(state: State<'_, AppState>, req: IssueCat) -> Result<Tran
     Ok(state.lock().await.issue_cat(req).await?)
 }
 
+#[command]
+#[specta]
+pub async fn mint_more_cat(
+    state: State<'_, AppState>,
+    req: MintMoreCat,
+) -> Result<TransactionResponse> {
+    Ok(state.lock().await.mint_more_cat(req).await?)
+}
+
+#[command]
+#[specta]
+pub async fn melt_cat(state: State<'_, AppState>, req: MeltCat) -> Result<TransactionResponse> {
+    Ok(state.lock().await.melt_cat(req).await?)
+}
+
 #[command]
 #[specta]
 pub async fn send_cat(state: State<'_, AppState>, req: SendCat) -> Result<TransactionResponse> {
     Ok(state.lock().await.send_cat(req).await?)
 }
 
+#[command]
+#[specta]
+pub async fn send_cat_multi(
+    state: State<'_, AppState>,
+    req: SendCatMulti,
+) -> Result<TransactionResponse> {
+    Ok(state.lock().await.send_cat_multi(req).await?)
+}
+
 #[command]
 #[specta]
 pub async fn create_did(state: State<'_, AppState>, req: CreateDid) -> Result<TransactionResponse> {
@@ -175,6 +286,15 @@ pub async fn bulk_mint_nfts(
     Ok(state.lock().await.bulk_mint_nfts(req).await?)
 }
 
+#[command]
+#[specta]
+pub async fn prepare_nft_mints(
+    state: State<'_, AppState>,
+    req: PrepareNftMints,
+) -> Result<PrepareNftMintsResponse> {
+    Ok(state.lock().await.prepare_nft_mints(req).await?)
+}
+
 #[command]
 #[specta]
 pub async fn transfer_nfts(
@@ -211,6 +331,24 @@ pub async fn transfer_dids(
     Ok(state.lock().await.transfer_dids(req).await?)
 }
 
+#[command]
+#[specta]
+pub async fn claim_clawback(
+    state: State<'_, AppState>,
+    req: ClaimClawback,
+) -> Result<TransactionResponse> {
+    Ok(state.lock().await.claim_clawback(req).await?)
+}
+
+#[command]
+#[specta]
+pub async fn revoke_clawback(
+    state: State<'_, AppState>,
+    req: RevokeClawback,
+) -> Result<TransactionResponse> {
+    Ok(state.lock().await.revoke_clawback(req).await?)
+}
+
 #[command]
 #[specta]
 pub async fn sign_coin_spends(
@@ -238,6 +376,33 @@ pub async fn submit_transaction(
     Ok(state.lock().await.submit_transaction(req).await?)
 }
 
+#[command]
+#[specta]
+pub async fn increase_transaction_fee(
+    state: State<'_, AppState>,
+    req: IncreaseTransactionFee,
+) -> Result<IncreaseTransactionFeeResponse> {
+    Ok(state.lock().await.increase_transaction_fee(req).await?)
+}
+
+#[command]
+#[specta]
+pub async fn cancel_transaction(
+    state: State<'_, AppState>,
+    req: CancelTransaction,
+) -> Result<CancelTransactionResponse> {
+    Ok(state.lock().await.cancel_transaction(req).await?)
+}
+
+#[command]
+#[specta]
+pub async fn estimate_fee(
+    state: State<'_, AppState>,
+    req: EstimateFee,
+) -> Result<EstimateFeeResponse> {
+    Ok(state.lock().await.estimate_fee(req).await?)
+}
+
 #[command]
 #[specta]
 pub async fn make_offer(state: State<'_, AppState>, req: MakeOffer) -> Result<MakeOfferResponse> {
@@ -286,6 +451,24 @@ pub async fn delete_offer(
     Ok(state.lock().await.delete_offer(req).await?)
 }
 
+#[command]
+#[specta]
+pub async fn cancel_offer(
+    state: State<'_, AppState>,
+    req: CancelOffer,
+) -> Result<TransactionResponse> {
+    Ok(state.lock().await.cancel_offer(req).await?)
+}
+
+#[command]
+#[specta]
+pub async fn cancel_offers(
+    state: State<'_, AppState>,
+    req: CancelOffers,
+) -> Result<TransactionResponse> {
+    Ok(state.lock().await.cancel_offers(req).await?)
+}
+
 #[command]
 #[specta]
 pub async fn get_sync_status(
@@ -304,6 +487,15 @@ pub async fn get_derivations(
     Ok(state.lock().await.get_derivations(req).await?)
 }
 
+#[command]
+#[specta]
+pub async fn get_next_address(
+    state: State<'_, AppState>,
+    req: GetNextAddress,
+) -> Result<GetNextAddressResponse> {
+    Ok(state.lock().await.get_next_address(req).await?)
+}
+
 #[command]
 #[specta]
 pub async fn get_xch_coins(
@@ -322,6 +514,15 @@ pub async fn get_cat_coins(
     Ok(state.lock().await.get_cat_coins(req).await?)
 }
 
+#[command]
+#[specta]
+pub async fn get_coins_by_ids(
+    state: State<'_, AppState>,
+    req: GetCoinsByIds,
+) -> Result<GetCoinsByIdsResponse> {
+    Ok(state.lock().await.get_coins_by_ids(req).await?)
+}
+
 #[command]
 #[specta]
 pub async fn get_cats(state: State<'_, AppState>, req: GetCats) -> Result<GetCatsResponse> {
@@ -349,6 +550,15 @@ pub async fn get_pending_transactions(
     Ok(state.lock().await.get_pending_transactions(req).await?)
 }
 
+#[command]
+#[specta]
+pub async fn get_clawbacks(
+    state: State<'_, AppState>,
+    req: GetClawbacks,
+) -> Result<GetClawbacksResponse> {
+    Ok(state.lock().await.get_clawbacks(req).await?)
+}
+
 #[command]
 #[specta]
 pub async fn get_transactions(
@@ -358,6 +568,24 @@ pub async fn get_transactions(
     Ok(state.lock().await.get_transactions(req).await?)
 }
 
+#[command]
+#[specta]
+pub async fn get_transaction(
+    state: State<'_, AppState>,
+    req: GetTransaction,
+) -> Result<GetTransactionResponse> {
+    Ok(state.lock().await.get_transaction(req).await?)
+}
+
+#[command]
+#[specta]
+pub async fn export_transactions(
+    state: State<'_, AppState>,
+    req: ExportTransactions,
+) -> Result<ExportTransactionsResponse> {
+    Ok(state.lock().await.export_transactions(req).await?)
+}
+
 #[command]
 #[specta]
 pub async fn get_nft_status(
@@ -391,6 +619,15 @@ pub async fn get_nfts(state: State<'_, AppState>, req: GetNfts) -> Result<GetNft
     Ok(state.lock().await.get_nfts(req).await?)
 }
 
+#[command]
+#[specta]
+pub async fn get_nft_traits(
+    state: State<'_, AppState>,
+    req: GetNftTraits,
+) -> Result<GetNftTraitsResponse> {
+    Ok(state.lock().await.get_nft_traits(req).await?)
+}
+
 #[command]
 #[specta]
 pub async fn get_nft(state: State<'_, AppState>, req: GetNft) -> Result<GetNftResponse> {
@@ -430,6 +667,105 @@ pub async fn update_nft(state: State<'_, AppState>, req: UpdateNft) -> Result<Up
     Ok(state.lock().await.update_nft(req).await?)
 }
 
+#[command]
+#[specta]
+pub async fn retry_nft_data(
+    state: State<'_, AppState>,
+    req: RetryNftData,
+) -> Result<RetryNftDataResponse> {
+    Ok(state.lock().await.retry_nft_data(req).await?)
+}
+
+#[command]
+#[specta]
+pub async fn update_transaction(
+    state: State<'_, AppState>,
+    req: UpdateTransaction,
+) -> Result<UpdateTransactionResponse> {
+    Ok(state.lock().await.update_transaction(req).await?)
+}
+
+#[command]
+#[specta]
+pub async fn update_derivation(
+    state: State<'_, AppState>,
+    req: UpdateDerivation,
+) -> Result<UpdateDerivationResponse> {
+    Ok(state.lock().await.update_derivation(req).await?)
+}
+
+#[command]
+#[specta]
+pub async fn get_contacts(
+    state: State<'_, AppState>,
+    req: GetContacts,
+) -> Result<GetContactsResponse> {
+    Ok(state.lock().await.get_contacts(req).await?)
+}
+
+#[command]
+#[specta]
+pub async fn add_contact(
+    state: State<'_, AppState>,
+    req: AddContact,
+) -> Result<AddContactResponse> {
+    Ok(state.lock().await.add_contact(req).await?)
+}
+
+#[command]
+#[specta]
+pub async fn update_contact(
+    state: State<'_, AppState>,
+    req: UpdateContact,
+) -> Result<UpdateContactResponse> {
+    Ok(state.lock().await.update_contact(req).await?)
+}
+
+#[command]
+#[specta]
+pub async fn delete_contact(
+    state: State<'_, AppState>,
+    req: DeleteContact,
+) -> Result<DeleteContactResponse> {
+    Ok(state.lock().await.delete_contact(req).await?)
+}
+
+#[command]
+#[specta]
+pub async fn create_invoice(
+    state: State<'_, AppState>,
+    req: CreateInvoice,
+) -> Result<CreateInvoiceResponse> {
+    Ok(state.lock().await.create_invoice(req).await?)
+}
+
+#[command]
+#[specta]
+pub async fn get_invoices(
+    state: State<'_, AppState>,
+    req: GetInvoices,
+) -> Result<GetInvoicesResponse> {
+    Ok(state.lock().await.get_invoices(req).await?)
+}
+
+#[command]
+#[specta]
+pub async fn get_invoice(
+    state: State<'_, AppState>,
+    req: GetInvoice,
+) -> Result<GetInvoiceResponse> {
+    Ok(state.lock().await.get_invoice(req).await?)
+}
+
+#[command]
+#[specta]
+pub async fn parse_payment_uri(
+    state: State<'_, AppState>,
+    req: ParsePaymentUri,
+) -> Result<ParsePaymentUriResponse> {
+    Ok(state.lock().await.parse_payment_uri(req)?)
+}
+
 #[command]
 #[specta]
 pub async fn get_peers(state: State<'_, AppState>, req: GetPeers) -> Result<GetPeersResponse> {
@@ -510,6 +846,33 @@ pub async fn set_derivation_batch_size(
     Ok(state.lock().await.set_derivation_batch_size(req)?)
 }
 
+#[command]
+#[specta]
+pub async fn set_hardened_addresses(
+    state: State<'_, AppState>,
+    req: SetHardenedAddresses,
+) -> Result<SetHardenedAddressesResponse> {
+    Ok(state.lock().await.set_hardened_addresses(req).await?)
+}
+
+#[command]
+#[specta]
+pub async fn set_coin_selection_strategy(
+    state: State<'_, AppState>,
+    req: SetCoinSelectionStrategy,
+) -> Result<SetCoinSelectionStrategyResponse> {
+    Ok(state.lock().await.set_coin_selection_strategy(req)?)
+}
+
+#[command]
+#[specta]
+pub async fn set_auto_consolidate(
+    state: State<'_, AppState>,
+    req: SetAutoConsolidate,
+) -> Result<SetAutoConsolidateResponse> {
+    Ok(state.lock().await.set_auto_consolidate(req)?)
+}
+
 #[command]
 #[specta]
 pub async fn get_networks(
@@ -546,6 +909,24 @@ pub async fn sign_message_with_public_key(
     Ok(state.lock().await.sign_message_with_public_key(req).await?)
 }
 
+#[command]
+#[specta]
+pub async fn sign_message_by_address(
+    state: State<'_, AppState>,
+    req: SignMessageByAddress,
+) -> Result<SignMessageByAddressResponse> {
+    Ok(state.lock().await.sign_message_by_address(req).await?)
+}
+
+#[command]
+#[specta]
+pub async fn verify_signature(
+    state: State<'_, AppState>,
+    req: VerifySignature,
+) -> Result<VerifySignatureResponse> {
+    Ok(state.lock().await.verify_signature(req).await?)
+}
+
 #[command]
 #[specta]
 pub async fn send_transaction_immediately(
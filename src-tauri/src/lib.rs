@@ -28,26 +28,43 @@ pub fn run() {
             commands::get_keys,
             commands::get_key,
             commands::get_secret_key,
+            commands::unlock_keychain,
+            commands::lock_keychain,
+            commands::change_password,
             commands::send_xch,
+            commands::send_xch_multi,
             commands::combine_xch,
+            commands::auto_consolidate,
+            commands::split_into_denominations,
             commands::split_xch,
             commands::send_cat,
+            commands::send_cat_multi,
             commands::combine_cat,
             commands::split_cat,
             commands::issue_cat,
+            commands::mint_more_cat,
+            commands::melt_cat,
             commands::create_did,
             commands::bulk_mint_nfts,
+            commands::prepare_nft_mints,
             commands::transfer_nfts,
             commands::transfer_dids,
+            commands::claim_clawback,
+            commands::revoke_clawback,
             commands::add_nft_uri,
             commands::assign_nfts_to_did,
             commands::sign_coin_spends,
             commands::view_coin_spends,
             commands::submit_transaction,
+            commands::increase_transaction_fee,
+            commands::cancel_transaction,
+            commands::estimate_fee,
             commands::get_sync_status,
             commands::get_derivations,
+            commands::get_next_address,
             commands::get_xch_coins,
             commands::get_cat_coins,
+            commands::get_coins_by_ids,
             commands::get_cats,
             commands::get_cat,
             commands::get_dids,
@@ -55,10 +72,14 @@ pub fn run() {
             commands::get_nft_collections,
             commands::get_nft_collection,
             commands::get_nfts,
+            commands::get_nft_traits,
             commands::get_nft,
             commands::get_nft_data,
             commands::get_pending_transactions,
+            commands::get_clawbacks,
             commands::get_transactions,
+            commands::get_transaction,
+            commands::export_transactions,
             commands::validate_address,
             commands::make_offer,
             commands::take_offer,
@@ -67,6 +88,8 @@ pub fn run() {
             commands::get_offers,
             commands::get_offer,
             commands::delete_offer,
+            commands::cancel_offer,
+            commands::cancel_offers,
             commands::network_config,
             commands::set_discover_peers,
             commands::set_target_peers,
@@ -74,17 +97,33 @@ pub fn run() {
             commands::wallet_config,
             commands::set_derive_automatically,
             commands::set_derivation_batch_size,
+            commands::set_hardened_addresses,
+            commands::set_coin_selection_strategy,
+            commands::set_auto_consolidate,
             commands::get_networks,
             commands::update_cat,
             commands::remove_cat,
             commands::update_did,
             commands::update_nft,
+            commands::retry_nft_data,
+            commands::update_transaction,
+            commands::update_derivation,
+            commands::get_contacts,
+            commands::add_contact,
+            commands::update_contact,
+            commands::delete_contact,
+            commands::create_invoice,
+            commands::get_invoices,
+            commands::get_invoice,
+            commands::parse_payment_uri,
             commands::get_peers,
             commands::add_peer,
             commands::remove_peer,
             commands::filter_unlocked_coins,
             commands::get_asset_coins,
             commands::sign_message_with_public_key,
+            commands::sign_message_by_address,
+            commands::verify_signature,
             commands::send_transaction_immediately,
         ])
         .events(collect_events![SyncEvent]);
@@ -38,18 +38,39 @@ async getKey(req: GetKey) : Promise<GetKeyResponse> {
 async getSecretKey(req: GetSecretKey) : Promise<GetSecretKeyResponse> {
     return await TAURI_INVOKE("get_secret_key", { req });
 },
+async unlockKeychain(req: UnlockKeychain) : Promise<UnlockKeychainResponse> {
+    return await TAURI_INVOKE("unlock_keychain", { req });
+},
+async lockKeychain(req: LockKeychain) : Promise<LockKeychainResponse> {
+    return await TAURI_INVOKE("lock_keychain", { req });
+},
+async changePassword(req: ChangePassword) : Promise<ChangePasswordResponse> {
+    return await TAURI_INVOKE("change_password", { req });
+},
 async sendXch(req: SendXch) : Promise<TransactionResponse> {
     return await TAURI_INVOKE("send_xch", { req });
 },
+async sendXchMulti(req: SendXchMulti) : Promise<TransactionResponse> {
+    return await TAURI_INVOKE("send_xch_multi", { req });
+},
 async combineXch(req: CombineXch) : Promise<TransactionResponse> {
     return await TAURI_INVOKE("combine_xch", { req });
 },
+async autoConsolidate(req: AutoConsolidate) : Promise<TransactionResponse> {
+    return await TAURI_INVOKE("auto_consolidate", { req });
+},
+async splitIntoDenominations(req: SplitIntoDenominations) : Promise<TransactionResponse> {
+    return await TAURI_INVOKE("split_into_denominations", { req });
+},
 async splitXch(req: SplitXch) : Promise<TransactionResponse> {
     return await TAURI_INVOKE("split_xch", { req });
 },
 async sendCat(req: SendCat) : Promise<TransactionResponse> {
     return await TAURI_INVOKE("send_cat", { req });
 },
+async sendCatMulti(req: SendCatMulti) : Promise<TransactionResponse> {
+    return await TAURI_INVOKE("send_cat_multi", { req });
+},
 async combineCat(req: CombineCat) : Promise<TransactionResponse> {
     return await TAURI_INVOKE("combine_cat", { req });
 },
@@ -59,18 +80,33 @@ async splitCat(req: SplitCat) : Promise<TransactionResponse> {
 async issueCat(req: IssueCat) : Promise<TransactionResponse> {
     return await TAURI_INVOKE("issue_cat", { req });
 },
+async mintMoreCat(req: MintMoreCat) : Promise<TransactionResponse> {
+    return await TAURI_INVOKE("mint_more_cat", { req });
+},
+async meltCat(req: MeltCat) : Promise<TransactionResponse> {
+    return await TAURI_INVOKE("melt_cat", { req });
+},
 async createDid(req: CreateDid) : Promise<TransactionResponse> {
     return await TAURI_INVOKE("create_did", { req });
 },
 async bulkMintNfts(req: BulkMintNfts) : Promise<TransactionResponse> {
     return await TAURI_INVOKE("bulk_mint_nfts", { req });
 },
+async prepareNftMints(req: PrepareNftMints) : Promise<PrepareNftMintsResponse> {
+    return await TAURI_INVOKE("prepare_nft_mints", { req });
+},
 async transferNfts(req: TransferNfts) : Promise<TransactionResponse> {
     return await TAURI_INVOKE("transfer_nfts", { req });
 },
 async transferDids(req: TransferDids) : Promise<TransactionResponse> {
     return await TAURI_INVOKE("transfer_dids", { req });
 },
+async claimClawback(req: ClaimClawback) : Promise<TransactionResponse> {
+    return await TAURI_INVOKE("claim_clawback", { req });
+},
+async revokeClawback(req: RevokeClawback) : Promise<TransactionResponse> {
+    return await TAURI_INVOKE("revoke_clawback", { req });
+},
 async addNftUri(req: AddNftUri) : Promise<TransactionResponse> {
     return await TAURI_INVOKE("add_nft_uri", { req });
 },
@@ -86,18 +122,33 @@ async viewCoinSpends(req: ViewCoinSpends) : Promise<ViewCoinSpendsResponse> {
 async submitTransaction(req: SubmitTransaction) : Promise<SubmitTransactionResponse> {
     return await TAURI_INVOKE("submit_transaction", { req });
 },
+async increaseTransactionFee(req: IncreaseTransactionFee) : Promise<IncreaseTransactionFeeResponse> {
+    return await TAURI_INVOKE("increase_transaction_fee", { req });
+},
+async cancelTransaction(req: CancelTransaction) : Promise<CancelTransactionResponse> {
+    return await TAURI_INVOKE("cancel_transaction", { req });
+},
+async estimateFee(req: EstimateFee) : Promise<EstimateFeeResponse> {
+    return await TAURI_INVOKE("estimate_fee", { req });
+},
 async getSyncStatus(req: GetSyncStatus) : Promise<GetSyncStatusResponse> {
     return await TAURI_INVOKE("get_sync_status", { req });
 },
 async getDerivations(req: GetDerivations) : Promise<GetDerivationsResponse> {
     return await TAURI_INVOKE("get_derivations", { req });
 },
+async getNextAddress(req: GetNextAddress) : Promise<GetNextAddressResponse> {
+    return await TAURI_INVOKE("get_next_address", { req });
+},
 async getXchCoins(req: GetXchCoins) : Promise<GetXchCoinsResponse> {
     return await TAURI_INVOKE("get_xch_coins", { req });
 },
 async getCatCoins(req: GetCatCoins) : Promise<GetCatCoinsResponse> {
     return await TAURI_INVOKE("get_cat_coins", { req });
 },
+async getCoinsByIds(req: GetCoinsByIds) : Promise<GetCoinsByIdsResponse> {
+    return await TAURI_INVOKE("get_coins_by_ids", { req });
+},
 async getCats(req: GetCats) : Promise<GetCatsResponse> {
     return await TAURI_INVOKE("get_cats", { req });
 },
@@ -119,6 +170,9 @@ async getNftCollection(req: GetNftCollection) : Promise<GetNftCollectionResponse
 async getNfts(req: GetNfts) : Promise<GetNftsResponse> {
     return await TAURI_INVOKE("get_nfts", { req });
 },
+async getNftTraits(req: GetNftTraits) : Promise<GetNftTraitsResponse> {
+    return await TAURI_INVOKE("get_nft_traits", { req });
+},
 async getNft(req: GetNft) : Promise<GetNftResponse> {
     return await TAURI_INVOKE("get_nft", { req });
 },
@@ -128,9 +182,18 @@ async getNftData(req: GetNftData) : Promise<GetNftDataResponse> {
 async getPendingTransactions(req: GetPendingTransactions) : Promise<GetPendingTransactionsResponse> {
     return await TAURI_INVOKE("get_pending_transactions", { req });
 },
+async getClawbacks(req: GetClawbacks) : Promise<GetClawbacksResponse> {
+    return await TAURI_INVOKE("get_clawbacks", { req });
+},
 async getTransactions(req: GetTransactions) : Promise<GetTransactionsResponse> {
     return await TAURI_INVOKE("get_transactions", { req });
 },
+async getTransaction(req: GetTransaction) : Promise<GetTransactionResponse> {
+    return await TAURI_INVOKE("get_transaction", { req });
+},
+async exportTransactions(req: ExportTransactions) : Promise<ExportTransactionsResponse> {
+    return await TAURI_INVOKE("export_transactions", { req });
+},
 async validateAddress(address: string) : Promise<boolean> {
     return await TAURI_INVOKE("validate_address", { address });
 },
@@ -155,6 +218,12 @@ async getOffer(req: GetOffer) : Promise<GetOfferResponse> {
 async deleteOffer(req: DeleteOffer) : Promise<DeleteOfferResponse> {
     return await TAURI_INVOKE("delete_offer", { req });
 },
+async cancelOffer(req: CancelOffer) : Promise<TransactionResponse> {
+    return await TAURI_INVOKE("cancel_offer", { req });
+},
+async cancelOffers(req: CancelOffers) : Promise<TransactionResponse> {
+    return await TAURI_INVOKE("cancel_offers", { req });
+},
 async networkConfig() : Promise<NetworkConfig> {
     return await TAURI_INVOKE("network_config");
 },
@@ -176,6 +245,15 @@ async setDeriveAutomatically(req: SetDeriveAutomatically) : Promise<SetDeriveAut
 async setDerivationBatchSize(req: SetDerivationBatchSize) : Promise<SetDerivationBatchSizeResponse> {
     return await TAURI_INVOKE("set_derivation_batch_size", { req });
 },
+async setHardenedAddresses(req: SetHardenedAddresses) : Promise<SetHardenedAddressesResponse> {
+    return await TAURI_INVOKE("set_hardened_addresses", { req });
+},
+async setCoinSelectionStrategy(req: SetCoinSelectionStrategy) : Promise<SetCoinSelectionStrategyResponse> {
+    return await TAURI_INVOKE("set_coin_selection_strategy", { req });
+},
+async setAutoConsolidate(req: SetAutoConsolidate) : Promise<SetAutoConsolidateResponse> {
+    return await TAURI_INVOKE("set_auto_consolidate", { req });
+},
 async getNetworks(req: GetNetworks) : Promise<GetNetworksResponse> {
     return await TAURI_INVOKE("get_networks", { req });
 },
@@ -191,6 +269,39 @@ async updateDid(req: UpdateDid) : Promise<UpdateDidResponse> {
 async updateNft(req: UpdateNft) : Promise<UpdateNftResponse> {
     return await TAURI_INVOKE("update_nft", { req });
 },
+async retryNftData(req: RetryNftData) : Promise<RetryNftDataResponse> {
+    return await TAURI_INVOKE("retry_nft_data", { req });
+},
+async updateTransaction(req: UpdateTransaction) : Promise<UpdateTransactionResponse> {
+    return await TAURI_INVOKE("update_transaction", { req });
+},
+async updateDerivation(req: UpdateDerivation) : Promise<UpdateDerivationResponse> {
+    return await TAURI_INVOKE("update_derivation", { req });
+},
+async getContacts(req: GetContacts) : Promise<GetContactsResponse> {
+    return await TAURI_INVOKE("get_contacts", { req });
+},
+async addContact(req: AddContact) : Promise<AddContactResponse> {
+    return await TAURI_INVOKE("add_contact", { req });
+},
+async updateContact(req: UpdateContact) : Promise<UpdateContactResponse> {
+    return await TAURI_INVOKE("update_contact", { req });
+},
+async deleteContact(req: DeleteContact) : Promise<DeleteContactResponse> {
+    return await TAURI_INVOKE("delete_contact", { req });
+},
+async createInvoice(req: CreateInvoice) : Promise<CreateInvoiceResponse> {
+    return await TAURI_INVOKE("create_invoice", { req });
+},
+async getInvoices(req: GetInvoices) : Promise<GetInvoicesResponse> {
+    return await TAURI_INVOKE("get_invoices", { req });
+},
+async getInvoice(req: GetInvoice) : Promise<GetInvoiceResponse> {
+    return await TAURI_INVOKE("get_invoice", { req });
+},
+async parsePaymentUri(req: ParsePaymentUri) : Promise<ParsePaymentUriResponse> {
+    return await TAURI_INVOKE("parse_payment_uri", { req });
+},
 async getPeers(req: GetPeers) : Promise<GetPeersResponse> {
     return await TAURI_INVOKE("get_peers", { req });
 },
@@ -209,6 +320,12 @@ async getAssetCoins(req: GetAssetCoins) : Promise<SpendableCoin[]> {
 async signMessageWithPublicKey(req: SignMessageWithPublicKey) : Promise<SignMessageWithPublicKeyResponse> {
     return await TAURI_INVOKE("sign_message_with_public_key", { req });
 },
+async signMessageByAddress(req: SignMessageByAddress) : Promise<SignMessageByAddressResponse> {
+    return await TAURI_INVOKE("sign_message_by_address", { req });
+},
+async verifySignature(req: VerifySignature) : Promise<VerifySignatureResponse> {
+    return await TAURI_INVOKE("verify_signature", { req });
+},
 async sendTransactionImmediately(req: SendTransactionImmediately) : Promise<SendTransactionImmediatelyResponse> {
     return await TAURI_INVOKE("send_transaction_immediately", { req });
 }
@@ -229,32 +346,96 @@ syncEvent: "sync-event"
 
 /** user-defined types **/
 
-export type AddNftUri = { nft_id: string; uri: string; fee: Amount; kind: NftUriKind; auto_submit?: boolean }
+export type AddContact = { name: string; address: string; did_id?: string | null; notes?: string | null }
+export type AddContactResponse = { contact: ContactRecord }
+export type AddNftUri = { nft_id: string; uri: string; fee: Amount; fee_target_seconds?: number | null; kind: NftUriKind; auto_submit?: boolean }
 export type AddPeer = { ip: string; trusted: boolean }
 export type AddPeerResponse = Record<string, never>
 export type Amount = string | number
 export type AssetCoinType = "cat" | "did" | "nft"
 export type Assets = { xch: Amount; cats: CatAmount[]; nfts: string[] }
-export type AssignNftsToDid = { nft_ids: string[]; did_id: string | null; fee: Amount; auto_submit?: boolean }
-export type BulkMintNfts = { mints: NftMint[]; did_id: string; fee: Amount; auto_submit?: boolean }
+export type AssignNftsToDid = { nft_ids: string[]; did_id: string | null; fee: Amount; fee_target_seconds?: number | null; auto_submit?: boolean }
+export type AutoConsolidate = { max_coins_per_asset: number; dust_threshold: Amount; fee: Amount; fee_target_seconds?: number | null; auto_submit?: boolean }
+/**
+ * Periodically merges the dust coins of every asset in the wallet into one coin per asset.
+ */
+export type AutoConsolidateConfig = { enabled: boolean; interval_seconds: number; max_coins_per_asset: number; dust_threshold: number; fee: number }
+export type BulkMintNfts = { mints: NftMint[]; did_id: string; fee: Amount; fee_target_seconds?: number | null; coin_ids?: string[]; coin_selection_strategy?: CoinSelectionStrategy | null; auto_submit?: boolean }
+export type CancelOffer = { offer_id: string; fee: Amount; fee_target_seconds?: number | null; auto_submit?: boolean }
+export type CancelOffers = { fee: Amount; fee_target_seconds?: number | null; auto_submit?: boolean }
+export type CancelTransaction = { transaction_id: string; fee: Amount; fee_target_seconds?: number | null }
+export type CancelTransactionResponse = { transaction_id: string; summary: TransactionSummary }
 export type CatAmount = { asset_id: string; amount: Amount }
 export type CatRecord = { asset_id: string; name: string | null; ticker: string | null; description: string | null; icon_url: string | null; visible: boolean; balance: Amount }
+export type ChangePassword = { old_password: string; new_password: string }
+export type ChangePasswordResponse = Record<string, never>
+export type ClaimClawback = { coin_id: string; fee: Amount; fee_target_seconds?: number | null; auto_submit?: boolean }
+export type ClawbackRecord = { coin_id: string; asset_id: string | null; amount: Amount; sender_address: string; recipient_address: string; seconds: number; created_height: number | null; incoming: boolean }
 export type Coin = { parent_coin_info: string; puzzle_hash: string; amount: number }
 export type CoinJson = { parent_coin_info: string; puzzle_hash: string; amount: Amount }
 export type CoinRecord = { coin_id: string; address: string; amount: Amount; created_height: number | null; spent_height: number | null; create_transaction_id: string | null; spend_transaction_id: string | null; offer_id: string | null }
+/**
+ * How the inputs of a transaction are chosen when they aren't pinned.
+ */
+export type CoinSelectionStrategy = 
+/**
+ * Uses the selection algorithm of the wallet SDK.
+ */
+"automatic" | 
+/**
+ * Spends the largest coins first, which keeps the number of inputs low.
+ */
+"largest_first" | 
+/**
+ * Spends the smallest coins first, which consolidates dust over time.
+ */
+"smallest_first" | 
+/**
+ * Spends the coins that were created the longest time ago first.
+ */
+"oldest_first" | 
+/**
+ * Searches for coins that add up to exactly the amount, so that no change is created.
+ * Falls back to automatic selection if there is no exact match.
+ */
+"exact_match" | 
+/**
+ * Spends coins received by only one address, so that addresses aren't linked together.
+ * Falls back to automatic selection if no single address has enough to cover the amount.
+ */
+"single_address"
 export type CoinSpend = { coin: Coin; puzzle_reveal: string; solution: string }
 export type CoinSpendJson = { coin: CoinJson; puzzle_reveal: string; solution: string }
-export type CombineCat = { coin_ids: string[]; fee: Amount; auto_submit?: boolean }
-export type CombineXch = { coin_ids: string[]; fee: Amount; auto_submit?: boolean }
-export type CreateDid = { name: string; fee: Amount; auto_submit?: boolean }
+export type CombineCat = { coin_ids: string[]; fee: Amount; fee_target_seconds?: number | null; auto_submit?: boolean }
+export type CombineXch = { coin_ids: string[]; fee: Amount; fee_target_seconds?: number | null; auto_submit?: boolean }
+export type ContactRecord = { id: number; name: string; address: string; did_id: string | null; notes: string | null }
+export type CreateDid = { name: string; fee: Amount; fee_target_seconds?: number | null; coin_ids?: string[]; coin_selection_strategy?: CoinSelectionStrategy | null; auto_submit?: boolean }
+export type CreateInvoice = { 
+/**
+ * Either "xch" or the asset id of a CAT.
+ */
+asset: string; amount: Amount; memo?: string | null; expires_at?: number | null }
+export type CreateInvoiceResponse = { invoice: InvoiceRecord }
+export type DeleteContact = { id: number }
+export type DeleteContactResponse = Record<string, never>
 export type DeleteKey = { fingerprint: number }
 export type DeleteKeyResponse = Record<string, never>
 export type DeleteOffer = { offer_id: string }
 export type DeleteOfferResponse = Record<string, never>
-export type DerivationRecord = { index: number; public_key: string; address: string }
+export type DenominationOutput = { amount: Amount; count: number }
+export type DerivationRecord = { index: number; hardened: boolean; public_key: string; address: string; label: string | null }
 export type DidRecord = { launcher_id: string; name: string | null; visible: boolean; coin_id: string; address: string; amount: Amount; created_height: number | null; create_transaction_id: string | null }
 export type Error = { kind: ErrorKind; reason: string }
-export type ErrorKind = "wallet" | "api" | "not_found" | "unauthorized" | "internal"
+export type ErrorKind = "wallet" | "api" | "not_found" | "unauthorized" | "locked" | "internal"
+export type EstimateFee = { target_seconds: number; cost: number }
+export type EstimateFeeResponse = { fee: Amount }
+export type ExportFormat = "csv" | "json"
+export type ExportTransactions = { format: ExportFormat; from_height?: number | null; to_height?: number | null; 
+/**
+ * Either "xch" or the asset id of a CAT.
+ */
+asset_id?: string | null }
+export type ExportTransactionsResponse = { content: string }
 export type FilterUnlockedCoins = { coin_ids: string[] }
 export type FilterUnlockedCoinsResponse = { coin_ids: string[] }
 export type GenerateMnemonic = { use_24_words: boolean }
@@ -266,27 +447,56 @@ export type GetCatCoinsResponse = { coins: CoinRecord[] }
 export type GetCatResponse = { cat: CatRecord | null }
 export type GetCats = Record<string, never>
 export type GetCatsResponse = { cats: CatRecord[] }
-export type GetDerivations = { offset: number; limit: number }
+export type GetClawbacks = Record<string, never>
+export type GetClawbacksResponse = { clawbacks: ClawbackRecord[] }
+export type GetCoinsByIds = { coin_ids: string[] }
+/**
+ * Only includes the coins that the wallet has synced.
+ */
+export type GetCoinsByIdsResponse = { coins: CoinRecord[] }
+export type GetContacts = Record<string, never>
+export type GetContactsResponse = { contacts: ContactRecord[] }
+export type GetDerivations = { hardened?: boolean; offset: number; limit: number }
 export type GetDerivationsResponse = { derivations: DerivationRecord[] }
 export type GetDids = Record<string, never>
 export type GetDidsResponse = { dids: DidRecord[] }
+export type GetInvoice = { id: number }
+export type GetInvoiceResponse = { invoice: InvoiceRecord | null; payments: InvoicePaymentRecord[] }
+export type GetInvoices = { offset: number; limit: number }
+export type GetInvoicesResponse = { invoices: InvoiceRecord[]; total: number }
 export type GetKey = { fingerprint?: number | null }
 export type GetKeyResponse = { key: KeyInfo | null }
 export type GetKeys = Record<string, never>
 export type GetKeysResponse = { keys: KeyInfo[] }
 export type GetNetworks = Record<string, never>
 export type GetNetworksResponse = { networks: { [key in string]: Network } }
+export type GetNextAddress = { mark_used?: boolean; label?: string | null }
+export type GetNextAddressResponse = { derivation: DerivationRecord }
 export type GetNft = { nft_id: string }
 export type GetNftCollection = { collection_id: string | null }
 export type GetNftCollectionResponse = { collection: NftCollectionRecord | null }
 export type GetNftCollections = { offset: number; limit: number; include_hidden: boolean }
 export type GetNftCollectionsResponse = { collections: NftCollectionRecord[] }
-export type GetNftData = { nft_id: string }
+export type GetNftData = { nft_id: string; size?: NftDataSize }
 export type GetNftDataResponse = { data: NftData | null }
 export type GetNftResponse = { nft: NftRecord | null }
 export type GetNftStatus = Record<string, never>
 export type GetNftStatusResponse = { nfts: number; visible_nfts: number; collections: number; visible_collections: number }
-export type GetNfts = { collection_id: string | null; offset: number; limit: number; sort_mode: NftSortMode; include_hidden: boolean }
+export type GetNftTraits = { 
+/**
+ * Either "all", a collection id, or none for NFTs without a collection.
+ */
+collection_id: string | null }
+export type GetNftTraitsResponse = { total: number; traits: NftTraitRecord[] }
+export type GetNfts = { collection_id: string | null; offset: number; limit: number; sort_mode: NftSortMode; include_hidden: boolean; 
+/**
+ * Only includes NFTs whose name contains words starting with each of the words given.
+ */
+search?: string | null; trait_type?: string | null; 
+/**
+ * Only used along with the trait type, to match a specific value of it.
+ */
+trait_value?: string | null }
 export type GetNftsResponse = { nfts: NftRecord[] }
 export type GetOffer = { offer_id: string }
 export type GetOfferResponse = { offer: OfferRecord }
@@ -300,32 +510,72 @@ export type GetSecretKey = { fingerprint: number }
 export type GetSecretKeyResponse = { secrets: SecretKeyInfo | null }
 export type GetSyncStatus = Record<string, never>
 export type GetSyncStatusResponse = { balance: Amount; unit: Unit; synced_coins: number; total_coins: number; receive_address: string; burn_address: string }
-export type GetTransactions = { offset: number; limit: number }
+export type GetTransaction = { id: string }
+export type GetTransactionResponse = { transaction: TransactionRecord | null }
+export type GetTransactions = { offset: number; limit: number; direction?: TransactionDirection | null; 
+/**
+ * Either "xch" or the asset id of a CAT.
+ */
+asset_id?: string | null; label?: string | null }
 export type GetTransactionsResponse = { transactions: TransactionRecord[]; total: number }
 export type GetXchCoins = Record<string, never>
 export type GetXchCoinsResponse = { coins: CoinRecord[] }
-export type ImportKey = { name: string; key: string; save_secrets?: boolean; login?: boolean }
+export type ImportKey = { name: string; key: string; save_secrets?: boolean; login?: boolean; password?: string }
 export type ImportKeyResponse = { fingerprint: number }
 export type ImportOffer = { offer: string }
 export type ImportOfferResponse = Record<string, never>
-export type IssueCat = { name: string; ticker: string; amount: Amount; fee: Amount; auto_submit?: boolean }
+export type IncreaseTransactionFee = { transaction_id: string; new_fee: Amount }
+export type IncreaseTransactionFeeResponse = { transaction_id: string; summary: TransactionSummary }
+export type InvoicePaymentRecord = { coin_id: string; amount: Amount; height: number }
+export type InvoiceRecord = { id: number; address: string; asset_id: string | null; amount: Amount; paid_amount: Amount; memo: string | null; expires_at: number | null; created_at: number; status: InvoiceRecordStatus; uri: string }
+export type InvoiceRecordStatus = "unpaid" | "partially_paid" | "paid" | "overpaid" | "expired"
+export type IssueCat = { name: string; ticker: string; amount: Amount; fee: Amount; fee_target_seconds?: number | null; multi_issuance?: boolean; coin_ids?: string[]; coin_selection_strategy?: CoinSelectionStrategy | null; auto_submit?: boolean }
 export type KeyInfo = { name: string; fingerprint: number; public_key: string; kind: KeyKind; has_secrets: boolean }
 export type KeyKind = "bls"
 export type LineageProof = { parentName: string | null; innerPuzzleHash: string | null; amount: number | null }
+export type LockKeychain = Record<string, never>
+export type LockKeychainResponse = Record<string, never>
 export type Login = { fingerprint: number }
 export type LoginResponse = Record<string, never>
 export type Logout = Record<string, never>
 export type LogoutResponse = Record<string, never>
-export type MakeOffer = { requested_assets: Assets; offered_assets: Assets; fee: Amount; expires_at_second: number | null }
+export type MakeOffer = { requested_assets: Assets; offered_assets: Assets; fee: Amount; fee_target_seconds?: number | null; expires_at_second: number | null; coin_ids?: string[]; coin_selection_strategy?: CoinSelectionStrategy | null }
 export type MakeOfferResponse = { offer: string; offer_id: string }
+export type MeltCat = { asset_id: string; amount: Amount; fee: Amount; fee_target_seconds?: number | null; coin_ids?: string[]; coin_selection_strategy?: CoinSelectionStrategy | null; auto_submit?: boolean }
+export type MintMoreCat = { asset_id: string; amount: Amount; fee: Amount; fee_target_seconds?: number | null; coin_ids?: string[]; coin_selection_strategy?: CoinSelectionStrategy | null; auto_submit?: boolean }
 export type Network = { default_port: number; ticker: string; address_prefix: string; precision: number; genesis_challenge: string; agg_sig_me: string; dns_introducers: string[] }
 export type NetworkConfig = { network_id: string; target_peers: number; discover_peers: boolean }
+export type NftAttributeRecord = { trait_type: string; value: string }
 export type NftCollectionRecord = { collection_id: string; did_id: string; metadata_collection_id: string; visible: boolean; name: string | null; icon: string | null; nfts: number; visible_nfts: number }
 export type NftData = { blob: string | null; mime_type: string | null; metadata_json: string | null }
-export type NftMint = { edition_number: number | null; edition_total: number | null; data_uris: string[]; metadata_uris: string[]; license_uris: string[]; royalty_address: string | null; royalty_ten_thousandths: number }
-export type NftRecord = { launcher_id: string; collection_id: string | null; collection_name: string | null; minter_did: string | null; owner_did: string | null; visible: boolean; sensitive_content: boolean; name: string | null; created_height: number | null; coin_id: string; address: string; royalty_address: string; royalty_ten_thousandths: number; data_uris: string[]; data_hash: string | null; metadata_uris: string[]; metadata_hash: string | null; license_uris: string[]; license_hash: string | null; edition_number: number | null; edition_total: number | null }
+export type NftDataSize = 
+/**
+ * A PNG thumbnail, if one could be generated for the media.
+ */
+"thumbnail" | "full"
+export type NftMint = { edition_number: number | null; edition_total: number | null; data_uris: string[]; 
+/**
+ * The hash of the data, which is computed by fetching the URIs if it's omitted.
+ */
+data_hash?: string | null; metadata_uris: string[]; 
+/**
+ * The hash of the metadata, which is computed by fetching the URIs if it's omitted.
+ */
+metadata_hash?: string | null; license_uris: string[]; 
+/**
+ * The hash of the license, which is computed by fetching the URIs if it's omitted.
+ */
+license_hash?: string | null; royalty_address: string | null; royalty_ten_thousandths: number }
+export type NftRecord = { launcher_id: string; collection_id: string | null; collection_name: string | null; minter_did: string | null; owner_did: string | null; visible: boolean; sensitive_content: boolean; name: string | null; created_height: number | null; coin_id: string; address: string; royalty_address: string; royalty_ten_thousandths: number; data_uris: string[]; data_hash: string | null; metadata_uris: string[]; metadata_hash: string | null; license_uris: string[]; license_hash: string | null; edition_number: number | null; edition_total: number | null; uri_health: NftUriRecord[]; attributes: NftAttributeRecord[] }
 export type NftSortMode = "name" | "recent"
+export type NftTraitRecord = { trait_type: string; value: string; count: number; 
+/**
+ * The fraction of NFTs that have this trait, between 0 and 1.
+ */
+rarity: number }
 export type NftUriKind = "data" | "metadata" | "license"
+export type NftUriRecord = { uri: string; hash: string; status: NftUriStatus; attempts: number; last_error: string | null; last_attempt: number | null; next_retry: number | null }
+export type NftUriStatus = "pending" | "fetched" | "retrying" | "failed"
 export type OfferAssets = { xch: OfferXch; cats: { [key in string]: OfferCat }; nfts: { [key in string]: OfferNft } }
 export type OfferCat = { amount: Amount; royalty: Amount; name: string | null; ticker: string | null; icon_url: string | null }
 export type OfferNft = { image_data: string | null; image_mime_type: string | null; name: string | null; royalty_ten_thousandths: number; royalty_address: string }
@@ -333,8 +583,17 @@ export type OfferRecord = { offer_id: string; offer: string; status: OfferRecord
 export type OfferRecordStatus = "active" | "completed" | "cancelled" | "expired"
 export type OfferSummary = { fee: Amount; maker: OfferAssets; taker: OfferAssets }
 export type OfferXch = { amount: Amount; royalty: Amount }
+export type ParsePaymentUri = { uri: string }
+export type ParsePaymentUriResponse = { address: string; asset_id: string | null; amount: Amount | null; memo: string | null }
+export type Payment = { address: string; amount: Amount; memos?: string[] }
 export type PeerRecord = { ip_addr: string; port: number; trusted: boolean; peak_height: number }
 export type PendingTransactionRecord = { transaction_id: string; fee: Amount; submitted_at: string | null }
+/**
+ * Fetches the URIs of each mint from every mirror and fills in their hashes,
+ * checking that the metadata follows CHIP-0007.
+ */
+export type PrepareNftMints = { mints: NftMint[] }
+export type PrepareNftMintsResponse = { mints: NftMint[] }
 export type RemoveCat = { asset_id: string }
 export type RemoveCatResponse = Record<string, never>
 export type RemovePeer = { ip: string; ban: boolean }
@@ -343,55 +602,104 @@ export type RenameKey = { fingerprint: number; name: string }
 export type RenameKeyResponse = Record<string, never>
 export type Resync = { fingerprint: number; delete_offer_files?: boolean }
 export type ResyncResponse = Record<string, never>
+export type RetryNftData = { nft_id: string }
+export type RetryNftDataResponse = Record<string, never>
+export type RevokeClawback = { coin_id: string; fee: Amount; fee_target_seconds?: number | null; auto_submit?: boolean }
 export type SecretKeyInfo = { mnemonic: string | null; secret_key: string }
-export type SendCat = { asset_id: string; address: string; amount: Amount; fee: Amount; memos?: string[]; auto_submit?: boolean }
+export type SendCat = { asset_id: string; address: string; amount: Amount; fee: Amount; fee_target_seconds?: number | null; memos?: string[]; clawback_seconds?: number | null; coin_ids?: string[]; coin_selection_strategy?: CoinSelectionStrategy | null; auto_submit?: boolean }
+export type SendCatMulti = { asset_id: string; payments: Payment[]; fee: Amount; fee_target_seconds?: number | null; coin_ids?: string[]; coin_selection_strategy?: CoinSelectionStrategy | null; auto_submit?: boolean }
 export type SendTransactionImmediately = { spend_bundle: SpendBundle }
 export type SendTransactionImmediatelyResponse = { status: number; error: string | null }
-export type SendXch = { address: string; amount: Amount; fee: Amount; memos?: string[]; auto_submit?: boolean }
+export type SendXch = { address: string; amount: Amount; fee: Amount; fee_target_seconds?: number | null; memos?: string[]; clawback_seconds?: number | null; coin_ids?: string[]; coin_selection_strategy?: CoinSelectionStrategy | null; auto_submit?: boolean }
+export type SendXchMulti = { payments: Payment[]; fee: Amount; fee_target_seconds?: number | null; coin_ids?: string[]; coin_selection_strategy?: CoinSelectionStrategy | null; auto_submit?: boolean }
+export type SetAutoConsolidate = { fingerprint: number; auto_consolidate: AutoConsolidateConfig }
+export type SetAutoConsolidateResponse = Record<string, never>
+export type SetCoinSelectionStrategy = { fingerprint: number; coin_selection_strategy: CoinSelectionStrategy }
+export type SetCoinSelectionStrategyResponse = Record<string, never>
 export type SetDerivationBatchSize = { fingerprint: number; derivation_batch_size: number }
 export type SetDerivationBatchSizeResponse = Record<string, never>
 export type SetDeriveAutomatically = { fingerprint: number; derive_automatically: boolean }
 export type SetDeriveAutomaticallyResponse = Record<string, never>
 export type SetDiscoverPeers = { discover_peers: boolean }
 export type SetDiscoverPeersResponse = Record<string, never>
+export type SetHardenedAddresses = { fingerprint: number; hardened_addresses: boolean }
+export type SetHardenedAddressesResponse = Record<string, never>
 export type SetNetworkId = { network_id: string }
 export type SetNetworkIdResponse = Record<string, never>
 export type SetTargetPeers = { target_peers: number }
 export type SetTargetPeersResponse = Record<string, never>
 export type SignCoinSpends = { coin_spends: CoinSpendJson[]; auto_submit?: boolean; partial?: boolean }
 export type SignCoinSpendsResponse = { spend_bundle: SpendBundleJson }
+export type SignMessageByAddress = { address: string; message: string; 
+/**
+ * Whether the message is hex encoded, rather than UTF-8 text.
+ */
+is_hex?: boolean }
+export type SignMessageByAddressResponse = { public_key: string; signature: string }
 export type SignMessageWithPublicKey = { message: string; publicKey: string }
 export type SignMessageWithPublicKeyResponse = { signature: string }
 export type SpendBundle = { coin_spends: CoinSpend[]; aggregated_signature: string }
 export type SpendBundleJson = { coin_spends: CoinSpendJson[]; aggregated_signature: string }
 export type SpendableCoin = { coin: Coin; coinName: string; puzzle: string; confirmedBlockIndex: number; locked: boolean; lineageProof: LineageProof | null }
-export type SplitCat = { coin_ids: string[]; output_count: number; fee: Amount; auto_submit?: boolean }
-export type SplitXch = { coin_ids: string[]; output_count: number; fee: Amount; auto_submit?: boolean }
+export type SplitCat = { coin_ids: string[]; output_count: number; fee: Amount; fee_target_seconds?: number | null; auto_submit?: boolean }
+export type SplitIntoDenominations = { asset_id?: string | null; outputs: DenominationOutput[]; fee: Amount; fee_target_seconds?: number | null; coin_ids?: string[]; coin_selection_strategy?: CoinSelectionStrategy | null; auto_submit?: boolean }
+export type SplitXch = { coin_ids: string[]; output_count: number; fee: Amount; fee_target_seconds?: number | null; auto_submit?: boolean }
 export type SubmitTransaction = { spend_bundle: SpendBundleJson }
 export type SubmitTransactionResponse = Record<string, never>
-export type SyncEvent = { type: "start"; ip: string } | { type: "stop" } | { type: "subscribed" } | { type: "derivation" } | { type: "coin_state" } | { type: "puzzle_batch_synced" } | { type: "cat_info" } | { type: "did_info" } | { type: "nft_data" }
-export type TakeOffer = { offer: string; fee: Amount; auto_submit?: boolean }
+export type SyncEvent = { type: "start"; ip: string } | { type: "stop" } | { type: "subscribed" } | { type: "derivation" } | { type: "coin_state"; coin_ids: string[] } | { type: "transaction_ended"; transaction_id: string; success: boolean } | { type: "transaction_replaced"; transaction_id: string; new_transaction_id: string } | { type: "offer_updated"; offer_id: string; status: OfferRecordStatus } | { type: "invoice_updated"; invoice_id: number; status: InvoiceRecordStatus } | { type: "puzzle_batch_synced" } | { type: "transaction_history" } | { type: "cat_info" } | { type: "did_info" } | { type: "nft_data" }
+export type TakeOffer = { offer: string; fee: Amount; fee_target_seconds?: number | null; auto_submit?: boolean }
 export type TakeOfferResponse = { summary: TransactionSummary; spend_bundle: SpendBundleJson; transaction_id: string }
-export type TransactionCoin = ({ type: "unknown" } | { type: "xch" } | { type: "launcher" } | { type: "cat"; asset_id: string; name: string | null; ticker: string | null; icon_url: string | null } | { type: "did"; launcher_id: string; name: string | null } | { type: "nft"; launcher_id: string; image_data: string | null; image_mime_type: string | null; name: string | null }) & { coin_id: string; amount: Amount; address: string | null }
+export type TransactionCoin = ({ type: "unknown" } | { type: "xch" } | { type: "launcher" } | { type: "cat"; asset_id: string; name: string | null; ticker: string | null; icon_url: string | null } | { type: "did"; launcher_id: string; name: string | null } | { type: "nft"; launcher_id: string; image_data: string | null; image_mime_type: string | null; name: string | null }) & { coin_id: string; amount: Amount; address: string | null; 
+/**
+ * The name of the contact with this address, if there is one.
+ */
+contact: string | null }
+export type TransactionDirection = "incoming" | "outgoing" | "internal"
 export type TransactionInput = ({ type: "unknown" } | { type: "xch" } | { type: "launcher" } | { type: "cat"; asset_id: string; name: string | null; ticker: string | null; icon_url: string | null } | { type: "did"; launcher_id: string; name: string | null } | { type: "nft"; launcher_id: string; image_data: string | null; image_mime_type: string | null; name: string | null }) & { coin_id: string; amount: Amount; address: string; outputs: TransactionOutput[] }
-export type TransactionOutput = { coin_id: string; amount: Amount; address: string; receiving: boolean; burning: boolean }
-export type TransactionRecord = { height: number; spent: TransactionCoin[]; created: TransactionCoin[] }
-export type TransactionResponse = { summary: TransactionSummary; coin_spends: CoinSpendJson[] }
+export type TransactionOutput = { coin_id: string; amount: Amount; address: string; 
+/**
+ * The name of the contact with this address, if there is one.
+ */
+contact: string | null; receiving: boolean; burning: boolean }
+export type TransactionRecord = { id: string; height: number | null; direction: TransactionDirection; fee: Amount; counterparty_address: string | null; memos: string[]; label: string | null; spent: TransactionCoin[]; created: TransactionCoin[] }
+export type TransactionResponse = { summary: TransactionSummary; coin_spends: CoinSpendJson[]; 
+/**
+ * The id of the transaction, if it was submitted.
+ */
+transaction_id?: string | null }
 export type TransactionSummary = { fee: Amount; inputs: TransactionInput[] }
-export type TransferDids = { did_ids: string[]; address: string; fee: Amount; auto_submit?: boolean }
-export type TransferNfts = { nft_ids: string[]; address: string; fee: Amount; auto_submit?: boolean }
+export type TransferDids = { did_ids: string[]; address: string; fee: Amount; fee_target_seconds?: number | null; auto_submit?: boolean }
+export type TransferNfts = { nft_ids: string[]; address: string; fee: Amount; fee_target_seconds?: number | null; auto_submit?: boolean }
 export type Unit = { ticker: string; decimals: number }
+export type UnlockKeychain = { password: string }
+export type UnlockKeychainResponse = Record<string, never>
 export type UpdateCat = { record: CatRecord }
 export type UpdateCatResponse = Record<string, never>
+export type UpdateContact = { id: number; name: string; address: string; did_id?: string | null; notes?: string | null }
+export type UpdateContactResponse = { contact: ContactRecord }
+export type UpdateDerivation = { address: string; label: string | null }
+export type UpdateDerivationResponse = Record<string, never>
 export type UpdateDid = { did_id: string; name: string | null; visible: boolean }
 export type UpdateDidResponse = Record<string, never>
 export type UpdateNft = { nft_id: string; visible: boolean }
 export type UpdateNftResponse = Record<string, never>
+export type UpdateTransaction = { id: string; label: string | null }
+export type UpdateTransactionResponse = Record<string, never>
+/**
+ * Verifies a signed message against either a public key, an address owned by
+ * this wallet, or both.
+ */
+export type VerifySignature = { public_key?: string | null; address?: string | null; message: string; signature: string; 
+/**
+ * Whether the message is hex encoded, rather than UTF-8 text.
+ */
+is_hex?: boolean }
+export type VerifySignatureResponse = { is_valid: boolean }
 export type ViewCoinSpends = { coin_spends: CoinSpendJson[] }
 export type ViewCoinSpendsResponse = { summary: TransactionSummary }
 export type ViewOffer = { offer: string }
 export type ViewOfferResponse = { offer: OfferSummary }
-export type WalletConfig = { name: string; derive_automatically: boolean; derivation_batch_size: number }
+export type WalletConfig = { name: string; derive_automatically: boolean; derivation_batch_size: number; hardened_addresses: boolean; coin_selection_strategy: CoinSelectionStrategy; auto_consolidate: AutoConsolidateConfig }
 
 /** tauri-specta globals **/
 
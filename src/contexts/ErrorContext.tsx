@@ -83,6 +83,10 @@ export default function ErrorDialog({ error, setError }: ErrorDialogProps) {
       kind = 'Auth';
       break;
 
+    case 'locked':
+      kind = 'Locked';
+      break;
+
     case 'wallet':
       kind = 'Wallet';
       break;
@@ -64,7 +64,10 @@ export function Offers() {
     updateOffers();
 
     const unlisten = events.syncEvent.listen((data) => {
-      if (data.payload.type === 'coin_state') {
+      if (
+        data.payload.type === 'coin_state' ||
+        data.payload.type === 'offer_updated'
+      ) {
         updateOffers();
       }
     });
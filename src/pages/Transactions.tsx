@@ -53,10 +53,13 @@ export function Transactions() {
     const unlisten = events.syncEvent.listen((data) => {
       switch (data.payload.type) {
         case 'coin_state':
+        case 'transaction_ended':
+        case 'transaction_replaced':
         case 'cat_info':
         case 'did_info':
         case 'nft_data':
         case 'puzzle_batch_synced':
+        case 'transaction_history':
           updateTransactions();
       }
     });
@@ -183,7 +186,11 @@ function Transaction({ transaction }: TransactionProps) {
         <div className='grid grid-cols-1 md:grid-cols-3 gap-4'>
           <div className='flex flex-col gap-2'>
             <div>
-              <Trans>Block #{transactionHeight}</Trans>
+              {transactionHeight === null ? (
+                <Trans>Pending</Trans>
+              ) : (
+                <Trans>Block #{transactionHeight}</Trans>
+              )}
             </div>
             <div className='text-sm text-muted-foreground truncate'>
               <Trans>{transactionSpentCount} coins spent,</Trans>{' '}
@@ -71,6 +71,8 @@ function updateNftStatus() {
 events.syncEvent.listen((event) => {
   switch (event.payload.type) {
     case 'coin_state':
+    case 'transaction_ended':
+    case 'transaction_replaced':
       updateCoins();
       updateSyncStatus();
       updateNftStatus();